@@ -0,0 +1,143 @@
+//! Canvas virtualization benchmark
+//!
+//! Builds a grid of N tables, each linked to its left and upper neighbour, and
+//! measures viewport culling the way the canvas runs it on every pan step, and
+//! edge routing: all relationships (auto layout, loading a diagram) and the
+//! relationships of one moved table (end of a drag).
+//!
+//! ```text
+//! cargo bench --bench canvas            # 100, 600 and 2000 tables
+//! cargo bench --bench canvas -- 5000    # custom sizes
+//! ```
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use archischema::core::{
+    Column, DetailLevel, Relationship, RelationshipType, RoutingConfig, SchemaGraph, TableNode,
+    Viewport, apply_routes, cull_edges, cull_tables, render_area, route_edges, route_local_edges,
+    stale_edges, table_outlines,
+};
+
+const DEFAULT_SIZES: [usize; 3] = [100, 600, 2000];
+const SCREEN: (f64, f64) = (1920.0, 1080.0);
+const MEASURE_FOR: Duration = Duration::from_millis(500);
+
+fn grid(tables: usize) -> SchemaGraph {
+    let columns = (tables as f64).sqrt().ceil() as usize;
+    let mut graph = SchemaGraph::new();
+    let nodes: Vec<_> = (0..tables)
+        .map(|i| {
+            let mut table = TableNode::new(format!("table_{}", i))
+                .with_position((i % columns) as f64 * 400.0, (i / columns) as f64 * 400.0);
+            table.columns = (0..6)
+                .map(|c| Column::new(format!("column_{}", c), "INTEGER"))
+                .collect();
+            graph.add_node(table)
+        })
+        .collect();
+    for (i, &node) in nodes.iter().enumerate() {
+        let mut link = |other: usize| {
+            graph.add_edge(
+                node,
+                nodes[other],
+                Relationship::new("fk", RelationshipType::ManyToOne, "column_1", "column_0"),
+            );
+        };
+        if i % columns > 0 {
+            link(i - 1);
+        }
+        if i >= columns {
+            link(i - columns);
+        }
+    }
+    graph
+}
+
+/// Average time of `run` over at least [`MEASURE_FOR`]
+fn measure(mut run: impl FnMut()) -> Duration {
+    let started = Instant::now();
+    let mut iterations = 0_u32;
+    while started.elapsed() < MEASURE_FOR {
+        run();
+        iterations += 1;
+    }
+    started.elapsed() / iterations
+}
+
+fn main() {
+    let sizes: Vec<usize> = std::env::args()
+        .skip(1)
+        .filter_map(|arg| arg.parse().ok())
+        .collect();
+    let sizes = if sizes.is_empty() {
+        DEFAULT_SIZES.to_vec()
+    } else {
+        sizes
+    };
+
+    println!(
+        "{:>7} {:>6} {:>9} {:>12} {:>12} {:>12}",
+        "tables", "zoom", "detail", "rendered", "edges", "cull time"
+    );
+    for &tables in &sizes {
+        let graph = grid(tables);
+        let all_tables: Vec<_> = graph.node_indices().collect();
+        let all_edges: Vec<_> = graph.edge_indices().collect();
+
+        for zoom in [1.0, 0.25] {
+            let extent = (tables as f64).sqrt().ceil() * 400.0;
+            let mut step = 0.0;
+            let mut rendered = (0, 0);
+            let time = measure(|| {
+                // Каждая итерация - шаг панорамирования по диагонали диаграммы
+                step = (step + 7.0) % extent;
+                let viewport = Viewport::from_center((step, step), zoom, SCREEN);
+                let area = render_area(&viewport, zoom);
+                let visible_tables = cull_tables(&graph, &area, black_box(&all_tables));
+                let visible_edges = cull_edges(&graph, &area, black_box(&all_edges));
+                rendered = (visible_tables.len(), visible_edges.len());
+            });
+            println!(
+                "{:>7} {:>6} {:>9} {:>12} {:>12} {:>12?}",
+                tables,
+                zoom,
+                format!("{:?}", DetailLevel::for_zoom(zoom)),
+                format!("{}/{}", rendered.0, tables),
+                format!("{}/{}", rendered.1, all_edges.len()),
+                time
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "{:>7} {:>12} {:>12} {:>12}",
+        "tables", "edges", "route all", "route moved"
+    );
+    for tables in sizes {
+        let mut graph = grid(tables);
+        let config = RoutingConfig::default();
+        let all = measure(|| {
+            black_box(route_edges(black_box(&graph), &config));
+        });
+        let routes = route_edges(&graph, &config);
+        apply_routes(&mut graph, routes);
+
+        // Сдвиг таблицы в середине сетки, как после перетаскивания
+        let outlines = table_outlines(&graph);
+        let moved = graph.node_indices().nth(tables / 2).unwrap();
+        graph[moved].position.0 += 37.0;
+        let stale = stale_edges(&graph, &outlines);
+        let local = measure(|| {
+            black_box(route_local_edges(black_box(&graph), &config, &stale));
+        });
+        println!(
+            "{:>7} {:>12} {:>12?} {:>12?}",
+            tables,
+            format!("{}/{}", stale.len(), graph.edge_count()),
+            all,
+            local
+        );
+    }
+}
@@ -51,6 +51,11 @@ When creating relationships:
 4. For foreign keys: the "from" side is the table with the FK column, "to" side is the referenced table
 5. Example: students.course_id -> courses.id should be from_table="students", from_column="course_id", to_table="courses", to_column="id"
 
+When deleting tables or columns:
+1. delete_table and delete_column refuse to run while other objects depend on the target and return the impact instead
+2. Show the user what will be affected before repeating the call with confirm=true
+3. Use get_impact to check dependencies without deleting anything
+
 Available data types: INT, BIGINT, VARCHAR, TEXT, BOOLEAN, DATE, DATETIME, TIMESTAMP, DECIMAL, FLOAT, JSON, ENUM, etc.
 
 Be concise but helpful. When creating a schema, create ALL tables first, then ALL relationships. If you're unsure about something, ask for clarification."#;
@@ -447,6 +452,66 @@ pub fn build_tool_definitions(mode: AiMode) -> Vec<ToolDefinition> {
                 }),
             },
         },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "get_impact".to_string(),
+                description: "List what depends on a table or column (relationships, orphaned foreign keys, indexes, junction tables) before changing or dropping it".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "table_name": {
+                            "type": "string",
+                            "description": "Name of the table"
+                        },
+                        "column_name": {
+                            "type": "string",
+                            "description": "Name of the column (omit to analyze the whole table)"
+                        }
+                    },
+                    "required": ["table_name"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "get_join_path".to_string(),
+                description: "Find the shortest relationship path connecting tables and generate a SELECT with JOIN clauses".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "tables": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Names of the tables to join (at least two)"
+                        },
+                        "max_paths": {
+                            "type": "integer",
+                            "description": "Maximum number of paths including alternatives (default 3)"
+                        }
+                    },
+                    "required": ["tables"]
+                }),
+            },
+        },
+        ToolDefinition {
+            tool_type: "function".to_string(),
+            function: FunctionDefinition {
+                name: "get_inferred_foreign_keys".to_string(),
+                description: "Propose foreign keys for columns without relationships, inferred from naming conventions and type compatibility, ranked by confidence".to_string(),
+                parameters: serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "min_confidence": {
+                            "type": "number",
+                            "description": "Minimum confidence (0-1) of returned candidates"
+                        }
+                    },
+                    "required": []
+                }),
+            },
+        },
     ];
 
     // Write tools (only in Write mode)
@@ -518,13 +583,17 @@ pub fn build_tool_definitions(mode: AiMode) -> Vec<ToolDefinition> {
                 tool_type: "function".to_string(),
                 function: FunctionDefinition {
                     name: "delete_table".to_string(),
-                    description: "Delete a table from the schema".to_string(),
+                    description: "Delete a table from the schema. If other objects depend on it, the impact is returned and the call must be repeated with confirm=true".to_string(),
                     parameters: serde_json::json!({
                         "type": "object",
                         "properties": {
                             "table_name": {
                                 "type": "string",
                                 "description": "Name of the table to delete"
+                            },
+                            "confirm": {
+                                "type": "boolean",
+                                "description": "Delete even though dependent objects are affected"
                             }
                         },
                         "required": ["table_name"]
@@ -617,7 +686,7 @@ pub fn build_tool_definitions(mode: AiMode) -> Vec<ToolDefinition> {
                 tool_type: "function".to_string(),
                 function: FunctionDefinition {
                     name: "delete_column".to_string(),
-                    description: "Delete a column from a table".to_string(),
+                    description: "Delete a column and the relationships that use it. If other objects depend on it, the impact is returned and the call must be repeated with confirm=true".to_string(),
                     parameters: serde_json::json!({
                         "type": "object",
                         "properties": {
@@ -628,6 +697,10 @@ pub fn build_tool_definitions(mode: AiMode) -> Vec<ToolDefinition> {
                             "column_name": {
                                 "type": "string",
                                 "description": "Name of the column to delete"
+                            },
+                            "confirm": {
+                                "type": "boolean",
+                                "description": "Delete even though dependent objects are affected"
                             }
                         },
                         "required": ["table_name", "column_name"]
@@ -701,6 +774,41 @@ pub fn build_tool_definitions(mode: AiMode) -> Vec<ToolDefinition> {
                     }),
                 },
             },
+            ToolDefinition {
+                tool_type: "function".to_string(),
+                function: FunctionDefinition {
+                    name: "apply_inferred_foreign_keys".to_string(),
+                    description: "Create relationships for inferred foreign key candidates at or above the confidence threshold".to_string(),
+                    parameters: serde_json::json!({
+                        "type": "object",
+                        "properties": {
+                            "min_confidence": {
+                                "type": "number",
+                                "description": "Minimum confidence (0-1), defaults to 0.75"
+                            }
+                        },
+                        "required": []
+                    }),
+                },
+            },
+            ToolDefinition {
+                tool_type: "function".to_string(),
+                function: FunctionDefinition {
+                    name: "auto_layout".to_string(),
+                    description: "Arrange tables on the canvas. Pinned tables stay in place; omit tables to arrange the whole diagram".to_string(),
+                    parameters: serde_json::json!({
+                        "type": "object",
+                        "properties": {
+                            "tables": {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "description": "Names of the tables to arrange"
+                            }
+                        },
+                        "required": []
+                    }),
+                },
+            },
         ]);
     }
 
@@ -16,10 +16,17 @@
 //! This module provides all of these through a set of "tools" that an AI agent can invoke.
 
 use super::{Column, Relationship, RelationshipOps, RelationshipType, SchemaGraph, TableOps};
-use crate::core::SqlDialect;
+use crate::core::auto_layout::{LayoutConfig, LayoutScope, auto_layout_with_config};
+use crate::core::fk_inference::{
+    DEFAULT_MIN_CONFIDENCE, FkCandidate, accept_fk_candidates, infer_foreign_keys,
+};
+use crate::core::impact::{ImpactTarget, analyze_impact, delete_column_with_dependents};
+use crate::core::join_path::{DEFAULT_MAX_JOIN_PATHS, find_join_paths_by_name, join_query_sql};
 use crate::core::liveshare::{ColumnData, GraphOperation, RelationshipData};
-use crate::core::sql_parser::{SqlValidationResult, validate_sql};
+use crate::core::sql_parser::{SqlValidationResult, validate_sql_with_lint};
+use crate::core::{DiagramSettings, SqlDialect};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 // ============================================================================
 // Tool Definitions - Structured descriptions for AI agent discovery
@@ -49,7 +56,7 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
         // Read operations
         ToolDefinition {
             name: "get_schema_sql".into(),
-            description: "Get the current database schema as SQL DDL statements. Use this to understand the current structure before making changes.".into(),
+            description: "Get the current database schema as SQL DDL statements in the diagram's SQL dialect. Use this to understand the current structure before making changes.".into(),
             parameters: vec![],
             returns: "SQL string with CREATE TABLE statements and foreign key constraints".into(),
         },
@@ -93,6 +100,48 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
             ],
             returns: "Array of relationships (both outgoing and incoming)".into(),
         },
+        ToolDefinition {
+            name: "get_impact".into(),
+            description: "List what depends on a table or column before changing or dropping it".into(),
+            parameters: vec![
+                ParameterDefinition {
+                    name: "table_name".into(),
+                    param_type: "string".into(),
+                    description: "Name of the table".into(),
+                    required: true,
+                    default_value: None,
+                },
+                ParameterDefinition {
+                    name: "column_name".into(),
+                    param_type: "string".into(),
+                    description: "Name of the column (omit to analyze the whole table)".into(),
+                    required: false,
+                    default_value: None,
+                },
+            ],
+            returns: "Dependent relationships, orphaned keys, indexes and junction tables".into(),
+        },
+        ToolDefinition {
+            name: "get_join_path".into(),
+            description: "Find the shortest chain of relationships connecting two or more tables and generate a SELECT with the JOIN clauses in the diagram's SQL dialect".into(),
+            parameters: vec![
+                ParameterDefinition {
+                    name: "tables".into(),
+                    param_type: "array".into(),
+                    description: "Names of the tables to join (at least two)".into(),
+                    required: true,
+                    default_value: None,
+                },
+                ParameterDefinition {
+                    name: "max_paths".into(),
+                    param_type: "integer".into(),
+                    description: "Maximum number of paths, including alternatives".into(),
+                    required: false,
+                    default_value: Some(DEFAULT_MAX_JOIN_PATHS.to_string()),
+                },
+            ],
+            returns: "Paths ordered by number of joins, each with its joins and SELECT query".into(),
+        },
         // Table operations
         ToolDefinition {
             name: "create_table".into(),
@@ -152,7 +201,7 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
         },
         ToolDefinition {
             name: "delete_table".into(),
-            description: "Delete a table and all its relationships".into(),
+            description: "Delete a table and all its relationships. Requires confirm=true when other objects depend on it".into(),
             parameters: vec![
                 ParameterDefinition {
                     name: "table_name".into(),
@@ -161,8 +210,15 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
                     required: true,
                     default_value: None,
                 },
+                ParameterDefinition {
+                    name: "confirm".into(),
+                    param_type: "boolean".into(),
+                    description: "Delete even though dependent objects are affected".into(),
+                    required: false,
+                    default_value: Some("false".into()),
+                },
             ],
-            returns: "Success message or error".into(),
+            returns: "Success message, impact report awaiting confirmation, or error".into(),
         },
         // Column operations
         ToolDefinition {
@@ -286,7 +342,7 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
         },
         ToolDefinition {
             name: "delete_column".into(),
-            description: "Delete a column from a table".into(),
+            description: "Delete a column and the relationships that use it. Requires confirm=true when other objects depend on it".into(),
             parameters: vec![
                 ParameterDefinition {
                     name: "table_name".into(),
@@ -302,13 +358,20 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
                     required: true,
                     default_value: None,
                 },
+                ParameterDefinition {
+                    name: "confirm".into(),
+                    param_type: "boolean".into(),
+                    description: "Delete even though dependent objects are affected".into(),
+                    required: false,
+                    default_value: Some("false".into()),
+                },
             ],
-            returns: "Success message or error".into(),
+            returns: "Success message, impact report awaiting confirmation, or error".into(),
         },
         // Relationship operations
         ToolDefinition {
             name: "create_relationship".into(),
-            description: "Create a foreign key relationship between two tables".into(),
+            description: "Create a foreign key relationship between two tables. Incompatible column types are rejected; cardinality and nullability mismatches are returned as warnings".into(),
             parameters: vec![
                 ParameterDefinition {
                     name: "name".into(),
@@ -420,16 +483,61 @@ pub fn get_tool_definitions() -> Vec<ToolDefinition> {
                 ParameterDefinition {
                     name: "dialect".into(),
                     param_type: "string".into(),
-                    description: "SQL dialect: 'mysql', 'postgresql', or 'sqlite'".into(),
+                    description: "SQL dialect: 'mysql', 'postgresql', or 'sqlite'. Defaults to the diagram's dialect".into(),
                     required: false,
-                    default_value: Some("mysql".into()),
+                    default_value: None,
+                },
+                ParameterDefinition {
+                    name: "apply_fixes".into(),
+                    param_type: "boolean".into(),
+                    description: "Apply the suggested fixes of all diagnostics and return the fixed SQL in data.fixed_sql".into(),
+                    required: false,
+                    default_value: Some("false".into()),
                 },
             ],
-            returns: "Validation result with is_valid flag, error_count, warning_count, and detailed diagnostics array".into(),
+            returns: "Validation result with is_valid flag, error_count, warning_count, and detailed diagnostics array (each diagnostic lists machine-applicable fixes as text edits)".into(),
+        },
+        // Inference operations
+        ToolDefinition {
+            name: "get_inferred_foreign_keys".into(),
+            description: "Propose foreign keys for columns without declared relationships, inferred from naming conventions (customer_id, customerId, fk_customer) and type compatibility. Candidates are ranked by confidence (0-1).".into(),
+            parameters: vec![ParameterDefinition {
+                name: "min_confidence".into(),
+                param_type: "number".into(),
+                description: "Only return candidates with at least this confidence".into(),
+                required: false,
+                default_value: Some("0".into()),
+            }],
+            returns: "Ranked list of candidates (from_table, from_column, to_table, to_column, relationship_type, confidence, reasons)".into(),
+        },
+        ToolDefinition {
+            name: "apply_inferred_foreign_keys".into(),
+            description: "Create relationships for inferred foreign key candidates. Review them with get_inferred_foreign_keys first.".into(),
+            parameters: vec![ParameterDefinition {
+                name: "min_confidence".into(),
+                param_type: "number".into(),
+                description: "Create only candidates with at least this confidence".into(),
+                required: false,
+                default_value: Some(DEFAULT_MIN_CONFIDENCE.to_string()),
+            }],
+            returns: "List of created relationships and candidates that could not be created".into(),
+        },
+        // Layout operations
+        ToolDefinition {
+            name: "auto_layout".into(),
+            description: "Arrange tables on the canvas using the diagram's layout algorithm. Pinned tables never move; other tables are placed without overlapping them.".into(),
+            parameters: vec![ParameterDefinition {
+                name: "tables".into(),
+                param_type: "array".into(),
+                description: "Names of the tables to arrange; the rest stay in place. Omit to arrange the whole diagram".into(),
+                required: false,
+                default_value: None,
+            }],
+            returns: "Number of moved tables".into(),
         },
         ToolDefinition {
             name: "check_schema".into(),
-            description: "Validate the current schema for consistency and correctness. Use this before saving or exporting.".into(),
+            description: "Validate the current schema for consistency and correctness, including relationship type, cardinality and nullability checks. Use this before saving or exporting.".into(),
             parameters: vec![],
             returns: "Validation result with is_valid flag and any issues found".into(),
         },
@@ -625,15 +733,26 @@ fn default_relationship_type() -> String {
 pub struct ToolExecutor;
 
 impl ToolExecutor {
-    /// Execute a tool operation by name
+    /// Execute a tool operation by name (with default diagram settings)
     pub fn execute(graph: &mut SchemaGraph, request: &ToolRequest) -> ToolResponse {
+        Self::execute_with_settings(graph, &DiagramSettings::default(), request)
+    }
+
+    /// Execute a tool operation using the diagram's settings (target dialect, etc.)
+    pub fn execute_with_settings(
+        graph: &mut SchemaGraph,
+        settings: &DiagramSettings,
+        request: &ToolRequest,
+    ) -> ToolResponse {
         match request.tool_name.as_str() {
             // Read operations
-            "get_schema_sql" => Self::get_schema_sql(graph),
+            "get_schema_sql" => Self::get_schema_sql(graph, settings),
             "get_schema_json" => Self::get_schema_json(graph),
             "list_tables" => Self::list_tables(graph),
             "get_table" => Self::get_table(graph, &request.parameters),
             "get_relationships" => Self::get_relationships(graph, &request.parameters),
+            "get_impact" => Self::get_impact(graph, &request.parameters),
+            "get_join_path" => Self::get_join_path(graph, &request.parameters, settings),
 
             // Table operations
             "create_table" => Self::create_table(graph, &request.parameters),
@@ -652,9 +771,20 @@ impl ToolExecutor {
             // SQL operations
             "apply_sql" => Self::apply_sql(graph, &request.parameters),
 
+            // Inference operations
+            "get_inferred_foreign_keys" => {
+                Self::get_inferred_foreign_keys(graph, &request.parameters)
+            }
+            "apply_inferred_foreign_keys" => {
+                Self::apply_inferred_foreign_keys(graph, &request.parameters)
+            }
+
+            // Layout operations
+            "auto_layout" => Self::auto_layout(graph, &request.parameters, settings),
+
             // Validation operations
-            "validate_sql" => Self::validate_sql_tool(&request.parameters),
-            "check_schema" => Self::check_schema(graph),
+            "validate_sql" => Self::validate_sql_tool(&request.parameters, settings),
+            "check_schema" => Self::check_schema(graph, settings),
 
             _ => ToolResponse::error(format!("Unknown tool: {}", request.tool_name)),
         }
@@ -664,10 +794,13 @@ impl ToolExecutor {
     // Read operations
     // ========================================================================
 
-    fn get_schema_sql(graph: &SchemaGraph) -> ToolResponse {
+    fn get_schema_sql(graph: &SchemaGraph, settings: &DiagramSettings) -> ToolResponse {
         use super::export::{ExportOptions, SchemaExporter};
 
-        let options = ExportOptions::default();
+        let options = ExportOptions {
+            sql_dialect: settings.dialect.clone(),
+            ..Default::default()
+        };
         match SchemaExporter::export_sql(graph, &options) {
             Ok(sql) => ToolResponse::success_with_data(
                 "Schema exported as SQL",
@@ -776,6 +909,75 @@ impl ToolExecutor {
         )
     }
 
+    fn get_impact(graph: &SchemaGraph, params: &serde_json::Value) -> ToolResponse {
+        let table_name = match params.get("table_name").and_then(|v| v.as_str()) {
+            Some(name) => name,
+            None => return ToolResponse::error("Missing required parameter: table_name"),
+        };
+
+        let idx = match graph.find_table_by_name(table_name) {
+            Some(idx) => idx,
+            None => return ToolResponse::error(format!("Table '{}' not found", table_name)),
+        };
+
+        let target = match params.get("column_name").and_then(|v| v.as_str()) {
+            Some(column) => ImpactTarget::Column(idx, column.to_string()),
+            None => ImpactTarget::Table(idx),
+        };
+
+        match analyze_impact(graph, &target) {
+            Ok(report) => ToolResponse::success_with_data(
+                report.summary(),
+                serde_json::to_value(&report).unwrap_or_default(),
+            ),
+            Err(e) => ToolResponse::error(e),
+        }
+    }
+
+    fn get_join_path(
+        graph: &SchemaGraph,
+        params: &serde_json::Value,
+        settings: &DiagramSettings,
+    ) -> ToolResponse {
+        let tables: Vec<String> = match params.get("tables").and_then(|v| v.as_array()) {
+            Some(list) => list
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect(),
+            None => return ToolResponse::error("Missing required parameter: tables"),
+        };
+        let max_paths = params
+            .get("max_paths")
+            .and_then(|v| v.as_u64())
+            .map(|v| v as usize)
+            .unwrap_or(DEFAULT_MAX_JOIN_PATHS);
+
+        match find_join_paths_by_name(graph, &tables, max_paths) {
+            Ok(paths) => {
+                let data: Vec<serde_json::Value> = paths
+                    .iter()
+                    .map(|path| {
+                        serde_json::json!({
+                            "joins": path.len(),
+                            "root_table": path.root_table,
+                            "steps": path.steps,
+                            "sql": join_query_sql(path, &settings.dialect),
+                        })
+                    })
+                    .collect();
+                ToolResponse::success_with_data(
+                    format!(
+                        "Found {} join paths, shortest has {} joins",
+                        paths.len(),
+                        paths[0].len()
+                    ),
+                    serde_json::json!(data),
+                )
+            }
+            Err(e) => ToolResponse::error(e),
+        }
+    }
+
     // ========================================================================
     // Table operations
     // ========================================================================
@@ -942,25 +1144,57 @@ impl ToolExecutor {
             None => return ToolResponse::error(format!("Table '{}' not found", table_name)),
         };
 
+        if let Some(response) = Self::require_confirmation(graph, &ImpactTarget::Table(idx), params)
+        {
+            return response;
+        }
+
         let node_id = idx.index() as u32;
+        let table_uuid = graph
+            .node_weight(idx)
+            .map(|n| n.uuid)
+            .unwrap_or_else(uuid::Uuid::new_v4);
         match graph.delete_table(idx) {
-            Ok(_) => {
-                let table_uuid = graph
-                    .node_weight(idx)
-                    .map(|n| n.uuid)
-                    .unwrap_or_else(uuid::Uuid::new_v4);
-                ToolResponse::success_with_ops(
-                    format!("Table '{}' deleted successfully", table_name),
-                    vec![GraphOperation::DeleteTable {
-                        node_id,
-                        table_uuid,
-                    }],
-                )
-            }
+            Ok(_) => ToolResponse::success_with_ops(
+                format!("Table '{}' deleted successfully", table_name),
+                vec![GraphOperation::DeleteTable {
+                    node_id,
+                    table_uuid,
+                }],
+            ),
             Err(e) => ToolResponse::error(e),
         }
     }
 
+    /// Impact check before a destructive operation.
+    ///
+    /// Returns a response with the impact report when something depends on the
+    /// target and the call was not confirmed.
+    fn require_confirmation(
+        graph: &SchemaGraph,
+        target: &ImpactTarget,
+        params: &serde_json::Value,
+    ) -> Option<ToolResponse> {
+        let confirmed = params
+            .get("confirm")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if confirmed {
+            return None;
+        }
+        let report = analyze_impact(graph, target).ok()?;
+        if report.is_empty() {
+            return None;
+        }
+
+        let mut response = ToolResponse::error(format!(
+            "{}. Review the impact and repeat the call with confirm=true to proceed",
+            report.summary()
+        ));
+        response.data = serde_json::to_value(&report).ok();
+        Some(response)
+    }
+
     // ========================================================================
     // Column operations
     // ========================================================================
@@ -1120,39 +1354,33 @@ impl ToolExecutor {
             None => return ToolResponse::error(format!("Table '{}' not found", table_name)),
         };
 
-        if let Some(table) = graph.node_weight_mut(idx) {
-            let col_idx = match table.find_column(column_name) {
-                Some((idx, _)) => idx,
-                None => {
-                    return ToolResponse::error(format!(
-                        "Column '{}' not found in table '{}'",
-                        column_name, table_name
-                    ));
-                }
-            };
-
-            match table.delete_column(col_idx) {
-                Ok(_) => {
-                    let table_uuid = graph
-                        .node_weight(idx)
-                        .map(|n| n.uuid)
-                        .unwrap_or_else(uuid::Uuid::new_v4);
-                    ToolResponse::success_with_ops(
-                        format!(
-                            "Column '{}' deleted from table '{}'",
-                            column_name, table_name
-                        ),
-                        vec![GraphOperation::DeleteColumn {
-                            node_id: idx.index() as u32,
-                            table_uuid,
-                            column_index: col_idx,
-                        }],
-                    )
-                }
-                Err(e) => ToolResponse::error(e),
+        let col_idx = match graph[idx].find_column(column_name) {
+            Some((col_idx, _)) => col_idx,
+            None => {
+                return ToolResponse::error(format!(
+                    "Column '{}' not found in table '{}'",
+                    column_name, table_name
+                ));
             }
-        } else {
-            ToolResponse::error(format!("Table '{}' not found", table_name))
+        };
+
+        if let Some(response) = Self::require_confirmation(
+            graph,
+            &ImpactTarget::Column(idx, column_name.to_string()),
+            params,
+        ) {
+            return response;
+        }
+
+        match delete_column_with_dependents(graph, idx, col_idx) {
+            Ok(ops) => ToolResponse::success_with_ops(
+                format!(
+                    "Column '{}' deleted from table '{}'",
+                    column_name, table_name
+                ),
+                ops,
+            ),
+            Err(e) => ToolResponse::error(e),
         }
     }
 
@@ -1207,24 +1435,34 @@ impl ToolExecutor {
         let relationship =
             Relationship::new(&rel_name, rel_type, &input.from_column, &input.to_column);
 
-        match graph.create_relationship(from_idx, to_idx, relationship) {
-            Ok(edge_idx) => ToolResponse::success_with_ops(
-                format!(
+        match graph.create_relationship_checked(from_idx, to_idx, relationship) {
+            Ok((edge_idx, issues)) => {
+                let mut message = format!(
                     "Relationship '{}' created: {}.{} -> {}.{}",
                     rel_name, input.from_table, input.from_column, input.to_table, input.to_column
-                ),
-                vec![GraphOperation::CreateRelationship {
-                    edge_id: edge_idx.index() as u32,
-                    from_node: from_idx.index() as u32,
-                    to_node: to_idx.index() as u32,
-                    relationship: RelationshipData {
-                        name: rel_name,
-                        relationship_type: input.relationship_type,
-                        from_column: input.from_column,
-                        to_column: input.to_column,
-                    },
-                }],
-            ),
+                );
+                for issue in &issues {
+                    message.push_str(&format!("\nWarning: {}", issue));
+                }
+                let mut response = ToolResponse::success_with_ops(
+                    message,
+                    vec![GraphOperation::CreateRelationship {
+                        edge_id: edge_idx.index() as u32,
+                        from_node: from_idx.index() as u32,
+                        to_node: to_idx.index() as u32,
+                        relationship: RelationshipData {
+                            name: rel_name,
+                            relationship_type: input.relationship_type,
+                            from_column: input.from_column,
+                            to_column: input.to_column,
+                        },
+                    }],
+                );
+                if !issues.is_empty() {
+                    response.data = Some(serde_json::json!({ "warnings": issues }));
+                }
+                response
+            }
             Err(e) => ToolResponse::error(e),
         }
     }
@@ -1345,30 +1583,119 @@ impl ToolExecutor {
         }
     }
 
+    // ========================================================================
+    // Inference operations
+    // ========================================================================
+
+    /// Inferred candidates at or above `min_confidence`
+    fn inferred_candidates(
+        graph: &SchemaGraph,
+        params: &serde_json::Value,
+        default_min: f32,
+    ) -> Vec<FkCandidate> {
+        let min_confidence = params
+            .get("min_confidence")
+            .and_then(|v| v.as_f64())
+            .map(|v| v as f32)
+            .unwrap_or(default_min);
+        infer_foreign_keys(graph)
+            .into_iter()
+            .filter(|c| c.confidence >= min_confidence)
+            .collect()
+    }
+
+    fn get_inferred_foreign_keys(graph: &SchemaGraph, params: &serde_json::Value) -> ToolResponse {
+        let candidates = Self::inferred_candidates(graph, params, 0.0);
+        ToolResponse::success_with_data(
+            format!("Found {} foreign key candidates", candidates.len()),
+            serde_json::json!({ "candidates": candidates }),
+        )
+    }
+
+    fn apply_inferred_foreign_keys(
+        graph: &mut SchemaGraph,
+        params: &serde_json::Value,
+    ) -> ToolResponse {
+        let candidates = Self::inferred_candidates(graph, params, DEFAULT_MIN_CONFIDENCE);
+        if candidates.is_empty() {
+            return ToolResponse::success("No foreign key candidates to apply");
+        }
+
+        let accepted = accept_fk_candidates(graph, &candidates);
+        let mut response = ToolResponse::success_with_ops(
+            format!(
+                "Created {} of {} inferred relationships",
+                accepted.created.len(),
+                candidates.len()
+            ),
+            accepted.graph_ops,
+        );
+        response.data = Some(serde_json::json!({
+            "created": accepted.created,
+            "errors": accepted.errors,
+        }));
+        response
+    }
+
+    // ========================================================================
+    // Layout operations
+    // ========================================================================
+
+    fn auto_layout(
+        graph: &mut SchemaGraph,
+        params: &serde_json::Value,
+        settings: &DiagramSettings,
+    ) -> ToolResponse {
+        let names: Vec<&str> = params
+            .get("tables")
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str()).collect())
+            .unwrap_or_default();
+
+        // Без списка таблиц раскладываем всю диаграмму
+        let scope = if names.is_empty() {
+            LayoutScope::All
+        } else {
+            let mut tables = HashSet::new();
+            for name in &names {
+                match graph.find_table_by_name(name) {
+                    Some(idx) => {
+                        tables.insert(idx);
+                    }
+                    None => return ToolResponse::error(format!("Table '{}' not found", name)),
+                }
+            }
+            LayoutScope::Tables(tables)
+        };
+
+        let config = LayoutConfig {
+            algorithm: settings.layout,
+            scope,
+            ..Default::default()
+        };
+        let ops = auto_layout_with_config(graph, &config);
+        ToolResponse::success_with_ops(format!("Moved {} tables", ops.len()), ops)
+    }
+
     // ========================================================================
     // Validation operations
     // ========================================================================
 
     /// Validate SQL DDL statements without applying them
-    fn validate_sql_tool(params: &serde_json::Value) -> ToolResponse {
+    fn validate_sql_tool(params: &serde_json::Value, settings: &DiagramSettings) -> ToolResponse {
         let sql = match params.get("sql").and_then(|v| v.as_str()) {
             Some(sql) => sql,
             None => return ToolResponse::error("Missing required parameter: sql"),
         };
 
-        let dialect = params
+        // Explicit dialect parameter wins, otherwise use the diagram's dialect
+        let sql_dialect = params
             .get("dialect")
             .and_then(|v| v.as_str())
-            .unwrap_or("mysql");
-
-        let sql_dialect = match dialect.to_lowercase().as_str() {
-            "mysql" => SqlDialect::MySQL,
-            "postgresql" | "postgres" => SqlDialect::PostgreSQL,
-            "sqlite" => SqlDialect::SQLite,
-            _ => SqlDialect::MySQL,
-        };
+            .and_then(SqlDialect::from_name)
+            .unwrap_or_else(|| settings.dialect.clone());
 
-        let result = validate_sql(sql, sql_dialect);
+        let result = validate_sql_with_lint(sql, sql_dialect, &settings.lint);
 
         let message = if result.is_valid {
             format!(
@@ -1382,12 +1709,30 @@ impl ToolExecutor {
             )
         };
 
+        let apply_fixes = params
+            .get("apply_fixes")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if apply_fixes && result.has_fixes() {
+            return match result.fix_all(sql) {
+                Ok(fixed_sql) => {
+                    let mut response = ToolResponse::success_with_validation(
+                        format!("{} Fixes applied, re-validate fixed_sql.", message),
+                        result,
+                    );
+                    response.data = Some(serde_json::json!({ "fixed_sql": fixed_sql }));
+                    response
+                }
+                Err(e) => ToolResponse::error(format!("Failed to apply fixes: {}", e)),
+            };
+        }
+
         ToolResponse::success_with_validation(message, result)
     }
 
     /// Check the current schema for validity
-    fn check_schema(graph: &SchemaGraph) -> ToolResponse {
-        let result = crate::core::check_schema_sql(graph, SqlDialect::MySQL);
+    fn check_schema(graph: &SchemaGraph, settings: &DiagramSettings) -> ToolResponse {
+        let result = crate::core::check_schema_sql(graph, settings.dialect.clone());
 
         let message = if result.is_valid {
             format!(
@@ -1794,6 +2139,261 @@ mod tests {
         assert!(response.success);
     }
 
+    #[test]
+    fn test_create_relationship_consistency() {
+        let mut graph = create_demo_graph();
+        let relationship =
+            |from_column: &str, to_column: &str, relationship_type: &str| ToolRequest {
+                tool_name: "create_relationship".into(),
+                parameters: serde_json::json!({
+                    "from_table": "comments",
+                    "from_column": from_column,
+                    "to_table": "users",
+                    "to_column": to_column,
+                    "relationship_type": relationship_type
+                }),
+            };
+
+        // Incompatible types are rejected
+        let response = ToolExecutor::execute(&mut graph, &relationship("content", "id", "N:1"));
+        assert!(!response.success);
+        assert_eq!(graph.edge_count(), 3);
+
+        // 1:1 from a non-unique column is created with a warning
+        let response = ToolExecutor::execute(&mut graph, &relationship("user_id", "id", "1:1"));
+        assert!(response.success);
+        assert!(response.message.contains("Warning: 1:1 relationship"));
+        assert_eq!(
+            response.data.unwrap()["warnings"][0]["kind"],
+            "CardinalityMismatch"
+        );
+
+        let request = ToolRequest {
+            tool_name: "check_schema".into(),
+            parameters: serde_json::json!({}),
+        };
+        let response = ToolExecutor::execute(&mut graph, &request);
+        let validation = response.validation.unwrap().to_string();
+        assert!(validation.contains("R003_CARDINALITY_MISMATCH"));
+    }
+
+    #[test]
+    fn test_get_schema_sql_uses_diagram_dialect() {
+        let mut graph = create_demo_graph();
+        let settings = DiagramSettings {
+            dialect: SqlDialect::PostgreSQL,
+            ..Default::default()
+        };
+        let request = ToolRequest {
+            tool_name: "get_schema_sql".into(),
+            parameters: serde_json::json!({}),
+        };
+
+        let response = ToolExecutor::execute_with_settings(&mut graph, &settings, &request);
+        assert!(response.success);
+        let sql = response.data.unwrap();
+        assert!(sql.as_str().unwrap().contains("CREATE TABLE \"users\""));
+    }
+
+    #[test]
+    fn test_validate_sql_apply_fixes() {
+        let mut graph = create_demo_graph();
+        let request = ToolRequest {
+            tool_name: "validate_sql".into(),
+            parameters: serde_json::json!({
+                "sql": "CREATE TABLE a (id INT PRIMARY KEY); CREATE TABLE b (id INT PRIMARY KEY, a_id INT, FOREIGN KEY (a_id) REFERENCES a(idd));",
+                "apply_fixes": true
+            }),
+        };
+
+        let response = ToolExecutor::execute(&mut graph, &request);
+        assert!(!response.success);
+        let validation = response.validation.unwrap();
+        assert_eq!(
+            validation["diagnostics"][0]["fixes"][0]["title"],
+            "Change to 'id'"
+        );
+        let fixed_sql = response.data.unwrap()["fixed_sql"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert!(fixed_sql.contains("REFERENCES a(id)"));
+    }
+
+    #[test]
+    fn test_delete_requires_confirmation_when_dependents_exist() {
+        let mut graph = SchemaGraph::new();
+        let customers = graph.add_node(
+            crate::core::TableNode::new("customers")
+                .add_column(Column::new("id", "INT").primary_key())
+                .add_column(Column::new("note", "TEXT")),
+        );
+        let orders = graph.add_node(
+            crate::core::TableNode::new("orders")
+                .add_column(Column::new("id", "INT").primary_key())
+                .add_column(Column::new("customer_id", "INT")),
+        );
+        graph.add_edge(
+            orders,
+            customers,
+            Relationship::new(
+                "fk_orders_customer",
+                RelationshipType::ManyToOne,
+                "customer_id",
+                "id",
+            ),
+        );
+
+        let request = |tool: &str, params: serde_json::Value| ToolRequest {
+            tool_name: tool.into(),
+            parameters: params,
+        };
+
+        // Колонка без зависимостей удаляется сразу
+        let response = ToolExecutor::execute(
+            &mut graph,
+            &request(
+                "delete_column",
+                serde_json::json!({ "table_name": "customers", "column_name": "note" }),
+            ),
+        );
+        assert!(response.success);
+
+        let response = ToolExecutor::execute(
+            &mut graph,
+            &request(
+                "delete_column",
+                serde_json::json!({ "table_name": "customers", "column_name": "id" }),
+            ),
+        );
+        assert!(!response.success);
+        assert!(response.data.unwrap()["items"].as_array().unwrap().len() >= 2);
+        assert_eq!(graph.edge_count(), 1);
+
+        let response = ToolExecutor::execute(
+            &mut graph,
+            &request(
+                "delete_column",
+                serde_json::json!({ "table_name": "customers", "column_name": "id", "confirm": true }),
+            ),
+        );
+        assert!(response.success);
+        assert_eq!(response.graph_ops.len(), 2);
+        assert_eq!(graph.edge_count(), 0);
+
+        let response = ToolExecutor::execute(
+            &mut graph,
+            &request(
+                "delete_table",
+                serde_json::json!({ "table_name": "orders" }),
+            ),
+        );
+        assert!(response.success);
+    }
+
+    #[test]
+    fn test_get_join_path() {
+        let mut graph = create_demo_graph();
+        let names: Vec<String> = graph.node_weights().map(|t| t.name.clone()).collect();
+        let linked = graph
+            .edge_indices()
+            .filter_map(|e| graph.edge_endpoints(e))
+            .find(|(a, b)| a != b)
+            .map(|(a, b)| (graph[a].name.clone(), graph[b].name.clone()))
+            .unwrap();
+        assert!(names.contains(&linked.0));
+
+        let response = ToolExecutor::execute(
+            &mut graph,
+            &ToolRequest {
+                tool_name: "get_join_path".into(),
+                parameters: serde_json::json!({ "tables": [linked.0, linked.1] }),
+            },
+        );
+        assert!(response.success);
+        let paths = response.data.unwrap();
+        assert_eq!(paths[0]["joins"], 1);
+        assert!(paths[0]["sql"].as_str().unwrap().contains("JOIN"));
+
+        let response = ToolExecutor::execute(
+            &mut graph,
+            &ToolRequest {
+                tool_name: "get_join_path".into(),
+                parameters: serde_json::json!({ "tables": [linked.0] }),
+            },
+        );
+        assert!(!response.success);
+    }
+
+    #[test]
+    fn test_inferred_foreign_keys() {
+        let mut graph = SchemaGraph::new();
+        graph.add_node(
+            crate::core::TableNode::new("customers")
+                .add_column(Column::new("id", "INT").primary_key()),
+        );
+        graph.add_node(
+            crate::core::TableNode::new("orders")
+                .add_column(Column::new("id", "INT").primary_key())
+                .add_column(Column::new("customer_id", "INT")),
+        );
+
+        let response = ToolExecutor::execute(
+            &mut graph,
+            &ToolRequest {
+                tool_name: "get_inferred_foreign_keys".into(),
+                parameters: serde_json::json!({}),
+            },
+        );
+        assert!(response.success);
+        let candidates = &response.data.unwrap()["candidates"];
+        assert_eq!(candidates[0]["from_column"], "customer_id");
+        assert_eq!(candidates[0]["to_table"], "customers");
+        assert_eq!(graph.edge_count(), 0);
+
+        let response = ToolExecutor::execute(
+            &mut graph,
+            &ToolRequest {
+                tool_name: "apply_inferred_foreign_keys".into(),
+                parameters: serde_json::json!({ "min_confidence": 0.9 }),
+            },
+        );
+        assert!(response.success);
+        assert_eq!(response.graph_ops.len(), 1);
+        assert_eq!(graph.edge_count(), 1);
+    }
+
+    #[test]
+    fn test_auto_layout_tool_respects_scope() {
+        let mut graph = create_demo_graph();
+        let users = graph.find_table_by_name("users").unwrap();
+        let posts = graph.find_table_by_name("posts").unwrap();
+        let users_before = graph[users].position;
+
+        let response = ToolExecutor::execute(
+            &mut graph,
+            &ToolRequest {
+                tool_name: "auto_layout".into(),
+                parameters: serde_json::json!({ "tables": ["posts"] }),
+            },
+        );
+        assert!(response.success);
+        assert_eq!(graph[users].position, users_before);
+        assert!(response.graph_ops.iter().all(|op| matches!(
+            op,
+            GraphOperation::MoveTable { table_uuid, .. } if *table_uuid == graph[posts].uuid
+        )));
+
+        let response = ToolExecutor::execute(
+            &mut graph,
+            &ToolRequest {
+                tool_name: "auto_layout".into(),
+                parameters: serde_json::json!({ "tables": ["missing"] }),
+            },
+        );
+        assert!(!response.success);
+    }
+
     #[test]
     fn test_get_tool_definitions() {
         let tools = get_tool_definitions();
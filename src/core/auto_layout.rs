@@ -1,23 +1,106 @@
 //! Auto-layout module for automatic table arrangement
 //!
-//! This module provides a force-directed layout algorithm that arranges tables
-//! so that related tables (connected by foreign keys) are positioned close together,
-//! making relationships easy to understand visually.
+//! Two algorithms are available, selected with [`LayoutConfig::algorithm`]:
 //!
-//! The algorithm simulates physical forces:
+//! **Layered** (Sugiyama-style): referenced tables are placed above the
+//! tables that reference them.
+//! 1. Cycles are broken by temporarily reversing a small set of relationships
+//! 2. Tables are assigned to layers along the FK direction (long relationships
+//!    get invisible dummy nodes in every layer they pass through)
+//! 3. Each layer is reordered with barycenter sweeps to minimize crossings
+//! 4. Coordinates are assigned: layers are as tall as their tallest table
+//!    (estimated from the rows each table shows), tables are centered over their
+//!    neighbours
+//!
+//! The result depends only on the schema, not on the current positions.
+//!
+//! **Force** (default): a force-directed simulation:
 //! - **Attraction**: Connected tables are pulled together (like springs)
 //! - **Repulsion**: All tables push each other apart (like electric charges)
 //! - **Centering**: Tables are gently pulled toward the center to prevent drift
 //!
-//! This results in clusters of related tables with minimal edge crossings.
-
-use crate::core::schema::SchemaGraph;
+//! This results in clusters of related tables, starting from current positions.
+//! Disconnected parts of the schema are simulated separately and packed into
+//! rows. On large schemas repulsion uses the Barnes-Hut approximation, and
+//! [`LayoutJob`] runs the simulation a few iterations at a time so the UI can
+//! show progress and cancel it.
+//!
+//! **Incremental layout**: pinned tables ([`TableNode::pinned`]) never move, and
+//! [`LayoutConfig::scope`] can limit a run to a selection or to newly added
+//! tables. Movable tables are then laid out group by group and placed next to
+//! the fixed tables they are related to, without overlapping anything.
+
+use crate::core::liveshare::GraphOperation;
+#[cfg(doc)]
+use crate::core::schema::TableNode;
+use crate::core::schema::{RelationshipType, SchemaGraph};
+use crate::core::table_display::{TableDisplay, displayed_table_height};
 use petgraph::graph::NodeIndex;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use uuid::Uuid;
+
+/// Table width on the canvas
+pub const TABLE_WIDTH: f64 = 280.0;
+/// Table header height on the canvas
+pub const TABLE_HEADER_HEIGHT: f64 = 48.0;
+/// Column row height on the canvas
+pub const TABLE_ROW_HEIGHT: f64 = 36.0;
+/// Vertical padding around the column list
+const TABLE_PADDING: f64 = 16.0;
+
+/// Estimated rendered height of a table with `column_count` columns
+pub fn estimated_table_height(column_count: usize) -> f64 {
+    TABLE_HEADER_HEIGHT + TABLE_PADDING + column_count as f64 * TABLE_ROW_HEIGHT
+}
+
+/// Auto-layout algorithm
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LayoutAlgorithm {
+    /// Force-directed simulation
+    #[default]
+    Force,
+    /// Layers along FK direction with crossing minimization
+    Layered,
+}
+
+impl LayoutAlgorithm {
+    pub const ALL: [LayoutAlgorithm; 2] = [LayoutAlgorithm::Force, LayoutAlgorithm::Layered];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LayoutAlgorithm::Layered => "Layered",
+            LayoutAlgorithm::Force => "Force-directed",
+        }
+    }
+}
+
+/// Tables an auto-layout run may move (pinned tables never move)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum LayoutScope {
+    /// Every table
+    #[default]
+    All,
+    /// Only these tables, e.g. a selection or newly added tables
+    Tables(HashSet<NodeIndex>),
+}
+
+impl LayoutScope {
+    fn includes(&self, node: NodeIndex) -> bool {
+        match self {
+            LayoutScope::All => true,
+            LayoutScope::Tables(tables) => tables.contains(&node),
+        }
+    }
+}
 
 /// Layout configuration
 #[derive(Clone, Debug)]
 pub struct LayoutConfig {
+    /// Layout algorithm
+    pub algorithm: LayoutAlgorithm,
+    /// Tables the layout may move
+    pub scope: LayoutScope,
     /// Horizontal spacing between tables
     pub horizontal_spacing: f64,
     /// Vertical spacing between layers
@@ -28,10 +111,15 @@ pub struct LayoutConfig {
     pub start_y: f64,
     /// Estimated table width for spacing calculations
     pub table_width: f64,
-    /// Estimated table height for spacing calculations
+    /// Estimated height of a table showing all its columns, for spacing calculations
+    ///
+    /// When no table shows all its columns, the force layout uses the tallest
+    /// displayed table instead.
     pub table_height: f64,
     /// Number of iterations for force simulation
     pub iterations: usize,
+    /// Number of barycenter sweeps for crossing minimization (layered layout)
+    pub crossing_sweeps: usize,
     /// Initial temperature (movement speed) for simulated annealing
     pub initial_temperature: f64,
     /// Cooling rate per iteration
@@ -42,23 +130,29 @@ pub struct LayoutConfig {
     pub repulsion_strength: f64,
     /// Attraction strength for connected nodes
     pub attraction_strength: f64,
+    /// Barnes-Hut accuracy for repulsion on large schemas (0 = exact pairwise)
+    pub barnes_hut_theta: f64,
 }
 
 impl Default for LayoutConfig {
     fn default() -> Self {
         Self {
+            algorithm: LayoutAlgorithm::default(),
+            scope: LayoutScope::All,
             horizontal_spacing: 80.0,
             vertical_spacing: 100.0,
             start_x: 100.0,
             start_y: 100.0,
-            table_width: 280.0,
+            table_width: TABLE_WIDTH,
             table_height: 250.0,
             iterations: 300,
+            crossing_sweeps: 24,
             initial_temperature: 100.0,
             cooling_rate: 0.95,
             ideal_edge_length: 400.0,
             repulsion_strength: 50000.0,
             attraction_strength: 0.1,
+            barnes_hut_theta: 0.9,
         }
     }
 }
@@ -69,6 +163,74 @@ pub struct LayoutResult {
     pub positions: Vec<(NodeIndex, (f64, f64))>,
 }
 
+/// Layout positions keyed by table UUID
+///
+/// A layout computed on a snapshot (on the server, or over several frames) is
+/// applied to a graph that may have changed meanwhile. Node indices of a
+/// [`SchemaGraph`] are reused after deletions, so the positions are matched
+/// back to tables by UUID.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TablePositions {
+    pub positions: Vec<(Uuid, (f64, f64))>,
+}
+
+impl TablePositions {
+    /// Positions of `layout`, calculated for `graph`
+    pub fn of(graph: &SchemaGraph, layout: &LayoutResult) -> Self {
+        Self {
+            positions: layout
+                .positions
+                .iter()
+                .filter_map(|&(node, position)| Some((graph.node_weight(node)?.uuid, position)))
+                .collect(),
+        }
+    }
+
+    /// Layout for the tables of `graph` that still exist
+    pub fn resolve(&self, graph: &SchemaGraph) -> LayoutResult {
+        let nodes: HashMap<Uuid, NodeIndex> =
+            graph.node_indices().map(|n| (graph[n].uuid, n)).collect();
+        LayoutResult {
+            positions: self
+                .positions
+                .iter()
+                .filter_map(|(uuid, position)| Some((*nodes.get(uuid)?, *position)))
+                .collect(),
+        }
+    }
+}
+
+/// Auto-layout of a whole diagram, calculated by the server
+///
+/// Layouts that cannot run step by step ([`LayoutJob::runs_in_steps`]) would
+/// block the page, so the canvas sends them to `/api/layout`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LayoutRequest {
+    pub graph: SchemaGraph,
+    pub algorithm: LayoutAlgorithm,
+}
+
+impl LayoutRequest {
+    /// Largest diagram the server lays out
+    pub const MAX_TABLES: usize = 2000;
+    /// Most relationships the server lays out
+    pub const MAX_RELATIONSHIPS: usize = 10_000;
+
+    /// Whether the diagram is small enough for the server
+    pub fn within_limits(&self) -> bool {
+        self.graph.node_count() <= Self::MAX_TABLES
+            && self.graph.edge_count() <= Self::MAX_RELATIONSHIPS
+    }
+
+    pub fn calculate(&self) -> TablePositions {
+        let config = LayoutConfig {
+            algorithm: self.algorithm,
+            ..Default::default()
+        };
+        TablePositions::of(&self.graph, &calculate_layout(&self.graph, &config))
+    }
+}
+
 /// 2D Vector for physics calculations
 #[derive(Clone, Copy, Debug, Default)]
 struct Vec2 {
@@ -107,80 +269,190 @@ impl Vec2 {
     }
 }
 
+/// Components smaller than this use exact pairwise repulsion
+const BARNES_HUT_MIN_TABLES: usize = 64;
+
+/// Depth limit of the Barnes-Hut quadtree; deeper cells keep several tables
+const QUADTREE_MAX_DEPTH: usize = 24;
+
 /// Performs force-directed layout on the schema graph
 ///
 /// The algorithm:
-/// 1. Initialize positions (use existing or arrange in circle)
-/// 2. Simulate physical forces iteratively:
-///    - Repulsion between all node pairs
+/// 1. Split the schema into connected components
+/// 2. Lay out each component on its own:
+///    - Initialize positions (use existing or arrange in circle)
+///    - Repulsion between all node pairs (Barnes-Hut approximation on large components)
 ///    - Attraction along edges (foreign key relationships)
 ///    - Centering force to prevent drift
-/// 3. Apply simulated annealing (gradually reduce movement)
+///    - Simulated annealing (gradually reduce movement)
+/// 3. Pack the components into rows, largest first
 /// 4. Return final positions
+///
+/// Use [`ForceLayoutJob`] to run the simulation in slices instead.
+pub fn calculate_force_layout(graph: &SchemaGraph, config: &LayoutConfig) -> LayoutResult {
+    let mut job = ForceLayoutJob::new(graph, config);
+    while !job.step() {}
+    job.finish()
+}
+
+/// Force-directed layout (the name predates the layered layout)
+#[deprecated(
+    note = "use `calculate_force_layout`, or `calculate_layered_layout` for a layered layout"
+)]
 pub fn calculate_hierarchical_layout(graph: &SchemaGraph, config: &LayoutConfig) -> LayoutResult {
-    let node_count = graph.node_count();
+    calculate_force_layout(graph, config)
+}
 
-    if node_count == 0 {
-        return LayoutResult { positions: vec![] };
-    }
+/// Force-directed layout that runs one iteration at a time
+///
+/// Large schemas take a while to simulate; the UI runs a few iterations per
+/// frame, shows [`progress`](Self::progress) and cancels by dropping the job.
+pub struct ForceLayoutJob {
+    config: LayoutConfig,
+    components: Vec<ForceSimulation>,
+    /// Index of the component being simulated
+    current: usize,
+}
 
-    if node_count == 1 {
-        let idx = graph.node_indices().next().unwrap();
-        return LayoutResult {
-            positions: vec![(idx, (config.start_x + 200.0, config.start_y + 200.0))],
+impl ForceLayoutJob {
+    /// Prepare the simulation of every connected component of `graph`
+    pub fn new(graph: &SchemaGraph, config: &LayoutConfig) -> Self {
+        let config = LayoutConfig {
+            table_height: spacing_table_height(graph, config),
+            ..config.clone()
         };
+        let nodes: Vec<NodeIndex> = graph.node_indices().collect();
+        let all: HashSet<NodeIndex> = nodes.iter().copied().collect();
+        let components = movable_groups(graph, &nodes, &all)
+            .into_iter()
+            .map(|component| ForceSimulation::new(graph, component, &config))
+            .collect();
+        Self {
+            config,
+            components,
+            current: 0,
+        }
     }
 
-    // Collect node indices
-    let nodes: Vec<NodeIndex> = graph.node_indices().collect();
-    let node_to_idx: HashMap<NodeIndex, usize> =
-        nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
-
-    // Initialize positions in a circle or use existing positions
-    let mut positions: Vec<Vec2> = initialize_positions(graph, &nodes, config);
+    /// Run one simulation iteration; returns `true` once the simulation is done
+    pub fn step(&mut self) -> bool {
+        while let Some(component) = self.components.get_mut(self.current) {
+            if component.is_finished(&self.config) {
+                self.current += 1;
+            } else {
+                component.step(&self.config);
+                return false;
+            }
+        }
+        true
+    }
 
-    // Calculate center of canvas
-    let center = Vec2::new(config.start_x + 600.0, config.start_y + 400.0);
+    /// Share of the simulation already done, from 0.0 to 1.0
+    pub fn progress(&self) -> f64 {
+        let total = self.components.len() * self.config.iterations;
+        if total == 0 {
+            return 1.0;
+        }
+        let done: usize = self
+            .components
+            .iter()
+            .map(|c| {
+                if c.is_finished(&self.config) {
+                    self.config.iterations
+                } else {
+                    c.iteration
+                }
+            })
+            .sum();
+        done as f64 / total as f64
+    }
 
-    // Collect edges for attraction forces
-    let edges: Vec<(usize, usize)> = graph
-        .edge_indices()
-        .filter_map(|e| {
-            let (a, b) = graph.edge_endpoints(e)?;
-            Some((*node_to_idx.get(&a).unwrap(), *node_to_idx.get(&b).unwrap()))
-        })
-        .collect();
+    /// Final positions: overlaps are removed and the components are packed
+    ///
+    /// Components that have not finished simulating keep their current state.
+    pub fn finish(self) -> LayoutResult {
+        let config = &self.config;
+        let blocks: Vec<Vec<(NodeIndex, Vec2)>> = self
+            .components
+            .iter()
+            .map(|c| {
+                let positions = prevent_overlaps(&c.positions, config);
+                let min_x = positions.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
+                let min_y = positions.iter().map(|p| p.y).fold(f64::INFINITY, f64::min);
+                c.nodes
+                    .iter()
+                    .zip(&positions)
+                    .map(|(&node, p)| (node, Vec2::new(p.x - min_x, p.y - min_y)))
+                    .collect()
+            })
+            .collect();
+        let sizes: Vec<(f64, f64)> = blocks
+            .iter()
+            .map(|block| {
+                (
+                    block.iter().map(|(_, p)| p.x).fold(0.0, f64::max) + config.table_width,
+                    block.iter().map(|(_, p)| p.y).fold(0.0, f64::max) + config.table_height,
+                )
+            })
+            .collect();
 
-    // Simulated annealing with force-directed layout
-    let mut temperature = config.initial_temperature;
+        let mut positions: Vec<(NodeIndex, (f64, f64))> = blocks
+            .iter()
+            .zip(pack_blocks(&sizes, config))
+            .flat_map(|(block, (dx, dy))| {
+                block.iter().map(move |&(node, p)| {
+                    (node, (p.x + dx + config.start_x, p.y + dy + config.start_y))
+                })
+            })
+            .collect();
+        positions.sort_by_key(|(node, _)| *node);
+        LayoutResult { positions }
+    }
+}
 
-    for _iteration in 0..config.iterations {
-        let mut forces: Vec<Vec2> = vec![Vec2::default(); node_count];
+/// Force simulation of one connected component
+struct ForceSimulation {
+    nodes: Vec<NodeIndex>,
+    positions: Vec<Vec2>,
+    /// Relationships as pairs of indices into `nodes`
+    edges: Vec<(usize, usize)>,
+    center: Vec2,
+    temperature: f64,
+    iteration: usize,
+}
 
-        // Calculate repulsion forces between all pairs
-        for i in 0..node_count {
-            for j in (i + 1)..node_count {
-                let delta = positions[i].sub(positions[j]);
-                let distance = delta.length().max(1.0);
+impl ForceSimulation {
+    fn new(graph: &SchemaGraph, nodes: Vec<NodeIndex>, config: &LayoutConfig) -> Self {
+        let local: HashMap<NodeIndex, usize> =
+            nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        let edges = graph
+            .edge_indices()
+            .filter_map(|e| {
+                let (a, b) = graph.edge_endpoints(e)?;
+                Some((*local.get(&a)?, *local.get(&b)?))
+            })
+            .collect();
+        Self {
+            positions: initialize_positions(graph, &nodes, config),
+            nodes,
+            edges,
+            center: Vec2::new(config.start_x + 600.0, config.start_y + 400.0),
+            temperature: config.initial_temperature,
+            iteration: 0,
+        }
+    }
 
-                // Repulsion force (inverse square law, but with table size consideration)
-                let min_distance = config.table_width + config.horizontal_spacing;
-                let repulsion = if distance < min_distance {
-                    // Strong repulsion when overlapping
-                    config.repulsion_strength * 2.0 / (distance * distance).max(1.0)
-                } else {
-                    config.repulsion_strength / (distance * distance)
-                };
+    fn is_finished(&self, config: &LayoutConfig) -> bool {
+        // Одиночной таблице нечего симулировать
+        self.nodes.len() < 2 || self.iteration >= config.iterations || self.temperature < 0.1
+    }
 
-                let force = delta.normalize().scale(repulsion);
-                forces[i] = forces[i].add(force);
-                forces[j] = forces[j].sub(force);
-            }
-        }
+    fn step(&mut self, config: &LayoutConfig) {
+        let mut forces = repulsion_forces(&self.positions, config);
 
         // Calculate attraction forces along edges
-        for &(a, b) in &edges {
-            let delta = positions[b].sub(positions[a]);
+        for &(a, b) in &self.edges {
+            let delta = self.positions[b].sub(self.positions[a]);
             let distance = delta.length().max(1.0);
 
             // Attraction force (spring-like, proportional to distance from ideal)
@@ -193,56 +465,266 @@ pub fn calculate_hierarchical_layout(graph: &SchemaGraph, config: &LayoutConfig)
         }
 
         // Centering force (gentle pull toward center)
-        for i in 0..node_count {
-            let to_center = center.sub(positions[i]);
-            let centering_force = to_center.scale(0.01);
-            forces[i] = forces[i].add(centering_force);
+        for (force, position) in forces.iter_mut().zip(&self.positions) {
+            *force = force.add(self.center.sub(*position).scale(0.01));
         }
 
         // Apply forces with temperature limiting
-        for i in 0..node_count {
-            let force_magnitude = forces[i].length();
+        for (position, force) in self.positions.iter_mut().zip(&forces) {
+            let force_magnitude = force.length();
             if force_magnitude > 0.01 {
                 // Limit movement by temperature
-                let capped_magnitude = force_magnitude.min(temperature);
-                let movement = forces[i].normalize().scale(capped_magnitude);
-                positions[i] = positions[i].add(movement);
+                let capped_magnitude = force_magnitude.min(self.temperature);
+                *position = position.add(force.normalize().scale(capped_magnitude));
             }
         }
 
         // Cool down
-        temperature *= config.cooling_rate;
+        self.temperature *= config.cooling_rate;
+        self.iteration += 1;
+    }
+}
 
-        // Stop if temperature is very low
-        if temperature < 0.1 {
-            break;
+/// Repulsion acting on every table
+fn repulsion_forces(positions: &[Vec2], config: &LayoutConfig) -> Vec<Vec2> {
+    if config.barnes_hut_theta > 0.0 && positions.len() >= BARNES_HUT_MIN_TABLES {
+        let tree = QuadTree::build(positions);
+        return positions
+            .iter()
+            .enumerate()
+            .map(|(i, &position)| tree.repulsion(i, position, positions, config))
+            .collect();
+    }
+
+    let mut forces = vec![Vec2::default(); positions.len()];
+    for i in 0..positions.len() {
+        for j in (i + 1)..positions.len() {
+            let force = repulsion_force(positions[i].sub(positions[j]), 1.0, config);
+            forces[i] = forces[i].add(force);
+            forces[j] = forces[j].sub(force);
         }
     }
+    forces
+}
 
-    // Prevent overlapping with final adjustment pass
-    positions = prevent_overlaps(&positions, config);
+/// Repulsion from `mass` tables located at `delta` from the table
+fn repulsion_force(delta: Vec2, mass: f64, config: &LayoutConfig) -> Vec2 {
+    let distance = delta.length().max(1.0);
 
-    // Normalize positions to start from config.start_x/start_y
-    let min_x = positions.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
-    let min_y = positions.iter().map(|p| p.y).fold(f64::INFINITY, f64::min);
+    // Repulsion force (inverse square law, but with table size consideration)
+    let min_distance = config.table_width + config.horizontal_spacing;
+    let repulsion = if distance < min_distance {
+        // Strong repulsion when overlapping
+        config.repulsion_strength * 2.0 / (distance * distance).max(1.0)
+    } else {
+        config.repulsion_strength / (distance * distance)
+    };
 
-    let final_positions: Vec<(NodeIndex, (f64, f64))> = nodes
-        .iter()
-        .zip(positions.iter())
-        .map(|(&node, pos)| {
-            (
-                node,
-                (
-                    pos.x - min_x + config.start_x,
-                    pos.y - min_y + config.start_y,
-                ),
-            )
-        })
-        .collect();
+    delta.normalize().scale(repulsion * mass)
+}
+
+/// Square cell of the Barnes-Hut quadtree
+struct QuadCell {
+    center: Vec2,
+    half_size: f64,
+    /// Number of tables inside the cell
+    mass: f64,
+    mass_center: Vec2,
+    children: Option<[usize; 4]>,
+    /// Tables of a leaf cell
+    bodies: Vec<usize>,
+}
+
+impl QuadCell {
+    fn new(center: Vec2, half_size: f64) -> Self {
+        Self {
+            center,
+            half_size,
+            mass: 0.0,
+            mass_center: Vec2::default(),
+            children: None,
+            bodies: Vec::new(),
+        }
+    }
+
+    fn quadrant(&self, position: Vec2) -> usize {
+        usize::from(position.x >= self.center.x) + 2 * usize::from(position.y >= self.center.y)
+    }
+
+    fn contains(&self, position: Vec2) -> bool {
+        (position.x - self.center.x).abs() <= self.half_size
+            && (position.y - self.center.y).abs() <= self.half_size
+    }
+}
+
+/// Quadtree over table positions for the Barnes-Hut approximation
+///
+/// A cell that looks small from a table (size / distance below
+/// [`LayoutConfig::barnes_hut_theta`]) repels it as a single body at its
+/// center of mass, so one repulsion pass costs O(n log n) instead of O(n²).
+struct QuadTree {
+    cells: Vec<QuadCell>,
+}
+
+impl QuadTree {
+    fn build(positions: &[Vec2]) -> Self {
+        let min_x = positions.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
+        let max_x = positions
+            .iter()
+            .map(|p| p.x)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let min_y = positions.iter().map(|p| p.y).fold(f64::INFINITY, f64::min);
+        let max_y = positions
+            .iter()
+            .map(|p| p.y)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let root = QuadCell::new(
+            Vec2::new((min_x + max_x) / 2.0, (min_y + max_y) / 2.0),
+            ((max_x - min_x).max(max_y - min_y) / 2.0).max(1.0),
+        );
+
+        let mut tree = Self { cells: vec![root] };
+        for (body, &position) in positions.iter().enumerate() {
+            tree.insert(0, body, position, positions, 0);
+        }
+        tree
+    }
+
+    fn insert(
+        &mut self,
+        cell: usize,
+        body: usize,
+        position: Vec2,
+        positions: &[Vec2],
+        depth: usize,
+    ) {
+        let node = &mut self.cells[cell];
+        node.mass_center = node
+            .mass_center
+            .scale(node.mass)
+            .add(position)
+            .scale(1.0 / (node.mass + 1.0));
+        node.mass += 1.0;
+
+        if let Some(children) = node.children {
+            let child = children[node.quadrant(position)];
+            self.insert(child, body, position, positions, depth + 1);
+            return;
+        }
+        if node.bodies.is_empty() || depth >= QUADTREE_MAX_DEPTH {
+            node.bodies.push(body);
+            return;
+        }
+
+        // Лист уже занят: делим ячейку и спускаем таблицы в дочерние
+        let existing = std::mem::take(&mut node.bodies);
+        let (center, half_size) = (node.center, node.half_size / 2.0);
+        let first = self.cells.len();
+        for (sx, sy) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+            self.cells.push(QuadCell::new(
+                Vec2::new(center.x + sx * half_size, center.y + sy * half_size),
+                half_size,
+            ));
+        }
+        self.cells[cell].children = Some([first, first + 1, first + 2, first + 3]);
+        for other in existing.into_iter().chain([body]) {
+            let child = first + self.cells[cell].quadrant(positions[other]);
+            self.insert(child, other, positions[other], positions, depth + 1);
+        }
+    }
+
+    /// Repulsion acting on table `body` at `position`
+    fn repulsion(
+        &self,
+        body: usize,
+        position: Vec2,
+        positions: &[Vec2],
+        config: &LayoutConfig,
+    ) -> Vec2 {
+        let mut force = Vec2::default();
+        let mut stack = vec![0];
+        while let Some(cell) = stack.pop() {
+            let cell = &self.cells[cell];
+            match cell.children {
+                Some(children) => {
+                    let delta = position.sub(cell.mass_center);
+                    // Далёкая ячейка отталкивает как одна таблица в центре масс
+                    if !cell.contains(position)
+                        && cell.half_size * 2.0 < config.barnes_hut_theta * delta.length()
+                    {
+                        force = force.add(repulsion_force(delta, cell.mass, config));
+                    } else {
+                        stack.extend(children);
+                    }
+                }
+                None => {
+                    for &other in cell.bodies.iter().filter(|&&other| other != body) {
+                        let delta = position.sub(positions[other]);
+                        force = force.add(repulsion_force(delta, 1.0, config));
+                    }
+                }
+            }
+        }
+        force
+    }
+}
+
+/// Width to height ratio the packed components aim for (canvas is wider than tall)
+const PACKING_ASPECT_RATIO: f64 = 1.5;
+
+/// Offsets of blocks of the given sizes packed into rows, tallest first
+///
+/// Every row width at which a row break changes is tried; the packing whose
+/// overall shape is closest to [`PACKING_ASPECT_RATIO`] wins.
+fn pack_blocks(sizes: &[(f64, f64)], config: &LayoutConfig) -> Vec<(f64, f64)> {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|&a, &b| sizes[b].1.total_cmp(&sizes[a].1));
+
+    // Ширины, при которых в первый ряд помещается 1, 2, ... блоков
+    let mut candidates = Vec::with_capacity(order.len());
+    let mut row_width = 0.0;
+    for &i in &order {
+        row_width += sizes[i].0;
+        candidates.push(row_width);
+        row_width += config.horizontal_spacing;
+    }
+
+    let mut best: Option<(f64, Vec<(f64, f64)>)> = None;
+    for row_width in candidates {
+        let (offsets, width, height) = pack_rows(sizes, &order, row_width, config);
+        let mismatch = (width / height / PACKING_ASPECT_RATIO).ln().abs();
+        if best.as_ref().is_none_or(|(m, _)| mismatch < *m) {
+            best = Some((mismatch, offsets));
+        }
+    }
+    best.map(|(_, offsets)| offsets).unwrap_or_default()
+}
 
-    LayoutResult {
-        positions: final_positions,
+/// Shelf packing of blocks in `order` into rows at most `row_width` wide
+///
+/// Returns the offsets and the overall width and height.
+fn pack_rows(
+    sizes: &[(f64, f64)],
+    order: &[usize],
+    row_width: f64,
+    config: &LayoutConfig,
+) -> (Vec<(f64, f64)>, f64, f64) {
+    let mut offsets = vec![(0.0, 0.0); sizes.len()];
+    let (mut x, mut y, mut row_height) = (0.0, 0.0, 0.0_f64);
+    let mut width = 0.0_f64;
+    for &i in order {
+        let (block_width, block_height) = sizes[i];
+        if x > 0.0 && x + block_width > row_width {
+            x = 0.0;
+            y += row_height + config.vertical_spacing;
+            row_height = 0.0;
+        }
+        offsets[i] = (x, y);
+        width = width.max(x + block_width);
+        x += block_width + config.horizontal_spacing;
+        row_height = row_height.max(block_height);
     }
+    (offsets, width, y + row_height)
 }
 
 /// Initialize positions - use existing or arrange in circle
@@ -293,6 +775,20 @@ fn initialize_positions(
     }
 }
 
+/// Table height used for uniform spacing
+///
+/// [`LayoutConfig::table_height`] estimates tables showing all columns; tables
+/// reduced by their display level count with their displayed height.
+fn spacing_table_height(graph: &SchemaGraph, config: &LayoutConfig) -> f64 {
+    graph
+        .node_indices()
+        .map(|n| match graph[n].display {
+            TableDisplay::Full => config.table_height,
+            _ => displayed_table_height(graph, n),
+        })
+        .fold(0.0, f64::max)
+}
+
 /// Final pass to prevent any remaining overlaps
 fn prevent_overlaps(positions: &[Vec2], config: &LayoutConfig) -> Vec<Vec2> {
     let mut result = positions.to_vec();
@@ -340,106 +836,908 @@ fn prevent_overlaps(positions: &[Vec2], config: &LayoutConfig) -> Vec<Vec2> {
     result
 }
 
-/// Applies the calculated layout to the graph
-pub fn apply_layout(graph: &mut SchemaGraph, layout: &LayoutResult) {
-    for (node_idx, (x, y)) in &layout.positions {
-        if let Some(node) = graph.node_weight_mut(*node_idx) {
-            node.position = (*x, *y);
-        }
+/// Calculate a layout with the algorithm selected in `config`
+///
+/// Only tables in `config.scope` that are not pinned get positions. When some
+/// tables have to stay in place, the others are placed around them.
+pub fn calculate_layout(graph: &SchemaGraph, config: &LayoutConfig) -> LayoutResult {
+    let movable = movable_tables(graph, config);
+    if movable.len() == graph.node_count() {
+        calculate_full_layout(graph, config)
+    } else {
+        calculate_incremental_layout(graph, &movable, config)
     }
 }
 
-/// Convenience function to auto-layout with default config
-pub fn auto_layout(graph: &mut SchemaGraph) {
-    let config = LayoutConfig::default();
-    let layout = calculate_hierarchical_layout(graph, &config);
-    apply_layout(graph, &layout);
+/// Tables in `config.scope` that are not pinned
+fn movable_tables(graph: &SchemaGraph, config: &LayoutConfig) -> Vec<NodeIndex> {
+    graph
+        .node_indices()
+        .filter(|&n| !graph[n].pinned && config.scope.includes(n))
+        .collect()
 }
 
-/// Convenience function to auto-layout with custom config
-pub fn auto_layout_with_config(graph: &mut SchemaGraph, config: &LayoutConfig) {
-    let layout = calculate_hierarchical_layout(graph, config);
-    apply_layout(graph, &layout);
+/// Layout run that can be spread over several UI frames
+///
+/// Only a full force-directed layout is simulated step by step; every other
+/// layout is computed when the job is created.
+pub enum LayoutJob {
+    /// Force simulation in progress
+    Force(ForceLayoutJob),
+    /// Layout computed up front
+    Ready(LayoutResult),
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::core::schema::{Column, Relationship, RelationshipOps, RelationshipType, TableNode};
+impl LayoutJob {
+    /// Start the layout selected in `config` (same result as [`calculate_layout`])
+    pub fn new(graph: &SchemaGraph, config: &LayoutConfig) -> Self {
+        if Self::runs_in_steps(graph, config) {
+            LayoutJob::Force(ForceLayoutJob::new(graph, config))
+        } else {
+            LayoutJob::Ready(calculate_layout(graph, config))
+        }
+    }
 
-    #[test]
-    fn test_empty_graph() {
-        let graph = SchemaGraph::new();
-        let config = LayoutConfig::default();
-        let result = calculate_hierarchical_layout(&graph, &config);
-        assert!(result.positions.is_empty());
+    /// Whether the job is simulated step by step rather than computed up front
+    pub fn runs_in_steps(graph: &SchemaGraph, config: &LayoutConfig) -> bool {
+        config.algorithm == LayoutAlgorithm::Force
+            && movable_tables(graph, config).len() == graph.node_count()
     }
 
-    #[test]
-    fn test_single_table() {
-        let mut graph = SchemaGraph::new();
-        let table = TableNode::new("users");
-        graph.add_node(table);
+    /// Run one step; returns `true` once the layout is ready
+    pub fn step(&mut self) -> bool {
+        match self {
+            LayoutJob::Force(job) => job.step(),
+            LayoutJob::Ready(_) => true,
+        }
+    }
 
-        let config = LayoutConfig::default();
-        let result = calculate_hierarchical_layout(&graph, &config);
+    /// Share of the work already done, from 0.0 to 1.0
+    pub fn progress(&self) -> f64 {
+        match self {
+            LayoutJob::Force(job) => job.progress(),
+            LayoutJob::Ready(_) => 1.0,
+        }
+    }
 
-        assert_eq!(result.positions.len(), 1);
+    /// Final positions
+    pub fn finish(self) -> LayoutResult {
+        match self {
+            LayoutJob::Force(job) => job.finish(),
+            LayoutJob::Ready(layout) => layout,
+        }
     }
+}
 
-    #[test]
-    fn test_two_related_tables() {
-        let mut graph = SchemaGraph::new();
+fn calculate_full_layout(graph: &SchemaGraph, config: &LayoutConfig) -> LayoutResult {
+    match config.algorithm {
+        LayoutAlgorithm::Layered => calculate_layered_layout(graph, config),
+        LayoutAlgorithm::Force => calculate_force_layout(graph, config),
+    }
+}
 
-        // Users table (parent)
-        let users = TableNode::new("users").add_column(Column::new("id", "INT").primary_key());
-        let users_idx = graph.add_node(users);
+/// Table rectangle used for overlap checks
+#[derive(Clone, Copy, Debug)]
+struct Rect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
 
-        // Orders table (child, references users)
-        let orders = TableNode::new("orders")
-            .add_column(Column::new("id", "INT").primary_key())
-            .add_column(Column::new("user_id", "INT"));
-        let orders_idx = graph.add_node(orders);
+impl Rect {
+    fn of(
+        graph: &SchemaGraph,
+        node: NodeIndex,
+        position: (f64, f64),
+        config: &LayoutConfig,
+    ) -> Self {
+        Self {
+            x: position.0,
+            y: position.1,
+            width: config.table_width,
+            height: displayed_table_height(graph, node),
+        }
+    }
 
-        // Add relationship: orders -> users
-        graph
-            .create_relationship(
-                orders_idx,
-                users_idx,
-                Relationship::new(
-                    "fk_orders_users",
-                    RelationshipType::ManyToOne,
-                    "user_id",
-                    "id",
-                ),
-            )
-            .unwrap();
+    fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
 
-        let config = LayoutConfig::default();
-        let result = calculate_hierarchical_layout(&graph, &config);
+    fn shifted(&self, dx: f64, dy: f64) -> Self {
+        Self {
+            x: self.x + dx,
+            y: self.y + dy,
+            ..*self
+        }
+    }
 
-        assert_eq!(result.positions.len(), 2);
+    /// Whether the rectangles are closer than the gaps
+    fn collides(&self, other: &Rect, gap_x: f64, gap_y: f64) -> bool {
+        self.x < other.x + other.width + gap_x
+            && other.x < self.x + self.width + gap_x
+            && self.y < other.y + other.height + gap_y
+            && other.y < self.y + self.height + gap_y
+    }
+}
 
-        // Find positions
-        let users_pos = result
-            .positions
-            .iter()
-            .find(|(idx, _)| *idx == users_idx)
-            .map(|(_, p)| p);
-        let orders_pos = result
-            .positions
+/// Lay out `movable` tables while all other tables stay in place
+///
+/// Every connected group of movable tables is laid out on its own with the
+/// configured algorithm and then moved, as a block, to the free spot closest
+/// to the fixed tables it is related to (or to where the group already is).
+fn calculate_incremental_layout(
+    graph: &SchemaGraph,
+    movable: &[NodeIndex],
+    config: &LayoutConfig,
+) -> LayoutResult {
+    let movable_set: HashSet<NodeIndex> = movable.iter().copied().collect();
+    let mut obstacles: Vec<Rect> = graph
+        .node_indices()
+        .filter(|n| !movable_set.contains(n))
+        .map(|n| Rect::of(graph, n, graph[n].position, config))
+        .collect();
+    let mut positions = Vec::with_capacity(movable.len());
+
+    for group in movable_groups(graph, movable, &movable_set) {
+        let block = layout_group(graph, &group, config);
+        let rects: Vec<Rect> = block
             .iter()
-            .find(|(idx, _)| *idx == orders_idx)
-            .map(|(_, p)| p);
+            .map(|&(n, position)| Rect::of(graph, n, position, config))
+            .collect();
+
+        // Центр притяжения: связанные неподвижные таблицы или сама группа
+        let anchors: Vec<(f64, f64)> = {
+            let related: Vec<(f64, f64)> = group
+                .iter()
+                .flat_map(|&n| graph.neighbors_undirected(n))
+                .filter(|m| !movable_set.contains(m))
+                .map(|m| Rect::of(graph, m, graph[m].position, config).center())
+                .collect();
+            if related.is_empty() {
+                group
+                    .iter()
+                    .map(|&n| Rect::of(graph, n, graph[n].position, config).center())
+                    .collect()
+            } else {
+                related
+            }
+        };
+        let target = (
+            anchors.iter().map(|p| p.0).sum::<f64>() / anchors.len() as f64,
+            anchors.iter().map(|p| p.1).sum::<f64>() / anchors.len() as f64,
+        );
+        let width = rects.iter().map(|r| r.x + r.width).fold(0.0, f64::max);
+        let height = rects.iter().map(|r| r.y + r.height).fold(0.0, f64::max);
+        let desired = (target.0 - width / 2.0, target.1 - height / 2.0);
+
+        let (dx, dy) = find_free_offset(&rects, desired, &obstacles, config);
+        for (&(node, (x, y)), rect) in block.iter().zip(&rects) {
+            positions.push((node, (x + dx, y + dy)));
+            obstacles.push(rect.shifted(dx, dy));
+        }
+    }
 
-        // Both should have positions
-        assert!(users_pos.is_some());
-        assert!(orders_pos.is_some());
+    LayoutResult { positions }
+}
 
-        // Connected tables should be relatively close (within ideal_edge_length * 2)
-        let (ux, uy) = users_pos.unwrap();
-        let (ox, oy) = orders_pos.unwrap();
-        let distance = ((ux - ox).powi(2) + (uy - oy).powi(2)).sqrt();
+/// Connected groups of movable tables (relationships to fixed tables are ignored)
+fn movable_groups(
+    graph: &SchemaGraph,
+    movable: &[NodeIndex],
+    movable_set: &HashSet<NodeIndex>,
+) -> Vec<Vec<NodeIndex>> {
+    let mut seen: HashSet<NodeIndex> = HashSet::new();
+    let mut groups = Vec::new();
+    for &start in movable {
+        if !seen.insert(start) {
+            continue;
+        }
+        let mut group = vec![start];
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            for next in graph.neighbors_undirected(node) {
+                if movable_set.contains(&next) && seen.insert(next) {
+                    group.push(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+        group.sort_unstable();
+        groups.push(group);
+    }
+    groups
+}
+
+/// Layout of a group on its own, with the top-left corner at (0, 0)
+fn layout_group(
+    graph: &SchemaGraph,
+    group: &[NodeIndex],
+    config: &LayoutConfig,
+) -> Vec<(NodeIndex, (f64, f64))> {
+    if let [single] = group {
+        return vec![(*single, (0.0, 0.0))];
+    }
+
+    let mut sub = SchemaGraph::new();
+    let mut local: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+    let mut original: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+    for &node in group {
+        let mut table = graph[node].clone();
+        table.pinned = false;
+        let idx = sub.add_node(table);
+        local.insert(node, idx);
+        original.insert(idx, node);
+    }
+    for edge in graph.edge_indices() {
+        if let Some((a, b)) = graph.edge_endpoints(edge)
+            && let (Some(&a), Some(&b)) = (local.get(&a), local.get(&b))
+        {
+            sub.add_edge(a, b, graph[edge].clone());
+        }
+    }
+
+    let whole = LayoutConfig {
+        scope: LayoutScope::All,
+        ..config.clone()
+    };
+    let layout = calculate_full_layout(&sub, &whole);
+    let min_x = layout
+        .positions
+        .iter()
+        .map(|p| p.1.0)
+        .fold(f64::INFINITY, f64::min);
+    let min_y = layout
+        .positions
+        .iter()
+        .map(|p| p.1.1)
+        .fold(f64::INFINITY, f64::min);
+    layout
+        .positions
+        .into_iter()
+        .map(|(idx, (x, y))| (original[&idx], (x - min_x, y - min_y)))
+        .collect()
+}
+
+/// Offset closest to `desired` at which the block overlaps no obstacle
+///
+/// Candidates are searched ring by ring around `desired`; far enough out the
+/// block clears all obstacles, so a spot is always found.
+fn find_free_offset(
+    block: &[Rect],
+    desired: (f64, f64),
+    obstacles: &[Rect],
+    config: &LayoutConfig,
+) -> (f64, f64) {
+    let gap_x = config.horizontal_spacing / 2.0;
+    let gap_y = config.vertical_spacing / 2.0;
+    let step = (config.table_width + config.horizontal_spacing) / 4.0;
+    let fits = |dx: f64, dy: f64| {
+        block.iter().all(|r| {
+            let moved = r.shifted(dx, dy);
+            obstacles.iter().all(|o| !moved.collides(o, gap_x, gap_y))
+        })
+    };
+
+    // Радиус, на котором блок гарантированно выходит за все препятствия
+    let width = block.iter().map(|r| r.x + r.width).fold(0.0, f64::max);
+    let height = block.iter().map(|r| r.y + r.height).fold(0.0, f64::max);
+    let reach = obstacles
+        .iter()
+        .flat_map(|o| {
+            [
+                desired.0 - (o.x - width - gap_x),
+                o.x + o.width + gap_x - desired.0,
+                desired.1 - (o.y - height - gap_y),
+                o.y + o.height + gap_y - desired.1,
+            ]
+        })
+        .fold(0.0, f64::max);
+    let max_ring = (reach / step).ceil() as i64 + 1;
+
+    for ring in 0..=max_ring {
+        let mut best: Option<(i64, (f64, f64))> = None;
+        for i in -ring..=ring {
+            for j in -ring..=ring {
+                if i.abs().max(j.abs()) != ring {
+                    continue;
+                }
+                let (dx, dy) = (desired.0 + i as f64 * step, desired.1 + j as f64 * step);
+                let distance = i * i + j * j;
+                if best.is_none_or(|(d, _)| distance < d) && fits(dx, dy) {
+                    best = Some((distance, (dx, dy)));
+                }
+            }
+        }
+        if let Some((_, offset)) = best {
+            return offset;
+        }
+    }
+    desired
+}
+
+/// Width reserved for a relationship passing through a layer
+const DUMMY_WIDTH: f64 = 20.0;
+
+/// Number of coordinate refinement passes in the layered layout
+const COORDINATE_PASSES: usize = 8;
+
+/// Performs layered (Sugiyama-style) layout on the schema graph
+///
+/// Referenced tables end up above the tables that reference them. Tables
+/// without relationships are arranged in a grid below the layered part.
+pub fn calculate_layered_layout(graph: &SchemaGraph, config: &LayoutConfig) -> LayoutResult {
+    let nodes: Vec<NodeIndex> = graph.node_indices().collect();
+    if nodes.is_empty() {
+        return LayoutResult { positions: vec![] };
+    }
+    let local: HashMap<NodeIndex, usize> = nodes.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+    let heights: Vec<f64> = nodes
+        .iter()
+        .map(|&n| displayed_table_height(graph, n))
+        .collect();
+
+    // Рёбра родитель -> потомок: ссылаемая таблица выше ссылающейся
+    let mut edges: Vec<(usize, usize)> = graph
+        .edge_indices()
+        .filter_map(|e| {
+            let (source, target) = graph.edge_endpoints(e)?;
+            if source == target {
+                return None;
+            }
+            let rel = graph.edge_weight(e)?;
+            let (parent, child) = if rel.relationship_type == RelationshipType::OneToMany {
+                (source, target)
+            } else {
+                (target, source)
+            };
+            Some((local[&parent], local[&child]))
+        })
+        .collect();
+    edges.sort_unstable();
+    edges.dedup();
+
+    let connected: HashSet<usize> = edges.iter().flat_map(|&(a, b)| [a, b]).collect();
+    let edges = break_cycles(nodes.len(), &edges);
+    let ranks = assign_ranks(nodes.len(), &edges);
+
+    // Вершины слоёного графа: таблицы + фиктивные вершины длинных связей
+    let mut vertices: Vec<LayeredVertex> = (0..nodes.len())
+        .filter(|i| connected.contains(i))
+        .map(|i| LayeredVertex {
+            table: Some(i),
+            width: config.table_width,
+            height: heights[i],
+            layer: ranks[i],
+        })
+        .collect();
+    let vertex_of: HashMap<usize, usize> = vertices
+        .iter()
+        .enumerate()
+        .filter_map(|(v, vertex)| vertex.table.map(|t| (t, v)))
+        .collect();
+
+    let mut segments: Vec<(usize, usize)> = Vec::new();
+    for &(parent, child) in &edges {
+        let mut upper = vertex_of[&parent];
+        for layer in ranks[parent] + 1..ranks[child] {
+            vertices.push(LayeredVertex {
+                table: None,
+                width: DUMMY_WIDTH,
+                height: 0.0,
+                layer,
+            });
+            let dummy = vertices.len() - 1;
+            segments.push((upper, dummy));
+            upper = dummy;
+        }
+        segments.push((upper, vertex_of[&child]));
+    }
+
+    let mut up: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+    let mut down: Vec<Vec<usize>> = vec![Vec::new(); vertices.len()];
+    for &(upper, lower) in &segments {
+        down[upper].push(lower);
+        up[lower].push(upper);
+    }
+
+    let layer_count = vertices.iter().map(|v| v.layer + 1).max().unwrap_or(0);
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); layer_count];
+    for (v, vertex) in vertices.iter().enumerate() {
+        layers[vertex.layer].push(v);
+    }
+
+    minimize_crossings(
+        &mut layers,
+        &up,
+        &down,
+        vertices.len(),
+        config.crossing_sweeps,
+    );
+    let x = assign_x(&layers, &vertices, &up, &down, config.horizontal_spacing);
+
+    // Высота слоя определяется самой высокой таблицей в нём
+    let mut layer_top = vec![0.0; layer_count];
+    let mut bottom = 0.0;
+    for (l, layer) in layers.iter().enumerate() {
+        layer_top[l] = bottom;
+        let height = layer
+            .iter()
+            .map(|&v| vertices[v].height)
+            .fold(0.0, f64::max);
+        bottom += height + config.vertical_spacing;
+    }
+
+    let min_x = vertices
+        .iter()
+        .enumerate()
+        .filter(|(_, vertex)| vertex.table.is_some())
+        .map(|(v, _)| x[v])
+        .fold(f64::INFINITY, f64::min);
+    let max_x = vertices
+        .iter()
+        .enumerate()
+        .filter(|(_, vertex)| vertex.table.is_some())
+        .map(|(v, vertex)| x[v] + vertex.width)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    let mut positions: Vec<(NodeIndex, (f64, f64))> = vertices
+        .iter()
+        .enumerate()
+        .filter_map(|(v, vertex)| {
+            let table = vertex.table?;
+            Some((
+                nodes[table],
+                (
+                    x[v] - min_x + config.start_x,
+                    layer_top[vertex.layer] + config.start_y,
+                ),
+            ))
+        })
+        .collect();
+
+    // Таблицы без связей - сеткой под основной частью
+    let isolated: Vec<usize> = (0..nodes.len())
+        .filter(|i| !connected.contains(i))
+        .collect();
+    if !isolated.is_empty() {
+        let cell_width = config.table_width + config.horizontal_spacing;
+        let columns = if positions.is_empty() {
+            (isolated.len() as f64).sqrt().ceil() as usize
+        } else {
+            ((max_x - min_x + config.horizontal_spacing) / cell_width).floor() as usize
+        }
+        .max(1);
+        let mut y = config.start_y + if positions.is_empty() { 0.0 } else { bottom };
+        for row in isolated.chunks(columns) {
+            for (column, &table) in row.iter().enumerate() {
+                positions.push((
+                    nodes[table],
+                    (config.start_x + column as f64 * cell_width, y),
+                ));
+            }
+            let row_height = row.iter().map(|&t| heights[t]).fold(0.0, f64::max);
+            y += row_height + config.vertical_spacing;
+        }
+    }
+
+    LayoutResult { positions }
+}
+
+/// Vertex of the layered graph
+struct LayeredVertex {
+    /// Table (local index), `None` for a dummy vertex of a long relationship
+    table: Option<usize>,
+    width: f64,
+    height: f64,
+    layer: usize,
+}
+
+/// Orient edges so that the graph is acyclic (greedy heuristic of Eades, Lin and Smyth)
+///
+/// Vertices are ordered by repeatedly taking sinks to the end, sources to the
+/// front, and otherwise the vertex with the largest out-degree surplus. Edges
+/// pointing backwards in that order are reversed.
+fn break_cycles(n: usize, edges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut outgoing: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut incoming: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for &(a, b) in edges {
+        outgoing[a].push(b);
+        incoming[b].push(a);
+    }
+    let mut out_degree: Vec<usize> = outgoing.iter().map(Vec::len).collect();
+    let mut in_degree: Vec<usize> = incoming.iter().map(Vec::len).collect();
+    let mut removed = vec![false; n];
+    let mut front: Vec<usize> = Vec::new();
+    let mut back: Vec<usize> = Vec::new();
+
+    for _ in 0..n {
+        let alive = |v: &usize| !removed[*v];
+        let next = (0..n)
+            .filter(alive)
+            .find(|&v| out_degree[v] == 0)
+            .map(|v| (v, false))
+            .or_else(|| {
+                (0..n)
+                    .filter(alive)
+                    .find(|&v| in_degree[v] == 0)
+                    .map(|v| (v, true))
+            })
+            .or_else(|| {
+                (0..n)
+                    .filter(alive)
+                    .max_by_key(|&v| {
+                        (
+                            out_degree[v] as isize - in_degree[v] as isize,
+                            std::cmp::Reverse(v),
+                        )
+                    })
+                    .map(|v| (v, true))
+            });
+        let Some((v, to_front)) = next else {
+            break;
+        };
+
+        removed[v] = true;
+        for &w in &outgoing[v] {
+            in_degree[w] -= 1;
+        }
+        for &u in &incoming[v] {
+            out_degree[u] -= 1;
+        }
+        if to_front {
+            front.push(v);
+        } else {
+            back.push(v);
+        }
+    }
+
+    let mut order = vec![0; n];
+    for (i, &v) in front.iter().chain(back.iter().rev()).enumerate() {
+        order[v] = i;
+    }
+    let mut result: Vec<(usize, usize)> = edges
+        .iter()
+        .map(|&(a, b)| if order[a] < order[b] { (a, b) } else { (b, a) })
+        .collect();
+    result.sort_unstable();
+    result.dedup();
+    result
+}
+
+/// Longest-path layering of an acyclic graph
+///
+/// Tables without parents are then moved down next to their highest child so
+/// that lookup tables do not all end up in the first layer.
+fn assign_ranks(n: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut parent_count = vec![0usize; n];
+    for &(a, b) in edges {
+        children[a].push(b);
+        parent_count[b] += 1;
+    }
+
+    // Топологический порядок (Kahn)
+    let mut pending = parent_count.clone();
+    let mut order: Vec<usize> = (0..n).filter(|&v| pending[v] == 0).collect();
+    let mut i = 0;
+    while i < order.len() {
+        let v = order[i];
+        for &c in &children[v] {
+            pending[c] -= 1;
+            if pending[c] == 0 {
+                order.push(c);
+            }
+        }
+        i += 1;
+    }
+
+    let mut rank = vec![0usize; n];
+    for &v in &order {
+        for &c in &children[v] {
+            rank[c] = rank[c].max(rank[v] + 1);
+        }
+    }
+    for &v in order.iter().rev() {
+        if parent_count[v] == 0
+            && let Some(min_child) = children[v].iter().map(|&c| rank[c]).min()
+        {
+            rank[v] = min_child - 1;
+        }
+    }
+    rank
+}
+
+/// Reorder layers with alternating barycenter sweeps, keeping the order with the fewest crossings
+fn minimize_crossings(
+    layers: &mut [Vec<usize>],
+    up: &[Vec<usize>],
+    down: &[Vec<usize>],
+    vertex_count: usize,
+    sweeps: usize,
+) {
+    let mut position = vec![0usize; vertex_count];
+    let update_positions = |layers: &[Vec<usize>], position: &mut Vec<usize>| {
+        for layer in layers {
+            for (i, &v) in layer.iter().enumerate() {
+                position[v] = i;
+            }
+        }
+    };
+    update_positions(layers, &mut position);
+
+    let mut best = layers.to_vec();
+    let mut best_crossings = count_crossings(layers, down, &position);
+
+    for sweep in 0..sweeps {
+        if best_crossings == 0 {
+            break;
+        }
+        let (range, neighbours): (Vec<usize>, &[Vec<usize>]) = if sweep % 2 == 0 {
+            ((1..layers.len()).collect(), up)
+        } else {
+            ((0..layers.len().saturating_sub(1)).rev().collect(), down)
+        };
+
+        for l in range {
+            let mut keyed: Vec<(f64, usize)> = layers[l]
+                .iter()
+                .map(|&v| {
+                    let nbrs = &neighbours[v];
+                    let key = if nbrs.is_empty() {
+                        position[v] as f64
+                    } else {
+                        nbrs.iter().map(|&u| position[u] as f64).sum::<f64>() / nbrs.len() as f64
+                    };
+                    (key, v)
+                })
+                .collect();
+            // Стабильная сортировка сохраняет текущий порядок при равенстве
+            keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+            layers[l] = keyed.into_iter().map(|(_, v)| v).collect();
+            for (i, &v) in layers[l].iter().enumerate() {
+                position[v] = i;
+            }
+        }
+
+        let crossings = count_crossings(layers, down, &position);
+        if crossings < best_crossings {
+            best_crossings = crossings;
+            best = layers.to_vec();
+        }
+    }
+
+    layers.clone_from_slice(&best);
+}
+
+/// Number of edge crossings between all pairs of adjacent layers
+fn count_crossings(layers: &[Vec<usize>], down: &[Vec<usize>], position: &[usize]) -> usize {
+    let mut total = 0;
+    for layer in layers {
+        let mut segments: Vec<(usize, usize)> = layer
+            .iter()
+            .flat_map(|&v| down[v].iter().map(move |&w| (position[v], position[w])))
+            .collect();
+        segments.sort_unstable();
+        for i in 0..segments.len() {
+            for j in i + 1..segments.len() {
+                if segments[i].0 < segments[j].0 && segments[i].1 > segments[j].1 {
+                    total += 1;
+                }
+            }
+        }
+    }
+    total
+}
+
+/// Horizontal coordinates (left edges): each vertex is pulled toward the mean
+/// center of its neighbours while keeping the layer order and spacing
+fn assign_x(
+    layers: &[Vec<usize>],
+    vertices: &[LayeredVertex],
+    up: &[Vec<usize>],
+    down: &[Vec<usize>],
+    spacing: f64,
+) -> Vec<f64> {
+    let mut x = vec![0.0; vertices.len()];
+    for layer in layers {
+        let total: f64 = layer.iter().map(|&v| vertices[v].width + spacing).sum();
+        let mut left = -(total - spacing) / 2.0;
+        for &v in layer {
+            x[v] = left;
+            left += vertices[v].width + spacing;
+        }
+    }
+
+    for pass in 0..COORDINATE_PASSES {
+        let (range, neighbours): (Vec<usize>, &[Vec<usize>]) = if pass % 2 == 0 {
+            ((1..layers.len()).collect(), up)
+        } else {
+            ((0..layers.len().saturating_sub(1)).rev().collect(), down)
+        };
+        for l in range {
+            let desired: Vec<f64> = layers[l]
+                .iter()
+                .map(|&v| {
+                    let nbrs = &neighbours[v];
+                    if nbrs.is_empty() {
+                        x[v]
+                    } else {
+                        let center = nbrs
+                            .iter()
+                            .map(|&u| x[u] + vertices[u].width / 2.0)
+                            .sum::<f64>()
+                            / nbrs.len() as f64;
+                        center - vertices[v].width / 2.0
+                    }
+                })
+                .collect();
+            let widths: Vec<f64> = layers[l].iter().map(|&v| vertices[v].width).collect();
+            for (&v, left) in layers[l]
+                .iter()
+                .zip(place_in_order(&desired, &widths, spacing))
+            {
+                x[v] = left;
+            }
+        }
+    }
+    x
+}
+
+/// Closest positions to `desired` (least squares) that keep the order and
+/// at least `spacing` between neighbours (pool-adjacent-violators)
+fn place_in_order(desired: &[f64], widths: &[f64], spacing: f64) -> Vec<f64> {
+    // Сдвигаем координаты так, что ограничение становится монотонностью
+    let mut offsets = Vec::with_capacity(desired.len());
+    let mut offset = 0.0;
+    for &width in widths {
+        offsets.push(offset);
+        offset += width + spacing;
+    }
+
+    // Блоки: (сумма, количество)
+    let mut blocks: Vec<(f64, usize)> = Vec::new();
+    for (d, o) in desired.iter().zip(&offsets) {
+        blocks.push((d - o, 1));
+        while blocks.len() >= 2 {
+            let (s2, c2) = blocks[blocks.len() - 1];
+            let (s1, c1) = blocks[blocks.len() - 2];
+            if s1 / c1 as f64 <= s2 / c2 as f64 {
+                break;
+            }
+            blocks.pop();
+            let last = blocks.len() - 1;
+            blocks[last] = (s1 + s2, c1 + c2);
+        }
+    }
+
+    blocks
+        .iter()
+        .flat_map(|&(sum, count)| std::iter::repeat_n(sum / count as f64, count))
+        .zip(&offsets)
+        .map(|(y, o)| y + o)
+        .collect()
+}
+
+/// Applies the calculated layout to the graph
+///
+/// Pinned tables stay in place. Returns `MoveTable` operations for the tables
+/// that actually moved, to broadcast through LiveShare.
+pub fn apply_layout(graph: &mut SchemaGraph, layout: &LayoutResult) -> Vec<GraphOperation> {
+    let mut ops = Vec::new();
+    for &(node_idx, position) in &layout.positions {
+        if let Some(node) = graph.node_weight_mut(node_idx)
+            && !node.pinned
+            && node.position != position
+        {
+            node.position = position;
+            ops.push(GraphOperation::MoveTable {
+                node_id: node_idx.index() as u32,
+                table_uuid: node.uuid,
+                position,
+            });
+        }
+    }
+    ops
+}
+
+/// Convenience function to auto-layout with default config
+pub fn auto_layout(graph: &mut SchemaGraph) -> Vec<GraphOperation> {
+    let config = LayoutConfig::default();
+    let layout = calculate_layout(graph, &config);
+    apply_layout(graph, &layout)
+}
+
+/// Convenience function to auto-layout with custom config
+pub fn auto_layout_with_config(
+    graph: &mut SchemaGraph,
+    config: &LayoutConfig,
+) -> Vec<GraphOperation> {
+    let layout = calculate_layout(graph, config);
+    apply_layout(graph, &layout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, Relationship, RelationshipOps, RelationshipType, TableNode};
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_empty_graph() {
+        let graph = SchemaGraph::new();
+        let config = LayoutConfig::default();
+        let result = calculate_hierarchical_layout(&graph, &config);
+        assert!(result.positions.is_empty());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_single_table() {
+        let mut graph = SchemaGraph::new();
+        let table = TableNode::new("users");
+        graph.add_node(table);
+
+        let config = LayoutConfig::default();
+        let result = calculate_hierarchical_layout(&graph, &config);
+
+        assert_eq!(result.positions.len(), 1);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_two_related_tables() {
+        let mut graph = SchemaGraph::new();
+
+        // Users table (parent)
+        let users = TableNode::new("users").add_column(Column::new("id", "INT").primary_key());
+        let users_idx = graph.add_node(users);
+
+        // Orders table (child, references users)
+        let orders = TableNode::new("orders")
+            .add_column(Column::new("id", "INT").primary_key())
+            .add_column(Column::new("user_id", "INT"));
+        let orders_idx = graph.add_node(orders);
+
+        // Add relationship: orders -> users
+        graph
+            .create_relationship(
+                orders_idx,
+                users_idx,
+                Relationship::new(
+                    "fk_orders_users",
+                    RelationshipType::ManyToOne,
+                    "user_id",
+                    "id",
+                ),
+            )
+            .unwrap();
+
+        let config = LayoutConfig::default();
+        let result = calculate_hierarchical_layout(&graph, &config);
+
+        assert_eq!(result.positions.len(), 2);
+
+        // Find positions
+        let users_pos = result
+            .positions
+            .iter()
+            .find(|(idx, _)| *idx == users_idx)
+            .map(|(_, p)| p);
+        let orders_pos = result
+            .positions
+            .iter()
+            .find(|(idx, _)| *idx == orders_idx)
+            .map(|(_, p)| p);
+
+        // Both should have positions
+        assert!(users_pos.is_some());
+        assert!(orders_pos.is_some());
+
+        // Connected tables should be relatively close (within ideal_edge_length * 2)
+        let (ux, uy) = users_pos.unwrap();
+        let (ox, oy) = orders_pos.unwrap();
+        let distance = ((ux - ox).powi(2) + (uy - oy).powi(2)).sqrt();
         assert!(
             distance < config.ideal_edge_length * 2.5,
             "Connected tables should be close together, but distance is {}",
@@ -474,6 +1772,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_no_overlaps() {
         let mut graph = SchemaGraph::new();
 
@@ -536,4 +1835,468 @@ mod tests {
         let norm = a.normalize();
         assert!((norm.length() - 1.0).abs() < 0.001);
     }
+
+    fn fk(graph: &mut SchemaGraph, from: NodeIndex, to: NodeIndex) {
+        // Раскладке колонки не важны, поэтому связь добавляется без проверок
+        graph.add_edge(
+            from,
+            to,
+            Relationship::new(
+                format!("fk_{}_{}", from.index(), to.index()),
+                RelationshipType::ManyToOne,
+                "ref_id",
+                "id",
+            ),
+        );
+    }
+
+    fn table(graph: &mut SchemaGraph, name: &str, columns: usize) -> NodeIndex {
+        let mut node = TableNode::new(name).add_column(Column::new("id", "INT").primary_key());
+        for i in 1..columns {
+            node = node.add_column(Column::new(format!("col_{}", i), "INT"));
+        }
+        graph.add_node(node)
+    }
+
+    fn position(result: &LayoutResult, idx: NodeIndex) -> (f64, f64) {
+        result
+            .positions
+            .iter()
+            .find(|(n, _)| *n == idx)
+            .map(|(_, p)| *p)
+            .unwrap()
+    }
+
+    fn assert_no_overlaps(graph: &SchemaGraph, result: &LayoutResult, config: &LayoutConfig) {
+        for (i, &(a, (x1, y1))) in result.positions.iter().enumerate() {
+            for &(b, (x2, y2)) in &result.positions[i + 1..] {
+                let h1 = displayed_table_height(graph, a);
+                let h2 = displayed_table_height(graph, b);
+                let apart_x =
+                    x1 + config.table_width <= x2 + 0.5 || x2 + config.table_width <= x1 + 0.5;
+                let apart_y = y1 + h1 <= y2 + 0.5 || y2 + h2 <= y1 + 0.5;
+                assert!(
+                    apart_x || apart_y,
+                    "{} and {} overlap",
+                    graph[a].name,
+                    graph[b].name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_table_positions_follow_uuids() {
+        let mut graph = SchemaGraph::new();
+        let users = graph.add_node(TableNode::new("users"));
+        let orders = graph.add_node(TableNode::new("orders"));
+        let request = LayoutRequest {
+            graph: graph.clone(),
+            algorithm: LayoutAlgorithm::Layered,
+        };
+        let positions = request.calculate();
+        assert_eq!(positions.positions.len(), 2);
+
+        // Индекс удалённой таблицы достаётся новой - ей позиция не применяется
+        graph.remove_node(users);
+        let reused = graph.add_node(TableNode::new("payments"));
+        assert_eq!(reused, users);
+        let layout = positions.resolve(&graph);
+        assert_eq!(layout.positions.len(), 1);
+        assert_eq!(layout.positions[0].0, orders);
+
+        assert!(request.within_limits());
+        let json = serde_json::to_string(&request).unwrap();
+        let restored: LayoutRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.calculate(), positions);
+    }
+
+    #[test]
+    fn test_force_is_default() {
+        // auto_layout() keeps the force layout it always used
+        assert_eq!(LayoutConfig::default().algorithm, LayoutAlgorithm::Force);
+        assert!(
+            calculate_layout(&SchemaGraph::new(), &LayoutConfig::default())
+                .positions
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_layered_referenced_tables_above() {
+        let mut graph = SchemaGraph::new();
+        let users = table(&mut graph, "users", 3);
+        let orders = table(&mut graph, "orders", 4);
+        let items = table(&mut graph, "order_items", 4);
+        let products = table(&mut graph, "products", 2);
+        fk(&mut graph, orders, users);
+        fk(&mut graph, items, orders);
+        fk(&mut graph, items, products);
+
+        let config = LayoutConfig::default();
+        let result = calculate_layered_layout(&graph, &config);
+        assert_eq!(result.positions.len(), 4);
+
+        assert!(position(&result, users).1 < position(&result, orders).1);
+        assert!(position(&result, orders).1 < position(&result, items).1);
+        assert!(position(&result, products).1 < position(&result, items).1);
+        assert_no_overlaps(&graph, &result, &config);
+    }
+
+    #[test]
+    fn test_layered_ignores_initial_positions() {
+        let build = |offset: f64| {
+            let mut graph = SchemaGraph::new();
+            let a = graph.add_node(TableNode::new("a").with_position(offset, 0.0));
+            let b = graph.add_node(TableNode::new("b").with_position(0.0, offset));
+            let c = graph.add_node(TableNode::new("c").with_position(-offset, offset));
+            fk(&mut graph, b, a);
+            fk(&mut graph, c, a);
+            graph
+        };
+
+        let config = LayoutConfig::default();
+        let first = calculate_layered_layout(&build(0.0), &config);
+        let second = calculate_layered_layout(&build(750.0), &config);
+        assert_eq!(first.positions, second.positions);
+    }
+
+    #[test]
+    fn test_layered_handles_cycles() {
+        let mut graph = SchemaGraph::new();
+        let a = table(&mut graph, "a", 2);
+        let b = table(&mut graph, "b", 2);
+        let c = table(&mut graph, "c", 2);
+        fk(&mut graph, a, b);
+        fk(&mut graph, b, c);
+        fk(&mut graph, c, a);
+        // Самоссылка (employees.manager_id)
+        fk(&mut graph, a, a);
+
+        let config = LayoutConfig::default();
+        let result = calculate_layered_layout(&graph, &config);
+        assert_eq!(result.positions.len(), 3);
+
+        let mut rows: Vec<f64> = result.positions.iter().map(|(_, p)| p.1).collect();
+        rows.sort_by(f64::total_cmp);
+        rows.dedup();
+        assert!(rows.len() >= 2, "cycle should still be layered");
+        assert_no_overlaps(&graph, &result, &config);
+    }
+
+    #[test]
+    fn test_layered_removes_crossings() {
+        // Два родителя и два потомка, связанные "крест-накрест" по порядку добавления
+        let mut graph = SchemaGraph::new();
+        let p1 = table(&mut graph, "p1", 1);
+        let p2 = table(&mut graph, "p2", 1);
+        let c1 = table(&mut graph, "c1", 1);
+        let c2 = table(&mut graph, "c2", 1);
+        fk(&mut graph, c1, p2);
+        fk(&mut graph, c2, p1);
+
+        let result = calculate_layered_layout(&graph, &LayoutConfig::default());
+        let p1_left = position(&result, p1).0 < position(&result, p2).0;
+        let c2_left = position(&result, c2).0 < position(&result, c1).0;
+        assert_eq!(p1_left, c2_left, "relationships should not cross");
+    }
+
+    #[test]
+    fn test_layered_uses_table_heights() {
+        let mut graph = SchemaGraph::new();
+        let wide = table(&mut graph, "wide", 8);
+        let small = table(&mut graph, "small", 1);
+        let child = table(&mut graph, "child", 2);
+        fk(&mut graph, child, wide);
+        fk(&mut graph, child, small);
+
+        let config = LayoutConfig::default();
+        let result = calculate_layered_layout(&graph, &config);
+        let gap = position(&result, child).1 - position(&result, wide).1;
+        assert!(gap >= estimated_table_height(8) + config.vertical_spacing - 0.5);
+        assert_no_overlaps(&graph, &result, &config);
+    }
+
+    #[test]
+    fn test_layout_uses_displayed_heights() {
+        let mut graph = SchemaGraph::new();
+        let parent = table(&mut graph, "parent", 20);
+        let child = table(&mut graph, "child", 20);
+        fk(&mut graph, child, parent);
+        crate::core::set_table_display(&mut graph, &[parent, child], TableDisplay::NameOnly);
+
+        // Слои подстраиваются под высоту заголовка
+        let config = LayoutConfig::default();
+        let result = calculate_layered_layout(&graph, &config);
+        let gap = position(&result, child).1 - position(&result, parent).1;
+        assert!(gap < estimated_table_height(20));
+        assert_no_overlaps(&graph, &result, &config);
+
+        // Силовая раскладка не резервирует под таблицы полную высоту
+        assert_eq!(spacing_table_height(&graph, &config), TABLE_HEADER_HEIGHT);
+        crate::core::set_table_display(&mut graph, &[child], TableDisplay::Full);
+        assert_eq!(spacing_table_height(&graph, &config), config.table_height);
+    }
+
+    #[test]
+    fn test_layered_places_isolated_tables() {
+        let mut graph = SchemaGraph::new();
+        let users = table(&mut graph, "users", 3);
+        let orders = table(&mut graph, "orders", 3);
+        fk(&mut graph, orders, users);
+        for i in 0..5 {
+            table(&mut graph, &format!("lonely_{}", i), i + 1);
+        }
+
+        let config = LayoutConfig::default();
+        let result = calculate_layered_layout(&graph, &config);
+        assert_eq!(result.positions.len(), 7);
+        assert_no_overlaps(&graph, &result, &config);
+    }
+
+    #[test]
+    fn test_pinned_tables_stay_in_place() {
+        let mut graph = SchemaGraph::new();
+        let users = table(&mut graph, "users", 3);
+        let orders = table(&mut graph, "orders", 3);
+        let items = table(&mut graph, "items", 3);
+        fk(&mut graph, orders, users);
+        fk(&mut graph, items, orders);
+        graph[users].position = (1234.0, 567.0);
+        graph[users].pinned = true;
+
+        let config = LayoutConfig::default();
+        let result = calculate_layout(&graph, &config);
+        assert!(result.positions.iter().all(|(n, _)| *n != users));
+        assert_eq!(result.positions.len(), 2);
+
+        let ops = apply_layout(&mut graph, &result);
+        assert_eq!(graph[users].position, (1234.0, 567.0));
+        assert!(ops.iter().all(|op| !matches!(
+            op,
+            GraphOperation::MoveTable { table_uuid, .. } if *table_uuid == graph[users].uuid
+        )));
+        assert_no_overlaps(&graph, &positions_of(&graph), &config);
+
+        // Даже если закреплённая таблица попала в результат, она не двигается
+        let forced = LayoutResult {
+            positions: vec![(users, (0.0, 0.0))],
+        };
+        assert!(apply_layout(&mut graph, &forced).is_empty());
+        assert_eq!(graph[users].position, (1234.0, 567.0));
+    }
+
+    #[test]
+    fn test_scope_places_new_tables_next_to_related() {
+        let mut graph = SchemaGraph::new();
+        let users = table(&mut graph, "users", 4);
+        let posts = table(&mut graph, "posts", 5);
+        let other = table(&mut graph, "audit_log", 6);
+        graph[users].position = (100.0, 100.0);
+        graph[posts].position = (500.0, 100.0);
+        graph[other].position = (2000.0, 1500.0);
+        fk(&mut graph, posts, users);
+
+        // Новые таблицы (например, созданные AI) лежат поверх существующих
+        let comments = table(&mut graph, "comments", 4);
+        let likes = table(&mut graph, "likes", 3);
+        graph[comments].position = (500.0, 100.0);
+        graph[likes].position = (500.0, 100.0);
+        fk(&mut graph, comments, posts);
+        fk(&mut graph, likes, comments);
+
+        let config = LayoutConfig {
+            scope: LayoutScope::Tables(HashSet::from([comments, likes])),
+            ..Default::default()
+        };
+        let before: Vec<_> = [users, posts, other].map(|n| graph[n].position).to_vec();
+        let ops = auto_layout_with_config(&mut graph, &config);
+
+        assert_eq!(ops.len(), 2);
+        assert_eq!(
+            [users, posts, other].map(|n| graph[n].position).to_vec(),
+            before
+        );
+        assert_no_overlaps(&graph, &positions_of(&graph), &config);
+
+        // Размещены рядом со связанной таблицей, а не у далёкой audit_log
+        let (px, py) = graph[posts].position;
+        let (cx, cy) = graph[comments].position;
+        assert!(((cx - px).powi(2) + (cy - py).powi(2)).sqrt() < 900.0);
+    }
+
+    #[test]
+    fn test_apply_layout_reports_moves() {
+        let mut graph = SchemaGraph::new();
+        let a = graph.add_node(TableNode::new("a").with_position(10.0, 10.0));
+        let b = graph.add_node(TableNode::new("b").with_position(20.0, 20.0));
+        let layout = LayoutResult {
+            positions: vec![(a, (10.0, 10.0)), (b, (300.0, 40.0))],
+        };
+
+        let ops = apply_layout(&mut graph, &layout);
+        assert_eq!(ops.len(), 1);
+        match &ops[0] {
+            GraphOperation::MoveTable {
+                node_id,
+                table_uuid,
+                position,
+            } => {
+                assert_eq!(*node_id, b.index() as u32);
+                assert_eq!(*table_uuid, graph[b].uuid);
+                assert_eq!(*position, (300.0, 40.0));
+            }
+            other => panic!("unexpected op {:?}", other),
+        }
+    }
+
+    fn positions_of(graph: &SchemaGraph) -> LayoutResult {
+        LayoutResult {
+            positions: graph
+                .node_indices()
+                .map(|n| (n, graph[n].position))
+                .collect(),
+        }
+    }
+
+    /// Deterministic scattered points
+    fn scattered(count: usize) -> Vec<Vec2> {
+        (0..count)
+            .map(|i| {
+                let i = i as f64;
+                Vec2::new(
+                    (i * 0.7).sin() * 2000.0 + i * 13.0,
+                    (i * 1.3).cos() * 1500.0 - i * 7.0,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_barnes_hut_approximates_exact_repulsion() {
+        let positions = scattered(200);
+        let exact_config = LayoutConfig {
+            barnes_hut_theta: 0.0,
+            ..Default::default()
+        };
+        let config = LayoutConfig {
+            barnes_hut_theta: 0.5,
+            ..Default::default()
+        };
+        let exact = repulsion_forces(&positions, &exact_config);
+        let approx = repulsion_forces(&positions, &config);
+
+        let error: f64 = exact
+            .iter()
+            .zip(&approx)
+            .map(|(e, a)| e.sub(*a).length())
+            .sum();
+        let magnitude: f64 = exact.iter().map(|e| e.length()).sum();
+        assert!(
+            error / magnitude < 0.05,
+            "relative error {}",
+            error / magnitude
+        );
+    }
+
+    #[test]
+    fn test_quadtree_handles_coincident_tables() {
+        let positions = vec![Vec2::new(10.0, 10.0); BARNES_HUT_MIN_TABLES];
+        let forces = repulsion_forces(&positions, &LayoutConfig::default());
+        assert!(forces.iter().all(|f| f.length() == 0.0));
+    }
+
+    #[test]
+    fn test_force_layout_packs_components() {
+        let mut graph = SchemaGraph::new();
+        let mut components = Vec::new();
+        for c in 0..4 {
+            let a = table(&mut graph, &format!("a{}", c), 2);
+            let b = table(&mut graph, &format!("b{}", c), 2);
+            fk(&mut graph, b, a);
+            components.push([a, b]);
+        }
+        let config = LayoutConfig {
+            algorithm: LayoutAlgorithm::Force,
+            ..Default::default()
+        };
+        let result = calculate_force_layout(&graph, &config);
+        assert_no_overlaps(&graph, &result, &config);
+
+        // Компоненты не перемешиваются: их рамки не пересекаются
+        let bounds: Vec<Rect> = components
+            .iter()
+            .map(|nodes| {
+                let points: Vec<(f64, f64)> = nodes.iter().map(|&n| position(&result, n)).collect();
+                let x = points.iter().map(|p| p.0).fold(f64::INFINITY, f64::min);
+                let y = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+                Rect {
+                    x,
+                    y,
+                    width: points.iter().map(|p| p.0).fold(0.0, f64::max) - x + config.table_width,
+                    height: points.iter().map(|p| p.1).fold(0.0, f64::max) - y
+                        + config.table_height,
+                }
+            })
+            .collect();
+        for (i, a) in bounds.iter().enumerate() {
+            for b in &bounds[i + 1..] {
+                assert!(!a.collides(b, 0.0, 0.0));
+            }
+        }
+
+        // Упаковка в ряды, а не в одну линию
+        let xs: HashSet<i64> = bounds.iter().map(|r| r.x as i64).collect();
+        let ys: HashSet<i64> = bounds.iter().map(|r| r.y as i64).collect();
+        assert!(xs.len() > 1 && ys.len() > 1);
+    }
+
+    #[test]
+    fn test_force_layout_job_reports_progress() {
+        let mut graph = SchemaGraph::new();
+        let nodes: Vec<NodeIndex> = (0..6)
+            .map(|i| table(&mut graph, &format!("t{}", i), 3))
+            .collect();
+        for pair in nodes.windows(2) {
+            fk(&mut graph, pair[1], pair[0]);
+        }
+        let config = LayoutConfig {
+            algorithm: LayoutAlgorithm::Force,
+            ..Default::default()
+        };
+
+        let mut job = LayoutJob::new(&graph, &config);
+        assert_eq!(job.progress(), 0.0);
+        assert!(!job.step());
+        assert!(job.progress() > 0.0);
+        while !job.step() {}
+        assert_eq!(job.progress(), 1.0);
+        assert_eq!(
+            job.finish().positions,
+            calculate_layout(&graph, &config).positions
+        );
+    }
+
+    #[test]
+    fn test_force_layout_large_schema() {
+        let mut graph = SchemaGraph::new();
+        let nodes: Vec<NodeIndex> = (0..150)
+            .map(|i| table(&mut graph, &format!("t{}", i), 1))
+            .collect();
+        for (i, &node) in nodes.iter().enumerate().skip(1) {
+            fk(&mut graph, node, nodes[(i - 1) / 3]);
+        }
+        let config = LayoutConfig {
+            algorithm: LayoutAlgorithm::Force,
+            ..Default::default()
+        };
+        let result = calculate_force_layout(&graph, &config);
+        assert_eq!(result.positions.len(), 150);
+        assert!(
+            result
+                .positions
+                .iter()
+                .all(|(_, (x, y))| x.is_finite() && y.is_finite())
+        );
+    }
 }
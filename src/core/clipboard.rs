@@ -0,0 +1,313 @@
+//! Copy and paste of tables between diagrams
+//!
+//! Copying a selection puts a single text payload on the system clipboard: the
+//! DDL of the copied tables followed by a comment line with a JSON
+//! [`ClipboardFragment`]. SQL editors receive plain DDL, while a canvas reads the
+//! fragment back and pastes the tables with fresh UUIDs and unique names.
+
+use std::collections::{HashMap, HashSet};
+
+use petgraph::graph::NodeIndex;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+use serde::{Deserialize, Serialize};
+
+use crate::core::export::{
+    ExportOptions, ExportedRelationship, ExportedSchema, ExportedTable, SchemaExporter,
+    SchemaImporter,
+};
+use crate::core::liveshare::{ColumnData, GraphOperation, RelationshipData};
+use crate::core::schema::{RelationshipOps, SchemaGraph, TableNode, TableOps};
+use crate::core::validation::validate_table_name;
+
+/// Value of [`ClipboardFragment::format`]
+pub const FRAGMENT_FORMAT: &str = "archischema/tables";
+
+/// Current version of the fragment layout
+pub const FRAGMENT_VERSION: &str = "1.0";
+
+/// Step by which pasted tables are shifted away from tables already in place
+pub const PASTE_OFFSET: f64 = 40.0;
+
+/// Prefix of the clipboard line that carries the JSON fragment
+const FRAGMENT_MARKER: &str = "-- archischema-fragment: ";
+
+/// Tables and their internal relationships, as placed on the clipboard
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct ClipboardFragment {
+    pub format: String,
+    pub version: String,
+    pub tables: Vec<ExportedTable>,
+    /// Relationships whose both ends are among `tables`
+    pub relationships: Vec<ExportedRelationship>,
+}
+
+impl ClipboardFragment {
+    /// Copy the given tables and the relationships between them
+    pub fn from_tables(graph: &SchemaGraph, tables: &[NodeIndex]) -> Self {
+        let selected: HashSet<NodeIndex> = tables.iter().copied().collect();
+        // Связи с таблицами вне выделения отбрасываются вместе с узлами
+        let subgraph = graph.filter_map(
+            |idx, table| selected.contains(&idx).then(|| table.clone()),
+            |_, relationship| Some(relationship.clone()),
+        );
+        let schema = SchemaExporter::to_exported_schema(&subgraph);
+        Self {
+            format: FRAGMENT_FORMAT.to_string(),
+            version: FRAGMENT_VERSION.to_string(),
+            tables: schema.tables,
+            relationships: schema.relationships,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Build a standalone graph of the fragment
+    pub fn to_graph(&self) -> Result<SchemaGraph, String> {
+        SchemaImporter::to_graph(&ExportedSchema {
+            version: self.version.clone(),
+            tables: self.tables.clone(),
+            relationships: self.relationships.clone(),
+        })
+    }
+
+    /// DDL of the copied tables
+    pub fn to_sql(&self, options: &ExportOptions) -> Result<String, String> {
+        SchemaExporter::export_sql(&self.to_graph()?, options)
+    }
+
+    /// Clipboard text: DDL followed by the fragment on a comment line
+    pub fn to_clipboard_text(&self, options: &ExportOptions) -> Result<String, String> {
+        let sql = self.to_sql(options)?;
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        Ok(format!("{}\n{}{}\n", sql.trim_end(), FRAGMENT_MARKER, json))
+    }
+
+    /// Read a fragment from clipboard text (as written by `to_clipboard_text`, or bare JSON)
+    pub fn from_clipboard_text(text: &str) -> Result<Self, String> {
+        let json = text
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(FRAGMENT_MARKER.trim_end()))
+            .map(str::trim)
+            .or_else(|| Some(text.trim()).filter(|t| t.starts_with('{')))
+            .ok_or_else(|| "Clipboard does not contain copied tables".to_string())?;
+
+        let fragment: Self =
+            serde_json::from_str(json).map_err(|e| format!("Invalid clipboard fragment: {}", e))?;
+        if fragment.format != FRAGMENT_FORMAT {
+            return Err(format!(
+                "Unsupported clipboard format '{}'",
+                fragment.format
+            ));
+        }
+        if fragment.version.split('.').next() != FRAGMENT_VERSION.split('.').next() {
+            return Err(format!(
+                "Unsupported clipboard fragment version {}",
+                fragment.version
+            ));
+        }
+        Ok(fragment)
+    }
+}
+
+/// Offset that keeps pasted tables from landing exactly on existing ones
+///
+/// Pasting into another diagram keeps the original positions when they are free;
+/// pasting next to the originals (or pasting again) shifts by [`PASTE_OFFSET`] steps.
+fn paste_offset(graph: &SchemaGraph, fragment: &SchemaGraph) -> f64 {
+    let occupied = |offset: f64| {
+        fragment.node_weights().any(|pasted| {
+            let position = (pasted.position.0 + offset, pasted.position.1 + offset);
+            graph.node_weights().any(|table| table.position == position)
+        })
+    };
+    let mut offset = 0.0;
+    while occupied(offset) {
+        offset += PASTE_OFFSET;
+    }
+    offset
+}
+
+/// Paste a fragment into `graph`
+///
+/// Tables get fresh UUIDs and unique names (`users` becomes `users_2` when taken);
+/// relationships between pasted tables are recreated. Returns the new tables and
+/// the operations to broadcast.
+pub fn paste_fragment(
+    graph: &mut SchemaGraph,
+    fragment: &ClipboardFragment,
+) -> Result<(Vec<NodeIndex>, Vec<GraphOperation>), String> {
+    for table in &fragment.tables {
+        validate_table_name(&table.name)?;
+    }
+    let source = fragment.to_graph()?;
+    let offset = paste_offset(graph, &source);
+
+    let mut ops = Vec::new();
+    let mut pasted: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+    let mut new_tables = Vec::with_capacity(source.node_count());
+
+    for idx in source.node_indices() {
+        let table = &source[idx];
+        let mut node = TableNode::new(graph.generate_unique_table_name(&table.name))
+            .with_position(table.position.0 + offset, table.position.1 + offset);
+        node.columns = table.columns.clone();
+
+        let new_idx = graph.add_node(node);
+        let node = &graph[new_idx];
+        let node_id = new_idx.index() as u32;
+        ops.push(GraphOperation::CreateTable {
+            node_id,
+            table_uuid: node.uuid,
+            name: node.name.clone(),
+            position: node.position,
+        });
+        ops.extend(node.columns.iter().map(|column| GraphOperation::AddColumn {
+            node_id,
+            table_uuid: node.uuid,
+            column: ColumnData::from(column),
+        }));
+        pasted.insert(idx, new_idx);
+        new_tables.push(new_idx);
+    }
+
+    for edge in source.edge_references() {
+        let (from, to) = (pasted[&edge.source()], pasted[&edge.target()]);
+        let mut relationship = edge.weight().clone();
+        for point in &mut relationship.route {
+            point.0 += offset;
+            point.1 += offset;
+        }
+        let data = RelationshipData::from(&relationship);
+        let edge_idx = graph.create_relationship(from, to, relationship)?;
+        ops.push(GraphOperation::CreateRelationship {
+            edge_id: edge_idx.index() as u32,
+            from_node: from.index() as u32,
+            to_node: to.index() as u32,
+            relationship: data,
+        });
+    }
+
+    Ok((new_tables, ops))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{SqlDialect, create_demo_graph};
+
+    fn tables(graph: &SchemaGraph, names: &[&str]) -> Vec<NodeIndex> {
+        names
+            .iter()
+            .map(|name| graph.find_table_by_name(name).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_copy_keeps_only_internal_relationships() {
+        let graph = create_demo_graph();
+        let fragment = ClipboardFragment::from_tables(&graph, &tables(&graph, &["users", "posts"]));
+
+        assert_eq!(fragment.tables.len(), 2);
+        assert_eq!(fragment.relationships.len(), 1);
+        assert_eq!(fragment.relationships[0].name, "user_posts");
+    }
+
+    #[test]
+    fn test_clipboard_text_roundtrip() {
+        let graph = create_demo_graph();
+        let fragment = ClipboardFragment::from_tables(&graph, &tables(&graph, &["users", "posts"]));
+        let options = ExportOptions {
+            sql_dialect: SqlDialect::PostgreSQL,
+            ..Default::default()
+        };
+
+        let text = fragment.to_clipboard_text(&options).unwrap();
+        assert!(text.contains("CREATE TABLE \"users\""));
+        assert!(!text.contains("\"comments\""));
+
+        let parsed = ClipboardFragment::from_clipboard_text(&text).unwrap();
+        assert_eq!(parsed.tables.len(), 2);
+        assert_eq!(parsed.relationships.len(), 1);
+
+        // Голый JSON тоже принимается
+        let json = serde_json::to_string(&fragment).unwrap();
+        assert!(ClipboardFragment::from_clipboard_text(&json).is_ok());
+    }
+
+    #[test]
+    fn test_foreign_clipboard_text_is_rejected() {
+        assert!(ClipboardFragment::from_clipboard_text("CREATE TABLE t (id INT);").is_err());
+        assert!(
+            ClipboardFragment::from_clipboard_text(
+                r#"{"format":"other","version":"1.0","tables":[],"relationships":[]}"#
+            )
+            .is_err()
+        );
+        assert!(
+            ClipboardFragment::from_clipboard_text(
+                r#"{"format":"archischema/tables","version":"2.0","tables":[],"relationships":[]}"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_paste_into_same_diagram_renames_and_offsets() {
+        let mut graph = create_demo_graph();
+        let source = tables(&graph, &["users", "posts"]);
+        let fragment = ClipboardFragment::from_tables(&graph, &source);
+
+        let (pasted, ops) = paste_fragment(&mut graph, &fragment).unwrap();
+
+        assert_eq!(pasted.len(), 2);
+        assert_eq!(graph.node_count(), 5);
+        let users = graph[source[0]].clone();
+        let copy = &graph[graph.find_table_by_name("users_2").unwrap()];
+        assert_ne!(copy.uuid, users.uuid);
+        assert_eq!(copy.columns, users.columns);
+        assert_eq!(
+            copy.position,
+            (
+                users.position.0 + PASTE_OFFSET,
+                users.position.1 + PASTE_OFFSET
+            )
+        );
+        assert!(graph.find_table_by_name("posts_2").is_some());
+
+        // Связь пересоздана между копиями
+        let rel_ops: Vec<_> = ops
+            .iter()
+            .filter(|op| matches!(op, GraphOperation::CreateRelationship { .. }))
+            .collect();
+        assert_eq!(rel_ops.len(), 1);
+        assert_eq!(graph.edge_count(), 4);
+        assert!(matches!(ops[0], GraphOperation::CreateTable { .. }));
+
+        // Повторная вставка сдвигается ещё на шаг
+        let (again, _) = paste_fragment(&mut graph, &fragment).unwrap();
+        assert_eq!(
+            graph[again[0]].position,
+            (
+                users.position.0 + 2.0 * PASTE_OFFSET,
+                users.position.1 + 2.0 * PASTE_OFFSET
+            )
+        );
+        assert!(graph.find_table_by_name("users_3").is_some());
+    }
+
+    #[test]
+    fn test_paste_into_other_diagram_keeps_positions() {
+        let source = create_demo_graph();
+        let fragment = ClipboardFragment::from_tables(&source, &tables(&source, &["posts"]));
+        let mut graph = SchemaGraph::new();
+
+        let (pasted, ops) = paste_fragment(&mut graph, &fragment).unwrap();
+
+        let original = &source[source.find_table_by_name("posts").unwrap()];
+        assert_eq!(graph[pasted[0]].name, "posts");
+        assert_eq!(graph[pasted[0]].position, original.position);
+        assert_eq!(ops.len(), 1 + original.columns.len());
+    }
+}
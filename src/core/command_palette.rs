@@ -0,0 +1,367 @@
+//! Search behind the Ctrl+K command palette
+//!
+//! One fuzzy query runs over editor commands, tables, columns and relationships.
+//! Results are ranked by match quality and carry the positions of the matched
+//! characters so the palette can highlight them.
+
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+use crate::core::schema::SchemaGraph;
+use crate::core::table_display::TableDisplay;
+
+/// Number of results the palette shows by default
+pub const DEFAULT_PALETTE_LIMIT: usize = 50;
+
+/// Editor command available from the palette
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaletteCommand {
+    NewTable,
+    AutoLayout,
+    Export,
+    ToggleSourceView,
+    /// Set the display level of every table
+    TableDisplay(TableDisplay),
+}
+
+impl PaletteCommand {
+    pub const ALL: [PaletteCommand; 8] = [
+        PaletteCommand::NewTable,
+        PaletteCommand::AutoLayout,
+        PaletteCommand::Export,
+        PaletteCommand::ToggleSourceView,
+        PaletteCommand::TableDisplay(TableDisplay::Full),
+        PaletteCommand::TableDisplay(TableDisplay::Keys),
+        PaletteCommand::TableDisplay(TableDisplay::NameOnly),
+        PaletteCommand::TableDisplay(TableDisplay::Collapsed),
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaletteCommand::NewTable => "New table",
+            PaletteCommand::AutoLayout => "Auto layout",
+            PaletteCommand::Export => "Export schema",
+            PaletteCommand::ToggleSourceView => "Toggle source view",
+            PaletteCommand::TableDisplay(TableDisplay::Full) => "Show all columns",
+            PaletteCommand::TableDisplay(TableDisplay::Keys) => "Show key columns only",
+            PaletteCommand::TableDisplay(TableDisplay::NameOnly) => "Show table names only",
+            PaletteCommand::TableDisplay(TableDisplay::Collapsed) => "Collapse all tables",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            PaletteCommand::NewTable => "Create a table in the middle of the view",
+            PaletteCommand::AutoLayout => "Arrange tables based on relationships",
+            PaletteCommand::Export => "Export as JSON, SQL or CSV",
+            PaletteCommand::ToggleSourceView => "Switch between the canvas and SQL source",
+            PaletteCommand::TableDisplay(TableDisplay::Full) => "Every table lists all its columns",
+            PaletteCommand::TableDisplay(TableDisplay::Keys) => {
+                "Every table lists primary key, unique and relationship columns"
+            }
+            PaletteCommand::TableDisplay(TableDisplay::NameOnly) => {
+                "Every table shows its name only"
+            }
+            PaletteCommand::TableDisplay(TableDisplay::Collapsed) => {
+                "Every table shows its name and a column count"
+            }
+        }
+    }
+}
+
+/// Element a palette result points to
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaletteItem {
+    Command(PaletteCommand),
+    Table(NodeIndex),
+    Column { table: NodeIndex, column: String },
+    Relationship(EdgeIndex),
+}
+
+impl PaletteItem {
+    /// Order of kinds among results with the same score
+    fn rank(&self) -> u8 {
+        match self {
+            PaletteItem::Command(_) => 0,
+            PaletteItem::Table(_) => 1,
+            PaletteItem::Column { .. } => 2,
+            PaletteItem::Relationship(_) => 3,
+        }
+    }
+}
+
+/// One ranked palette result
+#[derive(Clone, Debug, PartialEq)]
+pub struct PaletteMatch {
+    pub item: PaletteItem,
+    pub label: String,
+    /// Secondary text (column type, relationship ends, command description)
+    pub detail: String,
+    pub score: i32,
+    /// Character positions in `label` matched by the query
+    pub highlights: Vec<usize>,
+}
+
+/// Match `query` against `text` as a case-insensitive subsequence
+///
+/// Returns the score and the matched character positions. A contiguous substring
+/// beats a scattered match; matches at the start of the text or of a word
+/// (after `_`, `.`, space or `-`) score higher.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<(i32, Vec<usize>)> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let word_start = |i: usize| i == 0 || matches!(text[i - 1], '_' | '.' | ' ' | '-');
+
+    // Сплошное вхождение - лучший вариант (ищем то, что начинается с начала слова)
+    let substring = (0..text.len().saturating_sub(query.len() - 1))
+        .filter(|&i| text[i..i + query.len()] == query[..])
+        .min_by_key(|&i| (!word_start(i), i));
+    if let Some(start) = substring {
+        let mut score = 100 + 10 * query.len() as i32 - start as i32;
+        if word_start(start) {
+            score += 30;
+        }
+        if query.len() == text.len() {
+            score += 50;
+        }
+        return Some((score, (start..start + query.len()).collect()));
+    }
+
+    // Иначе - жадная подпоследовательность
+    let mut positions = Vec::with_capacity(query.len());
+    let mut score = 0;
+    let mut next = 0;
+    for &q in &query {
+        let pos = (next..text.len()).find(|&i| text[i] == q)?;
+        score += 5;
+        if word_start(pos) {
+            score += 8;
+        }
+        if positions.last().is_some_and(|&last| last + 1 == pos) {
+            score += 5;
+        }
+        positions.push(pos);
+        next = pos + 1;
+    }
+    // Разбросанные совпадения в длинных именах ниже
+    score -= (positions[positions.len() - 1] - positions[0]) as i32;
+    Some((score, positions))
+}
+
+/// Search commands, tables, columns and relationships
+///
+/// An empty query lists the commands followed by the tables in alphabetical order.
+pub fn search_palette(graph: &SchemaGraph, query: &str, limit: usize) -> Vec<PaletteMatch> {
+    let mut results = Vec::new();
+
+    if query.trim().is_empty() {
+        results.extend(PaletteCommand::ALL.iter().map(|&command| PaletteMatch {
+            item: PaletteItem::Command(command),
+            label: command.label().to_string(),
+            detail: command.description().to_string(),
+            score: 0,
+            highlights: Vec::new(),
+        }));
+        let mut tables: Vec<_> = graph
+            .node_indices()
+            .map(|idx| table_match(graph, idx, 0, Vec::new()))
+            .collect();
+        tables.sort_by(|a, b| a.label.cmp(&b.label));
+        results.extend(tables);
+        results.truncate(limit);
+        return results;
+    }
+
+    for command in PaletteCommand::ALL {
+        if let Some((score, highlights)) = fuzzy_match(query, command.label()) {
+            results.push(PaletteMatch {
+                item: PaletteItem::Command(command),
+                label: command.label().to_string(),
+                detail: command.description().to_string(),
+                score,
+                highlights,
+            });
+        }
+    }
+
+    for idx in graph.node_indices() {
+        let table = &graph[idx];
+        if let Some((score, highlights)) = fuzzy_match(query, &table.name) {
+            results.push(table_match(graph, idx, score, highlights));
+        }
+        for column in &table.columns {
+            let label = format!("{}.{}", table.name, column.name);
+            // Колонку можно найти и по "email", и по "users.email"
+            let matched = better(
+                fuzzy_match(query, &label),
+                fuzzy_match(query, &column.name).map(|(score, positions)| {
+                    let offset = table.name.chars().count() + 1;
+                    (score, positions.into_iter().map(|p| p + offset).collect())
+                }),
+            );
+            if let Some((score, highlights)) = matched {
+                results.push(PaletteMatch {
+                    item: PaletteItem::Column {
+                        table: idx,
+                        column: column.name.clone(),
+                    },
+                    label,
+                    detail: column.data_type.clone(),
+                    score,
+                    highlights,
+                });
+            }
+        }
+    }
+
+    for edge in graph.edge_references() {
+        let relationship = edge.weight();
+        let detail = format!(
+            "{}.{} → {}.{}",
+            graph[edge.source()].name,
+            relationship.from_column,
+            graph[edge.target()].name,
+            relationship.to_column
+        );
+        // По концам связи тоже ищем, но с меньшим весом
+        let matched = better(
+            fuzzy_match(query, &relationship.name),
+            fuzzy_match(query, &detail).map(|(score, _)| (score / 2, Vec::new())),
+        );
+        if let Some((score, highlights)) = matched {
+            results.push(PaletteMatch {
+                item: PaletteItem::Relationship(edge.id()),
+                label: relationship.name.clone(),
+                detail,
+                score,
+                highlights,
+            });
+        }
+    }
+
+    results.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then(a.item.rank().cmp(&b.item.rank()))
+            .then(a.label.len().cmp(&b.label.len()))
+            .then(a.label.cmp(&b.label))
+    });
+    results.truncate(limit);
+    results
+}
+
+fn table_match(
+    graph: &SchemaGraph,
+    idx: NodeIndex,
+    score: i32,
+    highlights: Vec<usize>,
+) -> PaletteMatch {
+    let table = &graph[idx];
+    PaletteMatch {
+        item: PaletteItem::Table(idx),
+        label: table.name.clone(),
+        detail: match table.columns.len() {
+            1 => "1 column".to_string(),
+            n => format!("{} columns", n),
+        },
+        score,
+        highlights,
+    }
+}
+
+/// The higher-scoring of two optional matches
+fn better(a: Option<(i32, Vec<usize>)>, b: Option<(i32, Vec<usize>)>) -> Option<(i32, Vec<usize>)> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if b.0 > a.0 { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{TableOps, create_demo_graph};
+
+    #[test]
+    fn test_fuzzy_match_prefers_substring_and_word_start() {
+        let (prefix, positions) = fuzzy_match("post", "posts").unwrap();
+        assert_eq!(positions, vec![0, 1, 2, 3]);
+        let (inner, _) = fuzzy_match("post", "user_posts").unwrap();
+        let (scattered, positions) = fuzzy_match("pst", "posts").unwrap();
+        assert_eq!(positions, vec![0, 2, 3]);
+
+        assert!(prefix > inner);
+        assert!(inner > scattered);
+        assert!(fuzzy_match("xyz", "posts").is_none());
+        assert!(fuzzy_match("POSTS", "posts").is_some());
+    }
+
+    #[test]
+    fn test_fuzzy_match_exact_beats_prefix() {
+        let (exact, _) = fuzzy_match("users", "users").unwrap();
+        let (prefix, _) = fuzzy_match("users", "users_roles").unwrap();
+        assert!(exact > prefix);
+    }
+
+    #[test]
+    fn test_search_finds_all_kinds() {
+        let graph = create_demo_graph();
+
+        let results = search_palette(&graph, "user", DEFAULT_PALETTE_LIMIT);
+        assert_eq!(
+            results[0].item,
+            PaletteItem::Table(graph.find_table_by_name("users").unwrap())
+        );
+        assert!(results.iter().any(|r| matches!(
+            &r.item,
+            PaletteItem::Column { column, .. } if column == "user_id"
+        )));
+        assert!(
+            results
+                .iter()
+                .any(|r| matches!(r.item, PaletteItem::Relationship(_)) && r.label == "user_posts")
+        );
+
+        let results = search_palette(&graph, "layout", DEFAULT_PALETTE_LIMIT);
+        assert_eq!(
+            results[0].item,
+            PaletteItem::Command(PaletteCommand::AutoLayout)
+        );
+    }
+
+    #[test]
+    fn test_search_column_by_qualified_name() {
+        let graph = create_demo_graph();
+        let results = search_palette(&graph, "posts.title", DEFAULT_PALETTE_LIMIT);
+
+        assert_eq!(results[0].label, "posts.title");
+        assert_eq!(results[0].detail, "VARCHAR(255)");
+        assert_eq!(results[0].highlights.len(), "posts.title".len());
+    }
+
+    #[test]
+    fn test_empty_query_lists_commands_then_tables() {
+        let graph = create_demo_graph();
+        let results = search_palette(&graph, "  ", DEFAULT_PALETTE_LIMIT);
+
+        assert_eq!(
+            results.len(),
+            PaletteCommand::ALL.len() + graph.node_count()
+        );
+        assert!(matches!(results[0].item, PaletteItem::Command(_)));
+        let tables: Vec<_> = results[PaletteCommand::ALL.len()..]
+            .iter()
+            .map(|r| r.label.as_str())
+            .collect();
+        assert_eq!(tables, vec!["comments", "posts", "users"]);
+
+        assert_eq!(search_palette(&graph, "", 2).len(), 2);
+    }
+}
@@ -0,0 +1,297 @@
+//! Drag-to-connect: relationships drawn between column rows on the canvas
+//!
+//! Dragging starts on a column of one table and ends on a column of another
+//! table, or on a table without a suitable column, in which case a foreign key
+//! column referencing the dragged column can be created there. The canvas asks
+//! this module which columns to highlight, which relationship type to preselect
+//! and finally applies the connection as one batch of operations.
+
+use std::collections::HashMap;
+
+use petgraph::graph::{EdgeIndex, NodeIndex};
+
+use crate::core::junction::{key_column_name, referencing_data_type};
+use crate::core::liveshare::{ColumnData, GraphOperation, RelationshipData};
+use crate::core::relationship_check::RelationshipIssue;
+use crate::core::schema::{Column, Relationship, RelationshipOps, RelationshipType, SchemaGraph};
+
+/// Where a connection drag was dropped
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConnectTarget {
+    /// An existing column of the target table
+    Column(String),
+    /// A new foreign key column, as proposed by [`foreign_key_column`]
+    NewColumn,
+}
+
+/// Columns of other tables whose type is compatible with the dragged column
+pub fn compatible_columns(
+    graph: &SchemaGraph,
+    table: NodeIndex,
+    column: &str,
+) -> HashMap<NodeIndex, Vec<String>> {
+    let Some((_, source)) = graph.node_weight(table).and_then(|t| t.find_column(column)) else {
+        return HashMap::new();
+    };
+    graph
+        .node_indices()
+        .filter(|&idx| idx != table)
+        .filter_map(|idx| {
+            let columns: Vec<String> = graph[idx]
+                .columns
+                .iter()
+                .filter(|c| source.is_type_compatible_with(c))
+                .map(|c| c.name.clone())
+                .collect();
+            (!columns.is_empty()).then_some((idx, columns))
+        })
+        .collect()
+}
+
+/// Relationship type that fits the keys on both ends of `source -> target`
+///
+/// A unique column is the "one" side; when neither side is unique the source is
+/// treated as the foreign key (N:1).
+pub fn suggest_relationship_type(source: &Column, target: &Column) -> RelationshipType {
+    let is_unique = |column: &Column| column.is_primary_key || column.is_unique;
+    match (is_unique(source), is_unique(target)) {
+        (true, true) => RelationshipType::OneToOne,
+        (true, false) => RelationshipType::OneToMany,
+        _ => RelationshipType::ManyToOne,
+    }
+}
+
+/// Foreign key column that `target` would get to reference `table.column`
+///
+/// `users.id` dropped on `posts` proposes `posts.user_id`; the name gets a numeric
+/// suffix when taken.
+pub fn foreign_key_column(
+    graph: &SchemaGraph,
+    table: NodeIndex,
+    column: &str,
+    target: NodeIndex,
+) -> Option<Column> {
+    let source_table = graph.node_weight(table)?;
+    let (_, source) = source_table.find_column(column)?;
+    let target_table = graph.node_weight(target)?;
+
+    let base = key_column_name(&source_table.name, &source.name);
+    let mut name = base.clone();
+    let mut suffix = 2;
+    while target_table.find_column(&name).is_some() {
+        name = format!("{}_{}", base, suffix);
+        suffix += 1;
+    }
+    Some(Column::new(name, referencing_data_type(source)))
+}
+
+/// Connect `table.column` to `target`, creating the foreign key column if requested
+///
+/// The relationship goes from the dragged column to the drop target. Returns the
+/// new edge, non-blocking issues of the relationship and the operations to broadcast.
+pub fn connect_columns(
+    graph: &mut SchemaGraph,
+    table: NodeIndex,
+    column: &str,
+    target: NodeIndex,
+    target_column: &ConnectTarget,
+    relationship_type: RelationshipType,
+) -> Result<(EdgeIndex, Vec<RelationshipIssue>, Vec<GraphOperation>), String> {
+    if table == target {
+        return Err("Drop the relationship on another table".to_string());
+    }
+    let source = graph
+        .node_weight(table)
+        .and_then(|t| t.find_column(column))
+        .map(|(_, c)| c.clone())
+        .ok_or_else(|| format!("Column '{}' not found", column))?;
+
+    let mut ops = Vec::new();
+    let to_column = match target_column {
+        ConnectTarget::Column(name) => {
+            let (_, existing) = graph
+                .node_weight(target)
+                .and_then(|t| t.find_column(name))
+                .ok_or_else(|| format!("Column '{}' not found", name))?;
+            if !source.is_type_compatible_with(existing) {
+                return Err(format!(
+                    "Column type {} is not compatible with target column type {}",
+                    source.data_type, existing.data_type
+                ));
+            }
+            name.clone()
+        }
+        ConnectTarget::NewColumn => {
+            let new_column = foreign_key_column(graph, table, column, target)
+                .ok_or_else(|| "Table not found".to_string())?;
+            let node = &mut graph[target];
+            node.columns.push(new_column.clone());
+            ops.push(GraphOperation::AddColumn {
+                node_id: target.index() as u32,
+                table_uuid: node.uuid,
+                column: ColumnData::from(&new_column),
+            });
+            new_column.name
+        }
+    };
+
+    // Имя по ключевой колонке: fk_posts_user_id
+    let (key_table, key_column) = if relationship_type.key_on_source() {
+        (table, column)
+    } else {
+        (target, to_column.as_str())
+    };
+    let relationship = Relationship::new(
+        format!("fk_{}_{}", graph[key_table].name, key_column),
+        relationship_type,
+        column,
+        to_column.as_str(),
+    );
+    let data = RelationshipData::from(&relationship);
+
+    match graph.create_relationship_checked(table, target, relationship) {
+        Ok((edge, issues)) => {
+            ops.push(GraphOperation::CreateRelationship {
+                edge_id: edge.index() as u32,
+                from_node: table.index() as u32,
+                to_node: target.index() as u32,
+                relationship: data,
+            });
+            Ok((edge, issues, ops))
+        }
+        Err(e) => {
+            // Созданная колонка без связи не нужна
+            if *target_column == ConnectTarget::NewColumn {
+                graph[target].columns.pop();
+            }
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{TableNode, TableOps, create_demo_graph};
+
+    fn table(graph: &SchemaGraph, name: &str) -> NodeIndex {
+        graph.find_table_by_name(name).unwrap()
+    }
+
+    #[test]
+    fn test_compatible_columns_skip_source_table() {
+        let graph = create_demo_graph();
+        let users = table(&graph, "users");
+
+        let targets = compatible_columns(&graph, users, "id");
+        assert!(!targets.contains_key(&users));
+        let posts = &targets[&table(&graph, "posts")];
+        assert!(posts.contains(&"user_id".to_string()));
+        assert!(!posts.contains(&"title".to_string()));
+
+        assert!(compatible_columns(&graph, users, "missing").is_empty());
+    }
+
+    #[test]
+    fn test_suggest_relationship_type() {
+        let id = Column::new("id", "INT").primary_key();
+        let user_id = Column::new("user_id", "INT");
+        let email = Column::new("email", "VARCHAR(255)").unique();
+
+        assert_eq!(
+            suggest_relationship_type(&id, &user_id),
+            RelationshipType::OneToMany
+        );
+        assert_eq!(
+            suggest_relationship_type(&user_id, &id),
+            RelationshipType::ManyToOne
+        );
+        assert_eq!(
+            suggest_relationship_type(&id, &email),
+            RelationshipType::OneToOne
+        );
+    }
+
+    #[test]
+    fn test_connect_existing_column() {
+        let mut graph = create_demo_graph();
+        let (users, posts) = (table(&graph, "users"), table(&graph, "posts"));
+        let edges_before = graph.edge_count();
+
+        let (edge, _, ops) = connect_columns(
+            &mut graph,
+            posts,
+            "user_id",
+            users,
+            &ConnectTarget::Column("id".to_string()),
+            RelationshipType::ManyToOne,
+        )
+        .unwrap();
+
+        assert_eq!(graph.edge_count(), edges_before + 1);
+        assert_eq!(graph[edge].name, "fk_posts_user_id");
+        assert_eq!(graph.edge_endpoints(edge), Some((posts, users)));
+        assert_eq!(ops.len(), 1);
+
+        // Несовместимые типы не соединяются
+        let err = connect_columns(
+            &mut graph,
+            posts,
+            "title",
+            users,
+            &ConnectTarget::Column("id".to_string()),
+            RelationshipType::ManyToOne,
+        );
+        assert!(err.is_err());
+        assert_eq!(graph.edge_count(), edges_before + 1);
+    }
+
+    #[test]
+    fn test_connect_creates_foreign_key_column() {
+        let mut graph = create_demo_graph();
+        let users = table(&graph, "users");
+        let tags = graph.add_node(TableNode::new("tags"));
+
+        let proposed = foreign_key_column(&graph, users, "id", tags).unwrap();
+        assert_eq!(proposed.name, "user_id");
+
+        let (edge, _, ops) = connect_columns(
+            &mut graph,
+            users,
+            "id",
+            tags,
+            &ConnectTarget::NewColumn,
+            RelationshipType::OneToMany,
+        )
+        .unwrap();
+
+        let (_, column) = graph[tags].find_column("user_id").unwrap();
+        assert_eq!(column.data_type, graph[users].columns[0].data_type);
+        assert!(!column.is_primary_key);
+        assert_eq!(graph[edge].to_column, "user_id");
+        assert_eq!(graph[edge].name, "fk_tags_user_id");
+        assert!(matches!(ops[0], GraphOperation::AddColumn { .. }));
+        assert!(matches!(ops[1], GraphOperation::CreateRelationship { .. }));
+
+        // Второй ключ на ту же таблицу получает суффикс
+        let again = foreign_key_column(&graph, users, "id", tags).unwrap();
+        assert_eq!(again.name, "user_id_2");
+    }
+
+    #[test]
+    fn test_connect_to_same_table_is_rejected() {
+        let mut graph = create_demo_graph();
+        let users = table(&graph, "users");
+        assert!(
+            connect_columns(
+                &mut graph,
+                users,
+                "id",
+                users,
+                &ConnectTarget::NewColumn,
+                RelationshipType::OneToMany,
+            )
+            .is_err()
+        );
+    }
+}
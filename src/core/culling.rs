@@ -0,0 +1,181 @@
+//! Viewport culling and level of detail for large diagrams
+//!
+//! The canvas only renders tables and relationships that intersect the visible
+//! area (plus a margin, so panning does not reveal empty space before the next
+//! render). When zoomed out far enough to make column rows unreadable, tables are
+//! drawn as name-only boxes and relationships are merged into a single SVG path.
+
+use petgraph::graph::{EdgeIndex, NodeIndex};
+
+use crate::core::minimap::Viewport;
+use crate::core::schema::SchemaGraph;
+use crate::core::selection::table_bounds;
+
+/// Extra area rendered around the viewport, in screen pixels
+pub const CULL_MARGIN: f64 = 200.0;
+
+/// Below this zoom tables are drawn as name-only boxes and edges are batched
+pub const COMPACT_ZOOM: f64 = 0.5;
+
+/// Distance a relationship path may run outside its tables' bounds, in canvas units
+const EDGE_SLACK: f64 = 60.0;
+
+/// How much of a table the canvas draws
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DetailLevel {
+    /// Columns, icons, labels and per-edge click targets
+    #[default]
+    Full,
+    /// Name-only boxes, one batched path for all relationships
+    Compact,
+}
+
+impl DetailLevel {
+    pub fn for_zoom(zoom: f64) -> Self {
+        if zoom < COMPACT_ZOOM {
+            DetailLevel::Compact
+        } else {
+            DetailLevel::Full
+        }
+    }
+}
+
+/// Canvas area worth rendering: the viewport grown by [`CULL_MARGIN`] screen pixels
+pub fn render_area(viewport: &Viewport, zoom: f64) -> Viewport {
+    let margin = CULL_MARGIN / zoom;
+    Viewport {
+        x: viewport.x - margin,
+        y: viewport.y - margin,
+        width: viewport.width + 2.0 * margin,
+        height: viewport.height + 2.0 * margin,
+    }
+}
+
+fn intersects(area: &Viewport, (left, top, right, bottom): (f64, f64, f64, f64)) -> bool {
+    left <= area.x + area.width
+        && right >= area.x
+        && top <= area.y + area.height
+        && bottom >= area.y
+}
+
+/// Tables among `tables` that intersect `area`, in the same order
+pub fn cull_tables(graph: &SchemaGraph, area: &Viewport, tables: &[NodeIndex]) -> Vec<NodeIndex> {
+    tables
+        .iter()
+        .copied()
+        .filter(|&idx| table_bounds(graph, idx).is_some_and(|bounds| intersects(area, bounds)))
+        .collect()
+}
+
+/// Relationships among `edges` whose path may cross `area`, in the same order
+///
+/// The path is bounded by both tables, the stored route and [`EDGE_SLACK`], so an
+/// edge between two off-screen tables stays rendered while it crosses the view.
+pub fn cull_edges(graph: &SchemaGraph, area: &Viewport, edges: &[EdgeIndex]) -> Vec<EdgeIndex> {
+    edges
+        .iter()
+        .copied()
+        .filter(|&edge| edge_bounds(graph, edge).is_some_and(|bounds| intersects(area, bounds)))
+        .collect()
+}
+
+/// Conservative bounds of a relationship path: (left, top, right, bottom)
+fn edge_bounds(graph: &SchemaGraph, edge: EdgeIndex) -> Option<(f64, f64, f64, f64)> {
+    let (from, to) = graph.edge_endpoints(edge)?;
+    let (l1, t1, r1, b1) = table_bounds(graph, from)?;
+    let (l2, t2, r2, b2) = table_bounds(graph, to)?;
+    let (mut left, mut top) = (l1.min(l2), t1.min(t2));
+    let (mut right, mut bottom) = (r1.max(r2), b1.max(b2));
+    // Устаревший маршрут тоже учитываем: лишняя отрисовка дешевле пропавшей связи
+    for &(x, y) in &graph[edge].route {
+        left = left.min(x);
+        top = top.min(y);
+        right = right.max(x);
+        bottom = bottom.max(y);
+    }
+    Some((
+        left - EDGE_SLACK,
+        top - EDGE_SLACK,
+        right + EDGE_SLACK,
+        bottom + EDGE_SLACK,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Relationship, RelationshipType, TableNode};
+
+    /// Tables in a row 1000 units apart, each linked to the next
+    fn row(count: usize) -> (SchemaGraph, Vec<NodeIndex>) {
+        let mut graph = SchemaGraph::new();
+        let tables: Vec<_> = (0..count)
+            .map(|i| {
+                graph.add_node(
+                    TableNode::new(format!("t{}", i)).with_position(i as f64 * 1000.0, 0.0),
+                )
+            })
+            .collect();
+        for pair in tables.windows(2) {
+            graph.add_edge(
+                pair[1],
+                pair[0],
+                Relationship::new("fk", RelationshipType::ManyToOne, "id", "id"),
+            );
+        }
+        (graph, tables)
+    }
+
+    fn view(x: f64, width: f64) -> Viewport {
+        Viewport {
+            x,
+            y: -100.0,
+            width,
+            height: 400.0,
+        }
+    }
+
+    #[test]
+    fn test_detail_level_for_zoom() {
+        assert_eq!(DetailLevel::for_zoom(1.0), DetailLevel::Full);
+        assert_eq!(DetailLevel::for_zoom(COMPACT_ZOOM), DetailLevel::Full);
+        assert_eq!(DetailLevel::for_zoom(0.2), DetailLevel::Compact);
+    }
+
+    #[test]
+    fn test_render_area_margin_scales_with_zoom() {
+        let viewport = view(0.0, 800.0);
+        assert_eq!(render_area(&viewport, 1.0).x, -CULL_MARGIN);
+        assert_eq!(render_area(&viewport, 0.5).width, 800.0 + 4.0 * CULL_MARGIN);
+    }
+
+    #[test]
+    fn test_cull_tables_keeps_order_and_drops_offscreen() {
+        let (graph, tables) = row(10);
+
+        let visible = cull_tables(&graph, &view(1200.0, 1900.0), &tables);
+        assert_eq!(visible, vec![tables[1], tables[2], tables[3]]);
+        assert!(cull_tables(&graph, &view(-5000.0, 100.0), &tables).is_empty());
+    }
+
+    #[test]
+    fn test_edge_crossing_view_is_kept() {
+        let (mut graph, tables) = row(3);
+        let edges: Vec<_> = graph.edge_indices().collect();
+
+        // Между t0 и t1 видна только середина связи, сами таблицы за кадром
+        let gap = view(500.0, 100.0);
+        assert!(cull_tables(&graph, &gap, &tables).is_empty());
+        assert_eq!(cull_edges(&graph, &gap, &edges), vec![edges[0]]);
+
+        // Маршрут в обход уводит связь далеко вниз
+        graph[edges[1]].route = vec![(1280.0, 50.0), (1280.0, 3000.0), (2000.0, 3000.0)];
+        let below = Viewport {
+            x: 1500.0,
+            y: 2900.0,
+            width: 200.0,
+            height: 200.0,
+        };
+        assert_eq!(cull_edges(&graph, &below, &edges), vec![edges[1]]);
+    }
+}
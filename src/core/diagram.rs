@@ -0,0 +1,124 @@
+//! Diagram document model
+//!
+//! The diagram document is what gets persisted in `schema_data`: the schema graph
+//! itself plus per-diagram settings (target SQL dialect, SQL formatting, lint rules, etc.).
+//!
+//! Settings are stored next to the serialized graph fields, so documents saved
+//! before settings existed still load (with default settings), and older clients
+//! simply ignore the extra key.
+
+use super::{FormatOptions, LayoutAlgorithm, LintSettings, Notation, SchemaGraph, SqlDialect};
+use serde::{Deserialize, Serialize};
+
+/// Per-diagram settings stored in the diagram document
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiagramSettings {
+    /// Target SQL dialect used by the source editor, validation and exporters
+    #[serde(default)]
+    pub dialect: SqlDialect,
+    /// SQL formatting used by the source editor and SQL export
+    #[serde(default)]
+    pub sql_format: FormatOptions,
+    /// Disabled lint rules and severity overrides
+    #[serde(default)]
+    pub lint: LintSettings,
+    /// Algorithm used by the canvas auto-layout button
+    #[serde(default)]
+    pub layout: LayoutAlgorithm,
+    /// Notation of relationship ends on the canvas and in exported images
+    #[serde(default)]
+    pub notation: Notation,
+}
+
+/// Persisted diagram document: graph + settings
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DiagramDocument {
+    #[serde(flatten)]
+    pub graph: SchemaGraph,
+    #[serde(default)]
+    pub settings: DiagramSettings,
+}
+
+impl DiagramDocument {
+    pub fn new(graph: SchemaGraph, settings: DiagramSettings) -> Self {
+        Self { graph, settings }
+    }
+
+    /// Parse a document from `schema_data` JSON
+    pub fn from_value(value: serde_json::Value) -> Result<Self, String> {
+        serde_json::from_value(value).map_err(|e| format!("Failed to parse diagram: {}", e))
+    }
+
+    /// Serialize the document for `schema_data`
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{TableDisplay, TableOps, create_demo_graph, set_table_display};
+
+    #[test]
+    fn test_document_roundtrip_keeps_dialect() {
+        let doc = DiagramDocument::new(
+            create_demo_graph(),
+            DiagramSettings {
+                dialect: SqlDialect::PostgreSQL,
+                ..Default::default()
+            },
+        );
+        let json = doc.to_json().unwrap();
+
+        let loaded = DiagramDocument::from_value(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(loaded.settings.dialect, SqlDialect::PostgreSQL);
+        assert_eq!(loaded.graph.node_count(), 3);
+        assert_eq!(loaded.graph.edge_count(), 3);
+    }
+
+    #[test]
+    fn test_document_roundtrip_keeps_notation() {
+        let doc = DiagramDocument::new(
+            create_demo_graph(),
+            DiagramSettings {
+                notation: Notation::CrowsFoot,
+                ..Default::default()
+            },
+        );
+        let json = doc.to_json().unwrap();
+
+        let loaded = DiagramDocument::from_value(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(loaded.settings.notation, Notation::CrowsFoot);
+    }
+
+    #[test]
+    fn test_document_roundtrip_keeps_table_display() {
+        let mut graph = create_demo_graph();
+        let users = graph.find_table_by_name("users").unwrap();
+        set_table_display(&mut graph, &[users], TableDisplay::Collapsed);
+        let json = DiagramDocument::new(graph, DiagramSettings::default())
+            .to_json()
+            .unwrap();
+
+        let loaded = DiagramDocument::from_value(serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(loaded.graph[users].display, TableDisplay::Collapsed);
+        assert!(
+            loaded
+                .graph
+                .node_indices()
+                .filter(|&idx| idx != users)
+                .all(|idx| loaded.graph[idx].display == TableDisplay::Full)
+        );
+    }
+
+    #[test]
+    fn test_legacy_document_without_settings() {
+        // Old documents contain only the serialized graph
+        let legacy = serde_json::to_value(create_demo_graph()).unwrap();
+        let loaded = DiagramDocument::from_value(legacy).unwrap();
+
+        assert_eq!(loaded.settings, DiagramSettings::default());
+        assert_eq!(loaded.graph.node_count(), 3);
+    }
+}
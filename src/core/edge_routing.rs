@@ -0,0 +1,1031 @@
+//! Orthogonal routing of relationship edges
+//!
+//! Every relationship is drawn as a polyline of horizontal and vertical
+//! segments. It leaves the source table beside the row of `from_column`, goes
+//! around the other tables and enters the target table beside the row of
+//! `to_column`.
+//!
+//! Routes are searched on a sparse orthogonal grid whose lines are the table
+//! outlines inflated by a margin, so a route never cuts through a table and
+//! prefers few bends. Overlapping segments of different relationships are then
+//! spread apart so parallel edges stay distinguishable.
+//!
+//! Routes are stored in [`Relationship::route`](crate::core::Relationship), i.e.
+//! in the diagram document, so everything that draws the diagram reproduces the
+//! same lines.
+
+use crate::core::auto_layout::{TABLE_HEADER_HEIGHT, TABLE_ROW_HEIGHT, TABLE_WIDTH};
+use crate::core::schema::SchemaGraph;
+use crate::core::table_display::{MAX_COLUMN_LIST_HEIGHT, TableRows, displayed_table_height};
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Point of a route in canvas coordinates
+pub type RoutePoint = (f64, f64);
+
+/// Padding above the first column row
+const COLUMN_LIST_PADDING: f64 = 8.0;
+/// Tolerance for comparing coordinates
+const EPS: f64 = 0.01;
+
+/// Edge routing configuration
+#[derive(Clone, Debug)]
+pub struct RoutingConfig {
+    /// Clearance kept between routes and table outlines
+    pub margin: f64,
+    /// Cost of a bend, in pixels of route length
+    pub bend_penalty: f64,
+    /// Distance between parallel segments of different relationships
+    pub parallel_spacing: f64,
+}
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        Self {
+            margin: 20.0,
+            bend_penalty: 60.0,
+            parallel_spacing: 8.0,
+        }
+    }
+}
+
+/// Rendered outline of a table on the canvas
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TableBounds {
+    pub left: f64,
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+}
+
+impl TableBounds {
+    /// Outline of `table` at its display level
+    pub fn of(graph: &SchemaGraph, table: NodeIndex) -> Self {
+        let (x, y) = graph[table].position;
+        Self {
+            left: x,
+            top: y,
+            right: x + TABLE_WIDTH,
+            bottom: y + displayed_table_height(graph, table),
+        }
+    }
+
+    fn intersects(&self, other: &Self) -> bool {
+        self.left < other.right
+            && other.left < self.right
+            && self.top < other.bottom
+            && other.top < self.bottom
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        Self {
+            left: self.left.min(other.left),
+            top: self.top.min(other.top),
+            right: self.right.max(other.right),
+            bottom: self.bottom.max(other.bottom),
+        }
+    }
+
+    fn inflate(&self, by: f64) -> Self {
+        Self {
+            left: self.left - by,
+            top: self.top - by,
+            right: self.right + by,
+            bottom: self.bottom + by,
+        }
+    }
+}
+
+/// Y coordinate where relationships attach to the row of `column`
+///
+/// Unknown columns attach to the first row; rows scrolled out of the column
+/// list attach to its bottom. Columns hidden by the table's display level
+/// attach to the `+N columns` row, or to the header of name-only tables.
+pub fn column_anchor_y(graph: &SchemaGraph, table: NodeIndex, column: &str) -> f64 {
+    let node = &graph[table];
+    let index = node
+        .columns
+        .iter()
+        .position(|c| c.name == column)
+        .unwrap_or(0);
+    let Some(row) = TableRows::of(graph, table).row_of(index) else {
+        return node.position.1 + TABLE_HEADER_HEIGHT / 2.0;
+    };
+    let y = node.position.1
+        + TABLE_HEADER_HEIGHT
+        + COLUMN_LIST_PADDING
+        + row as f64 * TABLE_ROW_HEIGHT
+        + TABLE_ROW_HEIGHT / 2.0;
+    y.min(TableBounds::of(graph, table).bottom - TABLE_ROW_HEIGHT / 2.0)
+}
+
+/// Index of the column shown at canvas height `y` (inverse of [`column_anchor_y`])
+///
+/// Rows scrolled out of the column list, the `+N columns` row and points
+/// outside the list give `None`.
+pub fn column_at_y(graph: &SchemaGraph, table: NodeIndex, y: f64) -> Option<usize> {
+    let offset = y - graph[table].position.1 - TABLE_HEADER_HEIGHT - COLUMN_LIST_PADDING;
+    if !(0.0..MAX_COLUMN_LIST_HEIGHT).contains(&offset) {
+        return None;
+    }
+    let row = (offset / TABLE_ROW_HEIGHT) as usize;
+    TableRows::of(graph, table).columns.get(row).copied()
+}
+
+/// Calculate routes for all relationships of the graph
+///
+/// Self-references on a single column keep an empty route.
+pub fn route_edges(
+    graph: &SchemaGraph,
+    config: &RoutingConfig,
+) -> Vec<(EdgeIndex, Vec<RoutePoint>)> {
+    let edges: Vec<EdgeIndex> = graph.edge_indices().collect();
+    let bounds = table_outlines(graph);
+    route_among(graph, config, &edges, &bounds)
+}
+
+/// Calculate routes for `edges` only
+///
+/// Only tables near these relationships are obstacles: the tables they connect,
+/// the tables overlapping them, and so on until no obstacle reaches outside the
+/// searched area. Moving one table therefore costs a search around it rather
+/// than over the whole diagram.
+pub fn route_local_edges(
+    graph: &SchemaGraph,
+    config: &RoutingConfig,
+    edges: &[EdgeIndex],
+) -> Vec<(EdgeIndex, Vec<RoutePoint>)> {
+    let outlines = table_outlines(graph);
+    let inflated = |n: &NodeIndex| outlines[n].inflate(config.margin);
+    let mut nearby: HashSet<NodeIndex> = edges
+        .iter()
+        .filter_map(|&e| graph.edge_endpoints(e))
+        .flat_map(|(from, to)| [from, to])
+        .collect();
+    let Some(mut window) = nearby.iter().map(inflated).reduce(|a, b| a.union(&b)) else {
+        return Vec::new();
+    };
+    loop {
+        let added: Vec<NodeIndex> = outlines
+            .keys()
+            .filter(|n| !nearby.contains(n) && inflated(n).intersects(&window))
+            .copied()
+            .collect();
+        if added.is_empty() {
+            break;
+        }
+        for n in added {
+            window = window.union(&inflated(&n));
+            nearby.insert(n);
+        }
+    }
+    let bounds: HashMap<NodeIndex, TableBounds> = outlines
+        .into_iter()
+        .filter(|(n, _)| nearby.contains(n))
+        .collect();
+    route_among(graph, config, edges, &bounds)
+}
+
+/// Relationships whose stored route has to be recalculated
+///
+/// `previous` holds the [`table_outlines`] the routes were calculated for. A
+/// route is recalculated when it no longer ends at its rows, when one of its
+/// tables moved or resized, or when a moved table now lies on it.
+pub fn stale_edges(
+    graph: &SchemaGraph,
+    previous: &HashMap<NodeIndex, TableBounds>,
+) -> Vec<EdgeIndex> {
+    let changed: Vec<(NodeIndex, TableBounds)> = table_outlines(graph)
+        .into_iter()
+        .filter(|(n, bounds)| previous.get(n) != Some(bounds))
+        .collect();
+    graph
+        .edge_indices()
+        .filter(|&e| {
+            let Some((from, to)) = graph.edge_endpoints(e) else {
+                return false;
+            };
+            !route_is_current(graph, e)
+                || changed.iter().any(|(n, bounds)| {
+                    *n == from || *n == to || route_crosses(&graph[e].route, bounds)
+                })
+        })
+        .collect()
+}
+
+/// Outline of every table at its display level
+pub fn table_outlines(graph: &SchemaGraph) -> HashMap<NodeIndex, TableBounds> {
+    graph
+        .node_indices()
+        .map(|n| (n, TableBounds::of(graph, n)))
+        .collect()
+}
+
+fn route_crosses(route: &[RoutePoint], bounds: &TableBounds) -> bool {
+    route.windows(2).any(|w| {
+        let (x1, x2) = (w[0].0.min(w[1].0), w[0].0.max(w[1].0));
+        let (y1, y2) = (w[0].1.min(w[1].1), w[0].1.max(w[1].1));
+        x1 < bounds.right && x2 > bounds.left && y1 < bounds.bottom && y2 > bounds.top
+    })
+}
+
+/// Route `edges` around the tables in `bounds` (which include their endpoints)
+fn route_among(
+    graph: &SchemaGraph,
+    config: &RoutingConfig,
+    edges: &[EdgeIndex],
+    bounds: &HashMap<NodeIndex, TableBounds>,
+) -> Vec<(EdgeIndex, Vec<RoutePoint>)> {
+    let obstacles: Vec<TableBounds> = bounds.values().map(|b| b.inflate(config.margin)).collect();
+
+    let ends: Vec<(EdgeIndex, NodeIndex, f64, NodeIndex, f64)> = edges
+        .iter()
+        .filter_map(|&e| {
+            let (from, to) = graph.edge_endpoints(e)?;
+            let rel = &graph[e];
+            Some((
+                e,
+                from,
+                column_anchor_y(graph, from, &rel.from_column),
+                to,
+                column_anchor_y(graph, to, &rel.to_column),
+            ))
+        })
+        .collect();
+    let anchor_ys: Vec<f64> = ends.iter().flat_map(|e| [e.2, e.4]).collect();
+    let grid = Grid::new(&obstacles, &anchor_ys);
+
+    let mut routes: Vec<(EdgeIndex, Vec<RoutePoint>)> = ends
+        .iter()
+        .map(|&(edge, from, from_y, to, to_y)| {
+            let route = if from == to {
+                self_loop(&bounds[&from], from_y, to_y, config.margin)
+            } else {
+                let from_ends = Endpoint::new(bounds[&from], from_y, config.margin);
+                let to_ends = Endpoint::new(bounds[&to], to_y, config.margin);
+                grid.route(&from_ends, &to_ends, config.bend_penalty)
+                    .unwrap_or_else(|| fallback_route(&from_ends, &to_ends))
+            };
+            (edge, route)
+        })
+        .collect();
+
+    spread_parallel_segments(&mut routes, config);
+    routes
+}
+
+/// Store routes in the relationships; returns whether anything changed
+pub fn apply_routes(graph: &mut SchemaGraph, routes: Vec<(EdgeIndex, Vec<RoutePoint>)>) -> bool {
+    let mut changed = false;
+    for (edge, route) in routes {
+        if let Some(rel) = graph.edge_weight_mut(edge)
+            && rel.route != route
+        {
+            rel.route = route;
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Route all relationships with the default configuration and store the routes
+pub fn route_all_edges(graph: &mut SchemaGraph) -> bool {
+    let routes = route_edges(graph, &RoutingConfig::default());
+    apply_routes(graph, routes)
+}
+
+/// Whether the stored route still starts and ends at the rows it belongs to
+///
+/// Moving either table or reordering its columns makes the route stale.
+pub fn route_is_current(graph: &SchemaGraph, edge: EdgeIndex) -> bool {
+    let (Some((from, to)), Some(rel)) = (graph.edge_endpoints(edge), graph.edge_weight(edge))
+    else {
+        return false;
+    };
+    let (Some(&first), Some(&last)) = (rel.route.first(), rel.route.last()) else {
+        return false;
+    };
+    is_anchor(graph, from, &rel.from_column, first) && is_anchor(graph, to, &rel.to_column, last)
+}
+
+fn is_anchor(graph: &SchemaGraph, table: NodeIndex, column: &str, (x, y): RoutePoint) -> bool {
+    let bounds = TableBounds::of(graph, table);
+    (y - column_anchor_y(graph, table, column)).abs() < EPS
+        && ((x - bounds.left).abs() < EPS || (x - bounds.right).abs() < EPS)
+}
+
+/// SVG path data for a route
+pub fn route_svg_path(route: &[RoutePoint]) -> String {
+    route
+        .iter()
+        .enumerate()
+        .map(|(i, (x, y))| format!("{} {} {}", if i == 0 { "M" } else { "L" }, x, y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Position for the relationship label: above the middle of the longest horizontal segment
+pub fn route_label_position(route: &[RoutePoint]) -> Option<RoutePoint> {
+    route
+        .windows(2)
+        .filter(|w| (w[0].1 - w[1].1).abs() < EPS)
+        .max_by(|a, b| (a[0].0 - a[1].0).abs().total_cmp(&(b[0].0 - b[1].0).abs()))
+        .map(|w| ((w[0].0 + w[1].0) / 2.0, w[0].1 - 5.0))
+}
+
+/// Attachment of a relationship to one table: a port on each side
+struct Endpoint {
+    bounds: TableBounds,
+    y: f64,
+    margin: f64,
+}
+
+impl Endpoint {
+    fn new(bounds: TableBounds, y: f64, margin: f64) -> Self {
+        Self { bounds, y, margin }
+    }
+
+    /// Point on the table outline
+    fn port(&self, side: Side) -> RoutePoint {
+        match side {
+            Side::Left => (self.bounds.left, self.y),
+            Side::Right => (self.bounds.right, self.y),
+        }
+    }
+
+    /// Point on the inflated outline where the route leaves the port
+    fn stub(&self, side: Side) -> RoutePoint {
+        match side {
+            Side::Left => (self.bounds.left - self.margin, self.y),
+            Side::Right => (self.bounds.right + self.margin, self.y),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    const BOTH: [Side; 2] = [Side::Left, Side::Right];
+
+    /// Direction of travel away from the table
+    fn outward(self) -> usize {
+        match self {
+            Side::Left => WEST,
+            Side::Right => EAST,
+        }
+    }
+
+    /// Direction of travel into the table
+    fn inward(self) -> usize {
+        match self {
+            Side::Left => EAST,
+            Side::Right => WEST,
+        }
+    }
+}
+
+// Направления движения по сетке
+const EAST: usize = 0;
+const WEST: usize = 1;
+const SOUTH: usize = 2;
+const NORTH: usize = 3;
+
+fn opposite(dir: usize) -> usize {
+    dir ^ 1
+}
+
+/// Sparse orthogonal grid over the canvas
+///
+/// Lines run along every inflated table outline and every attachment row.
+/// Nodes and segments strictly inside an inflated outline are blocked.
+struct Grid {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    blocked: Vec<bool>,
+    /// Segment from (i, j) to (i + 1, j)
+    h_blocked: Vec<bool>,
+    /// Segment from (i, j) to (i, j + 1)
+    v_blocked: Vec<bool>,
+}
+
+impl Grid {
+    fn new(obstacles: &[TableBounds], extra_ys: &[f64]) -> Self {
+        let xs = sorted_coordinates(obstacles.iter().flat_map(|r| [r.left, r.right]));
+        let ys = sorted_coordinates(
+            obstacles
+                .iter()
+                .flat_map(|r| [r.top, r.bottom])
+                .chain(extra_ys.iter().copied()),
+        );
+        let size = xs.len() * ys.len();
+        let mut grid = Self {
+            xs,
+            ys,
+            blocked: vec![false; size],
+            h_blocked: vec![false; size],
+            v_blocked: vec![false; size],
+        };
+
+        let nx = grid.xs.len();
+        for r in obstacles {
+            // Индексы линий строго внутри и на границах прямоугольника
+            let x_inner = strictly_between(&grid.xs, r.left, r.right);
+            let y_inner = strictly_between(&grid.ys, r.top, r.bottom);
+            let x_span = between(&grid.xs, r.left, r.right);
+            let y_span = between(&grid.ys, r.top, r.bottom);
+
+            for j in y_inner.clone() {
+                for i in x_inner.clone() {
+                    grid.blocked[j * nx + i] = true;
+                }
+                for i in x_span.start..x_span.end.saturating_sub(1) {
+                    grid.h_blocked[j * nx + i] = true;
+                }
+            }
+            for i in x_inner {
+                for j in y_span.start..y_span.end.saturating_sub(1) {
+                    grid.v_blocked[j * nx + i] = true;
+                }
+            }
+        }
+        grid
+    }
+
+    fn node(&self, (x, y): RoutePoint) -> Option<usize> {
+        let i = find_coordinate(&self.xs, x)?;
+        let j = find_coordinate(&self.ys, y)?;
+        let id = j * self.xs.len() + i;
+        (!self.blocked[id]).then_some(id)
+    }
+
+    fn point(&self, id: usize) -> RoutePoint {
+        let nx = self.xs.len();
+        (self.xs[id % nx], self.ys[id / nx])
+    }
+
+    /// Neighbour of `id` in direction `dir` and the segment length
+    fn step(&self, id: usize, dir: usize) -> Option<(usize, f64)> {
+        let nx = self.xs.len();
+        let (i, j) = (id % nx, id / nx);
+        let next = match dir {
+            EAST if i + 1 < nx && !self.h_blocked[id] => id + 1,
+            WEST if i > 0 && !self.h_blocked[id - 1] => id - 1,
+            SOUTH if j + 1 < self.ys.len() && !self.v_blocked[id] => id + nx,
+            NORTH if j > 0 && !self.v_blocked[id - nx] => id - nx,
+            _ => return None,
+        };
+        if self.blocked[next] {
+            return None;
+        }
+        let (x1, y1) = self.point(id);
+        let (x2, y2) = self.point(next);
+        Some((next, (x2 - x1).abs() + (y2 - y1).abs()))
+    }
+
+    /// Cheapest route over all side combinations
+    fn route(&self, from: &Endpoint, to: &Endpoint, bend_penalty: f64) -> Option<Vec<RoutePoint>> {
+        let targets: Vec<(Side, usize)> = Side::BOTH
+            .into_iter()
+            .filter_map(|side| Some((side, self.node(to.stub(side))?)))
+            .collect();
+        if targets.is_empty() {
+            return None;
+        }
+
+        let mut best: Option<(f64, Vec<RoutePoint>)> = None;
+        for from_side in Side::BOTH {
+            let Some(start) = self.node(from.stub(from_side)) else {
+                continue;
+            };
+            let goals: Vec<(usize, usize)> = targets
+                .iter()
+                .map(|&(side, node)| (node, side.inward()))
+                .collect();
+            let found = self.shortest_paths(start, from_side.outward(), &goals, bend_penalty);
+            for ((to_side, _), result) in targets.iter().zip(found) {
+                let Some((cost, nodes)) = result else {
+                    continue;
+                };
+                if best.as_ref().is_some_and(|(c, _)| *c <= cost) {
+                    continue;
+                }
+                let route = std::iter::once(from.port(from_side))
+                    .chain(nodes.into_iter().map(|n| self.point(n)))
+                    .chain(std::iter::once(to.port(*to_side)))
+                    .collect();
+                best = Some((cost, simplify(route)));
+            }
+        }
+        best.map(|(_, route)| route)
+    }
+
+    /// Dijkstra over (node, direction) states, bends cost `bend_penalty`
+    ///
+    /// Returns for every goal `(node, arrival direction)` the cost and the
+    /// nodes of the cheapest path.
+    fn shortest_paths(
+        &self,
+        start: usize,
+        start_dir: usize,
+        goals: &[(usize, usize)],
+        bend_penalty: f64,
+    ) -> Vec<Option<(f64, Vec<usize>)>> {
+        let states = self.blocked.len() * 4;
+        let mut dist = vec![f64::INFINITY; states];
+        let mut prev = vec![usize::MAX; states];
+        let mut heap = BinaryHeap::new();
+        let goal_cost = |dist: &[f64], (node, dir): (usize, usize)| {
+            (0..4)
+                .map(|d| {
+                    let state = node * 4 + d;
+                    let bend = if d == dir { 0.0 } else { bend_penalty };
+                    (dist[state] + bend, state)
+                })
+                .min_by(|a, b| a.0.total_cmp(&b.0))
+                .unwrap_or((f64::INFINITY, usize::MAX))
+        };
+
+        dist[start * 4 + start_dir] = 0.0;
+        heap.push(QueueItem {
+            cost: 0.0,
+            state: start * 4 + start_dir,
+        });
+
+        while let Some(QueueItem { cost, state }) = heap.pop() {
+            if cost > dist[state] {
+                continue;
+            }
+            // Дальше все пути дороже уже найденных для всех целей
+            if goals.iter().all(|&goal| goal_cost(&dist, goal).0 <= cost) {
+                break;
+            }
+            let (node, dir) = (state / 4, state % 4);
+            for next_dir in 0..4 {
+                if next_dir == opposite(dir) {
+                    continue;
+                }
+                let Some((next, length)) = self.step(node, next_dir) else {
+                    continue;
+                };
+                let bend = if next_dir == dir { 0.0 } else { bend_penalty };
+                let next_state = next * 4 + next_dir;
+                let next_cost = cost + length + bend;
+                if next_cost < dist[next_state] {
+                    dist[next_state] = next_cost;
+                    prev[next_state] = state;
+                    heap.push(QueueItem {
+                        cost: next_cost,
+                        state: next_state,
+                    });
+                }
+            }
+        }
+
+        goals
+            .iter()
+            .map(|&goal| {
+                let (cost, mut state) = goal_cost(&dist, goal);
+                if !cost.is_finite() {
+                    return None;
+                }
+                let mut nodes = vec![state / 4];
+                while prev[state] != usize::MAX {
+                    state = prev[state];
+                    nodes.push(state / 4);
+                }
+                nodes.reverse();
+                Some((cost, nodes))
+            })
+            .collect()
+    }
+}
+
+/// Min-heap entry for Dijkstra
+#[derive(PartialEq)]
+struct QueueItem {
+    cost: f64,
+    state: usize,
+}
+
+impl Eq for QueueItem {}
+
+impl Ord for QueueItem {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| other.state.cmp(&self.state))
+    }
+}
+
+impl PartialOrd for QueueItem {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn sorted_coordinates(values: impl Iterator<Item = f64>) -> Vec<f64> {
+    let mut values: Vec<f64> = values.collect();
+    values.sort_by(f64::total_cmp);
+    values.dedup_by(|a, b| (*a - *b).abs() < EPS);
+    values
+}
+
+fn find_coordinate(values: &[f64], value: f64) -> Option<usize> {
+    let i = values.partition_point(|&v| v < value - EPS);
+    (i < values.len() && (values[i] - value).abs() < EPS).then_some(i)
+}
+
+/// Indices of coordinates strictly inside `(low, high)`
+fn strictly_between(values: &[f64], low: f64, high: f64) -> std::ops::Range<usize> {
+    values.partition_point(|&v| v <= low + EPS)..values.partition_point(|&v| v < high - EPS)
+}
+
+/// Indices of coordinates inside `[low, high]`
+fn between(values: &[f64], low: f64, high: f64) -> std::ops::Range<usize> {
+    values.partition_point(|&v| v < low - EPS)..values.partition_point(|&v| v <= high + EPS)
+}
+
+/// Drop repeated points and points in the middle of straight runs
+fn simplify(route: Vec<RoutePoint>) -> Vec<RoutePoint> {
+    let mut result: Vec<RoutePoint> = Vec::with_capacity(route.len());
+    for point in route {
+        if result
+            .last()
+            .is_some_and(|last| (last.0 - point.0).abs() < EPS && (last.1 - point.1).abs() < EPS)
+        {
+            continue;
+        }
+        if let [.., a, b] = result[..] {
+            let vertical = (a.0 - b.0).abs() < EPS && (b.0 - point.0).abs() < EPS;
+            let horizontal = (a.1 - b.1).abs() < EPS && (b.1 - point.1).abs() < EPS;
+            if vertical || horizontal {
+                result.pop();
+            }
+        }
+        result.push(point);
+    }
+    result
+}
+
+/// Loop on the right side of a table for self-references
+fn self_loop(bounds: &TableBounds, from_y: f64, to_y: f64, margin: f64) -> Vec<RoutePoint> {
+    if (from_y - to_y).abs() < EPS {
+        return Vec::new();
+    }
+    let out = bounds.right + margin;
+    vec![
+        (bounds.right, from_y),
+        (out, from_y),
+        (out, to_y),
+        (bounds.right, to_y),
+    ]
+}
+
+/// Route without obstacle avoidance when the grid has no path (overlapping tables)
+fn fallback_route(from: &Endpoint, to: &Endpoint) -> Vec<RoutePoint> {
+    let from_center = (from.bounds.left + from.bounds.right) / 2.0;
+    let to_center = (to.bounds.left + to.bounds.right) / 2.0;
+    let (from_side, to_side) = if from_center <= to_center {
+        (Side::Right, Side::Left)
+    } else {
+        (Side::Left, Side::Right)
+    };
+    let start = from.port(from_side);
+    let end = to.port(to_side);
+    let mid_x = (from.stub(from_side).0 + to.stub(to_side).0) / 2.0;
+    simplify(vec![start, (mid_x, start.1), (mid_x, end.1), end])
+}
+
+/// Move overlapping collinear segments of different routes apart
+///
+/// Only inner segments move; the first and last segments stay attached to
+/// their column rows.
+fn spread_parallel_segments(routes: &mut [(EdgeIndex, Vec<RoutePoint>)], config: &RoutingConfig) {
+    // (вертикальный?, координата линии, начало, конец, маршрут, сегмент)
+    let mut segments: Vec<(bool, f64, f64, f64, usize, usize)> = Vec::new();
+    for (r, (_, route)) in routes.iter().enumerate() {
+        for s in 1..route.len().saturating_sub(2) {
+            let (a, b) = (route[s], route[s + 1]);
+            if (a.0 - b.0).abs() < EPS {
+                segments.push((true, a.0, a.1.min(b.1), a.1.max(b.1), r, s));
+            } else {
+                segments.push((false, a.1, a.0.min(b.0), a.0.max(b.0), r, s));
+            }
+        }
+    }
+    segments.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then(a.1.total_cmp(&b.1))
+            .then(a.2.total_cmp(&b.2))
+            .then(a.4.cmp(&b.4))
+    });
+
+    let mut shifts: Vec<(usize, usize, bool, f64)> = Vec::new();
+    let mut start = 0;
+    while start < segments.len() {
+        // Группа: сегменты на одной линии с перекрывающимися интервалами
+        let (vertical, line, _, mut reach, _, _) = segments[start];
+        let mut end = start + 1;
+        while end < segments.len()
+            && segments[end].0 == vertical
+            && (segments[end].1 - line).abs() < EPS
+            && segments[end].2 < reach - EPS
+        {
+            reach = reach.max(segments[end].3);
+            end += 1;
+        }
+
+        let mut group: Vec<usize> = segments[start..end].iter().map(|s| s.4).collect();
+        group.sort_unstable();
+        group.dedup();
+        if group.len() > 1 {
+            let count = group.len() as f64;
+            let spacing = config
+                .parallel_spacing
+                .min(2.0 * (config.margin - 4.0).max(0.0) / (count - 1.0));
+            for segment in &segments[start..end] {
+                let rank = group.iter().position(|&r| r == segment.4).unwrap_or(0) as f64;
+                let offset = (rank - (count - 1.0) / 2.0) * spacing;
+                shifts.push((segment.4, segment.5, vertical, offset));
+            }
+        }
+        start = end;
+    }
+
+    for (r, s, vertical, offset) in shifts {
+        let route = &mut routes[r].1;
+        for point in &mut route[s..=s + 1] {
+            if vertical {
+                point.0 += offset;
+            } else {
+                point.1 += offset;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::schema::{Column, Relationship, RelationshipType, TableNode};
+    use crate::core::table_display::{TableDisplay, set_table_display};
+
+    fn table(graph: &mut SchemaGraph, name: &str, x: f64, y: f64, columns: &[&str]) -> NodeIndex {
+        let mut node = TableNode::new(name).with_position(x, y);
+        for column in columns {
+            node = node.add_column(Column::new(*column, "INT"));
+        }
+        graph.add_node(node)
+    }
+
+    fn fk(graph: &mut SchemaGraph, from: NodeIndex, from_column: &str, to: NodeIndex) -> EdgeIndex {
+        graph.add_edge(
+            from,
+            to,
+            Relationship::new(
+                format!("fk_{}", from_column),
+                RelationshipType::ManyToOne,
+                from_column,
+                "id",
+            ),
+        )
+    }
+
+    fn assert_orthogonal(route: &[RoutePoint]) {
+        assert!(route.len() >= 2);
+        for w in route.windows(2) {
+            assert!(
+                (w[0].0 - w[1].0).abs() < EPS || (w[0].1 - w[1].1).abs() < EPS,
+                "diagonal segment {:?}",
+                w
+            );
+        }
+    }
+
+    #[test]
+    fn test_route_goes_around_tables() {
+        let mut graph = SchemaGraph::new();
+        let orders = table(&mut graph, "orders", 0.0, 0.0, &["id", "user_id"]);
+        let blocker = table(&mut graph, "audit", 400.0, -40.0, &["id", "a", "b", "c"]);
+        let users = table(&mut graph, "users", 800.0, 0.0, &["id"]);
+        let edge = fk(&mut graph, orders, "user_id", users);
+
+        let routes = route_edges(&graph, &RoutingConfig::default());
+        let route = &routes.iter().find(|(e, _)| *e == edge).unwrap().1;
+
+        assert_orthogonal(route);
+        assert!(!route_crosses(route, &TableBounds::of(&graph, blocker)));
+        assert_eq!(
+            route[0],
+            (TABLE_WIDTH, column_anchor_y(&graph, orders, "user_id"))
+        );
+        assert_eq!(
+            *route.last().unwrap(),
+            (800.0, column_anchor_y(&graph, users, "id"))
+        );
+    }
+
+    #[test]
+    fn test_route_attaches_to_column_rows() {
+        let mut graph = SchemaGraph::new();
+        let items = table(
+            &mut graph,
+            "items",
+            0.0,
+            300.0,
+            &["id", "name", "product_id"],
+        );
+        let products = table(&mut graph, "products", 0.0, 0.0, &["id", "title"]);
+        let edge = fk(&mut graph, items, "product_id", products);
+
+        assert!(route_all_edges(&mut graph));
+        assert!(route_is_current(&graph, edge));
+        let route = graph[edge].route.clone();
+        assert_orthogonal(&route);
+        assert_eq!(route[0].1, 300.0 + 48.0 + 8.0 + 2.0 * 36.0 + 18.0);
+        assert_eq!(route.last().unwrap().1, 48.0 + 8.0 + 18.0);
+        for node in [items, products] {
+            assert!(!route_crosses(&route, &TableBounds::of(&graph, node)));
+        }
+
+        // Маршрут устаревает при перемещении таблицы
+        graph[products].position = (500.0, 0.0);
+        assert!(!route_is_current(&graph, edge));
+        assert!(route_all_edges(&mut graph));
+        assert!(route_is_current(&graph, edge));
+        assert!(!route_all_edges(&mut graph));
+    }
+
+    #[test]
+    fn test_moving_a_table_reroutes_nearby_edges_only() {
+        let mut graph = SchemaGraph::new();
+        let orders = table(&mut graph, "orders", 0.0, 0.0, &["id", "user_id"]);
+        let users = table(&mut graph, "users", 800.0, 0.0, &["id"]);
+        let items = table(&mut graph, "items", 0.0, 2000.0, &["id", "product_id"]);
+        let products = table(&mut graph, "products", 800.0, 2000.0, &["id"]);
+        let near = fk(&mut graph, orders, "user_id", users);
+        let far = fk(&mut graph, items, "product_id", products);
+        route_all_edges(&mut graph);
+        let outlines = table_outlines(&graph);
+        assert!(stale_edges(&graph, &outlines).is_empty());
+
+        // Таблица, поставленная на маршрут, делает его устаревшим
+        let blocker = table(&mut graph, "audit", 400.0, -40.0, &["id", "a", "b", "c"]);
+        assert_eq!(stale_edges(&graph, &outlines), vec![near]);
+
+        let routes = route_local_edges(&graph, &RoutingConfig::default(), &[near]);
+        assert_eq!(routes.len(), 1);
+        assert!(!route_crosses(
+            &routes[0].1,
+            &TableBounds::of(&graph, blocker)
+        ));
+        assert_eq!(routes, route_edges(&graph, &RoutingConfig::default())[..1]);
+        apply_routes(&mut graph, routes);
+        assert!(stale_edges(&graph, &table_outlines(&graph)).is_empty());
+        assert!(route_is_current(&graph, far));
+    }
+
+    #[test]
+    fn test_parallel_segments_are_spread() {
+        let mut graph = SchemaGraph::new();
+        let source = table(
+            &mut graph,
+            "source",
+            0.0,
+            0.0,
+            &["id", "a_id", "b_id", "c_id"],
+        );
+        let a = table(&mut graph, "a", 700.0, 300.0, &["id"]);
+        let b = table(&mut graph, "b", 700.0, 500.0, &["id"]);
+        let c = table(&mut graph, "c", 700.0, 700.0, &["id"]);
+        fk(&mut graph, source, "a_id", a);
+        fk(&mut graph, source, "b_id", b);
+        fk(&mut graph, source, "c_id", c);
+
+        let routes = route_edges(&graph, &RoutingConfig::default());
+        let vertical: Vec<(usize, f64, f64, f64)> = routes
+            .iter()
+            .enumerate()
+            .flat_map(|(r, (_, route))| {
+                route
+                    .windows(2)
+                    .filter(|w| (w[0].0 - w[1].0).abs() < EPS)
+                    .map(move |w| (r, w[0].0, w[0].1.min(w[1].1), w[0].1.max(w[1].1)))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        for (i, s1) in vertical.iter().enumerate() {
+            for s2 in &vertical[i + 1..] {
+                let overlap = s1.3.min(s2.3) - s1.2.max(s2.2);
+                assert!(
+                    s1.0 == s2.0 || (s1.1 - s2.1).abs() > 1.0 || overlap <= 1.0,
+                    "segments {:?} and {:?} overlap",
+                    s1,
+                    s2
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_self_reference_and_serialization() {
+        let mut graph = SchemaGraph::new();
+        let employees = table(&mut graph, "employees", 0.0, 0.0, &["id", "manager_id"]);
+        let edge = fk(&mut graph, employees, "manager_id", employees);
+
+        route_all_edges(&mut graph);
+        let route = graph[edge].route.clone();
+        assert_eq!(route.len(), 4);
+        assert!(route.iter().all(|p| p.0 >= TABLE_WIDTH));
+
+        let json = serde_json::to_value(&graph[edge]).unwrap();
+        let restored: Relationship = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.route, route);
+
+        // Старые документы без маршрутов читаются
+        let legacy: Relationship = serde_json::from_value(serde_json::json!({
+            "name": "fk",
+            "relationship_type": "ManyToOne",
+            "from_column": "manager_id",
+            "to_column": "id"
+        }))
+        .unwrap();
+        assert!(legacy.route.is_empty());
+    }
+
+    #[test]
+    fn test_column_at_y_inverts_anchor() {
+        let mut graph = SchemaGraph::new();
+        let orders = table(
+            &mut graph,
+            "orders",
+            0.0,
+            100.0,
+            &["id", "user_id", "total"],
+        );
+
+        for (index, column) in graph[orders].columns.iter().enumerate() {
+            assert_eq!(
+                column_at_y(
+                    &graph,
+                    orders,
+                    column_anchor_y(&graph, orders, &column.name)
+                ),
+                Some(index)
+            );
+        }
+        assert_eq!(column_at_y(&graph, orders, 110.0), None);
+        assert_eq!(
+            column_at_y(
+                &graph,
+                orders,
+                column_anchor_y(&graph, orders, "total") + TABLE_ROW_HEIGHT
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_hidden_columns_keep_edges_attached() {
+        let mut graph = SchemaGraph::new();
+        let orders = table(
+            &mut graph,
+            "orders",
+            0.0,
+            0.0,
+            &["id", "note", "total", "user_id"],
+        );
+        let users = table(&mut graph, "users", 600.0, 0.0, &["id", "name"]);
+        let edge = fk(&mut graph, orders, "user_id", users);
+
+        // В режиме ключей видна только колонка связи
+        set_table_display(&mut graph, &[orders], TableDisplay::Keys);
+        assert_eq!(
+            column_anchor_y(&graph, orders, "user_id"),
+            48.0 + 8.0 + 18.0
+        );
+        assert_eq!(column_at_y(&graph, orders, 48.0 + 8.0 + 18.0), Some(3));
+        // Скрытая колонка крепится к строке «+N columns»
+        assert_eq!(
+            column_anchor_y(&graph, orders, "total"),
+            48.0 + 8.0 + 36.0 + 18.0
+        );
+        assert_eq!(column_at_y(&graph, orders, 48.0 + 8.0 + 36.0 + 18.0), None);
+
+        set_table_display(&mut graph, &[users], TableDisplay::NameOnly);
+        assert_eq!(column_anchor_y(&graph, users, "id"), 24.0);
+        assert_eq!(TableBounds::of(&graph, users).bottom, 48.0);
+
+        assert!(route_all_edges(&mut graph));
+        assert!(route_is_current(&graph, edge));
+        set_table_display(&mut graph, &[orders], TableDisplay::Full);
+        assert!(!route_is_current(&graph, edge));
+    }
+}
@@ -4,8 +4,15 @@
 //! - JSON (native format)
 //! - SQL (DDL statements)
 //! - CSV (tabular format)
-
-use super::{Column, RelationshipType, SchemaGraph, TableNode};
+//! - SVG (image of the diagram)
+
+use super::auto_layout::{TABLE_HEADER_HEIGHT, TABLE_ROW_HEIGHT};
+use super::edge_routing::{column_anchor_y, route_is_current, route_label_position};
+use super::notation::{EndDecoration, Notation, edge_decorations};
+use super::selection::table_bounds;
+use super::table_display::TableRows;
+use super::{Column, Relationship, RelationshipType, SchemaGraph, TableNode};
+use petgraph::graph::NodeIndex;
 use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 use serde::{Deserialize, Serialize};
 
@@ -38,6 +45,9 @@ pub struct ExportedRelationship {
     pub from_column: String,
     pub to_table: String,
     pub to_column: String,
+    /// Routed polyline of the relationship on the canvas
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub route: Vec<Position>,
 }
 
 /// Export format options
@@ -46,10 +56,11 @@ pub enum ExportFormat {
     Json,
     Sql,
     Csv,
+    Svg,
 }
 
 /// SQL dialect for export
-#[derive(Clone, Debug, PartialEq, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum SqlDialect {
     #[default]
     MySQL,
@@ -57,6 +68,261 @@ pub enum SqlDialect {
     SQLite,
 }
 
+/// Result of converting a column type to a target dialect
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeConversion {
+    /// Type as it should be rendered in the target dialect
+    pub data_type: String,
+    /// Set when the type has no exact equivalent in the target dialect
+    pub warning: Option<String>,
+}
+
+impl TypeConversion {
+    fn exact(data_type: impl Into<String>) -> Self {
+        Self {
+            data_type: data_type.into(),
+            warning: None,
+        }
+    }
+
+    fn lossy(data_type: impl Into<String>, warning: impl Into<String>) -> Self {
+        Self {
+            data_type: data_type.into(),
+            warning: Some(warning.into()),
+        }
+    }
+}
+
+/// Column type that does not map cleanly to the target dialect
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeConversionWarning {
+    pub table: String,
+    pub column: String,
+    pub original_type: String,
+    pub converted_type: String,
+    pub message: String,
+}
+
+impl SqlDialect {
+    /// All supported dialects (in the order shown in selectors)
+    pub const ALL: [SqlDialect; 3] = [
+        SqlDialect::MySQL,
+        SqlDialect::PostgreSQL,
+        SqlDialect::SQLite,
+    ];
+
+    /// Human-readable dialect name
+    pub fn label(&self) -> &'static str {
+        match self {
+            SqlDialect::MySQL => "MySQL",
+            SqlDialect::PostgreSQL => "PostgreSQL",
+            SqlDialect::SQLite => "SQLite",
+        }
+    }
+
+    /// Parse dialect name ("mysql", "postgresql", "postgres", "pg", "sqlite"), case-insensitive
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "mysql" => Some(SqlDialect::MySQL),
+            "postgresql" | "postgres" | "pg" => Some(SqlDialect::PostgreSQL),
+            "sqlite" => Some(SqlDialect::SQLite),
+            _ => None,
+        }
+    }
+
+    /// Column types offered for this dialect (base names, without arguments)
+    pub fn data_types(&self) -> &'static [&'static str] {
+        match self {
+            SqlDialect::MySQL => super::MySqlDataType::all_types(),
+            SqlDialect::PostgreSQL => &[
+                "INTEGER",
+                "BIGINT",
+                "SMALLINT",
+                "SERIAL",
+                "BIGSERIAL",
+                "NUMERIC",
+                "DECIMAL",
+                "REAL",
+                "DOUBLE PRECISION",
+                "VARCHAR",
+                "CHAR",
+                "TEXT",
+                "BYTEA",
+                "DATE",
+                "TIME",
+                "TIMESTAMP",
+                "TIMESTAMPTZ",
+                "INTERVAL",
+                "BOOLEAN",
+                "UUID",
+                "JSON",
+                "JSONB",
+            ],
+            SqlDialect::SQLite => &[
+                "INTEGER", "REAL", "TEXT", "BLOB", "NUMERIC", "VARCHAR", "BOOLEAN", "DATETIME",
+            ],
+        }
+    }
+
+    /// Quote an identifier using the dialect's quoting style
+    pub fn quote_identifier(&self, name: &str) -> String {
+        match self {
+            SqlDialect::MySQL => format!("`{}`", name.replace('`', "``")),
+            SqlDialect::PostgreSQL | SqlDialect::SQLite => {
+                format!("\"{}\"", name.replace('"', "\"\""))
+            }
+        }
+    }
+
+    /// Convert a column type (as stored in the diagram) to this dialect
+    ///
+    /// Types are stored as free-form strings, usually in MySQL spelling, but a diagram
+    /// edited in another dialect may contain e.g. PostgreSQL types. Conversions that lose
+    /// information (ranges, enforced values, time zones) carry a warning.
+    pub fn convert_type(&self, data_type: &str) -> TypeConversion {
+        let trimmed = data_type.trim();
+        // Только ASCII: смещения в `upper` должны совпадать со смещениями в `trimmed`
+        let upper = trimmed.to_ascii_uppercase();
+
+        // Split "DECIMAL(10,2) UNSIGNED" into base, arguments and modifiers
+        let (base, args, modifiers) = match upper.find('(') {
+            Some(open) => {
+                let close = upper
+                    .rfind(')')
+                    .filter(|&c| c > open)
+                    .unwrap_or(upper.len() - 1);
+                (
+                    upper[..open].trim().to_string(),
+                    trimmed[open..=close].to_string(),
+                    upper[close + 1..].trim().to_string(),
+                )
+            }
+            None => (upper.clone(), String::new(), String::new()),
+        };
+        let unsigned = modifiers.contains("UNSIGNED") || base.ends_with(" UNSIGNED");
+        let base = base.trim_end_matches(" UNSIGNED").trim().to_string();
+
+        // Original type without the UNSIGNED modifier (it is handled separately below)
+        let plain = match upper.rfind("UNSIGNED") {
+            Some(pos) if unsigned => format!(
+                "{} {}",
+                trimmed[..pos].trim(),
+                trimmed[pos + "UNSIGNED".len()..].trim()
+            )
+            .trim()
+            .to_string(),
+            _ => trimmed.to_string(),
+        };
+
+        let mut conversion = match self {
+            SqlDialect::PostgreSQL => Self::convert_to_postgres(&base, &args, &plain),
+            SqlDialect::MySQL => Self::convert_to_mysql(&base, &args, &plain),
+            SqlDialect::SQLite => Self::convert_to_sqlite(&base, &args, &plain),
+        };
+
+        if unsigned {
+            if *self == SqlDialect::MySQL {
+                conversion.data_type = format!("{} UNSIGNED", conversion.data_type);
+            } else {
+                let note = format!(
+                    "UNSIGNED is not supported in {}; the value range is no longer enforced",
+                    self.label()
+                );
+                conversion.warning = Some(match conversion.warning {
+                    Some(w) => format!("{}; {}", w, note),
+                    None => note,
+                });
+            }
+        }
+
+        conversion
+    }
+
+    fn convert_to_postgres(base: &str, args: &str, original: &str) -> TypeConversion {
+        match base {
+            "TINYINT" => TypeConversion::exact("SMALLINT"),
+            "MEDIUMINT" => TypeConversion::exact("INTEGER"),
+            "DOUBLE" => TypeConversion::exact("DOUBLE PRECISION"),
+            "FLOAT" if args.is_empty() => TypeConversion::exact("REAL"),
+            "DATETIME" => TypeConversion::exact(format!("TIMESTAMP{}", args)),
+            "TINYTEXT" | "MEDIUMTEXT" | "LONGTEXT" => TypeConversion::exact("TEXT"),
+            "TINYBLOB" | "BLOB" | "MEDIUMBLOB" | "LONGBLOB" => TypeConversion::exact("BYTEA"),
+            "BINARY" | "VARBINARY" => TypeConversion::lossy(
+                "BYTEA",
+                format!("{} has no length limit as BYTEA in PostgreSQL", original),
+            ),
+            "YEAR" => TypeConversion::lossy(
+                "SMALLINT",
+                "YEAR is not available in PostgreSQL; stored as SMALLINT",
+            ),
+            "ENUM" => TypeConversion::lossy(
+                "VARCHAR(255)",
+                format!(
+                    "{} values are not enforced in PostgreSQL; use CREATE TYPE ... AS ENUM or a CHECK constraint",
+                    original
+                ),
+            ),
+            "SET" => TypeConversion::lossy(
+                "TEXT",
+                format!("{} has no PostgreSQL equivalent; stored as TEXT", original),
+            ),
+            _ => TypeConversion::exact(original),
+        }
+    }
+
+    fn convert_to_mysql(base: &str, args: &str, original: &str) -> TypeConversion {
+        if base.ends_with("[]") {
+            return TypeConversion::lossy(
+                "JSON",
+                format!(
+                    "Array type {} is not supported in MySQL; stored as JSON",
+                    original
+                ),
+            );
+        }
+        match base {
+            "BYTEA" => TypeConversion::exact("LONGBLOB"),
+            "DOUBLE PRECISION" => TypeConversion::exact("DOUBLE"),
+            "JSONB" => TypeConversion::exact("JSON"),
+            "BIGSERIAL" => TypeConversion::lossy(
+                "BIGINT",
+                "BIGSERIAL is not available in MySQL; use AUTO_INCREMENT",
+            ),
+            "SMALLSERIAL" => TypeConversion::lossy(
+                "SMALLINT",
+                "SMALLSERIAL is not available in MySQL; use AUTO_INCREMENT",
+            ),
+            "UUID" => TypeConversion::lossy("CHAR(36)", "UUID is stored as CHAR(36) in MySQL"),
+            "TIMESTAMPTZ" | "TIMESTAMP WITH TIME ZONE" => TypeConversion::lossy(
+                format!("TIMESTAMP{}", args),
+                "MySQL TIMESTAMP does not keep the time zone offset",
+            ),
+            _ => TypeConversion::exact(original),
+        }
+    }
+
+    fn convert_to_sqlite(base: &str, _args: &str, original: &str) -> TypeConversion {
+        match base {
+            "BYTEA" => TypeConversion::exact("BLOB"),
+            "SERIAL" | "BIGSERIAL" | "SMALLSERIAL" => TypeConversion::lossy(
+                "INTEGER",
+                format!(
+                    "{} is not available in SQLite; use INTEGER PRIMARY KEY for auto-increment",
+                    original
+                ),
+            ),
+            "ENUM" | "SET" => TypeConversion::lossy(
+                "TEXT",
+                format!(
+                    "{} values are not enforced in SQLite; use a CHECK constraint",
+                    original
+                ),
+            ),
+            _ => TypeConversion::exact(original),
+        }
+    }
+}
+
 /// Export options
 #[derive(Clone, Debug)]
 pub struct ExportOptions {
@@ -65,6 +331,10 @@ pub struct ExportOptions {
     pub include_positions: bool,
     pub include_drop_statements: bool,
     pub pretty_print: bool,
+    /// Run SQL output through the formatter (None keeps the generator's layout)
+    pub sql_format: Option<super::FormatOptions>,
+    /// Relationship notation of image export
+    pub notation: Notation,
 }
 
 impl Default for ExportOptions {
@@ -75,6 +345,8 @@ impl Default for ExportOptions {
             include_positions: true,
             include_drop_statements: false,
             pretty_print: true,
+            sql_format: None,
+            notation: Notation::default(),
         }
     }
 }
@@ -89,6 +361,7 @@ impl SchemaExporter {
             ExportFormat::Json => Self::export_json(graph, options),
             ExportFormat::Sql => Self::export_sql(graph, options),
             ExportFormat::Csv => Self::export_csv(graph, options),
+            ExportFormat::Svg => Self::export_svg(graph, options),
         }
     }
 
@@ -133,6 +406,7 @@ impl SchemaExporter {
                 from_column: rel.from_column.clone(),
                 to_table,
                 to_column: rel.to_column.clone(),
+                route: rel.route.iter().map(|&(x, y)| Position { x, y }).collect(),
             });
         }
 
@@ -157,7 +431,16 @@ impl SchemaExporter {
     /// Export to SQL DDL format
     pub fn export_sql(graph: &SchemaGraph, options: &ExportOptions) -> Result<String, String> {
         let mut sql = String::new();
-        let schema = Self::to_exported_schema(graph);
+        // N:M edges have no direct SQL form: export them as junction tables
+        let has_many_to_many = graph
+            .edge_weights()
+            .any(|rel| rel.relationship_type == RelationshipType::ManyToMany);
+        let (schema, junctions) = if has_many_to_many {
+            let (expanded, junctions) = super::expand_many_to_many(graph);
+            (Self::to_exported_schema(&expanded), junctions)
+        } else {
+            (Self::to_exported_schema(graph), Vec::new())
+        };
 
         // Header comment
         sql.push_str("-- Database Schema\n");
@@ -170,13 +453,21 @@ impl SchemaExporter {
         if options.include_drop_statements {
             sql.push_str("-- Drop existing tables (in reverse order for foreign keys)\n");
             for table in schema.tables.iter().rev() {
-                sql.push_str(&format!("DROP TABLE IF EXISTS `{}`;\n", table.name));
+                sql.push_str(&format!(
+                    "DROP TABLE IF EXISTS {};\n",
+                    options.sql_dialect.quote_identifier(&table.name)
+                ));
             }
             sql.push('\n');
         }
 
         // Create tables
         for table in &schema.tables {
+            // Маркер позволяет свернуть таблицу обратно в связь N:M при применении SQL
+            if junctions.contains(&table.name) {
+                sql.push_str(super::junction::MANY_TO_MANY_MARKER);
+                sql.push('\n');
+            }
             sql.push_str(&Self::generate_create_table(table, &schema, options));
             sql.push('\n');
         }
@@ -189,6 +480,10 @@ impl SchemaExporter {
             sql.push_str(&fk_statements);
         }
 
+        if let Some(format) = &options.sql_format {
+            sql = super::format_sql(&sql, format);
+        }
+
         Ok(sql)
     }
 
@@ -199,6 +494,8 @@ impl SchemaExporter {
         options: &ExportOptions,
     ) -> String {
         let mut sql = String::new();
+        let dialect = &options.sql_dialect;
+        let quote = |name: &str| dialect.quote_identifier(name);
 
         // Position comment if requested
         if options.include_positions {
@@ -208,14 +505,15 @@ impl SchemaExporter {
             ));
         }
 
-        sql.push_str(&format!("CREATE TABLE `{}` (\n", table.name));
+        sql.push_str(&format!("CREATE TABLE {} (\n", quote(&table.name)));
 
         let mut column_defs: Vec<String> = Vec::new();
         let mut primary_keys: Vec<String> = Vec::new();
         let mut unique_columns: Vec<String> = Vec::new();
 
         for column in &table.columns {
-            let mut col_def = format!("    `{}` {}", column.name, column.data_type);
+            let data_type = dialect.convert_type(&column.data_type).data_type;
+            let mut col_def = format!("    {} {}", quote(&column.name), data_type);
 
             if !column.is_nullable && !column.is_primary_key {
                 col_def.push_str(" NOT NULL");
@@ -239,13 +537,13 @@ impl SchemaExporter {
 
         // Add PRIMARY KEY constraint
         if !primary_keys.is_empty() {
-            let pk_cols: Vec<String> = primary_keys.iter().map(|c| format!("`{}`", c)).collect();
+            let pk_cols: Vec<String> = primary_keys.iter().map(|c| quote(c)).collect();
             column_defs.push(format!("    PRIMARY KEY ({})", pk_cols.join(", ")));
         }
 
         // Add UNIQUE constraints
         for unique_col in unique_columns {
-            column_defs.push(format!("    UNIQUE (`{}`)", unique_col));
+            column_defs.push(format!("    UNIQUE ({})", quote(&unique_col)));
         }
 
         // Add inline FOREIGN KEY constraints for simple cases
@@ -253,8 +551,11 @@ impl SchemaExporter {
             if rel.from_table == table.name {
                 let fk_name = format!("fk_{}_{}", table.name, rel.to_column);
                 column_defs.push(format!(
-                    "    CONSTRAINT `{}` FOREIGN KEY (`{}`) REFERENCES `{}`(`{}`)",
-                    fk_name, rel.from_column, rel.to_table, rel.to_column
+                    "    CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {}({})",
+                    quote(&fk_name),
+                    quote(&rel.from_column),
+                    quote(&rel.to_table),
+                    quote(&rel.to_column)
                 ));
             }
         }
@@ -263,7 +564,7 @@ impl SchemaExporter {
         sql.push_str("\n)");
 
         // Add engine for MySQL
-        if options.sql_dialect == SqlDialect::MySQL {
+        if *dialect == SqlDialect::MySQL {
             sql.push_str(" ENGINE=InnoDB DEFAULT CHARSET=utf8mb4");
         }
 
@@ -272,20 +573,52 @@ impl SchemaExporter {
     }
 
     /// Generate ALTER TABLE statements for foreign keys
-    fn generate_foreign_keys(schema: &ExportedSchema, _options: &ExportOptions) -> String {
+    fn generate_foreign_keys(schema: &ExportedSchema, options: &ExportOptions) -> String {
         let mut sql = String::new();
+        let quote = |name: &str| options.sql_dialect.quote_identifier(name);
 
         for rel in &schema.relationships {
             let fk_name = format!("fk_{}_{}_{}", rel.from_table, rel.from_column, rel.to_table);
             sql.push_str(&format!(
-                "ALTER TABLE `{}` ADD CONSTRAINT `{}` FOREIGN KEY (`{}`) REFERENCES `{}`(`{}`);\n",
-                rel.from_table, fk_name, rel.from_column, rel.to_table, rel.to_column
+                "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {}({});\n",
+                quote(&rel.from_table),
+                quote(&fk_name),
+                quote(&rel.from_column),
+                quote(&rel.to_table),
+                quote(&rel.to_column)
             ));
         }
 
         sql
     }
 
+    /// Collect column types that do not map cleanly to the given dialect
+    pub fn type_conversion_warnings(
+        graph: &SchemaGraph,
+        dialect: &SqlDialect,
+    ) -> Vec<TypeConversionWarning> {
+        let mut warnings = Vec::new();
+
+        for node_idx in graph.node_indices() {
+            if let Some(table) = graph.node_weight(node_idx) {
+                for column in &table.columns {
+                    let conversion = dialect.convert_type(&column.data_type);
+                    if let Some(message) = conversion.warning {
+                        warnings.push(TypeConversionWarning {
+                            table: table.name.clone(),
+                            column: column.name.clone(),
+                            original_type: column.data_type.clone(),
+                            converted_type: conversion.data_type,
+                            message,
+                        });
+                    }
+                }
+            }
+        }
+
+        warnings
+    }
+
     /// Export to CSV format
     /// Returns a string with multiple CSV sections separated by headers
     pub fn export_csv(graph: &SchemaGraph, options: &ExportOptions) -> Result<String, String> {
@@ -351,6 +684,210 @@ impl SchemaExporter {
         Ok(csv)
     }
 
+    /// Export the diagram as an SVG image
+    ///
+    /// Tables are drawn at their canvas positions with a header and one row per
+    /// column; relationships follow their stored route, or connect the table
+    /// centers when no route has been calculated.
+    pub fn export_svg(graph: &SchemaGraph, options: &ExportOptions) -> Result<String, String> {
+        const MARGIN: f64 = 40.0;
+
+        let (mut left, mut top) = (f64::INFINITY, f64::INFINITY);
+        let (mut right, mut bottom) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for (l, t, r, b) in graph.node_indices().filter_map(|n| table_bounds(graph, n)) {
+            left = left.min(l);
+            top = top.min(t);
+            right = right.max(r);
+            bottom = bottom.max(b);
+        }
+        if graph.node_count() == 0 {
+            (left, top, right, bottom) = (0.0, 0.0, 0.0, 0.0);
+        }
+        let (origin_x, origin_y) = (left - MARGIN, top - MARGIN);
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"{x} {y} {w} {h}\" font-family=\"sans-serif\" font-size=\"13\">\n",
+            x = origin_x,
+            y = origin_y,
+            w = right - left + 2.0 * MARGIN,
+            h = bottom - top + 2.0 * MARGIN,
+        );
+        svg.push_str(&format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>\n",
+            origin_x, origin_y
+        ));
+
+        if options.notation.shows_arrow() {
+            svg.push_str("  <defs><marker id=\"arrowhead\" markerWidth=\"10\" markerHeight=\"10\" refX=\"9\" refY=\"3\" orient=\"auto\"><polygon points=\"0 0, 10 3, 0 6\" fill=\"#6b7280\"/></marker></defs>\n");
+        }
+
+        // Связи под таблицами
+        for edge in graph.edge_references() {
+            let rel = edge.weight();
+            // Маршрут, сохранённый до перемещения таблиц, уже не ведёт к их строкам
+            let points = if route_is_current(graph, edge.id()) {
+                rel.route.clone()
+            } else {
+                Self::fallback_route(graph, edge.source(), edge.target(), rel)
+            };
+            let polyline: Vec<String> =
+                points.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+            svg.push_str(&format!(
+                "  <polyline points=\"{}\" fill=\"none\" stroke=\"#6b7280\" stroke-width=\"2\"{}><title>{}</title></polyline>\n",
+                polyline.join(" "),
+                if options.notation.shows_arrow() {
+                    " marker-end=\"url(#arrowhead)\""
+                } else {
+                    ""
+                },
+                Self::escape_xml(&rel.name)
+            ));
+            if options.notation.shows_arrow() {
+                if let Some((x, y)) = route_label_position(&points) {
+                    svg.push_str(&format!(
+                        "  <text x=\"{}\" y=\"{}\" fill=\"#6b7280\" font-size=\"12\">{}</text>\n",
+                        x, y, rel.relationship_type
+                    ));
+                }
+            } else if let Some((source, target)) =
+                edge_decorations(graph, edge.id(), options.notation, &points)
+            {
+                Self::push_svg_decoration(&mut svg, &source);
+                Self::push_svg_decoration(&mut svg, &target);
+            }
+        }
+
+        for idx in graph.node_indices() {
+            let table = &graph[idx];
+            let Some((l, t, r, b)) = table_bounds(graph, idx) else {
+                continue;
+            };
+            let rows = TableRows::of(graph, idx);
+            svg.push_str(&format!(
+                "  <g>\n    <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"8\" fill=\"#ffffff\" stroke=\"#d1d5db\"/>\n",
+                l,
+                t,
+                r - l,
+                b - t
+            ));
+            svg.push_str(&format!(
+                "    <path d=\"M{l} {hb} V{r8} Q{l} {t} {l8} {t} H{rr8} Q{r} {t} {r} {r8} V{hb} Z\" fill=\"#3b82f6\"/>\n",
+                l = l,
+                t = t,
+                r = r,
+                l8 = l + 8.0,
+                rr8 = r - 8.0,
+                r8 = t + 8.0,
+                hb = t + TABLE_HEADER_HEIGHT,
+            ));
+            svg.push_str(&format!(
+                "    <text x=\"{}\" y=\"{}\" fill=\"#ffffff\" font-weight=\"bold\" font-size=\"15\">{}</text>\n",
+                l + 16.0,
+                t + TABLE_HEADER_HEIGHT / 2.0 + 5.0,
+                Self::escape_xml(&table.name)
+            ));
+            for (i, column) in rows.columns.iter().map(|&c| &table.columns[c]).enumerate() {
+                let y = t + TABLE_HEADER_HEIGHT + 8.0 + (i as f64 + 0.5) * TABLE_ROW_HEIGHT + 4.0;
+                let name = if column.is_primary_key {
+                    format!("🔑 {}", column.name)
+                } else {
+                    column.name.clone()
+                };
+                svg.push_str(&format!(
+                    "    <text x=\"{}\" y=\"{}\" fill=\"#111827\">{}</text>\n    <text x=\"{}\" y=\"{}\" fill=\"#6b7280\" text-anchor=\"end\">{}</text>\n",
+                    l + 16.0,
+                    y,
+                    Self::escape_xml(&name),
+                    r - 16.0,
+                    y,
+                    Self::escape_xml(&column.data_type)
+                ));
+            }
+            if rows.has_summary_row() {
+                let y = t
+                    + TABLE_HEADER_HEIGHT
+                    + 8.0
+                    + (rows.columns.len() as f64 + 0.5) * TABLE_ROW_HEIGHT
+                    + 4.0;
+                svg.push_str(&format!(
+                    "    <text x=\"{}\" y=\"{}\" fill=\"#6b7280\" font-style=\"italic\">+{} columns</text>\n",
+                    l + 16.0,
+                    y,
+                    rows.hidden
+                ));
+            }
+            svg.push_str("  </g>\n");
+        }
+
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+
+    /// Orthogonal path between the facing sides of two tables, for relationships without a route
+    fn fallback_route(
+        graph: &SchemaGraph,
+        from: NodeIndex,
+        to: NodeIndex,
+        rel: &Relationship,
+    ) -> Vec<(f64, f64)> {
+        const GAP: f64 = 30.0;
+
+        let (from_left, _, from_right, _) = table_bounds(graph, from).unwrap_or_default();
+        let (to_left, _, to_right, _) = table_bounds(graph, to).unwrap_or_default();
+        let from_y = column_anchor_y(graph, from, &rel.from_column);
+        let to_y = column_anchor_y(graph, to, &rel.to_column);
+
+        let (start_x, end_x, bend_x) = if to_left >= from_right {
+            (from_right, to_left, (from_right + to_left) / 2.0)
+        } else if from_left >= to_right {
+            (from_left, to_right, (from_left + to_right) / 2.0)
+        } else {
+            // Таблицы перекрываются по X: обходим справа
+            (from_right, to_right, from_right.max(to_right) + GAP)
+        };
+        vec![
+            (start_x, from_y),
+            (bend_x, from_y),
+            (bend_x, to_y),
+            (end_x, to_y),
+        ]
+    }
+
+    /// Append the SVG shapes of one relationship end
+    fn push_svg_decoration(svg: &mut String, decoration: &EndDecoration) {
+        if !decoration.path.is_empty() {
+            svg.push_str(&format!(
+                "  <path d=\"{}\" fill=\"none\" stroke=\"#6b7280\" stroke-width=\"2\"/>\n",
+                decoration.path
+            ));
+        }
+        if let Some(circle) = &decoration.circle {
+            svg.push_str(&format!(
+                "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"#ffffff\" stroke=\"#6b7280\" stroke-width=\"2\"/>\n",
+                circle.x,
+                circle.y,
+                circle.r
+            ));
+        }
+        if let Some(label) = &decoration.label {
+            svg.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" fill=\"#6b7280\" font-size=\"12\" text-anchor=\"middle\" dominant-baseline=\"middle\">{}</text>\n",
+                label.x,
+                label.y,
+                Self::escape_xml(&label.text)
+            ));
+        }
+    }
+
+    /// Escape text for XML content and attributes
+    fn escape_xml(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+
     /// Escape a value for CSV (handle commas, quotes, newlines)
     fn escape_csv(value: &str) -> String {
         if value.contains(',') || value.contains('"') || value.contains('\n') {
@@ -406,8 +943,9 @@ impl SchemaImporter {
                 _ => RelationshipType::OneToMany,
             };
 
-            let relationship =
+            let mut relationship =
                 super::Relationship::new(&rel.name, rel_type, &rel.from_column, &rel.to_column);
+            relationship.route = rel.route.iter().map(|p| (p.x, p.y)).collect();
 
             graph
                 .create_relationship(*from_idx, *to_idx, relationship)
@@ -421,7 +959,7 @@ impl SchemaImporter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::create_demo_graph;
+    use crate::core::{TableDisplay, TableOps, create_demo_graph};
 
     #[test]
     fn test_export_json() {
@@ -434,6 +972,85 @@ mod tests {
         assert!(json.contains("comments"));
     }
 
+    #[test]
+    fn test_export_svg() {
+        let mut graph = create_demo_graph();
+        let users = graph.find_table_by_name("users").unwrap();
+        graph[users].name = "users<&>".to_string();
+        let options = ExportOptions {
+            format: ExportFormat::Svg,
+            ..Default::default()
+        };
+        let svg = SchemaExporter::export(&graph, &options).unwrap();
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<polyline").count(), graph.edge_count());
+        assert_eq!(svg.matches("<g>").count(), graph.node_count());
+        assert!(svg.contains("users&lt;&amp;&gt;"));
+        assert!(svg.contains("VARCHAR(255)"));
+
+        let empty = SchemaExporter::export_svg(&SchemaGraph::new(), &options).unwrap();
+        assert!(empty.contains("</svg>"));
+    }
+
+    #[test]
+    fn test_export_svg_skips_stale_routes() {
+        let mut graph = create_demo_graph();
+        crate::core::route_all_edges(&mut graph);
+        let edge = graph.edge_indices().next().unwrap();
+        let stale = graph[edge].route.clone();
+        let (from, _) = graph.edge_endpoints(edge).unwrap();
+        graph[from].position.0 += 1000.0;
+
+        let svg = SchemaExporter::export_svg(&graph, &ExportOptions::default()).unwrap();
+        let stale_points: Vec<String> = stale.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+        assert!(!svg.contains(&stale_points.join(" ")));
+        assert_eq!(svg.matches("<polyline").count(), graph.edge_count());
+    }
+
+    #[test]
+    fn test_export_svg_notation() {
+        let graph = create_demo_graph();
+        let simple = SchemaExporter::export_svg(&graph, &ExportOptions::default()).unwrap();
+        assert_eq!(
+            simple.matches("marker-end=\"url(#arrowhead)\"").count(),
+            graph.edge_count()
+        );
+
+        let options = ExportOptions {
+            format: ExportFormat::Svg,
+            notation: Notation::CrowsFoot,
+            ..Default::default()
+        };
+        let crows_foot = SchemaExporter::export_svg(&graph, &options).unwrap();
+        assert!(!crows_foot.contains("marker-end"));
+        // Каждый конец связи получает глиф
+        assert_eq!(
+            crows_foot
+                .matches("fill=\"none\" stroke=\"#6b7280\" stroke-width=\"2\"/>")
+                .count(),
+            2 * graph.edge_count()
+        );
+        assert_eq!(crows_foot.matches("<polyline").count(), graph.edge_count());
+    }
+
+    #[test]
+    fn test_export_svg_respects_table_display() {
+        let mut graph = create_demo_graph();
+        let posts = graph.find_table_by_name("posts").unwrap();
+        let users = graph.find_table_by_name("users").unwrap();
+        crate::core::set_table_display(&mut graph, &[posts], TableDisplay::Keys);
+        crate::core::set_table_display(&mut graph, &[users], TableDisplay::NameOnly);
+        let svg = SchemaExporter::export_svg(&graph, &ExportOptions::default()).unwrap();
+
+        assert!(!svg.contains(">title<"));
+        assert!(svg.contains(">+3 columns<"));
+        // У таблицы только с именем нет ни колонок, ни строки «+N columns»
+        assert!(!svg.contains(">username<"));
+        assert_eq!(svg.matches(" columns</text>").count(), 1);
+    }
+
     #[test]
     fn test_export_sql() {
         let graph = create_demo_graph();
@@ -447,6 +1064,114 @@ mod tests {
         assert!(sql.contains("PRIMARY KEY"));
     }
 
+    #[test]
+    fn test_export_sql_many_to_many_as_junction_table() {
+        use crate::core::{Relationship, RelationshipOps};
+
+        let mut graph = SchemaGraph::new();
+        let posts = graph
+            .add_node(TableNode::new("posts").add_column(Column::new("id", "INT").primary_key()));
+        let tags = graph
+            .add_node(TableNode::new("tags").add_column(Column::new("id", "INT").primary_key()));
+        graph
+            .create_relationship(
+                posts,
+                tags,
+                Relationship::new("post_tags", RelationshipType::ManyToMany, "id", "id"),
+            )
+            .unwrap();
+
+        let options = ExportOptions {
+            format: ExportFormat::Sql,
+            sql_dialect: SqlDialect::PostgreSQL,
+            include_positions: false,
+            ..Default::default()
+        };
+        let sql = SchemaExporter::export_sql(&graph, &options).unwrap();
+
+        assert!(sql.contains("-- Many-to-many\nCREATE TABLE \"post_tags\""));
+        assert!(sql.contains("PRIMARY KEY (\"post_id\", \"tag_id\")"));
+        assert!(sql.contains("FOREIGN KEY (\"tag_id\") REFERENCES \"tags\"(\"id\")"));
+        assert!(!sql.contains("FOREIGN KEY (\"id\")"));
+        // The graph itself keeps the N:M edge
+        assert_eq!(graph.node_count(), 2);
+    }
+
+    #[test]
+    fn test_export_sql_postgresql() {
+        let mut graph = create_demo_graph();
+        let users = graph.node_indices().next().unwrap();
+        graph[users]
+            .columns
+            .push(Column::new("updated_at", "DATETIME").not_null());
+
+        let options = ExportOptions {
+            format: ExportFormat::Sql,
+            sql_dialect: SqlDialect::PostgreSQL,
+            ..Default::default()
+        };
+        let sql = SchemaExporter::export_sql(&graph, &options).unwrap();
+
+        assert!(sql.contains("CREATE TABLE \"users\""));
+        assert!(sql.contains("\"updated_at\" TIMESTAMP NOT NULL"));
+        assert!(!sql.contains('`'));
+        assert!(!sql.contains("ENGINE=InnoDB"));
+    }
+
+    #[test]
+    fn test_convert_type() {
+        let pg = SqlDialect::PostgreSQL;
+        assert_eq!(pg.convert_type("LONGTEXT").data_type, "TEXT");
+        assert_eq!(pg.convert_type("DOUBLE").data_type, "DOUBLE PRECISION");
+        assert_eq!(
+            pg.convert_type("VARCHAR(255)"),
+            TypeConversion::exact("VARCHAR(255)")
+        );
+
+        let enum_type = pg.convert_type("ENUM('a','b')");
+        assert_eq!(enum_type.data_type, "VARCHAR(255)");
+        assert!(enum_type.warning.is_some());
+
+        let unsigned = pg.convert_type("INT UNSIGNED");
+        assert_eq!(unsigned.data_type, "INT");
+        assert!(unsigned.warning.unwrap().contains("UNSIGNED"));
+
+        let mysql = SqlDialect::MySQL;
+        assert_eq!(mysql.convert_type("INT UNSIGNED").data_type, "INT UNSIGNED");
+        assert_eq!(mysql.convert_type("BYTEA").data_type, "LONGBLOB");
+        assert!(mysql.convert_type("UUID").warning.is_some());
+    }
+
+    #[test]
+    fn test_convert_type_non_ascii_arguments() {
+        // Верхний регистр 'ŉ' длиннее в байтах - смещения не должны съезжать
+        let pg = SqlDialect::PostgreSQL;
+        assert_eq!(pg.convert_type("ENUM('ŉ')").data_type, "VARCHAR(255)");
+        assert_eq!(
+            pg.convert_type("varchar(10) unsigned ß").data_type,
+            "varchar(10) ß"
+        );
+        assert_eq!(
+            SqlDialect::MySQL.convert_type("ENUM('ŉ','ß')").data_type,
+            "ENUM('ŉ','ß')"
+        );
+    }
+
+    #[test]
+    fn test_type_conversion_warnings() {
+        let mut graph = create_demo_graph();
+        let users = graph.node_indices().next().unwrap();
+        graph[users]
+            .columns
+            .push(Column::new("status", "ENUM('active','banned')"));
+
+        let warnings = SchemaExporter::type_conversion_warnings(&graph, &SqlDialect::PostgreSQL);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].column, "status");
+
+        assert!(SchemaExporter::type_conversion_warnings(&graph, &SqlDialect::MySQL).is_empty());
+    }
+
     #[test]
     fn test_export_csv() {
         let graph = create_demo_graph();
@@ -471,4 +1196,20 @@ mod tests {
         assert_eq!(imported.tables.len(), 3);
         assert_eq!(imported.relationships.len(), 3);
     }
+
+    #[test]
+    fn test_json_roundtrip_keeps_routes() {
+        let mut graph = create_demo_graph();
+        crate::core::route_all_edges(&mut graph);
+        let json = SchemaExporter::export_json(&graph, &ExportOptions::default()).unwrap();
+
+        let imported = SchemaImporter::import_json(&json).unwrap();
+        assert!(imported.relationships.iter().all(|r| r.route.len() >= 2));
+        let restored = SchemaImporter::to_graph(&imported).unwrap();
+        let mut original: Vec<_> = graph.edge_weights().map(|r| r.route.clone()).collect();
+        let mut routes: Vec<_> = restored.edge_weights().map(|r| r.route.clone()).collect();
+        original.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        routes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(routes, original);
+    }
 }
@@ -0,0 +1,379 @@
+//! Foreign-key inference by naming convention
+//!
+//! Legacy schemas often carry `customer_id` columns without declared foreign keys.
+//! The inference pass matches column names against table names and proposes
+//! relationships ranked by confidence:
+//! - `<singular>_id` / `<table>_id` (e.g. `orders.customer_id -> customers.id`)
+//! - `<singular>Id` camelCase keys (e.g. `customerId`)
+//! - `fk_<table>` prefixed keys (e.g. `fk_customer`)
+//! - role prefixes (e.g. `author_user_id -> users.id`), with lower confidence
+//!
+//! Candidates whose types are incompatible with the referenced key are dropped.
+
+use super::lint::{singularize, to_snake_case};
+use super::liveshare::GraphOperation;
+use super::{Relationship, RelationshipOps, RelationshipType, SchemaGraph, TableNode};
+use petgraph::graph::NodeIndex;
+use serde::Serialize;
+
+/// Candidates at or above this confidence are preselected for acceptance
+pub const DEFAULT_MIN_CONFIDENCE: f32 = 0.75;
+
+/// Proposed foreign key
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FkCandidate {
+    #[serde(skip)]
+    pub from_node: NodeIndex,
+    pub from_table: String,
+    pub from_column: String,
+    #[serde(skip)]
+    pub to_node: NodeIndex,
+    pub to_table: String,
+    pub to_column: String,
+    pub relationship_type: RelationshipType,
+    /// 0.0 - 1.0
+    pub confidence: f32,
+    /// Why the candidate was proposed (shown in the review dialog)
+    pub reasons: Vec<String>,
+}
+
+impl FkCandidate {
+    /// Relationship to create when the candidate is accepted
+    pub fn relationship(&self) -> Relationship {
+        Relationship::new(
+            format!("fk_{}_{}", self.from_table, self.from_column),
+            self.relationship_type.clone(),
+            &self.from_column,
+            &self.to_column,
+        )
+    }
+}
+
+impl std::fmt::Display for FkCandidate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{} -> {}.{} ({:.0}%)",
+            self.from_table,
+            self.from_column,
+            self.to_table,
+            self.to_column,
+            self.confidence * 100.0
+        )
+    }
+}
+
+/// How a column name refers to a table
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum NamePattern {
+    SnakeId,
+    CamelId,
+    FkPrefix,
+}
+
+impl NamePattern {
+    fn confidence(&self) -> f32 {
+        match self {
+            NamePattern::SnakeId => 0.9,
+            NamePattern::CamelId | NamePattern::FkPrefix => 0.85,
+        }
+    }
+
+    fn describe(&self) -> &'static str {
+        match self {
+            NamePattern::SnakeId => "<table>_id naming",
+            NamePattern::CamelId => "<table>Id naming",
+            NamePattern::FkPrefix => "fk_ prefix",
+        }
+    }
+}
+
+/// Split a column name into the referenced name stem and the pattern used
+fn parse_key_name(column: &str) -> Option<(String, NamePattern)> {
+    let snake = to_snake_case(column);
+    if let Some(rest) = snake.strip_prefix("fk_") {
+        let stem = rest.strip_suffix("_id").unwrap_or(rest);
+        return (!stem.is_empty()).then(|| (stem.to_string(), NamePattern::FkPrefix));
+    }
+    let stem = snake.strip_suffix("_id").filter(|s| !s.is_empty())?;
+    let pattern = if column.ends_with("Id") {
+        NamePattern::CamelId
+    } else {
+        NamePattern::SnakeId
+    };
+    Some((stem.to_string(), pattern))
+}
+
+/// Key column referenced by other tables: the single-column PK, or an `id` column
+fn referenced_key(table: &TableNode) -> Option<(&super::Column, bool)> {
+    let mut primary_keys = table.columns.iter().filter(|c| c.is_primary_key);
+    match (primary_keys.next(), primary_keys.next()) {
+        (Some(pk), None) => Some((pk, true)),
+        (None, _) => table
+            .columns
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case("id"))
+            .map(|c| (c, false)),
+        _ => None,
+    }
+}
+
+/// Whether a relationship already uses `column` of `node` as its key
+fn has_relationship(graph: &SchemaGraph, node: NodeIndex, column: &str) -> bool {
+    graph
+        .edges_directed(node, petgraph::Direction::Outgoing)
+        .any(|e| e.weight().relationship_type.key_on_source() && e.weight().from_column == column)
+        || graph
+            .edges_directed(node, petgraph::Direction::Incoming)
+            .any(|e| {
+                !e.weight().relationship_type.key_on_source() && e.weight().to_column == column
+            })
+}
+
+/// Propose foreign keys for columns without a declared relationship, best first
+///
+/// Only the best candidate per column is kept.
+pub fn infer_foreign_keys(graph: &SchemaGraph) -> Vec<FkCandidate> {
+    let tables: Vec<(NodeIndex, String, String)> = graph
+        .node_indices()
+        .filter_map(|idx| {
+            let name = graph.node_weight(idx)?.name.to_lowercase();
+            Some((idx, singularize(&name), name))
+        })
+        .collect();
+
+    let mut candidates = Vec::new();
+    for &(from_node, _, _) in &tables {
+        let Some(from_table) = graph.node_weight(from_node) else {
+            continue;
+        };
+        for column in &from_table.columns {
+            let Some((stem, pattern)) = parse_key_name(&column.name) else {
+                continue;
+            };
+            if has_relationship(graph, from_node, &column.name) {
+                continue;
+            }
+
+            let best = tables
+                .iter()
+                .filter_map(|(to_node, singular, plural)| {
+                    let (mut confidence, mut reasons) = if stem == *singular || stem == *plural {
+                        (pattern.confidence(), vec![pattern.describe().to_string()])
+                    } else if stem.ends_with(&format!("_{}", singular))
+                        || stem.ends_with(&format!("_{}", plural))
+                    {
+                        (0.6, vec![format!("role-prefixed {}", pattern.describe())])
+                    } else {
+                        return None;
+                    };
+
+                    let to_table = graph.node_weight(*to_node)?;
+                    let (key, is_primary_key) = referenced_key(to_table)?;
+                    if *to_node == from_node && key.name == column.name {
+                        return None;
+                    }
+                    if !column.is_type_compatible_with(key) {
+                        return None;
+                    }
+                    if column.get_base_type() == key.get_base_type() {
+                        confidence += 0.05;
+                        reasons.push("same type".to_string());
+                    } else {
+                        confidence -= 0.05;
+                        reasons.push(format!(
+                            "compatible types ({} / {})",
+                            column.data_type, key.data_type
+                        ));
+                    }
+                    if !is_primary_key {
+                        confidence -= 0.15;
+                        reasons.push(format!(
+                            "{}.{} is not a primary key",
+                            to_table.name, key.name
+                        ));
+                    }
+
+                    let unique = column.is_unique
+                        || (column.is_primary_key
+                            && from_table
+                                .columns
+                                .iter()
+                                .filter(|c| c.is_primary_key)
+                                .count()
+                                == 1);
+                    Some(FkCandidate {
+                        from_node,
+                        from_table: from_table.name.clone(),
+                        from_column: column.name.clone(),
+                        to_node: *to_node,
+                        to_table: to_table.name.clone(),
+                        to_column: key.name.clone(),
+                        relationship_type: if unique {
+                            RelationshipType::OneToOne
+                        } else {
+                            RelationshipType::ManyToOne
+                        },
+                        confidence: (confidence.clamp(0.0, 1.0) * 100.0).round() / 100.0,
+                        reasons,
+                    })
+                })
+                .max_by(|a, b| a.confidence.total_cmp(&b.confidence));
+            candidates.extend(best);
+        }
+    }
+
+    candidates.sort_by(|a, b| {
+        b.confidence
+            .total_cmp(&a.confidence)
+            .then_with(|| a.from_table.cmp(&b.from_table))
+            .then_with(|| a.from_column.cmp(&b.from_column))
+    });
+    candidates
+}
+
+/// Result of accepting inferred foreign keys
+#[derive(Clone, Debug, Default)]
+pub struct AcceptedForeignKeys {
+    /// Relationships created
+    pub created: Vec<String>,
+    /// Candidates that could not be created
+    pub errors: Vec<String>,
+    /// Operations for LiveShare sync
+    pub graph_ops: Vec<GraphOperation>,
+}
+
+/// Create relationships for the accepted candidates
+pub fn accept_fk_candidates(
+    graph: &mut SchemaGraph,
+    candidates: &[FkCandidate],
+) -> AcceptedForeignKeys {
+    let mut result = AcceptedForeignKeys::default();
+    for candidate in candidates {
+        let relationship = candidate.relationship();
+        match graph.create_relationship_checked(
+            candidate.from_node,
+            candidate.to_node,
+            relationship.clone(),
+        ) {
+            Ok((edge, _)) => {
+                result.graph_ops.push(GraphOperation::CreateRelationship {
+                    edge_id: edge.index() as u32,
+                    from_node: candidate.from_node.index() as u32,
+                    to_node: candidate.to_node.index() as u32,
+                    relationship: (&relationship).into(),
+                });
+                result.created.push(candidate.to_string());
+            }
+            Err(e) => result.errors.push(format!("{}: {}", candidate, e)),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Column;
+
+    fn legacy_graph() -> SchemaGraph {
+        let mut graph = SchemaGraph::new();
+        graph.add_node(
+            TableNode::new("customers")
+                .add_column(Column::new("id", "INT").primary_key())
+                .add_column(Column::new("name", "VARCHAR(100)")),
+        );
+        graph.add_node(
+            TableNode::new("users").add_column(Column::new("id", "BIGINT").primary_key()),
+        );
+        graph.add_node(
+            TableNode::new("orders")
+                .add_column(Column::new("id", "INT").primary_key())
+                .add_column(Column::new("customer_id", "INT"))
+                .add_column(Column::new("createdById", "INT"))
+                .add_column(Column::new("approver_user_id", "BIGINT"))
+                .add_column(Column::new("warehouse_id", "INT"))
+                .add_column(Column::new("note", "TEXT")),
+        );
+        graph.add_node(
+            TableNode::new("invoices")
+                .add_column(Column::new("id", "INT").primary_key())
+                .add_column(Column::new("fk_customer", "INT"))
+                .add_column(Column::new("user_id", "VARCHAR(36)")),
+        );
+        graph
+    }
+
+    fn find<'a>(
+        candidates: &'a [FkCandidate],
+        table: &str,
+        column: &str,
+    ) -> Option<&'a FkCandidate> {
+        candidates
+            .iter()
+            .find(|c| c.from_table == table && c.from_column == column)
+    }
+
+    #[test]
+    fn test_infer_by_naming_patterns() {
+        let candidates = infer_foreign_keys(&legacy_graph());
+
+        let customer = find(&candidates, "orders", "customer_id").unwrap();
+        assert_eq!(
+            (customer.to_table.as_str(), customer.to_column.as_str()),
+            ("customers", "id")
+        );
+        assert_eq!(customer.confidence, 0.95);
+        assert_eq!(candidates[0], *customer);
+
+        let prefixed = find(&candidates, "invoices", "fk_customer").unwrap();
+        assert_eq!(prefixed.to_table, "customers");
+        assert_eq!(prefixed.confidence, 0.9);
+
+        let role = find(&candidates, "orders", "approver_user_id").unwrap();
+        assert_eq!(role.to_table, "users");
+        assert_eq!(role.confidence, 0.65);
+
+        // No table named like the stem, incompatible type, not a key column
+        assert!(find(&candidates, "orders", "createdById").is_none());
+        assert!(find(&candidates, "orders", "warehouse_id").is_none());
+        assert!(find(&candidates, "invoices", "user_id").is_none());
+        assert!(find(&candidates, "orders", "note").is_none());
+        assert!(find(&candidates, "orders", "id").is_none());
+    }
+
+    #[test]
+    fn test_camel_case_keys() {
+        let mut graph = legacy_graph();
+        graph.add_node(
+            TableNode::new("created_bies").add_column(Column::new("id", "INT").primary_key()),
+        );
+        graph.add_node(TableNode::new("product").add_column(Column::new("id", "INT")));
+        let items = graph
+            .add_node(TableNode::new("items").add_column(Column::new("productId", "INT").unique()));
+
+        let candidates = infer_foreign_keys(&graph);
+        let product = find(&candidates, "items", "productId").unwrap();
+        assert_eq!(product.to_table, "product");
+        assert_eq!(product.relationship_type, RelationshipType::OneToOne);
+        // camelCase 0.85 + same type 0.05 - not a primary key 0.15
+        assert_eq!(product.confidence, 0.75);
+        assert_eq!(product.from_node, items);
+    }
+
+    #[test]
+    fn test_accept_candidates_skips_declared_keys() {
+        let mut graph = legacy_graph();
+        let candidates = infer_foreign_keys(&graph);
+        let accepted = accept_fk_candidates(&mut graph, &candidates);
+
+        assert_eq!(accepted.created.len(), candidates.len());
+        assert!(accepted.errors.is_empty());
+        assert_eq!(accepted.graph_ops.len(), candidates.len());
+        assert_eq!(graph.edge_count(), candidates.len());
+
+        // Second pass proposes nothing: all keys are declared now
+        assert!(infer_foreign_keys(&graph).is_empty());
+        assert!(infer_foreign_keys(&crate::core::create_demo_graph()).is_empty());
+    }
+}
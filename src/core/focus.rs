@@ -0,0 +1,137 @@
+//! Focus mode: work on one area of a large diagram
+//!
+//! Focusing a table shows only the tables reachable from it within a number of
+//! relationship hops. Relationships are followed in both directions, so a focus
+//! on `invoices` includes the tables it references and the tables referencing it.
+//! The focused subset can be extracted as a standalone graph for export.
+
+use std::collections::{HashSet, VecDeque};
+
+use petgraph::graph::NodeIndex;
+
+use crate::core::schema::SchemaGraph;
+
+/// Hop depth used when focus mode is entered
+pub const DEFAULT_FOCUS_DEPTH: usize = 1;
+
+/// Largest hop depth offered by the canvas
+pub const MAX_FOCUS_DEPTH: usize = 5;
+
+/// Focused table and hop depth
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FocusMode {
+    pub table: NodeIndex,
+    pub depth: usize,
+}
+
+impl FocusMode {
+    pub fn new(table: NodeIndex) -> Self {
+        Self {
+            table,
+            depth: DEFAULT_FOCUS_DEPTH,
+        }
+    }
+
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth.min(MAX_FOCUS_DEPTH);
+        self
+    }
+
+    /// Tables shown in focus mode (empty if the focused table no longer exists)
+    pub fn visible_tables(&self, graph: &SchemaGraph) -> HashSet<NodeIndex> {
+        neighborhood(graph, self.table, self.depth)
+    }
+
+    /// Standalone graph of the focused tables and the relationships between them
+    pub fn subgraph(&self, graph: &SchemaGraph) -> SchemaGraph {
+        let visible = self.visible_tables(graph);
+        graph.filter_map(
+            |idx, table| visible.contains(&idx).then(|| table.clone()),
+            |_, relationship| Some(relationship.clone()),
+        )
+    }
+}
+
+/// Tables within `depth` relationship hops of `center`, including `center`
+pub fn neighborhood(graph: &SchemaGraph, center: NodeIndex, depth: usize) -> HashSet<NodeIndex> {
+    let mut visited = HashSet::new();
+    if !graph.contains_node(center) {
+        return visited;
+    }
+    visited.insert(center);
+
+    // Обход в ширину с учётом глубины, связи считаем ненаправленными
+    let mut queue = VecDeque::from([(center, 0)]);
+    while let Some((node, hops)) = queue.pop_front() {
+        if hops == depth {
+            continue;
+        }
+        for neighbor in graph.neighbors_undirected(node) {
+            if visited.insert(neighbor) {
+                queue.push_back((neighbor, hops + 1));
+            }
+        }
+    }
+    visited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Relationship, RelationshipType, TableNode, TableOps};
+
+    /// a - b - c - d, plus e unconnected
+    fn chain() -> (SchemaGraph, Vec<NodeIndex>) {
+        let mut graph = SchemaGraph::new();
+        let nodes: Vec<_> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| graph.add_node(TableNode::new(*name)))
+            .collect();
+        for pair in nodes[..4].windows(2) {
+            graph.add_edge(
+                pair[1],
+                pair[0],
+                Relationship::new("fk", RelationshipType::ManyToOne, "id", "id"),
+            );
+        }
+        (graph, nodes)
+    }
+
+    #[test]
+    fn test_neighborhood_by_depth() {
+        let (graph, n) = chain();
+
+        assert_eq!(neighborhood(&graph, n[1], 0), HashSet::from([n[1]]));
+        assert_eq!(
+            neighborhood(&graph, n[1], 1),
+            HashSet::from([n[0], n[1], n[2]])
+        );
+        assert_eq!(
+            neighborhood(&graph, n[1], 2),
+            HashSet::from([n[0], n[1], n[2], n[3]])
+        );
+        // Изолированная таблица недостижима на любой глубине
+        assert!(!neighborhood(&graph, n[0], MAX_FOCUS_DEPTH).contains(&n[4]));
+    }
+
+    #[test]
+    fn test_neighborhood_of_removed_table_is_empty() {
+        let (mut graph, n) = chain();
+        graph.remove_node(n[2]);
+        assert!(neighborhood(&graph, n[2], 2).is_empty());
+        assert!(FocusMode::new(n[2]).subgraph(&graph).node_count() == 0);
+    }
+
+    #[test]
+    fn test_subgraph_keeps_internal_relationships() {
+        let (graph, n) = chain();
+        let focus = FocusMode::new(n[0]).with_depth(2);
+
+        let subgraph = focus.subgraph(&graph);
+        assert_eq!(subgraph.node_count(), 3);
+        assert_eq!(subgraph.edge_count(), 2);
+        assert!(subgraph.find_table_by_name("d").is_none());
+
+        assert_eq!(FocusMode::new(n[0]).with_depth(99).depth, MAX_FOCUS_DEPTH);
+    }
+}
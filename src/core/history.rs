@@ -0,0 +1,767 @@
+//! Undo/redo history of local edits
+//!
+//! Every local gesture is recorded as the [`GraphOperation`]s it produced
+//! together with its inverse: the state of the tables and relationships the
+//! operations touched, before and after the gesture. All operations of one
+//! gesture (applying SQL, an AI answer with several tool calls) form a single
+//! entry.
+//!
+//! Undo and redo turn an entry back into operations against the current graph,
+//! so they can be broadcast to LiveShare like any other local change. Only
+//! this user's edits are in the history, and a property is reverted only while
+//! it still holds the value the entry left behind: edits made in the meantime
+//! by other users are kept, and the rest of the entry is applied on top of them.
+
+use super::liveshare::{ColumnData, GraphOperation, RelationshipData};
+use super::table_display::TableDisplay;
+use super::{Column, Relationship, SchemaGraph, TableNode, TableOps};
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::EdgeRef;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Default number of entries kept for undo
+pub const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+/// Relationship identity that survives index changes: tables by UUID plus columns
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct RelationshipKey {
+    from_table: Uuid,
+    to_table: Uuid,
+    from_column: String,
+    to_column: String,
+}
+
+/// Table before and after an entry (`None` - the table did not exist)
+#[derive(Clone, Debug)]
+struct TableChange {
+    uuid: Uuid,
+    before: Option<TableNode>,
+    after: Option<TableNode>,
+}
+
+/// Relationship before and after an entry (`None` - it did not exist)
+#[derive(Clone, Debug)]
+struct RelationshipChange {
+    key: RelationshipKey,
+    before: Option<Relationship>,
+    after: Option<Relationship>,
+}
+
+/// State an entry brings the graph back to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Side {
+    Before,
+    After,
+}
+
+impl Side {
+    /// (state the graph is expected to be in, state to restore)
+    fn pick<'a, T>(
+        self,
+        before: &'a Option<T>,
+        after: &'a Option<T>,
+    ) -> (Option<&'a T>, Option<&'a T>) {
+        match self {
+            Side::Before => (after.as_ref(), before.as_ref()),
+            Side::After => (before.as_ref(), after.as_ref()),
+        }
+    }
+}
+
+/// One undoable gesture
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Operations of the gesture as they were sent
+    pub ops: Vec<GraphOperation>,
+    tables: Vec<TableChange>,
+    relationships: Vec<RelationshipChange>,
+}
+
+impl HistoryEntry {
+    fn new(before: &SchemaGraph, after: &SchemaGraph, ops: Vec<GraphOperation>) -> Self {
+        let mut touched: Vec<Uuid> = Vec::new();
+        for op in &ops {
+            match op {
+                GraphOperation::CreateTable { table_uuid, .. }
+                | GraphOperation::DeleteTable { table_uuid, .. }
+                | GraphOperation::RenameTable { table_uuid, .. }
+                | GraphOperation::MoveTable { table_uuid, .. }
+                | GraphOperation::SetTablePinned { table_uuid, .. }
+                | GraphOperation::SetTableDisplay { table_uuid, .. }
+                | GraphOperation::AddColumn { table_uuid, .. }
+                | GraphOperation::UpdateColumn { table_uuid, .. }
+                | GraphOperation::DeleteColumn { table_uuid, .. } => touched.push(*table_uuid),
+                GraphOperation::CreateRelationship {
+                    from_node, to_node, ..
+                } => {
+                    for node in [from_node, to_node] {
+                        if let Some(table) = after.node_weight(NodeIndex::new(*node as usize)) {
+                            touched.push(table.uuid);
+                        }
+                    }
+                }
+                GraphOperation::DeleteRelationship { edge_id } => {
+                    if let Some((from, to)) =
+                        before.edge_endpoints(EdgeIndex::new(*edge_id as usize))
+                    {
+                        touched.push(before[from].uuid);
+                        touched.push(before[to].uuid);
+                    }
+                }
+            }
+        }
+        let mut seen = HashSet::new();
+        touched.retain(|uuid| seen.insert(*uuid));
+
+        let tables = touched
+            .iter()
+            .map(|&uuid| TableChange {
+                uuid,
+                before: find_table(before, uuid).map(|idx| before[idx].clone()),
+                after: find_table(after, uuid).map(|idx| after[idx].clone()),
+            })
+            .filter(|change| change.before != change.after)
+            .collect();
+
+        // Связи сравниваются у всех затронутых таблиц, в том числе удалённые вместе с таблицей
+        let touched: HashSet<Uuid> = seen;
+        let before_relationships = relationships_of(before, &touched);
+        let after_relationships = relationships_of(after, &touched);
+        let mut relationships: Vec<RelationshipChange> = Vec::new();
+        for (key, _) in before_relationships.iter().chain(&after_relationships) {
+            if relationships.iter().any(|c| &c.key == key) {
+                continue;
+            }
+            let lookup = |list: &[(RelationshipKey, Relationship)]| {
+                list.iter().find(|(k, _)| k == key).map(|(_, r)| r.clone())
+            };
+            let change = RelationshipChange {
+                key: key.clone(),
+                before: lookup(&before_relationships),
+                after: lookup(&after_relationships),
+            };
+            if !same_relationship(change.before.as_ref(), change.after.as_ref()) {
+                relationships.push(change);
+            }
+        }
+
+        Self {
+            ops,
+            tables,
+            relationships,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.tables.is_empty() && self.relationships.is_empty()
+    }
+
+    /// Bring the touched elements to `side`, returning the applied operations
+    fn restore(&self, graph: &mut SchemaGraph, side: Side) -> Vec<GraphOperation> {
+        let mut ops = Vec::new();
+
+        // Сначала удаляем связи, которых не должно остаться
+        for change in &self.relationships {
+            let (expected, target) = side.pick(&change.before, &change.after);
+            if expected.is_some()
+                && !same_relationship(expected, target)
+                && let Some(edge) = find_relationship(graph, &change.key)
+                && same_relationship(Some(&graph[edge]), expected)
+            {
+                graph.remove_edge(edge);
+                ops.push(GraphOperation::DeleteRelationship {
+                    edge_id: edge.index() as u32,
+                });
+            }
+        }
+
+        // Таблицы удаляются раньше остальных изменений: восстанавливаемая таблица
+        // может носить имя удаляемой (применение SQL пересоздаёт все таблицы)
+        for deletions in [true, false] {
+            for change in &self.tables {
+                let (expected, target) = side.pick(&change.before, &change.after);
+                if target.is_none() == deletions {
+                    restore_table(graph, change.uuid, expected, target, &mut ops);
+                }
+            }
+        }
+
+        // Связи создаются последними, когда обе таблицы уже на месте
+        for change in &self.relationships {
+            let (expected, target) = side.pick(&change.before, &change.after);
+            let Some(target) = target else { continue };
+            if same_relationship(expected, Some(target))
+                || find_relationship(graph, &change.key).is_some()
+            {
+                continue;
+            }
+            let (Some(from), Some(to)) = (
+                find_table(graph, change.key.from_table),
+                find_table(graph, change.key.to_table),
+            ) else {
+                continue;
+            };
+            let mut relationship = target.clone();
+            relationship.route.clear();
+            let edge = graph.add_edge(from, to, relationship);
+            ops.push(GraphOperation::CreateRelationship {
+                edge_id: edge.index() as u32,
+                from_node: from.index() as u32,
+                to_node: to.index() as u32,
+                relationship: RelationshipData::from(target),
+            });
+        }
+
+        ops
+    }
+}
+
+/// Undo and redo stacks of local edits
+#[derive(Clone, Debug)]
+pub struct UndoHistory {
+    undo: Vec<HistoryEntry>,
+    redo: Vec<HistoryEntry>,
+    limit: usize,
+}
+
+impl Default for UndoHistory {
+    fn default() -> Self {
+        Self::with_limit(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+impl UndoHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// History keeping at most `limit` entries for undo
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            limit,
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forget all entries (e.g. when another diagram is loaded)
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Record a local gesture as one entry
+    ///
+    /// `before` and `after` are the graph before and after the gesture, `ops`
+    /// the operations it produced. Gestures that changed nothing are ignored.
+    pub fn record(&mut self, before: &SchemaGraph, after: &SchemaGraph, ops: Vec<GraphOperation>) {
+        if ops.is_empty() {
+            return;
+        }
+        let entry = HistoryEntry::new(before, after, ops);
+        if entry.is_empty() {
+            return;
+        }
+        self.undo.push(entry);
+        self.redo.clear();
+        if self.undo.len() > self.limit {
+            self.undo.remove(0);
+        }
+    }
+
+    /// Undo the last entry on `graph`, returning the operations to broadcast
+    ///
+    /// Returns `None` when there is nothing to undo. The operations may be
+    /// empty when other users have changed everything the entry touched.
+    pub fn undo(&mut self, graph: &mut SchemaGraph) -> Option<Vec<GraphOperation>> {
+        let entry = self.undo.pop()?;
+        let ops = entry.restore(graph, Side::Before);
+        self.redo.push(entry);
+        Some(ops)
+    }
+
+    /// Redo the last undone entry on `graph`, returning the operations to broadcast
+    pub fn redo(&mut self, graph: &mut SchemaGraph) -> Option<Vec<GraphOperation>> {
+        let entry = self.redo.pop()?;
+        let ops = entry.restore(graph, Side::After);
+        self.undo.push(entry);
+        Some(ops)
+    }
+}
+
+/// Bring one table from `expected` to `target`, skipping what others changed since
+fn restore_table(
+    graph: &mut SchemaGraph,
+    uuid: Uuid,
+    expected: Option<&TableNode>,
+    target: Option<&TableNode>,
+    ops: &mut Vec<GraphOperation>,
+) {
+    let current = find_table(graph, uuid);
+    match (expected, target) {
+        (Some(expected), None) => {
+            // Удаляем, только если с тех пор таблицу никто не менял
+            if let Some(idx) = current
+                && graph[idx].name == expected.name
+                && graph[idx].columns == expected.columns
+            {
+                graph.remove_node(idx);
+                ops.push(GraphOperation::DeleteTable {
+                    node_id: idx.index() as u32,
+                    table_uuid: uuid,
+                });
+            }
+        }
+        (None, Some(target)) => {
+            if current.is_some() || graph.find_table_by_name(&target.name).is_some() {
+                return;
+            }
+            let idx = graph.add_node(target.clone());
+            let node_id = idx.index() as u32;
+            ops.push(GraphOperation::CreateTable {
+                node_id,
+                table_uuid: uuid,
+                name: target.name.clone(),
+                position: target.position,
+            });
+            ops.extend(
+                target
+                    .columns
+                    .iter()
+                    .map(|column| GraphOperation::AddColumn {
+                        node_id,
+                        table_uuid: uuid,
+                        column: ColumnData::from(column),
+                    }),
+            );
+            if target.pinned {
+                ops.push(GraphOperation::SetTablePinned {
+                    node_id,
+                    table_uuid: uuid,
+                    pinned: true,
+                });
+            }
+            if target.display != TableDisplay::default() {
+                ops.push(GraphOperation::SetTableDisplay {
+                    node_id,
+                    table_uuid: uuid,
+                    display: target.display,
+                });
+            }
+        }
+        (Some(expected), Some(target)) => {
+            let Some(idx) = current else { return };
+            let node_id = idx.index() as u32;
+            let name_free = graph
+                .find_table_by_name(&target.name)
+                .is_none_or(|other| other == idx);
+            let table = &mut graph[idx];
+
+            if table.name == expected.name && table.name != target.name && name_free {
+                table.name = target.name.clone();
+                ops.push(GraphOperation::RenameTable {
+                    node_id,
+                    table_uuid: uuid,
+                    new_name: target.name.clone(),
+                });
+            }
+            if table.position == expected.position && table.position != target.position {
+                table.position = target.position;
+                ops.push(GraphOperation::MoveTable {
+                    node_id,
+                    table_uuid: uuid,
+                    position: target.position,
+                });
+            }
+            if table.pinned == expected.pinned && table.pinned != target.pinned {
+                table.pinned = target.pinned;
+                ops.push(GraphOperation::SetTablePinned {
+                    node_id,
+                    table_uuid: uuid,
+                    pinned: target.pinned,
+                });
+            }
+            if table.display == expected.display && table.display != target.display {
+                table.display = target.display;
+                ops.push(GraphOperation::SetTableDisplay {
+                    node_id,
+                    table_uuid: uuid,
+                    display: target.display,
+                });
+            }
+            if table.columns == expected.columns && table.columns != target.columns {
+                ops.extend(column_ops(node_id, uuid, &table.columns, &target.columns));
+                table.columns = target.columns.clone();
+            }
+        }
+        (None, None) => {}
+    }
+}
+
+/// Operations turning the column list `from` into `to`
+fn column_ops(
+    node_id: u32,
+    table_uuid: Uuid,
+    from: &[Column],
+    to: &[Column],
+) -> Vec<GraphOperation> {
+    let mut ops = Vec::new();
+    for (column_index, (old, new)) in from.iter().zip(to).enumerate() {
+        if old != new {
+            ops.push(GraphOperation::UpdateColumn {
+                node_id,
+                table_uuid,
+                column_index,
+                column: ColumnData::from(new),
+            });
+        }
+    }
+    for column_index in (to.len()..from.len()).rev() {
+        ops.push(GraphOperation::DeleteColumn {
+            node_id,
+            table_uuid,
+            column_index,
+        });
+    }
+    for column in to.iter().skip(from.len()) {
+        ops.push(GraphOperation::AddColumn {
+            node_id,
+            table_uuid,
+            column: ColumnData::from(column),
+        });
+    }
+    ops
+}
+
+fn find_table(graph: &SchemaGraph, uuid: Uuid) -> Option<NodeIndex> {
+    graph.node_indices().find(|&idx| graph[idx].uuid == uuid)
+}
+
+fn find_relationship(graph: &SchemaGraph, key: &RelationshipKey) -> Option<EdgeIndex> {
+    let from = find_table(graph, key.from_table)?;
+    let to = find_table(graph, key.to_table)?;
+    graph
+        .edges_connecting(from, to)
+        .find(|e| {
+            e.weight().from_column == key.from_column && e.weight().to_column == key.to_column
+        })
+        .map(|e| e.id())
+}
+
+/// Relationships with at least one end in `tables`
+fn relationships_of(
+    graph: &SchemaGraph,
+    tables: &HashSet<Uuid>,
+) -> Vec<(RelationshipKey, Relationship)> {
+    graph
+        .edge_indices()
+        .filter_map(|edge| {
+            let (from, to) = graph.edge_endpoints(edge)?;
+            let (from, to) = (graph[from].uuid, graph[to].uuid);
+            if !tables.contains(&from) && !tables.contains(&to) {
+                return None;
+            }
+            let relationship = &graph[edge];
+            let key = RelationshipKey {
+                from_table: from,
+                to_table: to,
+                from_column: relationship.from_column.clone(),
+                to_column: relationship.to_column.clone(),
+            };
+            Some((key, relationship.clone()))
+        })
+        .collect()
+}
+
+/// Same relationship apart from its rendered route
+fn same_relationship(a: Option<&Relationship>, b: Option<&Relationship>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.name == b.name && a.relationship_type == b.relationship_type,
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{
+        ExportOptions, RelationshipType, SchemaExporter, SqlDialect, create_demo_graph,
+    };
+
+    /// Apply a local edit to `graph` and record it like the UI does
+    fn edit(
+        history: &mut UndoHistory,
+        graph: &mut SchemaGraph,
+        change: impl FnOnce(&mut SchemaGraph) -> Vec<GraphOperation>,
+    ) {
+        let before = graph.clone();
+        let ops = change(graph);
+        history.record(&before, graph, ops);
+    }
+
+    fn delete_table(graph: &mut SchemaGraph, name: &str) -> Vec<GraphOperation> {
+        let idx = graph.find_table_by_name(name).unwrap();
+        let table_uuid = graph[idx].uuid;
+        graph.delete_table(idx).unwrap();
+        vec![GraphOperation::DeleteTable {
+            node_id: idx.index() as u32,
+            table_uuid,
+        }]
+    }
+
+    fn move_table(
+        graph: &mut SchemaGraph,
+        name: &str,
+        position: (f64, f64),
+    ) -> Vec<GraphOperation> {
+        let idx = graph.find_table_by_name(name).unwrap();
+        graph[idx].position = position;
+        vec![GraphOperation::MoveTable {
+            node_id: idx.index() as u32,
+            table_uuid: graph[idx].uuid,
+            position,
+        }]
+    }
+
+    fn table<'a>(graph: &'a SchemaGraph, name: &str) -> &'a TableNode {
+        &graph[graph.find_table_by_name(name).unwrap()]
+    }
+
+    #[test]
+    fn test_undo_redo_table_deletion() {
+        let mut graph = create_demo_graph();
+        let original = graph.clone();
+        let relationships = graph.edge_count();
+        let mut history = UndoHistory::new();
+
+        edit(&mut history, &mut graph, |g| delete_table(g, "users"));
+        assert!(graph.find_table_by_name("users").is_none());
+        assert!(graph.edge_count() < relationships);
+
+        let ops = history.undo(&mut graph).unwrap();
+        assert!(matches!(ops[0], GraphOperation::CreateTable { .. }));
+        assert_eq!(table(&graph, "users"), table(&original, "users"));
+        assert_eq!(graph.edge_count(), relationships);
+        assert!(
+            ops.iter()
+                .any(|op| matches!(op, GraphOperation::CreateRelationship { .. }))
+        );
+
+        let ops = history.redo(&mut graph).unwrap();
+        assert!(matches!(
+            ops.last(),
+            Some(GraphOperation::DeleteTable { .. })
+        ));
+        assert!(graph.find_table_by_name("users").is_none());
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn test_undo_applied_sql() {
+        let mut graph = create_demo_graph();
+        let original = graph.clone();
+        let mut history = UndoHistory::new();
+        let options = ExportOptions {
+            sql_dialect: SqlDialect::PostgreSQL,
+            ..Default::default()
+        };
+        let sql = SchemaExporter::export_sql(&graph, &options)
+            .unwrap()
+            .replace(r#""comments""#, r#""remarks""#);
+
+        edit(&mut history, &mut graph, |g| {
+            let result = crate::core::apply_sql_to_graph(&sql, SqlDialect::PostgreSQL, g);
+            assert!(result.success, "{:?}", result.errors);
+            result.graph_ops
+        });
+        assert!(graph.find_table_by_name("remarks").is_some());
+
+        // Все таблицы пересозданы с новыми UUID и теми же именами
+        history.undo(&mut graph).unwrap();
+        assert_eq!(graph.node_count(), original.node_count());
+        assert_eq!(graph.edge_count(), original.edge_count());
+        for node in original.node_weights() {
+            assert_eq!(table(&graph, &node.name).columns, node.columns);
+        }
+        assert!(graph.find_table_by_name("remarks").is_none());
+
+        history.redo(&mut graph).unwrap();
+        assert!(graph.find_table_by_name("remarks").is_some());
+        assert!(graph.find_table_by_name("comments").is_none());
+    }
+
+    #[test]
+    fn test_gesture_is_one_entry() {
+        let mut graph = create_demo_graph();
+        let mut history = UndoHistory::new();
+
+        edit(&mut history, &mut graph, |g| {
+            let mut ops = delete_table(g, "comments");
+            ops.extend(move_table(g, "posts", (900.0, 900.0)));
+            ops
+        });
+        history.undo(&mut graph).unwrap();
+
+        assert!(graph.find_table_by_name("comments").is_some());
+        assert_ne!(table(&graph, "posts").position, (900.0, 900.0));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn test_undo_keeps_remote_changes() {
+        let mut graph = create_demo_graph();
+        let mut history = UndoHistory::new();
+        let start = table(&graph, "users").position;
+
+        edit(&mut history, &mut graph, |g| {
+            let mut ops = move_table(g, "users", (500.0, 500.0));
+            let idx = g.find_table_by_name("users").unwrap();
+            g.rename_table(idx, "accounts").unwrap();
+            ops.push(GraphOperation::RenameTable {
+                node_id: idx.index() as u32,
+                table_uuid: g[idx].uuid,
+                new_name: "accounts".into(),
+            });
+            ops
+        });
+
+        // Другой участник передвинул таблицу после нас
+        let idx = graph.find_table_by_name("accounts").unwrap();
+        graph[idx].position = (700.0, 100.0);
+
+        let ops = history.undo(&mut graph).unwrap();
+        assert_eq!(graph[idx].name, "users");
+        assert_eq!(graph[idx].position, (700.0, 100.0));
+        assert_ne!(graph[idx].position, start);
+        assert!(matches!(ops[..], [GraphOperation::RenameTable { .. }]));
+    }
+
+    #[test]
+    fn test_undo_created_table_skips_remote_edits() {
+        let mut graph = SchemaGraph::new();
+        let mut history = UndoHistory::new();
+
+        edit(&mut history, &mut graph, |g| {
+            let idx = g.create_table("orders", (0.0, 0.0)).unwrap();
+            vec![GraphOperation::CreateTable {
+                node_id: idx.index() as u32,
+                table_uuid: g[idx].uuid,
+                name: "orders".into(),
+                position: (0.0, 0.0),
+            }]
+        });
+
+        // Другой участник добавил колонку в новую таблицу
+        let idx = graph.find_table_by_name("orders").unwrap();
+        graph[idx].columns.push(Column::new("id", "INT"));
+
+        assert!(history.undo(&mut graph).unwrap().is_empty());
+        assert!(graph.find_table_by_name("orders").is_some());
+    }
+
+    #[test]
+    fn test_column_changes_roundtrip() {
+        let mut graph = create_demo_graph();
+        let mut history = UndoHistory::new();
+        let original = table(&graph, "posts").clone();
+
+        edit(&mut history, &mut graph, |g| {
+            let idx = g.find_table_by_name("posts").unwrap();
+            let table_uuid = g[idx].uuid;
+            g[idx].columns.remove(1);
+            g[idx].columns[0].data_type = "BIGINT".into();
+            vec![
+                GraphOperation::DeleteColumn {
+                    node_id: idx.index() as u32,
+                    table_uuid,
+                    column_index: 1,
+                },
+                GraphOperation::UpdateColumn {
+                    node_id: idx.index() as u32,
+                    table_uuid,
+                    column_index: 0,
+                    column: ColumnData::from(&g[idx].columns[0]),
+                },
+            ]
+        });
+        let edited = table(&graph, "posts").clone();
+
+        history.undo(&mut graph).unwrap();
+        assert_eq!(table(&graph, "posts"), &original);
+        history.redo(&mut graph).unwrap();
+        assert_eq!(table(&graph, "posts"), &edited);
+    }
+
+    #[test]
+    fn test_relationship_undo() {
+        let mut graph = create_demo_graph();
+        let mut history = UndoHistory::new();
+        let edges = graph.edge_count();
+
+        edit(&mut history, &mut graph, |g| {
+            let from = g.find_table_by_name("comments").unwrap();
+            let to = g.find_table_by_name("users").unwrap();
+            let relationship =
+                Relationship::new("fk_extra", RelationshipType::ManyToOne, "user_id", "id");
+            let data = RelationshipData::from(&relationship);
+            let edge = g.add_edge(from, to, relationship);
+            vec![GraphOperation::CreateRelationship {
+                edge_id: edge.index() as u32,
+                from_node: from.index() as u32,
+                to_node: to.index() as u32,
+                relationship: data,
+            }]
+        });
+        assert_eq!(graph.edge_count(), edges + 1);
+
+        let ops = history.undo(&mut graph).unwrap();
+        assert_eq!(graph.edge_count(), edges);
+        assert!(matches!(
+            ops[..],
+            [GraphOperation::DeleteRelationship { .. }]
+        ));
+    }
+
+    #[test]
+    fn test_new_edit_clears_redo_and_limit_applies() {
+        let mut graph = create_demo_graph();
+        let mut history = UndoHistory::with_limit(2);
+
+        for x in 0..3 {
+            edit(&mut history, &mut graph, |g| {
+                move_table(g, "users", (x as f64, 0.0))
+            });
+        }
+        history.undo(&mut graph).unwrap();
+        history.undo(&mut graph).unwrap();
+        assert!(!history.can_undo());
+        assert_eq!(table(&graph, "users").position, (0.0, 0.0));
+
+        edit(&mut history, &mut graph, |g| {
+            move_table(g, "users", (42.0, 0.0))
+        });
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn test_noop_gesture_is_not_recorded() {
+        let mut graph = create_demo_graph();
+        let mut history = UndoHistory::new();
+        let position = table(&graph, "users").position;
+
+        edit(&mut history, &mut graph, |g| {
+            move_table(g, "users", position)
+        });
+        assert!(!history.can_undo());
+    }
+}
@@ -0,0 +1,570 @@
+//! Impact analysis for destructive schema changes
+//!
+//! Before a table or column is dropped, lists everything that depends on it:
+//! - relationships that use the column (they are removed together with it)
+//! - foreign key columns left without a reference, and transitively the keys
+//!   that reference those columns in turn
+//! - indexes implied by the column (primary key, unique, foreign key index)
+//! - junction tables that lose one of their sides
+//!
+//! The walk follows `find_relationships_from`/`find_relationships_to` by key
+//! column, so unrelated relationships of the same table are not reported.
+
+use super::junction::find_junction;
+use super::liveshare::GraphOperation;
+use super::{RelationshipOps, SchemaGraph};
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+/// What is being changed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImpactTarget {
+    Table(NodeIndex),
+    Column(NodeIndex, String),
+}
+
+/// Kind of dependent object
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImpactKind {
+    /// Relationship that uses the column and is removed with it
+    Relationship,
+    /// Foreign key column that loses its referenced key
+    OrphanedKey,
+    /// Foreign key that references an orphaned key
+    DependentKey,
+    /// Index implied by the column
+    Index,
+    /// Junction table that loses one of its sides
+    JunctionTable,
+}
+
+impl ImpactKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ImpactKind::Relationship => "Relationship",
+            ImpactKind::OrphanedKey => "Orphaned key",
+            ImpactKind::DependentKey => "Dependent key",
+            ImpactKind::Index => "Index",
+            ImpactKind::JunctionTable => "Junction table",
+        }
+    }
+}
+
+/// Object affected by the change
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ImpactItem {
+    pub kind: ImpactKind,
+    /// Distance from the changed object (1 = direct dependency)
+    pub depth: usize,
+    pub table: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationship: Option<String>,
+    pub description: String,
+    #[serde(skip)]
+    pub node: NodeIndex,
+    #[serde(skip)]
+    pub edge: Option<EdgeIndex>,
+}
+
+/// Result of impact analysis
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ImpactReport {
+    /// Changed object as text ("orders" or "orders.customer_id")
+    pub target: String,
+    pub items: Vec<ImpactItem>,
+}
+
+impl ImpactReport {
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Relationships involved (for highlighting on the canvas)
+    pub fn edges(&self) -> HashSet<EdgeIndex> {
+        self.items.iter().filter_map(|item| item.edge).collect()
+    }
+
+    /// Affected tables other than the changed one
+    pub fn tables(&self) -> BTreeSet<String> {
+        let own_table = self.target.split('.').next().unwrap_or_default();
+        self.items
+            .iter()
+            .map(|item| item.table.clone())
+            .filter(|table| table != own_table)
+            .collect()
+    }
+
+    /// Affected columns as (table, column), without the changed column itself
+    pub fn columns(&self) -> BTreeSet<(String, String)> {
+        self.items
+            .iter()
+            .filter_map(|item| Some((item.table.clone(), item.column.clone()?)))
+            .filter(|(table, column)| format!("{}.{}", table, column) != self.target)
+            .collect()
+    }
+
+    /// One-line summary for confirmations
+    pub fn summary(&self) -> String {
+        if self.items.is_empty() {
+            return format!("Nothing depends on {}", self.target);
+        }
+        let count = |kind: ImpactKind| self.items.iter().filter(|i| i.kind == kind).count();
+        let parts: Vec<String> = [
+            (ImpactKind::Relationship, "relationship"),
+            (ImpactKind::OrphanedKey, "orphaned key"),
+            (ImpactKind::DependentKey, "dependent key"),
+            (ImpactKind::Index, "index"),
+            (ImpactKind::JunctionTable, "junction table"),
+        ]
+        .into_iter()
+        .filter_map(|(kind, noun)| match count(kind) {
+            0 => None,
+            1 => Some(format!("1 {}", noun)),
+            n if noun == "index" => Some(format!("{} indexes", n)),
+            n => Some(format!("{} {}s", n, noun)),
+        })
+        .collect();
+        format!("Dropping {} affects {}", self.target, parts.join(", "))
+    }
+}
+
+/// Analyze what depends on a table or column
+pub fn analyze_impact(graph: &SchemaGraph, target: &ImpactTarget) -> Result<ImpactReport, String> {
+    let node = match target {
+        ImpactTarget::Table(node) | ImpactTarget::Column(node, _) => *node,
+    };
+    let table = graph
+        .node_weight(node)
+        .ok_or_else(|| format!("Table with index {} not found", node.index()))?;
+
+    let mut analyzer = Analyzer {
+        graph,
+        dropped_table: None,
+        items: Vec::new(),
+        seen_edges: HashSet::new(),
+        seen_keys: HashSet::new(),
+        queue: VecDeque::new(),
+    };
+
+    let target_name = match target {
+        ImpactTarget::Table(_) => {
+            analyzer.dropped_table = Some(node);
+            for column in &table.columns {
+                analyzer.queue.push_back((node, column.name.clone(), 1));
+            }
+            table.name.clone()
+        }
+        ImpactTarget::Column(_, column_name) => {
+            let column = table
+                .find_column(column_name)
+                .map(|(_, c)| c)
+                .ok_or_else(|| {
+                    format!(
+                        "Column '{}' not found in table '{}'",
+                        column_name, table.name
+                    )
+                })?;
+            analyzer.column_indexes(node, &table.name, column);
+            analyzer.queue.push_back((node, column.name.clone(), 1));
+            format!("{}.{}", table.name, column.name)
+        }
+    };
+
+    analyzer.run();
+
+    let mut items = analyzer.items;
+    items.sort_by(|a, b| a.depth.cmp(&b.depth).then(a.kind.cmp(&b.kind)));
+    Ok(ImpactReport {
+        target: target_name,
+        items,
+    })
+}
+
+struct Analyzer<'a> {
+    graph: &'a SchemaGraph,
+    /// Table being dropped: its own columns are not reported as orphaned
+    dropped_table: Option<NodeIndex>,
+    items: Vec<ImpactItem>,
+    seen_edges: HashSet<EdgeIndex>,
+    seen_keys: HashSet<(NodeIndex, String)>,
+    /// (table, column, depth) whose dependents are still to be walked
+    queue: VecDeque<(NodeIndex, String, usize)>,
+}
+
+impl Analyzer<'_> {
+    fn table_name(&self, node: NodeIndex) -> String {
+        self.graph
+            .node_weight(node)
+            .map(|t| t.name.clone())
+            .unwrap_or_default()
+    }
+
+    fn column_indexes(&mut self, node: NodeIndex, table: &str, column: &super::Column) {
+        let mut push = |description: String| {
+            self.items.push(ImpactItem {
+                kind: ImpactKind::Index,
+                depth: 1,
+                table: table.to_string(),
+                column: Some(column.name.clone()),
+                relationship: None,
+                description,
+                node,
+                edge: None,
+            });
+        };
+        if column.is_primary_key {
+            push(format!("Primary key of {} loses {}", table, column.name));
+        }
+        if column.is_unique {
+            push(format!("Unique index on {}.{}", table, column.name));
+        }
+        let is_fk = self
+            .graph
+            .find_relationships_from(node)
+            .iter()
+            .any(|(_, _, rel)| {
+                rel.relationship_type.key_on_source() && rel.from_column == column.name
+            })
+            || self
+                .graph
+                .find_relationships_to(node)
+                .iter()
+                .any(|(_, _, rel)| {
+                    !rel.relationship_type.key_on_source() && rel.to_column == column.name
+                });
+        if is_fk {
+            push(format!("Foreign key index on {}.{}", table, column.name));
+        }
+    }
+
+    fn run(&mut self) {
+        while let Some((node, column, depth)) = self.queue.pop_front() {
+            if !self.seen_keys.insert((node, column.clone())) {
+                continue;
+            }
+
+            // (edge, other table, column on this side, column on the other side, key on this side)
+            let mut uses = Vec::new();
+            for (edge, other, rel) in self.graph.find_relationships_from(node) {
+                if rel.from_column == column {
+                    uses.push((
+                        edge,
+                        other,
+                        rel.to_column.clone(),
+                        rel.name.clone(),
+                        rel.relationship_type.key_on_source(),
+                    ));
+                }
+            }
+            for (edge, other, rel) in self.graph.find_relationships_to(node) {
+                if rel.to_column == column {
+                    uses.push((
+                        edge,
+                        other,
+                        rel.from_column.clone(),
+                        rel.name.clone(),
+                        !rel.relationship_type.key_on_source(),
+                    ));
+                }
+            }
+            uses.sort_by_key(|u| u.0);
+
+            for (edge, other, other_column, rel_name, key_here) in uses {
+                if !self.seen_edges.insert(edge) {
+                    continue;
+                }
+                let table = self.table_name(node);
+                let other_table = self.table_name(other);
+
+                // Связь удаляется вместе с колонкой
+                if depth == 1 {
+                    self.items.push(ImpactItem {
+                        kind: ImpactKind::Relationship,
+                        depth,
+                        table: other_table.clone(),
+                        column: Some(other_column.clone()),
+                        relationship: Some(rel_name.clone()),
+                        description: format!(
+                            "{} ({}.{} - {}.{}) is removed",
+                            rel_name, table, column, other_table, other_column
+                        ),
+                        node: other,
+                        edge: Some(edge),
+                    });
+                }
+
+                // Ключ на другой стороне теряет ссылку (или ссылается на такой ключ)
+                if !key_here && Some(other) != self.dropped_table {
+                    let (kind, description) = if depth == 1 {
+                        (
+                            ImpactKind::OrphanedKey,
+                            format!(
+                                "{}.{} no longer references {}.{}",
+                                other_table, other_column, table, column
+                            ),
+                        )
+                    } else {
+                        (
+                            ImpactKind::DependentKey,
+                            format!(
+                                "{}.{} references {}.{}, which loses its reference",
+                                other_table, other_column, table, column
+                            ),
+                        )
+                    };
+                    self.items.push(ImpactItem {
+                        kind,
+                        depth,
+                        table: other_table.clone(),
+                        column: Some(other_column.clone()),
+                        relationship: Some(rel_name),
+                        description,
+                        node: other,
+                        edge: Some(edge),
+                    });
+                    self.queue.push_back((other, other_column, depth + 1));
+                }
+
+                if depth == 1
+                    && Some(other) != self.dropped_table
+                    && let Some(junction) = find_junction(self.graph, other)
+                    && [junction.left.edge, junction.right.edge].contains(&edge)
+                {
+                    self.items.push(ImpactItem {
+                        kind: ImpactKind::JunctionTable,
+                        depth,
+                        table: junction.name.clone(),
+                        column: None,
+                        relationship: None,
+                        description: format!(
+                            "{} no longer links {} and {}",
+                            junction.name,
+                            self.table_name(junction.left.table),
+                            self.table_name(junction.right.table)
+                        ),
+                        node: other,
+                        edge: Some(edge),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Analyze what depends on a group of tables dropped together
+///
+/// Objects inside the group (relationships between the dropped tables, their
+/// own keys) go away with it and are not reported.
+pub fn analyze_tables_impact(
+    graph: &SchemaGraph,
+    tables: &[NodeIndex],
+) -> Result<ImpactReport, String> {
+    let dropped: HashSet<NodeIndex> = tables.iter().copied().collect();
+    let inside = |edge: EdgeIndex| {
+        graph
+            .edge_endpoints(edge)
+            .is_some_and(|(from, to)| dropped.contains(&from) && dropped.contains(&to))
+    };
+
+    let mut names = Vec::new();
+    let mut items: Vec<ImpactItem> = Vec::new();
+    for &node in tables {
+        let report = analyze_impact(graph, &ImpactTarget::Table(node))?;
+        names.push(report.target);
+        items.extend(
+            report
+                .items
+                .into_iter()
+                .filter(|item| !dropped.contains(&item.node) && !item.edge.is_some_and(inside)),
+        );
+    }
+    items.sort_by(|a, b| a.depth.cmp(&b.depth).then(a.kind.cmp(&b.kind)));
+    Ok(ImpactReport {
+        target: names.join(", "),
+        items,
+    })
+}
+
+/// Delete a column together with the relationships that use it
+///
+/// Returns the operations for LiveShare sync (relationships first, then the column).
+pub fn delete_column_with_dependents(
+    graph: &mut SchemaGraph,
+    node: NodeIndex,
+    column_index: usize,
+) -> Result<Vec<GraphOperation>, String> {
+    let table = graph
+        .node_weight(node)
+        .ok_or_else(|| format!("Table with index {} not found", node.index()))?;
+    let table_uuid = table.uuid;
+    let column_name = table
+        .columns
+        .get(column_index)
+        .map(|c| c.name.clone())
+        .ok_or_else(|| format!("Column index {} out of bounds", column_index))?;
+
+    let mut edges: Vec<EdgeIndex> = graph
+        .find_relationships_from(node)
+        .into_iter()
+        .filter(|(_, _, rel)| rel.from_column == column_name)
+        .map(|(edge, _, _)| edge)
+        .chain(
+            graph
+                .find_relationships_to(node)
+                .into_iter()
+                .filter(|(_, _, rel)| rel.to_column == column_name)
+                .map(|(edge, _, _)| edge),
+        )
+        .collect();
+    edges.sort();
+    edges.dedup();
+
+    let mut ops = Vec::new();
+    for edge in edges {
+        graph.delete_relationship(edge)?;
+        ops.push(GraphOperation::DeleteRelationship {
+            edge_id: edge.index() as u32,
+        });
+    }
+
+    if let Some(table) = graph.node_weight_mut(node) {
+        table.delete_column(column_index)?;
+    }
+    ops.push(GraphOperation::DeleteColumn {
+        node_id: node.index() as u32,
+        table_uuid,
+        column_index,
+    });
+    Ok(ops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Column, Relationship, RelationshipType, TableNode};
+
+    /// customers <- orders <- order_items (order_id is part of the PK) <- shipments
+    fn graph() -> (SchemaGraph, Vec<NodeIndex>) {
+        let mut graph = SchemaGraph::new();
+        let customers = graph.add_node(
+            TableNode::new("customers")
+                .add_column(Column::new("id", "INT").primary_key())
+                .add_column(Column::new("email", "VARCHAR(255)").unique()),
+        );
+        let orders = graph.add_node(
+            TableNode::new("orders")
+                .add_column(Column::new("id", "INT").primary_key())
+                .add_column(Column::new("customer_id", "INT")),
+        );
+        let items = graph.add_node(
+            TableNode::new("order_items")
+                .add_column(Column::new("order_id", "INT").primary_key())
+                .add_column(Column::new("line", "INT").primary_key()),
+        );
+        let shipments = graph.add_node(
+            TableNode::new("shipments")
+                .add_column(Column::new("id", "INT").primary_key())
+                .add_column(Column::new("order_id", "INT")),
+        );
+        let fk = |name: &str, col: &str, to: &str| {
+            Relationship::new(name, RelationshipType::ManyToOne, col, to)
+        };
+        graph.add_edge(
+            orders,
+            customers,
+            fk("fk_orders_customer", "customer_id", "id"),
+        );
+        graph.add_edge(items, orders, fk("fk_items_order", "order_id", "id"));
+        graph.add_edge(
+            shipments,
+            items,
+            fk("fk_shipments_item", "order_id", "order_id"),
+        );
+        (graph, vec![customers, orders, items, shipments])
+    }
+
+    #[test]
+    fn test_column_impact_is_transitive() {
+        let (graph, n) = graph();
+        let report = analyze_impact(&graph, &ImpactTarget::Column(n[1], "id".into())).unwrap();
+
+        assert_eq!(report.target, "orders.id");
+        let keys: Vec<(ImpactKind, usize, &str)> = report
+            .items
+            .iter()
+            .filter(|i| matches!(i.kind, ImpactKind::OrphanedKey | ImpactKind::DependentKey))
+            .map(|i| (i.kind, i.depth, i.table.as_str()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (ImpactKind::OrphanedKey, 1, "order_items"),
+                (ImpactKind::DependentKey, 2, "shipments")
+            ]
+        );
+        assert!(report.items.iter().any(|i| i.kind == ImpactKind::Index));
+        assert_eq!(report.edges().len(), 2);
+        // Связь orders -> customers не зависит от orders.id
+        assert!(!report.tables().contains("customers"));
+        let columns = report.columns();
+        assert!(columns.contains(&("order_items".to_string(), "order_id".to_string())));
+        assert!(columns.contains(&("shipments".to_string(), "order_id".to_string())));
+        assert!(!columns.contains(&("orders".to_string(), "id".to_string())));
+    }
+
+    #[test]
+    fn test_table_and_unreferenced_column_impact() {
+        let (graph, n) = graph();
+        let report = analyze_impact(&graph, &ImpactTarget::Table(n[1])).unwrap();
+        assert_eq!(report.edges().len(), 3);
+        assert!(report.tables().contains("customers"));
+        assert!(report.summary().starts_with(
+            "Dropping orders affects 2 relationships, 1 orphaned key, 1 dependent key"
+        ));
+
+        let report = analyze_impact(&graph, &ImpactTarget::Column(n[0], "email".into())).unwrap();
+        assert_eq!(report.items.len(), 1);
+        assert_eq!(report.items[0].kind, ImpactKind::Index);
+
+        assert!(analyze_impact(&graph, &ImpactTarget::Column(n[0], "nope".into())).is_err());
+    }
+
+    #[test]
+    fn test_tables_impact_skips_dropped_group() {
+        let (graph, n) = graph();
+        let report = analyze_tables_impact(&graph, &[n[0], n[1]]).unwrap();
+
+        assert_eq!(report.target, "customers, orders");
+        assert!(!report.tables().contains("customers"));
+        // Остаются только зависимости вне группы: order_items и дальше shipments
+        assert!(
+            report
+                .items
+                .iter()
+                .all(|i| i.node == n[2] || i.node == n[3])
+        );
+        assert_eq!(report.edges().len(), 2);
+    }
+
+    #[test]
+    fn test_delete_column_with_dependents() {
+        let (mut graph, n) = graph();
+        let ops = delete_column_with_dependents(&mut graph, n[1], 0).unwrap();
+
+        assert_eq!(ops.len(), 2);
+        assert!(matches!(ops[0], GraphOperation::DeleteRelationship { .. }));
+        assert!(matches!(
+            ops[1],
+            GraphOperation::DeleteColumn {
+                column_index: 0,
+                ..
+            }
+        ));
+        assert_eq!(graph.edge_count(), 2);
+        assert_eq!(graph[n[1]].columns.len(), 1);
+    }
+}
@@ -0,0 +1,473 @@
+//! Join path finder
+//!
+//! Finds the shortest chain of relationships connecting two or more tables and
+//! scaffolds a SELECT with the matching JOIN ... ON clauses.
+//!
+//! Relationships are walked in both directions. For more than two tables the
+//! path is a tree: each remaining table is attached to the already joined ones
+//! through its nearest connection. Alternatives are found by excluding one
+//! relationship of the best path at a time.
+//!
+//! N:M relationships have no key column to join on and are skipped; materialize
+//! them as junction tables to join through them.
+
+use super::validation::is_reserved_in;
+use super::{RelationshipType, SchemaGraph, SqlDialect, TableOps};
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::{EdgeRef, IntoEdges, UndirectedAdaptor};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Default number of paths returned (best path included)
+pub const DEFAULT_MAX_JOIN_PATHS: usize = 3;
+
+/// One JOIN in a path
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct JoinStep {
+    #[serde(skip)]
+    pub edge: EdgeIndex,
+    /// Table added by this join
+    #[serde(skip)]
+    pub joined: NodeIndex,
+    pub joined_table: String,
+    /// Relationship name
+    pub relationship: String,
+    /// Source table and column of the relationship
+    pub from_table: String,
+    pub from_column: String,
+    /// Target table and column of the relationship
+    pub to_table: String,
+    pub to_column: String,
+    /// Whether the join should keep rows without a match (nullable key)
+    pub optional: bool,
+}
+
+/// Tables connected by a chain of relationships, in join order
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct JoinPath {
+    #[serde(skip)]
+    pub root: NodeIndex,
+    pub root_table: String,
+    pub steps: Vec<JoinStep>,
+}
+
+impl JoinPath {
+    /// Number of joins
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Relationships used by the path (for highlighting on the canvas)
+    pub fn edges(&self) -> Vec<EdgeIndex> {
+        self.steps.iter().map(|s| s.edge).collect()
+    }
+
+    /// Tables in join order
+    pub fn tables(&self) -> Vec<NodeIndex> {
+        std::iter::once(self.root)
+            .chain(self.steps.iter().map(|s| s.joined))
+            .collect()
+    }
+}
+
+/// Find the shortest join path connecting `tables`, plus up to
+/// `max_paths - 1` alternatives ordered by length
+pub fn find_join_paths(
+    graph: &SchemaGraph,
+    tables: &[NodeIndex],
+    max_paths: usize,
+) -> Result<Vec<JoinPath>, String> {
+    let mut required: Vec<NodeIndex> = Vec::new();
+    for &node in tables {
+        if graph.node_weight(node).is_none() {
+            return Err(format!("Table with index {} not found", node.index()));
+        }
+        if !required.contains(&node) {
+            required.push(node);
+        }
+    }
+    if required.len() < 2 {
+        return Err("Select at least two different tables".to_string());
+    }
+
+    let best = connect(graph, &required, &HashSet::new()).ok_or_else(|| {
+        let names: Vec<&str> = required
+            .iter()
+            .filter_map(|&n| graph.node_weight(n).map(|t| t.name.as_str()))
+            .collect();
+        format!("No relationship path connects {}", names.join(", "))
+    })?;
+
+    let mut seen: HashSet<Vec<EdgeIndex>> = HashSet::new();
+    seen.insert(sorted_edges(&best));
+    let mut alternatives = Vec::new();
+    for step in &best {
+        let banned = HashSet::from([step.0]);
+        if let Some(path) = connect(graph, &required, &banned)
+            && seen.insert(sorted_edges(&path))
+        {
+            alternatives.push(path);
+        }
+    }
+    alternatives.sort_by_key(|path| path.len());
+
+    let paths = std::iter::once(best)
+        .chain(alternatives)
+        .take(max_paths.max(1))
+        .map(|steps| build_path(graph, required[0], &steps))
+        .collect();
+    Ok(paths)
+}
+
+/// Find join paths between tables given by name
+pub fn find_join_paths_by_name(
+    graph: &SchemaGraph,
+    table_names: &[String],
+    max_paths: usize,
+) -> Result<Vec<JoinPath>, String> {
+    let nodes = table_names
+        .iter()
+        .map(|name| {
+            graph
+                .find_table_by_name(name)
+                .ok_or_else(|| format!("Table '{}' not found", name))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    find_join_paths(graph, &nodes, max_paths)
+}
+
+/// (edge, table reached through it)
+type RawStep = (EdgeIndex, NodeIndex);
+
+fn sorted_edges(steps: &[RawStep]) -> Vec<EdgeIndex> {
+    let mut edges: Vec<EdgeIndex> = steps.iter().map(|s| s.0).collect();
+    edges.sort();
+    edges
+}
+
+/// Connect all required tables starting from the first one.
+///
+/// Each iteration runs a BFS from every table joined so far and attaches the
+/// nearest required table that is still missing.
+fn connect(
+    graph: &SchemaGraph,
+    required: &[NodeIndex],
+    banned: &HashSet<EdgeIndex>,
+) -> Option<Vec<RawStep>> {
+    let mut joined: Vec<NodeIndex> = vec![required[0]];
+    let mut steps: Vec<RawStep> = Vec::new();
+
+    loop {
+        let remaining: HashSet<NodeIndex> = required
+            .iter()
+            .copied()
+            .filter(|n| !joined.contains(n))
+            .collect();
+        if remaining.is_empty() {
+            break;
+        }
+        let (reached, parents) = bfs(graph, &joined, &remaining, banned)?;
+
+        // Восстанавливаем путь от найденной таблицы до уже присоединённых
+        let mut chain = Vec::new();
+        let mut current = reached;
+        while let Some(&(edge, prev)) = parents.get(&current) {
+            chain.push((edge, current));
+            current = prev;
+        }
+        chain.reverse();
+        for step in chain {
+            joined.push(step.1);
+            steps.push(step);
+        }
+    }
+
+    Some(steps)
+}
+
+/// Multi-source BFS over relationships in both directions.
+///
+/// Returns the first reached target and the parent map (node -> (edge, parent)).
+#[allow(clippy::type_complexity)]
+fn bfs(
+    graph: &SchemaGraph,
+    sources: &[NodeIndex],
+    targets: &HashSet<NodeIndex>,
+    banned: &HashSet<EdgeIndex>,
+) -> Option<(NodeIndex, HashMap<NodeIndex, (EdgeIndex, NodeIndex)>)> {
+    let undirected = UndirectedAdaptor(graph);
+    let mut visited: HashSet<NodeIndex> = sources.iter().copied().collect();
+    let mut parents: HashMap<NodeIndex, (EdgeIndex, NodeIndex)> = HashMap::new();
+    let mut queue: VecDeque<NodeIndex> = sources.iter().copied().collect();
+
+    while let Some(node) = queue.pop_front() {
+        // Стабильный порядок обхода - по индексу ребра
+        let mut edges: Vec<(EdgeIndex, NodeIndex)> = undirected
+            .edges(node)
+            .filter(|e| !banned.contains(&e.id()))
+            .filter(|e| e.weight().relationship_type != RelationshipType::ManyToMany)
+            .map(|e| (e.id(), e.target()))
+            .collect();
+        edges.sort();
+
+        for (edge, next) in edges {
+            if !visited.insert(next) {
+                continue;
+            }
+            parents.insert(next, (edge, node));
+            if targets.contains(&next) {
+                return Some((next, parents));
+            }
+            queue.push_back(next);
+        }
+    }
+
+    None
+}
+
+fn build_path(graph: &SchemaGraph, root: NodeIndex, steps: &[RawStep]) -> JoinPath {
+    let table_name = |idx: NodeIndex| {
+        graph
+            .node_weight(idx)
+            .map(|t| t.name.clone())
+            .unwrap_or_default()
+    };
+
+    let steps = steps
+        .iter()
+        .filter_map(|&(edge, joined)| {
+            let (source, target) = graph.edge_endpoints(edge)?;
+            let rel = graph.edge_weight(edge)?;
+
+            // Связь необязательна, если присоединяем ссылаемую таблицу через nullable ключ
+            let (key_node, key_column) = if rel.relationship_type.key_on_source() {
+                (source, &rel.from_column)
+            } else {
+                (target, &rel.to_column)
+            };
+            let key_nullable = graph
+                .node_weight(key_node)
+                .and_then(|t| t.columns.iter().find(|c| &c.name == key_column))
+                .is_some_and(|c| c.is_nullable);
+            let optional = key_nullable && joined != key_node;
+
+            Some(JoinStep {
+                edge,
+                joined,
+                joined_table: table_name(joined),
+                relationship: rel.name.clone(),
+                from_table: table_name(source),
+                from_column: rel.from_column.clone(),
+                to_table: table_name(target),
+                to_column: rel.to_column.clone(),
+                optional,
+            })
+        })
+        .collect();
+
+    JoinPath {
+        root,
+        root_table: table_name(root),
+        steps,
+    }
+}
+
+/// Quote an identifier only when the dialect requires it
+fn quote_if_needed(name: &str, dialect: &SqlDialect) -> String {
+    let plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if plain && !is_reserved_in(name, dialect) {
+        name.to_string()
+    } else {
+        dialect.quote_identifier(name)
+    }
+}
+
+/// Short unique alias for each table: initials of the snake_case parts
+///
+/// Initials that form a reserved word (`order_notes` -> `on`) get a counter suffix.
+fn table_aliases(tables: &[String], dialect: &SqlDialect) -> HashMap<String, String> {
+    let mut aliases: HashMap<String, String> = HashMap::new();
+    let mut used: HashSet<String> = HashSet::new();
+
+    for table in tables {
+        if aliases.contains_key(table) {
+            continue;
+        }
+        let base: String = table
+            .split(['_', '.', ' '])
+            .filter_map(|part| part.chars().next())
+            .map(|c| c.to_ascii_lowercase())
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+        let base = if base.is_empty() || base.starts_with(|c: char| c.is_ascii_digit()) {
+            "t".to_string()
+        } else {
+            base
+        };
+
+        let mut alias = base.clone();
+        let mut counter = 2;
+        while used.contains(&alias) || is_reserved_in(&alias, dialect) {
+            alias = format!("{}{}", base, counter);
+            counter += 1;
+        }
+        used.insert(alias.clone());
+        aliases.insert(table.clone(), alias);
+    }
+
+    aliases
+}
+
+/// Generate a SELECT joining every table of the path
+pub fn join_query_sql(path: &JoinPath, dialect: &SqlDialect) -> String {
+    let tables: Vec<String> = std::iter::once(path.root_table.clone())
+        .chain(path.steps.iter().map(|s| s.joined_table.clone()))
+        .collect();
+    let aliases = table_aliases(&tables, dialect);
+    let alias = |table: &str| aliases.get(table).cloned().unwrap_or_default();
+    let column = |table: &str, column: &str| {
+        format!("{}.{}", alias(table), quote_if_needed(column, dialect))
+    };
+
+    let mut sql = String::from("SELECT\n");
+    let select: Vec<String> = tables
+        .iter()
+        .map(|t| format!("    {}.*", alias(t)))
+        .collect();
+    sql.push_str(&select.join(",\n"));
+    sql.push_str(&format!(
+        "\nFROM {} AS {}",
+        quote_if_needed(&path.root_table, dialect),
+        alias(&path.root_table)
+    ));
+
+    for step in &path.steps {
+        // Колонка присоединяемой таблицы идёт первой в условии
+        let (left, right) = if step.joined_table == step.to_table {
+            (
+                column(&step.to_table, &step.to_column),
+                column(&step.from_table, &step.from_column),
+            )
+        } else {
+            (
+                column(&step.from_table, &step.from_column),
+                column(&step.to_table, &step.to_column),
+            )
+        };
+        sql.push_str(&format!(
+            "\n{} {} AS {} ON {} = {}",
+            if step.optional {
+                "LEFT JOIN"
+            } else {
+                "INNER JOIN"
+            },
+            quote_if_needed(&step.joined_table, dialect),
+            alias(&step.joined_table),
+            left,
+            right
+        ));
+    }
+    sql.push(';');
+    sql
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Column, Relationship, TableNode};
+
+    /// customers <- orders <- order_items -> products, plus customers <- reviews -> products
+    fn shop_graph() -> (SchemaGraph, Vec<NodeIndex>) {
+        let mut graph = SchemaGraph::new();
+        let table =
+            |name: &str| TableNode::new(name).add_column(Column::new("id", "INT").primary_key());
+        let customers = graph.add_node(table("customers"));
+        let orders = graph.add_node(table("orders").add_column(Column::new("customer_id", "INT")));
+        let items = graph.add_node(
+            table("order_items")
+                .add_column(Column::new("order_id", "INT").not_null())
+                .add_column(Column::new("product_id", "INT").not_null()),
+        );
+        let products = graph.add_node(table("products"));
+        let reviews = graph.add_node(
+            table("reviews")
+                .add_column(Column::new("customer_id", "INT").not_null())
+                .add_column(Column::new("product_id", "INT").not_null()),
+        );
+
+        let fk =
+            |name: &str, col: &str| Relationship::new(name, RelationshipType::ManyToOne, col, "id");
+        graph.add_edge(orders, customers, fk("fk_orders_customer", "customer_id"));
+        graph.add_edge(items, orders, fk("fk_items_order", "order_id"));
+        graph.add_edge(items, products, fk("fk_items_product", "product_id"));
+        graph.add_edge(reviews, customers, fk("fk_reviews_customer", "customer_id"));
+        graph.add_edge(reviews, products, fk("fk_reviews_product", "product_id"));
+
+        (graph, vec![customers, orders, items, products, reviews])
+    }
+
+    #[test]
+    fn test_shortest_path_and_alternatives() {
+        let (graph, n) = shop_graph();
+        let paths = find_join_paths(&graph, &[n[0], n[3]], 3).unwrap();
+
+        // customers -> reviews -> products (2 joins) beats the path via orders (3 joins)
+        assert_eq!(paths[0].len(), 2);
+        assert_eq!(paths[0].tables(), vec![n[0], n[4], n[3]]);
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[1].tables(), vec![n[0], n[1], n[2], n[3]]);
+    }
+
+    #[test]
+    fn test_join_sql() {
+        let (graph, n) = shop_graph();
+        let paths = find_join_paths(&graph, &[n[2], n[0]], 1).unwrap();
+        let sql = join_query_sql(&paths[0], &SqlDialect::PostgreSQL);
+
+        assert_eq!(
+            sql,
+            "SELECT\n    oi.*,\n    o.*,\n    c.*\nFROM order_items AS oi\n\
+             INNER JOIN orders AS o ON o.id = oi.order_id\n\
+             LEFT JOIN customers AS c ON c.id = o.customer_id;"
+        );
+    }
+
+    #[test]
+    fn test_aliases_skip_reserved_words() {
+        let tables = [
+            "order_notes".to_string(),
+            "account_settings".to_string(),
+            "offices".to_string(),
+        ];
+        for dialect in SqlDialect::ALL {
+            let aliases = table_aliases(&tables, &dialect);
+            assert_eq!(aliases["order_notes"], "on2");
+            assert_eq!(aliases["account_settings"], "as2");
+            assert_eq!(aliases["offices"], "o");
+        }
+    }
+
+    #[test]
+    fn test_multiple_tables_and_errors() {
+        let (mut graph, n) = shop_graph();
+        let paths = find_join_paths(&graph, &[n[1], n[3], n[4]], 1).unwrap();
+        let tables = paths[0].tables();
+        assert!(tables.contains(&n[1]) && tables.contains(&n[3]) && tables.contains(&n[4]));
+        assert_eq!(paths[0].len(), tables.len() - 1);
+
+        let lonely = graph.add_node(TableNode::new("audit_log"));
+        assert!(find_join_paths(&graph, &[n[0], lonely], 3).is_err());
+        assert!(find_join_paths(&graph, &[n[0], n[0]], 3).is_err());
+        assert!(find_join_paths_by_name(&graph, &["customers".into(), "nope".into()], 3).is_err());
+    }
+}
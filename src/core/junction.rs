@@ -0,0 +1,583 @@
+//! Junction tables for many-to-many relationships
+//!
+//! An N:M edge is a logical relationship: SQL needs a junction table with a composite
+//! primary key and a foreign key to each side. This module converts between the two forms:
+//! - `materialize_junction` replaces an N:M edge with a junction table and two N:1 edges
+//! - `find_junction_tables` / `collapse_junction` detect pure junction tables and fold them
+//!   back into an N:M edge
+//!
+//! SQL export writes N:M edges as junction tables marked with [`MANY_TO_MANY_MARKER`];
+//! `collapse_junction_tables` folds only those back when the SQL is applied. Junction
+//! tables created by the user stay tables until collapsed explicitly.
+//!
+//! Both conversions return the `GraphOperation`s to broadcast over LiveShare.
+
+use super::lint::singularize;
+use super::liveshare::{ColumnData, GraphOperation};
+use super::validation::validate_table_name;
+use super::{
+    Column, Relationship, RelationshipOps, RelationshipType, SchemaGraph, TableNode, TableOps,
+};
+use petgraph::Direction;
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::EdgeRef;
+
+/// One side of a junction table: a key column and the table it references
+#[derive(Clone, Debug, PartialEq)]
+pub struct JunctionSide {
+    /// Referenced table
+    pub table: NodeIndex,
+    /// Key column in the junction table
+    pub column: String,
+    /// Referenced column in `table`
+    pub referenced_column: String,
+    /// Edge carrying the foreign key
+    pub edge: EdgeIndex,
+}
+
+/// Table whose only purpose is to link two other tables
+#[derive(Clone, Debug, PartialEq)]
+pub struct JunctionTable {
+    pub node: NodeIndex,
+    pub name: String,
+    /// Side of the first key column
+    pub left: JunctionSide,
+    /// Side of the second key column
+    pub right: JunctionSide,
+}
+
+/// Detect a pure junction table: exactly two columns forming the primary key,
+/// each of them an N:1 key to another table, and no other relationships
+pub fn find_junction(graph: &SchemaGraph, node: NodeIndex) -> Option<JunctionTable> {
+    let table = graph.node_weight(node)?;
+    if table.columns.len() != 2 || !table.columns.iter().all(|c| c.is_primary_key) {
+        return None;
+    }
+
+    let mut sides = Vec::new();
+    for edge in graph.edges_directed(node, Direction::Outgoing) {
+        let rel = edge.weight();
+        if rel.relationship_type != RelationshipType::ManyToOne || edge.target() == node {
+            return None;
+        }
+        sides.push(JunctionSide {
+            table: edge.target(),
+            column: rel.from_column.clone(),
+            referenced_column: rel.to_column.clone(),
+            edge: edge.id(),
+        });
+    }
+    // 1:N drawn from the referenced table keeps the key on the junction side
+    for edge in graph.edges_directed(node, Direction::Incoming) {
+        let rel = edge.weight();
+        if rel.relationship_type != RelationshipType::OneToMany || edge.source() == node {
+            return None;
+        }
+        sides.push(JunctionSide {
+            table: edge.source(),
+            column: rel.to_column.clone(),
+            referenced_column: rel.from_column.clone(),
+            edge: edge.id(),
+        });
+    }
+
+    let position = |side: &JunctionSide| table.find_column(&side.column).map(|(i, _)| i);
+    let [mut left, mut right]: [JunctionSide; 2] = sides.try_into().ok()?;
+    let (Some(left_pos), Some(right_pos)) = (position(&left), position(&right)) else {
+        return None;
+    };
+    if left_pos == right_pos {
+        return None;
+    }
+    if left_pos > right_pos {
+        std::mem::swap(&mut left, &mut right);
+    }
+
+    Some(JunctionTable {
+        node,
+        name: table.name.clone(),
+        left,
+        right,
+    })
+}
+
+/// All pure junction tables of the graph
+pub fn find_junction_tables(graph: &SchemaGraph) -> Vec<JunctionTable> {
+    graph
+        .node_indices()
+        .filter_map(|node| find_junction(graph, node))
+        .collect()
+}
+
+/// Replace an N:M edge with a junction table placed between the two tables
+///
+/// The junction is named after the relationship (or `<from>_<to>` for generated
+/// `fk_...` names) and gets a `<singular table>_<column>` key per side.
+/// Returns the junction node and the operations to sync.
+pub fn materialize_junction(
+    graph: &mut SchemaGraph,
+    edge: EdgeIndex,
+) -> Result<(NodeIndex, Vec<GraphOperation>), String> {
+    let (from, to) = graph.edge_endpoints(edge).ok_or("Relationship not found")?;
+    let rel = graph
+        .edge_weight(edge)
+        .ok_or("Relationship not found")?
+        .clone();
+    if rel.relationship_type != RelationshipType::ManyToMany {
+        return Err(format!(
+            "Relationship '{}' is {}, only N:M relationships need a junction table",
+            rel.name, rel.relationship_type
+        ));
+    }
+
+    let (from_table, to_table) = match (graph.node_weight(from), graph.node_weight(to)) {
+        (Some(from_table), Some(to_table)) => (from_table, to_table),
+        _ => return Err("Table not found".to_string()),
+    };
+    let referenced = |table: &TableNode, column: &str| {
+        table
+            .find_column(column)
+            .map(|(_, c)| c.clone())
+            .ok_or_else(|| format!("Column '{}' not found in table '{}'", column, table.name))
+    };
+    let from_column = referenced(from_table, &rel.from_column)?;
+    let to_column = referenced(to_table, &rel.to_column)?;
+
+    let base_name = if rel.name.starts_with("fk_") || validate_table_name(&rel.name).is_err() {
+        format!("{}_{}", from_table.name, to_table.name)
+    } else {
+        rel.name.clone()
+    };
+    let name = graph.generate_unique_table_name(&base_name);
+
+    let left_key = key_column_name(&from_table.name, &from_column.name);
+    let mut right_key = key_column_name(&to_table.name, &to_column.name);
+    if right_key == left_key {
+        // Self-referencing N:M (e.g. users <-> users)
+        right_key = format!("related_{}", right_key);
+    }
+
+    let position = (
+        (from_table.position.0 + to_table.position.0) / 2.0,
+        (from_table.position.1 + to_table.position.1) / 2.0,
+    );
+    let key_columns = [
+        key_column(&left_key, &from_column),
+        key_column(&right_key, &to_column),
+    ];
+    for column in &key_columns {
+        Column::validate_name(&column.name)?;
+    }
+
+    // Таблица создаётся через проверяемый путь (имя, уникальность)
+    let node = graph.create_table(name.clone(), position)?;
+    let junction = &mut graph[node];
+    junction.columns.extend(key_columns);
+    let table_uuid = junction.uuid;
+    let columns: Vec<ColumnData> = junction.columns.iter().map(ColumnData::from).collect();
+    graph.remove_edge(edge);
+
+    let mut graph_ops = vec![
+        GraphOperation::DeleteRelationship {
+            edge_id: edge.index() as u32,
+        },
+        GraphOperation::CreateTable {
+            node_id: node.index() as u32,
+            table_uuid,
+            name: name.clone(),
+            position,
+        },
+    ];
+    graph_ops.extend(columns.into_iter().map(|column| GraphOperation::AddColumn {
+        node_id: node.index() as u32,
+        table_uuid,
+        column,
+    }));
+
+    for (target, key, referenced_column) in [
+        (from, &left_key, &from_column.name),
+        (to, &right_key, &to_column.name),
+    ] {
+        let relationship = Relationship::new(
+            format!("fk_{}_{}", name, key),
+            RelationshipType::ManyToOne,
+            key.clone(),
+            referenced_column.clone(),
+        );
+        let edge = graph.create_relationship(node, target, relationship.clone())?;
+        graph_ops.push(create_relationship_op(edge, node, target, &relationship));
+    }
+
+    Ok((node, graph_ops))
+}
+
+/// Replace a pure junction table with an N:M edge named after it
+///
+/// Returns the new edge and the operations to sync.
+pub fn collapse_junction(
+    graph: &mut SchemaGraph,
+    node: NodeIndex,
+) -> Result<(EdgeIndex, Vec<GraphOperation>), String> {
+    let junction = find_junction(graph, node).ok_or_else(|| {
+        let name = graph
+            .node_weight(node)
+            .map(|t| t.name.as_str())
+            .unwrap_or_default();
+        format!("Table '{}' is not a pure junction table", name)
+    })?;
+    let JunctionTable {
+        name, left, right, ..
+    } = junction;
+
+    if graph
+        .find_relationship_by_columns(
+            left.table,
+            right.table,
+            &left.referenced_column,
+            &right.referenced_column,
+        )
+        .is_some()
+    {
+        return Err("Relationship already exists".to_string());
+    }
+
+    let table_uuid = graph.delete_table(node)?.uuid;
+    let relationship = Relationship::new(
+        name,
+        RelationshipType::ManyToMany,
+        left.referenced_column,
+        right.referenced_column,
+    );
+    let edge = graph.create_relationship(left.table, right.table, relationship.clone())?;
+
+    let graph_ops = vec![
+        GraphOperation::DeleteTable {
+            node_id: node.index() as u32,
+            table_uuid,
+        },
+        create_relationship_op(edge, left.table, right.table, &relationship),
+    ];
+    Ok((edge, graph_ops))
+}
+
+/// Comment written by SQL export above junction tables generated from N:M edges
+pub const MANY_TO_MANY_MARKER: &str = "-- Many-to-many";
+
+/// Whether `materialize_junction` on the collapsed edge recreates `junction` unchanged
+/// (same table name, key column names and types)
+fn is_canonical_junction(graph: &SchemaGraph, junction: &JunctionTable) -> bool {
+    let Some(table) = graph.node_weight(junction.node) else {
+        return false;
+    };
+    if junction.name.starts_with("fk_") || validate_table_name(&junction.name).is_err() {
+        return false;
+    }
+
+    let key_of = |side: &JunctionSide| {
+        let referenced = graph
+            .node_weight(side.table)?
+            .find_column(&side.referenced_column)?
+            .1;
+        let name = key_column_name(&graph[side.table].name, &referenced.name);
+        Some((name, referencing_data_type(referenced)))
+    };
+    let (Some(left), Some(mut right)) = (key_of(&junction.left), key_of(&junction.right)) else {
+        return false;
+    };
+    if right.0 == left.0 {
+        right.0 = format!("related_{}", right.0);
+    }
+
+    [(&junction.left, left), (&junction.right, right)]
+        .into_iter()
+        .all(|(side, (name, data_type))| {
+            side.column == name
+                && table
+                    .find_column(&side.column)
+                    .is_some_and(|(_, c)| c.data_type == data_type)
+        })
+}
+
+/// Collapse the junction tables named in `tables` back into N:M edges
+///
+/// `tables` are the tables SQL export generated from N:M edges (see
+/// `sql_parser::many_to_many_tables`). Tables that were edited since (other columns,
+/// names or types) are kept: collapsing them would lose those details.
+/// Returns the operations to sync.
+pub fn collapse_junction_tables(graph: &mut SchemaGraph, tables: &[String]) -> Vec<GraphOperation> {
+    let junctions: Vec<NodeIndex> = find_junction_tables(graph)
+        .into_iter()
+        .filter(|junction| {
+            tables
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(&junction.name))
+        })
+        .filter(|junction| is_canonical_junction(graph, junction))
+        .map(|junction| junction.node)
+        .collect();
+    junctions
+        .into_iter()
+        .filter_map(|node| collapse_junction(graph, node).ok())
+        .flat_map(|(_, ops)| ops)
+        .collect()
+}
+
+/// Copy of the graph with every N:M edge replaced by a junction table (used for SQL export)
+///
+/// Also returns the names of the generated junction tables.
+pub fn expand_many_to_many(graph: &SchemaGraph) -> (SchemaGraph, Vec<String>) {
+    let mut expanded = graph.clone();
+    let mut junctions = Vec::new();
+    let edges: Vec<EdgeIndex> = expanded
+        .edge_indices()
+        .filter(|&e| expanded[e].relationship_type == RelationshipType::ManyToMany)
+        .collect();
+    for edge in edges {
+        // Broken N:M edges (missing columns) are left as they are
+        if let Ok((node, _)) = materialize_junction(&mut expanded, edge) {
+            junctions.push(expanded[node].name.clone());
+        }
+    }
+    (expanded, junctions)
+}
+
+/// Key column name: `users.id` -> `user_id`
+pub(crate) fn key_column_name(table: &str, column: &str) -> String {
+    format!("{}_{}", singularize(table), column)
+}
+
+/// Type of a column referencing `referenced`
+pub(crate) fn referencing_data_type(referenced: &Column) -> String {
+    // Auto-increment pseudo types become plain integers in the referencing column
+    match referenced.data_type.to_uppercase().as_str() {
+        "SERIAL" => "INTEGER".to_string(),
+        "BIGSERIAL" => "BIGINT".to_string(),
+        "SMALLSERIAL" => "SMALLINT".to_string(),
+        _ => referenced.data_type.clone(),
+    }
+}
+
+/// Junction key column with the type of the referenced column
+fn key_column(name: &str, referenced: &Column) -> Column {
+    Column::new(name, referencing_data_type(referenced)).primary_key()
+}
+
+fn create_relationship_op(
+    edge: EdgeIndex,
+    from: NodeIndex,
+    to: NodeIndex,
+    relationship: &Relationship,
+) -> GraphOperation {
+    GraphOperation::CreateRelationship {
+        edge_id: edge.index() as u32,
+        from_node: from.index() as u32,
+        to_node: to.index() as u32,
+        relationship: relationship.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph_with_many_to_many() -> (SchemaGraph, NodeIndex, NodeIndex, EdgeIndex) {
+        let mut graph = SchemaGraph::new();
+        let users = graph.add_node(
+            TableNode::new("users")
+                .with_position(0.0, 0.0)
+                .add_column(Column::new("id", "SERIAL").primary_key()),
+        );
+        let roles = graph.add_node(
+            TableNode::new("roles")
+                .with_position(400.0, 200.0)
+                .add_column(Column::new("id", "INT").primary_key()),
+        );
+        let edge = graph
+            .create_relationship(
+                users,
+                roles,
+                Relationship::new("user_roles", RelationshipType::ManyToMany, "id", "id"),
+            )
+            .unwrap();
+        (graph, users, roles, edge)
+    }
+
+    #[test]
+    fn test_materialize_junction() {
+        let (mut graph, users, roles, edge) = graph_with_many_to_many();
+        let (node, ops) = materialize_junction(&mut graph, edge).unwrap();
+
+        let junction = &graph[node];
+        assert_eq!(junction.name, "user_roles");
+        assert_eq!(junction.position, (200.0, 100.0));
+        let columns: Vec<_> = junction
+            .columns
+            .iter()
+            .map(|c| (c.name.as_str(), c.data_type.as_str(), c.is_primary_key))
+            .collect();
+        assert_eq!(
+            columns,
+            vec![("user_id", "INTEGER", true), ("role_id", "INT", true)]
+        );
+
+        assert_eq!(graph.edge_count(), 2);
+        assert!(graph.contains_edge(node, users) && graph.contains_edge(node, roles));
+        assert!(matches!(ops[0], GraphOperation::DeleteRelationship { .. }));
+        assert!(matches!(ops[1], GraphOperation::CreateTable { .. }));
+        assert_eq!(ops.len(), 6);
+
+        let found = find_junction_tables(&graph);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].left.table, users);
+        assert_eq!(found[0].right.table, roles);
+    }
+
+    #[test]
+    fn test_collapse_junction_roundtrip() {
+        let (mut graph, users, roles, edge) = graph_with_many_to_many();
+        let (node, _) = materialize_junction(&mut graph, edge).unwrap();
+
+        let (edge, ops) = collapse_junction(&mut graph, node).unwrap();
+        assert_eq!(graph.node_count(), 2);
+        assert_eq!(graph.edge_endpoints(edge), Some((users, roles)));
+        assert_eq!(
+            graph[edge],
+            Relationship::new("user_roles", RelationshipType::ManyToMany, "id", "id")
+        );
+        assert!(matches!(ops[0], GraphOperation::DeleteTable { .. }));
+        assert!(matches!(
+            &ops[1],
+            GraphOperation::CreateRelationship { relationship, .. } if relationship.relationship_type == "N:M"
+        ));
+    }
+
+    /// Export to SQL and apply it to a fresh diagram, as a Source editor save does
+    fn reapply_sql(graph: &SchemaGraph) -> (SchemaGraph, Vec<GraphOperation>) {
+        use crate::core::{ExportOptions, SchemaExporter, SqlDialect, apply_sql_to_graph};
+
+        let options = ExportOptions {
+            sql_dialect: SqlDialect::PostgreSQL,
+            ..Default::default()
+        };
+        let sql = SchemaExporter::export_sql(graph, &options).unwrap();
+        let mut applied = SchemaGraph::new();
+        let result = apply_sql_to_graph(&sql, SqlDialect::PostgreSQL, &mut applied);
+        assert!(result.success, "{:?}", result.errors);
+        let tables = crate::core::many_to_many_tables(&sql);
+        let ops = collapse_junction_tables(&mut applied, &tables);
+        (applied, ops)
+    }
+
+    #[test]
+    fn test_exported_many_to_many_collapses_back() {
+        let (graph, _, _, _) = graph_with_many_to_many();
+        let (applied, ops) = reapply_sql(&graph);
+
+        assert_eq!(ops.len(), 2);
+        assert_eq!(applied.node_count(), 2);
+        let users = applied.find_table_by_name("users").unwrap();
+        let roles = applied.find_table_by_name("roles").unwrap();
+        let edge = applied.find_edge(users, roles).unwrap();
+        assert_eq!(
+            applied[edge].relationship_type,
+            RelationshipType::ManyToMany
+        );
+        assert_eq!(applied[edge].name, "user_roles");
+    }
+
+    #[test]
+    fn test_materialized_junction_survives_sql_roundtrip() {
+        let (mut graph, _, _, edge) = graph_with_many_to_many();
+        materialize_junction(&mut graph, edge).unwrap();
+
+        // The user's own junction table is not marked, so it stays a table
+        let (applied, ops) = reapply_sql(&graph);
+        assert!(ops.is_empty());
+        assert_eq!(applied.node_count(), 3);
+        assert!(applied.find_table_by_name("user_roles").is_some());
+        assert!(
+            applied
+                .edge_weights()
+                .all(|rel| rel.relationship_type != RelationshipType::ManyToMany)
+        );
+    }
+
+    #[test]
+    fn test_collapse_junction_tables_keeps_custom_junctions() {
+        let (mut graph, users, roles, edge) = graph_with_many_to_many();
+        materialize_junction(&mut graph, edge).unwrap();
+        let marked = vec!["user_roles".to_string()];
+
+        // Unmarked junction tables stay tables
+        assert!(collapse_junction_tables(&mut graph, &[]).is_empty());
+
+        // Marked junction tables fold back into the N:M edge
+        let ops = collapse_junction_tables(&mut graph, &marked);
+        assert_eq!(ops.len(), 2);
+        assert_eq!(graph.node_count(), 2);
+        let edge = graph.find_edge(users, roles).unwrap();
+        assert_eq!(graph[edge].relationship_type, RelationshipType::ManyToMany);
+
+        // A renamed key column would be lost: the table stays
+        let (node, _) = materialize_junction(&mut graph, edge).unwrap();
+        graph[node].columns[0].name = "member_id".to_string();
+        for edge in graph
+            .edges_directed(node, Direction::Outgoing)
+            .map(|e| e.id())
+            .collect::<Vec<_>>()
+        {
+            if graph[edge].from_column == "user_id" {
+                graph[edge].from_column = "member_id".to_string();
+            }
+        }
+        assert!(find_junction(&graph, node).is_some());
+        assert!(collapse_junction_tables(&mut graph, &marked).is_empty());
+    }
+
+    #[test]
+    fn test_materialize_uses_unique_table_name() {
+        let (mut graph, _, _, edge) = graph_with_many_to_many();
+        graph.create_table("user_roles", (0.0, 0.0)).unwrap();
+        let (node, _) = materialize_junction(&mut graph, edge).unwrap();
+        assert_eq!(graph[node].name, "user_roles_2");
+    }
+
+    #[test]
+    fn test_impure_tables_are_not_junctions() {
+        let (mut graph, _, _, edge) = graph_with_many_to_many();
+        let (node, _) = materialize_junction(&mut graph, edge).unwrap();
+
+        graph[node]
+            .columns
+            .push(Column::new("granted_at", "TIMESTAMP"));
+        assert!(find_junction(&graph, node).is_none());
+        assert!(collapse_junction(&mut graph, node).is_err());
+
+        let demo = crate::core::create_demo_graph();
+        assert!(find_junction_tables(&demo).is_empty());
+    }
+
+    #[test]
+    fn test_self_referencing_and_non_many_to_many() {
+        let mut graph = SchemaGraph::new();
+        let users = graph
+            .add_node(TableNode::new("users").add_column(Column::new("id", "INT").primary_key()));
+        let edge = graph
+            .create_relationship(
+                users,
+                users,
+                Relationship::new("fk_friends", RelationshipType::ManyToMany, "id", "id"),
+            )
+            .unwrap();
+        let (node, _) = materialize_junction(&mut graph, edge).unwrap();
+        assert_eq!(graph[node].name, "users_users");
+        assert_eq!(graph[node].columns[1].name, "related_user_id");
+
+        let (_, edge) = graph
+            .edge_indices()
+            .map(|e| (graph[e].clone(), e))
+            .next()
+            .unwrap();
+        assert!(materialize_junction(&mut graph, edge).is_err());
+    }
+}
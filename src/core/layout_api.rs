@@ -0,0 +1,38 @@
+//! Auto-layout API server-side handler
+//!
+//! Layouts that cannot be simulated step by step in the browser (layered
+//! layout, layouts keeping pinned tables in place) are calculated here, so
+//! arranging a large diagram does not freeze the page.
+
+use axum::{Json, Router, http::StatusCode, routing::post};
+
+use crate::core::auto_layout::{LayoutRequest, TablePositions};
+
+/// Create the auto-layout API router
+pub fn layout_api_router() -> Router {
+    Router::new().route("/api/layout", post(layout_handler))
+}
+
+/// Calculate a layout on a blocking thread
+///
+/// Diagrams over the [`LayoutRequest`] limits are rejected with 413, so one
+/// request cannot hold a blocking thread for long.
+async fn layout_handler(
+    Json(request): Json<LayoutRequest>,
+) -> Result<Json<TablePositions>, StatusCode> {
+    if !request.within_limits() {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+    tracing::info!(
+        "Layout request: algorithm={:?}, tables={}",
+        request.algorithm,
+        request.graph.node_count()
+    );
+    tokio::task::spawn_blocking(move || request.calculate())
+        .await
+        .map(Json)
+        .map_err(|e| {
+            tracing::error!("Layout task failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
@@ -0,0 +1,1022 @@
+//! Schema lint engine
+//!
+//! Lint rules check team conventions that are valid SQL but still worth flagging:
+//! naming style, missing keys, unindexed foreign keys, money stored as floats, etc.
+//!
+//! Every rule has a stable ID (`L001_SNAKE_CASE`, ...) which is used as the
+//! diagnostic code, so per-diagram settings can disable a rule or override its
+//! severity. Rules run on the schema graph; `lint_sql` runs them on SQL text and
+//! maps the issues back to source spans for the Source editor.
+//!
+//! The same settings control the validator's convention checks listed in
+//! [`SOURCE_CHECKS`]: they need the SQL text for their quick fixes, so
+//! `validate_sql_with_lint` runs them instead of the engine.
+
+use super::sql_parser::{
+    SchemaValidator, SourceSpan, SqlParser, SqlValidationError, validate_sql_with_lint,
+};
+use super::{ErrorSeverity, SchemaGraph, SqlDialect, SqlValidationResult, apply_sql_to_graph};
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use serde::{Deserialize, Serialize};
+use sqlparser::ast::{Expr, IndexColumn, Statement, TableConstraint};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Per-diagram lint configuration
+///
+/// Only deviations from the defaults are stored: disabled rule IDs and
+/// severity overrides. Unknown IDs are kept (a newer client may know them).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LintSettings {
+    /// IDs of disabled rules
+    #[serde(default)]
+    pub disabled: BTreeSet<String>,
+    /// Severity overrides by rule ID
+    #[serde(default)]
+    pub severity: BTreeMap<String, ErrorSeverity>,
+}
+
+impl LintSettings {
+    pub fn is_enabled(&self, rule_id: &str) -> bool {
+        !self.disabled.contains(rule_id)
+    }
+
+    pub fn set_enabled(&mut self, rule_id: &str, enabled: bool) {
+        if enabled {
+            self.disabled.remove(rule_id);
+        } else {
+            self.disabled.insert(rule_id.to_string());
+        }
+    }
+
+    /// Effective severity of a rule
+    pub fn severity_for(&self, rule: &dyn LintRule) -> ErrorSeverity {
+        self.severity
+            .get(rule.id())
+            .copied()
+            .unwrap_or_else(|| rule.default_severity())
+    }
+
+    /// Override the severity of a rule (`None` restores the default)
+    pub fn set_severity(&mut self, rule_id: &str, severity: Option<ErrorSeverity>) {
+        match severity {
+            Some(severity) => {
+                self.severity.insert(rule_id.to_string(), severity);
+            }
+            None => {
+                self.severity.remove(rule_id);
+            }
+        }
+    }
+}
+
+/// Element of the schema a lint issue points at
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LintTarget {
+    pub table: String,
+    pub column: Option<String>,
+}
+
+/// Issue reported by a lint rule
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LintIssue {
+    /// Stable rule ID
+    pub rule_id: String,
+    pub severity: ErrorSeverity,
+    pub message: String,
+    pub target: LintTarget,
+    pub suggestion: Option<String>,
+}
+
+/// Facts about the schema that are not stored in the graph
+#[derive(Clone, Debug, Default)]
+pub struct LintContext {
+    pub dialect: SqlDialect,
+    /// (table, column) pairs that lead an index, lowercase
+    pub indexed_columns: HashSet<(String, String)>,
+    /// Whether `indexed_columns` lists every index; the graph does not model
+    /// indexes, so rules that need them are skipped until SQL is collected
+    pub indexes_known: bool,
+}
+
+impl LintContext {
+    pub fn new(dialect: SqlDialect) -> Self {
+        Self {
+            dialect,
+            indexed_columns: HashSet::new(),
+            indexes_known: false,
+        }
+    }
+
+    /// Collect leading index columns from CREATE INDEX and table constraints
+    pub fn collect_indexes(&mut self, statements: &[Statement]) {
+        self.indexes_known = true;
+        for stmt in statements {
+            match stmt {
+                Statement::CreateIndex(create_index) => {
+                    let table = object_name(&create_index.table_name);
+                    self.add_index(&table, &create_index.columns);
+                }
+                Statement::CreateTable(create_table) => {
+                    let table = object_name(&create_table.name);
+                    for constraint in &create_table.constraints {
+                        let columns = match constraint {
+                            TableConstraint::Index(index) => &index.columns,
+                            TableConstraint::Unique(unique) => &unique.columns,
+                            TableConstraint::PrimaryKey(pk) => &pk.columns,
+                            _ => continue,
+                        };
+                        self.add_index(&table, columns);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn add_index(&mut self, table: &str, columns: &[IndexColumn]) {
+        let column = columns.first().and_then(|c| match &c.column.expr {
+            Expr::Identifier(ident) => Some(ident.value.clone()),
+            Expr::CompoundIdentifier(parts) => parts.last().map(|i| i.value.clone()),
+            _ => None,
+        });
+        if let Some(column) = column {
+            self.indexed_columns
+                .insert((table.to_lowercase(), column.to_lowercase()));
+        }
+    }
+
+    fn is_indexed(&self, table: &str, column: &str) -> bool {
+        self.indexed_columns
+            .contains(&(table.to_lowercase(), column.to_lowercase()))
+    }
+}
+
+/// Unquoted last part of a (possibly qualified) name
+fn object_name(name: &sqlparser::ast::ObjectName) -> String {
+    name.0
+        .last()
+        .and_then(|part| part.as_ident())
+        .map(|ident| ident.value.clone())
+        .unwrap_or_default()
+}
+
+/// Sink passed to rules; stamps issues with the rule ID and effective severity
+pub struct LintSink<'a> {
+    rule_id: &'static str,
+    severity: ErrorSeverity,
+    issues: &'a mut Vec<LintIssue>,
+}
+
+impl LintSink<'_> {
+    pub fn report(
+        &mut self,
+        target: LintTarget,
+        message: impl Into<String>,
+        suggestion: Option<String>,
+    ) {
+        self.issues.push(LintIssue {
+            rule_id: self.rule_id.to_string(),
+            severity: self.severity,
+            message: message.into(),
+            target,
+            suggestion,
+        });
+    }
+
+    pub fn table(&mut self, table: &str, message: impl Into<String>, suggestion: Option<String>) {
+        let target = LintTarget {
+            table: table.to_string(),
+            column: None,
+        };
+        self.report(target, message, suggestion);
+    }
+
+    pub fn column(
+        &mut self,
+        table: &str,
+        column: &str,
+        message: impl Into<String>,
+        suggestion: Option<String>,
+    ) {
+        let target = LintTarget {
+            table: table.to_string(),
+            column: Some(column.to_string()),
+        };
+        self.report(target, message, suggestion);
+    }
+}
+
+/// A lint rule
+///
+/// Implement this trait and register the rule with `LintEngine::with_rule` to
+/// add project-specific checks.
+pub trait LintRule: Send + Sync {
+    /// Stable ID used as diagnostic code and in settings
+    fn id(&self) -> &'static str;
+    /// Short description shown in settings
+    fn description(&self) -> &'static str;
+    fn default_severity(&self) -> ErrorSeverity;
+    fn check(&self, graph: &SchemaGraph, context: &LintContext, sink: &mut LintSink<'_>);
+}
+
+/// Collection of lint rules
+pub struct LintEngine {
+    rules: Vec<Box<dyn LintRule>>,
+}
+
+impl Default for LintEngine {
+    /// Engine with all built-in rules
+    fn default() -> Self {
+        Self {
+            rules: vec![
+                Box::new(SnakeCaseRule),
+                Box::new(PluralTableNameRule),
+                Box::new(PrimaryKeyRequiredRule),
+                Box::new(IndexedForeignKeyRule),
+                Box::new(FloatForMoneyRule),
+                Box::new(TimestampsRule),
+                Box::new(NullableBooleanRule),
+            ],
+        }
+    }
+}
+
+impl LintEngine {
+    /// Engine without rules
+    pub fn empty() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    pub fn with_rule(mut self, rule: Box<dyn LintRule>) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &dyn LintRule> {
+        self.rules.iter().map(|rule| rule.as_ref())
+    }
+
+    pub fn run(&self, graph: &SchemaGraph, settings: &LintSettings) -> LintReport {
+        self.run_with_context(graph, settings, &LintContext::default())
+    }
+
+    pub fn run_with_context(
+        &self,
+        graph: &SchemaGraph,
+        settings: &LintSettings,
+        context: &LintContext,
+    ) -> LintReport {
+        let mut issues = Vec::new();
+        for rule in self.rules() {
+            if !settings.is_enabled(rule.id()) {
+                continue;
+            }
+            let mut sink = LintSink {
+                rule_id: rule.id(),
+                severity: settings.severity_for(rule),
+                issues: &mut issues,
+            };
+            rule.check(graph, context, &mut sink);
+        }
+        LintReport { issues }
+    }
+}
+
+/// Result of a lint run
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LintReport {
+    pub issues: Vec<LintIssue>,
+}
+
+/// Validator checks controlled by the lint settings: (ID, description, default severity)
+///
+/// `W004_RESERVED_KEYWORD` is a hint when only other dialects reserve the word.
+pub const SOURCE_CHECKS: &[(&str, &str, ErrorSeverity)] = &[
+    (
+        "W004_RESERVED_KEYWORD",
+        "Names are not reserved keywords",
+        ErrorSeverity::Warning,
+    ),
+    (
+        "H001_MISSING_PRIMARY_KEY",
+        "Tables in the source have a primary key",
+        ErrorSeverity::Hint,
+    ),
+];
+
+/// Lint rules that replace a validator diagnostic on the same element
+/// (the validator diagnostic's fixes are moved to the lint issue)
+const SUPERSEDED: &[(&str, &str)] = &[("L003_PRIMARY_KEY_REQUIRED", "H001_MISSING_PRIMARY_KEY")];
+
+impl LintReport {
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn count(&self, severity: ErrorSeverity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .count()
+    }
+
+    /// Convert issues to diagnostics with spans in `source`
+    pub fn to_validation_result(&self, source: &str, dialect: &SqlDialect) -> SqlValidationResult {
+        let registry = SchemaValidator::from_source(source, dialect.clone());
+        let mut result = SqlValidationResult::new();
+
+        for issue in &self.issues {
+            let mut diagnostic = SqlValidationError {
+                severity: issue.severity,
+                message: issue.message.clone(),
+                span: None,
+                code: issue.rule_id.clone(),
+                suggestion: issue.suggestion.clone(),
+                related: Vec::new(),
+                fixes: Vec::new(),
+            };
+            if let Some(span) = target_span(&registry, &issue.target) {
+                diagnostic = diagnostic.with_span(span);
+            }
+            result.add_error(diagnostic);
+        }
+
+        result.is_valid = result.stats.error_count == 0;
+        result
+    }
+
+    /// Add the issues to a validation result of the same source
+    pub fn merge_into(
+        &self,
+        validation: &mut SqlValidationResult,
+        source: &str,
+        dialect: &SqlDialect,
+    ) {
+        let mut lint = self.to_validation_result(source, dialect);
+        let mut kept = Vec::with_capacity(validation.diagnostics.len());
+
+        for diagnostic in std::mem::take(&mut validation.diagnostics) {
+            let replacement = lint.diagnostics.iter_mut().find(|issue| {
+                issue.span.is_some()
+                    && issue.span == diagnostic.span
+                    && SUPERSEDED
+                        .iter()
+                        .any(|(rule, code)| issue.code == *rule && diagnostic.code == *code)
+            });
+            match replacement {
+                Some(issue) => issue.fixes.extend(diagnostic.fixes),
+                None => kept.push(diagnostic),
+            }
+        }
+
+        let stats = std::mem::take(&mut validation.stats);
+        validation.stats.table_count = stats.table_count;
+        validation.stats.relationship_count = stats.relationship_count;
+        for diagnostic in kept.into_iter().chain(lint.diagnostics) {
+            validation.add_error(diagnostic);
+        }
+        validation.is_valid = validation.stats.error_count == 0;
+    }
+}
+
+/// Span of a table or column name in its CREATE TABLE statement
+fn target_span(registry: &SchemaValidator, target: &LintTarget) -> Option<SourceSpan> {
+    let table = registry.table(&target.table)?;
+    match &target.column {
+        Some(column) => table.column(column)?.source_position,
+        None => table.source_position,
+    }
+}
+
+/// Lint SQL text: build a schema from it and map issues back to the text
+///
+/// Returns an empty report if the SQL cannot be applied (syntax/semantic errors
+/// are reported by the validator instead).
+pub fn lint_sql(source: &str, dialect: &SqlDialect, settings: &LintSettings) -> LintReport {
+    let mut graph = SchemaGraph::default();
+    if !apply_sql_to_graph(source, dialect.clone(), &mut graph).success {
+        return LintReport::default();
+    }
+
+    let mut context = LintContext::new(dialect.clone());
+    if let Ok(statements) = SqlParser::new(dialect.clone()).parse(source) {
+        context.collect_indexes(&statements);
+    }
+    LintEngine::default().run_with_context(&graph, settings, &context)
+}
+
+/// Validate SQL text and lint it with the same settings
+pub fn validate_and_lint_sql(
+    source: &str,
+    dialect: &SqlDialect,
+    settings: &LintSettings,
+) -> SqlValidationResult {
+    let mut validation = validate_sql_with_lint(source, dialect.clone(), settings);
+    lint_sql(source, dialect, settings).merge_into(&mut validation, source, dialect);
+    validation
+}
+
+/// Table and column holding the foreign key of a relationship
+///
+/// For 1:N the key lives on the "many" side (the target); otherwise on the source.
+pub fn foreign_key_side(graph: &SchemaGraph, edge: EdgeIndex) -> Option<(NodeIndex, String)> {
+    let (source, target) = graph.edge_endpoints(edge)?;
+    let relationship = graph.edge_weight(edge)?;
+    Some(if relationship.relationship_type.key_on_source() {
+        (source, relationship.from_column.clone())
+    } else {
+        (target, relationship.to_column.clone())
+    })
+}
+
+/// Convert an identifier to snake_case (`OrderItem` -> `order_item`)
+pub fn to_snake_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len() + 4);
+    let chars: Vec<char> = name.chars().collect();
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch.is_ascii_alphanumeric() {
+            let boundary = ch.is_ascii_uppercase()
+                && i > 0
+                && (chars[i - 1].is_ascii_lowercase()
+                    || chars[i - 1].is_ascii_digit()
+                    || (chars[i - 1].is_ascii_uppercase()
+                        && chars.get(i + 1).is_some_and(|c| c.is_ascii_lowercase())));
+            if boundary && !result.ends_with('_') {
+                result.push('_');
+            }
+            result.push(ch.to_ascii_lowercase());
+        } else if !result.is_empty() && !result.ends_with('_') {
+            result.push('_');
+        }
+    }
+    result.trim_end_matches('_').to_string()
+}
+
+fn is_snake_case(name: &str) -> bool {
+    !name.is_empty()
+        && name.starts_with(|c: char| c.is_ascii_lowercase())
+        && !name.ends_with('_')
+        && !name.contains("__")
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Nouns whose plural does not end in "s" (or that are uncountable)
+const IRREGULAR_PLURALS: &[&str] = &[
+    "people",
+    "children",
+    "men",
+    "women",
+    "data",
+    "metadata",
+    "media",
+    "criteria",
+    "feet",
+    "teeth",
+    "mice",
+    "geese",
+    "series",
+    "species",
+    "news",
+    "information",
+    "equipment",
+    "staff",
+];
+
+fn is_plural(word: &str) -> bool {
+    let word = word.to_lowercase();
+    IRREGULAR_PLURALS.contains(&word.as_str())
+        || (word.ends_with('s')
+            && !word.ends_with("ss")
+            && !word.ends_with("us")
+            && !word.ends_with("is"))
+}
+
+/// Naive English plural of the last word of a snake_case name
+fn pluralize(name: &str) -> String {
+    let lower = name.to_lowercase();
+    if lower.ends_with("person") {
+        return format!("{}ople", &name[..name.len() - 4]);
+    }
+    if lower.ends_with("child") {
+        return format!("{}ren", name);
+    }
+    if let Some(stem) = lower.strip_suffix('y')
+        && !stem.ends_with(['a', 'e', 'i', 'o', 'u'])
+    {
+        return format!("{}ies", &name[..name.len() - 1]);
+    }
+    if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|suffix| lower.ends_with(suffix))
+    {
+        return format!("{}es", name);
+    }
+    format!("{}s", name)
+}
+
+/// Naive English singular of the last word of a snake_case name (inverse of `pluralize`)
+pub(crate) fn singularize(name: &str) -> String {
+    let lower = name.to_lowercase();
+    if lower.ends_with("people") {
+        return format!("{}rson", &name[..name.len() - 4]);
+    }
+    if let Some(stem) = lower.strip_suffix("children") {
+        return format!("{}child", &name[..stem.len()]);
+    }
+    if !is_plural(&lower) || IRREGULAR_PLURALS.contains(&lower.as_str()) {
+        return name.to_string();
+    }
+    if lower.ends_with("ies") {
+        return format!("{}y", &name[..name.len() - 3]);
+    }
+    if ["sses", "xes", "ches", "shes"]
+        .iter()
+        .any(|suffix| lower.ends_with(suffix))
+    {
+        return name[..name.len() - 2].to_string();
+    }
+    name[..name.len() - 1].to_string()
+}
+
+// ============================================================================
+// Built-in rules
+// ============================================================================
+
+/// L001: table and column names are snake_case
+struct SnakeCaseRule;
+
+impl LintRule for SnakeCaseRule {
+    fn id(&self) -> &'static str {
+        "L001_SNAKE_CASE"
+    }
+
+    fn description(&self) -> &'static str {
+        "Table and column names use snake_case"
+    }
+
+    fn default_severity(&self) -> ErrorSeverity {
+        ErrorSeverity::Warning
+    }
+
+    fn check(&self, graph: &SchemaGraph, _context: &LintContext, sink: &mut LintSink<'_>) {
+        for table in graph.node_weights() {
+            if !is_snake_case(&table.name) {
+                sink.table(
+                    &table.name,
+                    format!("Table name '{}' is not snake_case", table.name),
+                    Some(format!("Rename to '{}'", to_snake_case(&table.name))),
+                );
+            }
+            for column in &table.columns {
+                if !is_snake_case(&column.name) {
+                    sink.column(
+                        &table.name,
+                        &column.name,
+                        format!(
+                            "Column name '{}.{}' is not snake_case",
+                            table.name, column.name
+                        ),
+                        Some(format!("Rename to '{}'", to_snake_case(&column.name))),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// L002: table names are plural nouns
+struct PluralTableNameRule;
+
+impl LintRule for PluralTableNameRule {
+    fn id(&self) -> &'static str {
+        "L002_PLURAL_TABLE_NAME"
+    }
+
+    fn description(&self) -> &'static str {
+        "Table names are plural (users, order_items)"
+    }
+
+    fn default_severity(&self) -> ErrorSeverity {
+        ErrorSeverity::Hint
+    }
+
+    fn check(&self, graph: &SchemaGraph, _context: &LintContext, sink: &mut LintSink<'_>) {
+        for table in graph.node_weights() {
+            let snake = to_snake_case(&table.name);
+            let last_word = snake.rsplit('_').next().unwrap_or_default();
+            if !last_word.is_empty() && !is_plural(last_word) {
+                sink.table(
+                    &table.name,
+                    format!("Table name '{}' is not plural", table.name),
+                    Some(format!("Rename to '{}'", pluralize(&table.name))),
+                );
+            }
+        }
+    }
+}
+
+/// L003: every table has a primary key
+struct PrimaryKeyRequiredRule;
+
+impl LintRule for PrimaryKeyRequiredRule {
+    fn id(&self) -> &'static str {
+        "L003_PRIMARY_KEY_REQUIRED"
+    }
+
+    fn description(&self) -> &'static str {
+        "Every table has a primary key"
+    }
+
+    fn default_severity(&self) -> ErrorSeverity {
+        ErrorSeverity::Warning
+    }
+
+    fn check(&self, graph: &SchemaGraph, _context: &LintContext, sink: &mut LintSink<'_>) {
+        for table in graph.node_weights() {
+            if !table.columns.iter().any(|c| c.is_primary_key) {
+                sink.table(
+                    &table.name,
+                    format!("Table '{}' has no primary key", table.name),
+                    Some("Add a primary key column (e.g. 'id')".to_string()),
+                );
+            }
+        }
+    }
+}
+
+/// L004: foreign key columns are indexed
+///
+/// A column counts as indexed if it is unique, leads the primary key, or leads
+/// an explicit index. MySQL (InnoDB) indexes foreign key columns automatically.
+/// Runs only when the context knows the indexes (linting SQL text).
+struct IndexedForeignKeyRule;
+
+impl LintRule for IndexedForeignKeyRule {
+    fn id(&self) -> &'static str {
+        "L004_UNINDEXED_FOREIGN_KEY"
+    }
+
+    fn description(&self) -> &'static str {
+        "Foreign key columns are indexed"
+    }
+
+    fn default_severity(&self) -> ErrorSeverity {
+        ErrorSeverity::Warning
+    }
+
+    fn check(&self, graph: &SchemaGraph, context: &LintContext, sink: &mut LintSink<'_>) {
+        if context.dialect == SqlDialect::MySQL || !context.indexes_known {
+            return;
+        }
+
+        let mut reported = HashSet::new();
+        for edge in graph.edge_indices() {
+            let Some((node, column_name)) = foreign_key_side(graph, edge) else {
+                continue;
+            };
+            let Some(table) = graph.node_weight(node) else {
+                continue;
+            };
+            let Some((_, column)) = table.find_column(&column_name) else {
+                continue;
+            };
+            let leads_primary_key = table
+                .columns
+                .iter()
+                .find(|c| c.is_primary_key)
+                .is_some_and(|pk| pk.name == column.name);
+            if column.is_unique
+                || leads_primary_key
+                || context.is_indexed(&table.name, &column.name)
+                || !reported.insert((node, column.name.clone()))
+            {
+                continue;
+            }
+            sink.column(
+                &table.name,
+                &column.name,
+                format!(
+                    "Foreign key column '{}.{}' is not indexed",
+                    table.name, column.name
+                ),
+                Some(format!(
+                    "CREATE INDEX idx_{}_{} ON {} ({});",
+                    table.name,
+                    column.name,
+                    context.dialect.quote_identifier(&table.name),
+                    context.dialect.quote_identifier(&column.name)
+                )),
+            );
+        }
+    }
+}
+
+/// Words that mark a column as holding money
+const MONEY_WORDS: &[&str] = &[
+    "amount", "balance", "cost", "fee", "money", "payment", "price", "revenue", "salary", "tax",
+    "total", "discount", "charge", "wage",
+];
+
+/// L005: money is not stored in floating point columns
+struct FloatForMoneyRule;
+
+impl LintRule for FloatForMoneyRule {
+    fn id(&self) -> &'static str {
+        "L005_FLOAT_FOR_MONEY"
+    }
+
+    fn description(&self) -> &'static str {
+        "Money columns use DECIMAL, not FLOAT/DOUBLE"
+    }
+
+    fn default_severity(&self) -> ErrorSeverity {
+        ErrorSeverity::Warning
+    }
+
+    fn check(&self, graph: &SchemaGraph, _context: &LintContext, sink: &mut LintSink<'_>) {
+        for table in graph.node_weights() {
+            for column in &table.columns {
+                let base = column.get_base_type();
+                let is_float = matches!(base.as_str(), "FLOAT" | "REAL" | "FLOAT4" | "FLOAT8")
+                    || base.starts_with("DOUBLE");
+                let is_money = to_snake_case(&column.name)
+                    .split('_')
+                    .any(|word| MONEY_WORDS.contains(&word.trim_end_matches('s')));
+                if is_float && is_money {
+                    sink.column(
+                        &table.name,
+                        &column.name,
+                        format!(
+                            "Column '{}.{}' stores money as {}",
+                            table.name, column.name, column.data_type
+                        ),
+                        Some("Use DECIMAL(19,4) to avoid rounding errors".to_string()),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// L006: tables have created_at and updated_at columns
+struct TimestampsRule;
+
+impl LintRule for TimestampsRule {
+    fn id(&self) -> &'static str {
+        "L006_MISSING_TIMESTAMPS"
+    }
+
+    fn description(&self) -> &'static str {
+        "Tables have created_at and updated_at columns"
+    }
+
+    fn default_severity(&self) -> ErrorSeverity {
+        ErrorSeverity::Hint
+    }
+
+    fn check(&self, graph: &SchemaGraph, _context: &LintContext, sink: &mut LintSink<'_>) {
+        for table in graph.node_weights() {
+            let missing: Vec<&str> = ["created_at", "updated_at"]
+                .into_iter()
+                .filter(|name| {
+                    !table
+                        .columns
+                        .iter()
+                        .any(|c| to_snake_case(&c.name) == *name)
+                })
+                .collect();
+            if !missing.is_empty() {
+                sink.table(
+                    &table.name,
+                    format!("Table '{}' has no {}", table.name, missing.join(" or ")),
+                    Some(format!("Add {} TIMESTAMP NOT NULL", missing.join(", "))),
+                );
+            }
+        }
+    }
+}
+
+/// L007: boolean columns are NOT NULL
+struct NullableBooleanRule;
+
+impl LintRule for NullableBooleanRule {
+    fn id(&self) -> &'static str {
+        "L007_NULLABLE_BOOLEAN"
+    }
+
+    fn description(&self) -> &'static str {
+        "Boolean columns are NOT NULL"
+    }
+
+    fn default_severity(&self) -> ErrorSeverity {
+        ErrorSeverity::Warning
+    }
+
+    fn check(&self, graph: &SchemaGraph, _context: &LintContext, sink: &mut LintSink<'_>) {
+        for table in graph.node_weights() {
+            for column in &table.columns {
+                let data_type = column.data_type.to_uppercase().replace(' ', "");
+                let is_boolean = matches!(
+                    data_type.as_str(),
+                    "BOOLEAN" | "BOOL" | "TINYINT(1)" | "BIT" | "BIT(1)"
+                );
+                if is_boolean && column.is_nullable {
+                    sink.column(
+                        &table.name,
+                        &column.name,
+                        format!(
+                            "Boolean column '{}.{}' is nullable",
+                            table.name, column.name
+                        ),
+                        Some("Make it NOT NULL with a DEFAULT".to_string()),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Column, TableNode, create_demo_graph};
+
+    fn rule_ids(report: &LintReport) -> Vec<&str> {
+        report.issues.iter().map(|i| i.rule_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_naming_rules() {
+        assert_eq!(to_snake_case("OrderItem"), "order_item");
+        assert_eq!(to_snake_case("userID"), "user_id");
+        assert_eq!(to_snake_case("HTTPRequest"), "http_request");
+        assert_eq!(pluralize("category"), "categories");
+        assert_eq!(pluralize("address"), "addresses");
+        assert_eq!(pluralize("person"), "people");
+        assert!(is_plural("order_items") && is_plural("people"));
+        assert!(!is_plural("status") && !is_plural("user"));
+
+        let mut graph = SchemaGraph::new();
+        graph.add_node(
+            TableNode::new("OrderItem")
+                .add_column(Column::new("id", "INT").primary_key())
+                .add_column(Column::new("unitPrice", "DECIMAL(10,2)").not_null())
+                .add_column(Column::new("created_at", "TIMESTAMP").not_null())
+                .add_column(Column::new("updated_at", "TIMESTAMP").not_null()),
+        );
+        let report = LintEngine::default().run(&graph, &LintSettings::default());
+        assert_eq!(
+            rule_ids(&report),
+            vec![
+                "L001_SNAKE_CASE",
+                "L001_SNAKE_CASE",
+                "L002_PLURAL_TABLE_NAME"
+            ]
+        );
+        assert_eq!(report.issues[1].target.column.as_deref(), Some("unitPrice"));
+        assert_eq!(
+            report.issues[2].suggestion.as_deref(),
+            Some("Rename to 'OrderItems'")
+        );
+    }
+
+    #[test]
+    fn test_column_rules() {
+        let mut graph = SchemaGraph::new();
+        graph.add_node(
+            TableNode::new("payments")
+                .add_column(Column::new("total_amount", "DOUBLE"))
+                .add_column(Column::new("is_refunded", "BOOLEAN"))
+                .add_column(Column::new("created_at", "TIMESTAMP").not_null()),
+        );
+        let report = LintEngine::default().run(&graph, &LintSettings::default());
+        assert_eq!(
+            rule_ids(&report),
+            vec![
+                "L003_PRIMARY_KEY_REQUIRED",
+                "L005_FLOAT_FOR_MONEY",
+                "L006_MISSING_TIMESTAMPS",
+                "L007_NULLABLE_BOOLEAN"
+            ]
+        );
+        assert!(report.issues[2].message.ends_with("has no updated_at"));
+    }
+
+    #[test]
+    fn test_settings_disable_and_override_severity() {
+        let graph = create_demo_graph();
+        let engine = LintEngine::default();
+
+        let report = engine.run(&graph, &LintSettings::default());
+        assert_eq!(report.count(ErrorSeverity::Hint), 3); // no updated_at
+
+        let mut settings = LintSettings::default();
+        settings.set_severity("L006_MISSING_TIMESTAMPS", Some(ErrorSeverity::Error));
+        let report = engine.run(&graph, &settings);
+        assert_eq!(report.count(ErrorSeverity::Error), 3);
+
+        settings.set_enabled("L006_MISSING_TIMESTAMPS", false);
+        assert!(engine.run(&graph, &settings).is_empty());
+
+        let json = serde_json::to_string(&settings).unwrap();
+        assert_eq!(
+            serde_json::from_str::<LintSettings>(&json).unwrap(),
+            settings
+        );
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        struct NoTmpTables;
+        impl LintRule for NoTmpTables {
+            fn id(&self) -> &'static str {
+                "X001_NO_TMP"
+            }
+            fn description(&self) -> &'static str {
+                "No temporary tables"
+            }
+            fn default_severity(&self) -> ErrorSeverity {
+                ErrorSeverity::Error
+            }
+            fn check(&self, graph: &SchemaGraph, _: &LintContext, sink: &mut LintSink<'_>) {
+                for table in graph.node_weights().filter(|t| t.name.starts_with("tmp_")) {
+                    sink.table(&table.name, "Temporary table", None);
+                }
+            }
+        }
+
+        let mut graph = SchemaGraph::new();
+        graph.add_node(TableNode::new("tmp_users"));
+        let report = LintEngine::empty()
+            .with_rule(Box::new(NoTmpTables))
+            .run(&graph, &LintSettings::default());
+        assert_eq!(rule_ids(&report), vec!["X001_NO_TMP"]);
+        assert_eq!(report.issues[0].severity, ErrorSeverity::Error);
+    }
+
+    #[test]
+    fn test_lint_sql_foreign_key_index_and_spans() {
+        let sql = "CREATE TABLE users (\n    id INTEGER PRIMARY KEY,\n    created_at TIMESTAMP NOT NULL,\n    updated_at TIMESTAMP NOT NULL\n);\nCREATE TABLE posts (\n    id INTEGER PRIMARY KEY,\n    user_id INTEGER NOT NULL,\n    created_at TIMESTAMP NOT NULL,\n    updated_at TIMESTAMP NOT NULL,\n    FOREIGN KEY (user_id) REFERENCES users(id)\n);\n";
+        let dialect = SqlDialect::PostgreSQL;
+        let settings = LintSettings::default();
+
+        let report = lint_sql(sql, &dialect, &settings);
+        assert_eq!(rule_ids(&report), vec!["L004_UNINDEXED_FOREIGN_KEY"]);
+        let result = report.to_validation_result(sql, &dialect);
+        let span = result.diagnostics[0].span.unwrap();
+        assert_eq!(&sql[span.start.offset..span.end.offset], "user_id");
+
+        // Explicit index satisfies the rule; MySQL indexes foreign keys itself
+        let indexed = format!(
+            "{}CREATE INDEX idx_posts_user_id ON posts (user_id);\n",
+            sql
+        );
+        assert!(lint_sql(&indexed, &dialect, &settings).is_empty());
+        assert!(lint_sql(sql, &SqlDialect::MySQL, &settings).is_empty());
+    }
+
+    #[test]
+    fn test_foreign_key_index_rule_needs_known_indexes() {
+        let graph = create_demo_graph();
+        let settings = LintSettings::default();
+        let engine = LintEngine::default();
+
+        // The graph has no indexes: the rule cannot tell and stays silent
+        let context = LintContext::new(SqlDialect::PostgreSQL);
+        let report = engine.run_with_context(&graph, &settings, &context);
+        assert!(!rule_ids(&report).contains(&"L004_UNINDEXED_FOREIGN_KEY"));
+
+        let mut context = LintContext::new(SqlDialect::PostgreSQL);
+        context.collect_indexes(&[]);
+        let report = engine.run_with_context(&graph, &settings, &context);
+        assert!(rule_ids(&report).contains(&"L004_UNINDEXED_FOREIGN_KEY"));
+    }
+
+    #[test]
+    fn test_merge_replaces_missing_primary_key_hint() {
+        let sql = "CREATE TABLE logs (\n    id INTEGER,\n    created_at TIMESTAMP NOT NULL,\n    updated_at TIMESTAMP NOT NULL\n);\n";
+        let dialect = SqlDialect::PostgreSQL;
+        let settings = LintSettings::default();
+        let mut validation = validate_sql_with_lint(sql, dialect.clone(), &settings);
+        assert!(
+            validation
+                .diagnostics
+                .iter()
+                .any(|d| d.code == "H001_MISSING_PRIMARY_KEY")
+        );
+
+        lint_sql(sql, &dialect, &settings).merge_into(&mut validation, sql, &dialect);
+        let codes: Vec<&str> = validation
+            .diagnostics
+            .iter()
+            .map(|d| d.code.as_str())
+            .collect();
+        assert_eq!(codes, vec!["L003_PRIMARY_KEY_REQUIRED"]);
+        assert_eq!(validation.stats.warning_count, 1);
+        assert_eq!(validation.stats.hint_count, 0);
+        // The validator's quick fix is kept
+        assert!(validation.has_fixes());
+    }
+}
@@ -6,6 +6,9 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::core::schema::{Column, Relationship};
+use crate::core::table_display::TableDisplay;
+
 // ============================================================================
 // User Activity Status
 // ============================================================================
@@ -302,6 +305,9 @@ pub enum ClientMessage {
         center: (f64, f64),
         /// Viewport zoom level
         zoom: f64,
+        /// Visible canvas area in screen pixels (width, height)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        size: Option<(f64, f64)>,
     },
 
     /// Start dragging a table (attaches cursor to table)
@@ -378,6 +384,8 @@ pub enum ServerMessage {
         user_id: UserId,
         center: (f64, f64),
         zoom: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        size: Option<(f64, f64)>,
     },
 
     /// Broadcast table drag start from another user
@@ -456,6 +464,18 @@ pub enum GraphOperation {
         table_uuid: Uuid,
         position: (f64, f64),
     },
+    /// Pin or unpin a table (pinned tables are not moved by auto-layout)
+    SetTablePinned {
+        node_id: u32,
+        table_uuid: Uuid,
+        pinned: bool,
+    },
+    /// Change how much of a table the canvas shows
+    SetTableDisplay {
+        node_id: u32,
+        table_uuid: Uuid,
+        display: TableDisplay,
+    },
     /// Add a column to a table
     AddColumn {
         node_id: u32,
@@ -514,6 +534,31 @@ pub struct RelationshipData {
     pub to_column: String,
 }
 
+impl From<&Column> for ColumnData {
+    fn from(column: &Column) -> Self {
+        Self {
+            name: column.name.clone(),
+            data_type: column.data_type.clone(),
+            is_primary_key: column.is_primary_key,
+            is_nullable: column.is_nullable,
+            is_unique: column.is_unique,
+            default_value: column.default_value.clone(),
+            foreign_key: None,
+        }
+    }
+}
+
+impl From<&Relationship> for RelationshipData {
+    fn from(relationship: &Relationship) -> Self {
+        Self {
+            name: relationship.name.clone(),
+            relationship_type: relationship.relationship_type.to_string(),
+            from_column: relationship.from_column.clone(),
+            to_column: relationship.to_column.clone(),
+        }
+    }
+}
+
 /// Full graph state snapshot for initial sync
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphStateSnapshot {
@@ -529,6 +574,12 @@ pub struct TableSnapshot {
     pub name: String,
     pub position: (f64, f64),
     pub columns: Vec<ColumnData>,
+    /// Whether the table is pinned (kept in place by auto-layout)
+    #[serde(default)]
+    pub pinned: bool,
+    /// Display level of the table on the canvas
+    #[serde(default)]
+    pub display: TableDisplay,
     /// Version counter for tracking changes, incremented on each modification
     #[serde(default)]
     pub version: u64,
@@ -970,6 +1021,8 @@ mod tests {
                 name: "users".to_string(),
                 position: (0.0, 0.0),
                 columns: vec![],
+                pinned: false,
+                display: TableDisplay::default(),
                 version: 1,
                 last_modified_at: 0,
                 is_deleted: false,
@@ -1210,6 +1263,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_graph_operation_set_table_pinned() {
+        let test_uuid = Uuid::new_v4();
+        let op = GraphOperation::SetTablePinned {
+            node_id: 4,
+            table_uuid: test_uuid,
+            pinned: true,
+        };
+
+        let json = serde_json::to_string(&op).unwrap();
+        assert!(json.contains("\"op_type\":\"SetTablePinned\""));
+        let parsed: GraphOperation = serde_json::from_str(&json).unwrap();
+
+        match parsed {
+            GraphOperation::SetTablePinned {
+                node_id,
+                table_uuid,
+                pinned,
+            } => {
+                assert_eq!(node_id, 4);
+                assert_eq!(table_uuid, test_uuid);
+                assert!(pinned);
+            }
+            _ => panic!("Wrong operation type"),
+        }
+    }
+
+    #[test]
+    fn test_graph_operation_set_table_display() {
+        let test_uuid = Uuid::new_v4();
+        let op = GraphOperation::SetTableDisplay {
+            node_id: 4,
+            table_uuid: test_uuid,
+            display: TableDisplay::Keys,
+        };
+
+        let json = serde_json::to_string(&op).unwrap();
+        assert!(json.contains("\"op_type\":\"SetTableDisplay\""));
+        let parsed: GraphOperation = serde_json::from_str(&json).unwrap();
+
+        match parsed {
+            GraphOperation::SetTableDisplay {
+                node_id,
+                table_uuid,
+                display,
+            } => {
+                assert_eq!(node_id, 4);
+                assert_eq!(table_uuid, test_uuid);
+                assert_eq!(display, TableDisplay::Keys);
+            }
+            _ => panic!("Wrong operation type"),
+        }
+    }
+
     #[test]
     fn test_graph_operation_add_column() {
         let test_uuid = Uuid::new_v4();
@@ -1445,6 +1552,8 @@ mod tests {
                         default_value: None,
                         foreign_key: None,
                     }],
+                    pinned: false,
+                    display: TableDisplay::default(),
                     version: 1,
                     last_modified_at: 1234567890,
                     is_deleted: false,
@@ -1455,6 +1564,8 @@ mod tests {
                     name: "posts".to_string(),
                     position: (300.0, 400.0),
                     columns: vec![],
+                    pinned: false,
+                    display: TableDisplay::default(),
                     version: 2,
                     last_modified_at: 1234567900,
                     is_deleted: false,
@@ -1708,20 +1819,33 @@ mod tests {
         let msg = ClientMessage::UserViewport {
             center: (500.0, 600.0),
             zoom: 1.5,
+            size: Some((1280.0, 720.0)),
         };
 
         let json = serde_json::to_string(&msg).unwrap();
         let parsed: ClientMessage = serde_json::from_str(&json).unwrap();
 
         match parsed {
-            ClientMessage::UserViewport { center, zoom } => {
+            ClientMessage::UserViewport { center, zoom, size } => {
                 assert_eq!(center, (500.0, 600.0));
                 assert_eq!(zoom, 1.5);
+                assert_eq!(size, Some((1280.0, 720.0)));
             }
             _ => panic!("Expected UserViewport variant"),
         }
     }
 
+    #[test]
+    fn test_user_viewport_size_is_optional() {
+        // Клиенты старых версий не присылают размер вьюпорта
+        let json = r#"{"type":"UserViewport","payload":{"center":[10.0,20.0],"zoom":1.0}}"#;
+        let parsed: ClientMessage = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            parsed,
+            ClientMessage::UserViewport { size: None, .. }
+        ));
+    }
+
     #[test]
     fn test_server_message_cursor_move_broadcast() {
         let user_id = Uuid::new_v4();
@@ -1775,6 +1899,7 @@ mod tests {
             user_id,
             center: (800.0, 600.0),
             zoom: 2.0,
+            size: None,
         };
 
         let json = serde_json::to_string(&msg).unwrap();
@@ -1785,10 +1910,12 @@ mod tests {
                 user_id: uid,
                 center,
                 zoom,
+                size,
             } => {
                 assert_eq!(uid, user_id);
                 assert_eq!(center, (800.0, 600.0));
                 assert_eq!(zoom, 2.0);
+                assert_eq!(size, None);
             }
             _ => panic!("Expected UserViewport variant"),
         }
@@ -1824,6 +1951,7 @@ mod tests {
         let viewport_msg = ClientMessage::UserViewport {
             center: (0.0, 0.0),
             zoom: 1.0,
+            size: None,
         };
         assert_eq!(viewport_msg.priority(), MessagePriority::Low);
         assert!(viewport_msg.is_droppable());
@@ -1871,6 +1999,7 @@ mod tests {
             user_id,
             center: (400.0, 300.0),
             zoom: 1.5,
+            size: None,
         };
         assert_eq!(viewport_msg.priority(), MessagePriority::Low);
         assert!(viewport_msg.is_droppable());
@@ -157,6 +157,8 @@ mod tests {
             name: format!("table_{}", id),
             position: (0.0, 0.0),
             columns: vec![],
+            pinned: false,
+            display: Default::default(),
             version,
             last_modified_at: ts,
             is_deleted: false,
@@ -292,9 +292,9 @@ impl ConnectionSession {
                 self.handle_idle_status(is_active).await
             }
 
-            ClientMessage::UserViewport { center, zoom } => {
+            ClientMessage::UserViewport { center, zoom, size } => {
                 self.require_auth()?;
-                self.handle_user_viewport(center, zoom).await
+                self.handle_user_viewport(center, zoom, size).await
             }
 
             ClientMessage::TableDragStart { node_id, offset } => {
@@ -411,7 +411,12 @@ impl ConnectionSession {
     }
 
     /// Handle user viewport update - broadcast to other users
-    async fn handle_user_viewport(&self, center: (f64, f64), zoom: f64) -> Result<(), String> {
+    async fn handle_user_viewport(
+        &self,
+        center: (f64, f64),
+        zoom: f64,
+        size: Option<(f64, f64)>,
+    ) -> Result<(), String> {
         if let Some(ref room) = self.room
             && let Some(user_id) = self.user_id
         {
@@ -419,6 +424,7 @@ impl ConnectionSession {
                 user_id,
                 center,
                 zoom,
+                size,
             });
         }
         Ok(())
@@ -1382,7 +1388,9 @@ mod tests {
         session.authenticated = true;
         session.room = Some(Arc::clone(&room));
 
-        let result = session.handle_user_viewport((800.0, 600.0), 2.0).await;
+        let result = session
+            .handle_user_viewport((800.0, 600.0), 2.0, Some((1280.0, 720.0)))
+            .await;
 
         assert!(result.is_ok());
     }
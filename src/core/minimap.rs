@@ -0,0 +1,209 @@
+//! Minimap geometry
+//!
+//! The minimap draws the whole diagram scaled down into a small box, together
+//! with the local viewport and the viewports of LiveShare peers. This module maps
+//! between canvas coordinates and minimap pixels; the component only renders.
+
+use crate::core::schema::SchemaGraph;
+use crate::core::selection::table_bounds;
+
+/// Margin around the diagram inside the minimap, in canvas units
+pub const MINIMAP_PADDING: f64 = 80.0;
+
+/// Visible part of the canvas, in canvas coordinates
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Viewport {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Viewport {
+    /// Viewport of a screen area of `size` pixels, with the canvas panned by `pan` and scaled by `zoom`
+    pub fn from_transform(pan: (f64, f64), zoom: f64, size: (f64, f64)) -> Self {
+        Self {
+            x: -pan.0 / zoom,
+            y: -pan.1 / zoom,
+            width: size.0 / zoom,
+            height: size.1 / zoom,
+        }
+    }
+
+    /// Viewport of a screen area of `size` pixels centered on a canvas point
+    pub fn from_center(center: (f64, f64), zoom: f64, size: (f64, f64)) -> Self {
+        let (width, height) = (size.0 / zoom, size.1 / zoom);
+        Self {
+            x: center.0 - width / 2.0,
+            y: center.1 - height / 2.0,
+            width,
+            height,
+        }
+    }
+
+    pub fn center(&self) -> (f64, f64) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    /// Pan that shows canvas point `center` in the middle of a screen area of `size` pixels
+    pub fn pan_to_center(center: (f64, f64), zoom: f64, size: (f64, f64)) -> (f64, f64) {
+        (
+            size.0 / 2.0 - center.0 * zoom,
+            size.1 / 2.0 - center.1 * zoom,
+        )
+    }
+}
+
+/// Mapping from canvas coordinates to minimap pixels
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MinimapProjection {
+    /// Canvas point shown at the top-left corner of the content
+    origin: (f64, f64),
+    /// Minimap pixels per canvas unit
+    scale: f64,
+    /// Offset that centers the content in the minimap
+    offset: (f64, f64),
+}
+
+impl MinimapProjection {
+    /// Fit all tables and the given viewports into a minimap of `size` pixels
+    pub fn fit(graph: &SchemaGraph, viewports: &[Viewport], size: (f64, f64)) -> Self {
+        let rects = graph
+            .node_indices()
+            .filter_map(|n| table_bounds(graph, n))
+            .chain(
+                viewports
+                    .iter()
+                    .map(|v| (v.x, v.y, v.x + v.width, v.y + v.height)),
+            );
+        let (mut left, mut top) = (f64::INFINITY, f64::INFINITY);
+        let (mut right, mut bottom) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for (l, t, r, b) in rects {
+            left = left.min(l);
+            top = top.min(t);
+            right = right.max(r);
+            bottom = bottom.max(b);
+        }
+        // Пустой канвас без вьюпортов
+        if left > right {
+            (left, top, right, bottom) = (0.0, 0.0, 1.0, 1.0);
+        }
+
+        let width = (right - left).max(1.0) + 2.0 * MINIMAP_PADDING;
+        let height = (bottom - top).max(1.0) + 2.0 * MINIMAP_PADDING;
+        let scale = (size.0 / width).min(size.1 / height);
+        Self {
+            origin: (left - MINIMAP_PADDING, top - MINIMAP_PADDING),
+            scale,
+            offset: (
+                (size.0 - width * scale) / 2.0,
+                (size.1 - height * scale) / 2.0,
+            ),
+        }
+    }
+
+    /// Minimap pixels per canvas unit
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    pub fn to_minimap(&self, point: (f64, f64)) -> (f64, f64) {
+        (
+            self.offset.0 + (point.0 - self.origin.0) * self.scale,
+            self.offset.1 + (point.1 - self.origin.1) * self.scale,
+        )
+    }
+
+    pub fn to_canvas(&self, point: (f64, f64)) -> (f64, f64) {
+        (
+            (point.0 - self.offset.0) / self.scale + self.origin.0,
+            (point.1 - self.offset.1) / self.scale + self.origin.1,
+        )
+    }
+
+    /// Rectangle of a canvas area in minimap pixels
+    pub fn project(&self, area: &Viewport) -> Viewport {
+        let (x, y) = self.to_minimap((area.x, area.y));
+        Viewport {
+            x,
+            y,
+            width: area.width * self.scale,
+            height: area.height * self.scale,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::create_demo_graph;
+
+    const SIZE: (f64, f64) = (200.0, 120.0);
+
+    fn assert_close(a: (f64, f64), b: (f64, f64)) {
+        assert!(
+            (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9,
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn test_viewport_transform_roundtrip() {
+        let viewport = Viewport::from_transform((-300.0, 150.0), 2.0, (800.0, 600.0));
+        assert_eq!(viewport.x, 150.0);
+        assert_eq!(viewport.y, -75.0);
+        assert_eq!(viewport.width, 400.0);
+
+        let pan = Viewport::pan_to_center(viewport.center(), 2.0, (800.0, 600.0));
+        assert_close(pan, (-300.0, 150.0));
+
+        let centered = Viewport::from_center(viewport.center(), 2.0, (800.0, 600.0));
+        assert_eq!(centered, viewport);
+    }
+
+    #[test]
+    fn test_fit_keeps_everything_inside() {
+        let graph = create_demo_graph();
+        let far = Viewport {
+            x: 3000.0,
+            y: -500.0,
+            width: 400.0,
+            height: 300.0,
+        };
+        let projection = MinimapProjection::fit(&graph, &[far], SIZE);
+
+        let inside = |area: Viewport| {
+            area.x >= 0.0
+                && area.y >= 0.0
+                && area.x + area.width <= SIZE.0 + 1e-9
+                && area.y + area.height <= SIZE.1 + 1e-9
+        };
+        assert!(inside(projection.project(&far)));
+        for table in graph.node_indices() {
+            let (l, t, r, b) = table_bounds(&graph, table).unwrap();
+            assert!(inside(projection.project(&Viewport {
+                x: l,
+                y: t,
+                width: r - l,
+                height: b - t,
+            })));
+        }
+    }
+
+    #[test]
+    fn test_minimap_canvas_roundtrip() {
+        let graph = create_demo_graph();
+        let projection = MinimapProjection::fit(&graph, &[], SIZE);
+
+        let point = (123.0, 456.0);
+        assert_close(projection.to_canvas(projection.to_minimap(point)), point);
+    }
+
+    #[test]
+    fn test_fit_empty_graph() {
+        let projection = MinimapProjection::fit(&SchemaGraph::new(), &[], SIZE);
+        assert!(projection.scale().is_finite() && projection.scale() > 0.0);
+    }
+}
@@ -7,19 +7,42 @@ pub mod ai_tools;
 #[cfg(feature = "ssr")]
 pub mod auth;
 pub mod auto_layout;
+pub mod clipboard;
+pub mod command_palette;
 #[cfg(feature = "ssr")]
 pub mod config;
+pub mod connect;
+pub mod culling;
 #[cfg(feature = "ssr")]
 pub mod db;
+pub mod diagram;
 #[cfg(feature = "ssr")]
 pub mod diagrams;
+pub mod edge_routing;
 pub mod export;
+pub mod fk_inference;
+pub mod focus;
 #[cfg(feature = "ssr")]
 pub mod folders;
+pub mod history;
+pub mod impact;
+pub mod join_path;
+pub mod junction;
+#[cfg(feature = "ssr")]
+pub mod layout_api;
+pub mod lint;
+pub mod minimap;
+pub mod notation;
+pub mod relationship_check;
 mod schema;
+pub mod selection;
 #[cfg(feature = "ssr")]
 pub mod sharing;
+pub mod sql_completion;
+pub mod sql_formatter;
+pub mod sql_lexer;
 pub mod sql_parser;
+pub mod table_display;
 #[cfg(test)]
 mod tests;
 pub mod validation;
@@ -32,21 +55,86 @@ pub use ai_config::{
     AiConfig, AiMode, ChatMessage, ChatRequest, ChatResponse, build_tool_definitions,
 };
 pub use ai_tools::{ToolDefinition, ToolExecutor, ToolRequest, ToolResponse, get_tool_definitions};
+#[allow(deprecated)]
+pub use auto_layout::calculate_hierarchical_layout;
 pub use auto_layout::{
-    LayoutConfig, LayoutResult, apply_layout, auto_layout, auto_layout_with_config,
-    calculate_hierarchical_layout,
+    ForceLayoutJob, LayoutAlgorithm, LayoutConfig, LayoutJob, LayoutRequest, LayoutResult,
+    LayoutScope, TablePositions, apply_layout, auto_layout, auto_layout_with_config,
+    calculate_force_layout, calculate_layered_layout, calculate_layout, estimated_table_height,
+};
+pub use clipboard::{
+    ClipboardFragment, FRAGMENT_FORMAT, FRAGMENT_VERSION, PASTE_OFFSET, paste_fragment,
+};
+pub use command_palette::{
+    DEFAULT_PALETTE_LIMIT, PaletteCommand, PaletteItem, PaletteMatch, fuzzy_match, search_palette,
+};
+pub use connect::{
+    ConnectTarget, compatible_columns, connect_columns, foreign_key_column,
+    suggest_relationship_type,
+};
+pub use culling::{COMPACT_ZOOM, CULL_MARGIN, DetailLevel, cull_edges, cull_tables, render_area};
+pub use diagram::{DiagramDocument, DiagramSettings};
+pub use edge_routing::{
+    RoutePoint, RoutingConfig, TableBounds, apply_routes, column_anchor_y, column_at_y,
+    route_all_edges, route_edges, route_is_current, route_label_position, route_local_edges,
+    route_svg_path, stale_edges, table_outlines,
 };
 pub use export::{
     ExportFormat, ExportOptions, ExportedRelationship, ExportedSchema, ExportedTable,
-    SchemaExporter, SchemaImporter, SqlDialect,
+    SchemaExporter, SchemaImporter, SqlDialect, TypeConversion, TypeConversionWarning,
+};
+pub use fk_inference::{
+    AcceptedForeignKeys, DEFAULT_MIN_CONFIDENCE, FkCandidate, accept_fk_candidates,
+    infer_foreign_keys,
+};
+pub use focus::{DEFAULT_FOCUS_DEPTH, FocusMode, MAX_FOCUS_DEPTH, neighborhood};
+pub use history::{DEFAULT_HISTORY_LIMIT, HistoryEntry, UndoHistory};
+pub use impact::{
+    ImpactItem, ImpactKind, ImpactReport, ImpactTarget, analyze_impact, analyze_tables_impact,
+    delete_column_with_dependents,
+};
+pub use join_path::{
+    DEFAULT_MAX_JOIN_PATHS, JoinPath, JoinStep, find_join_paths, find_join_paths_by_name,
+    join_query_sql,
+};
+pub use junction::{
+    JunctionSide, JunctionTable, MANY_TO_MANY_MARKER, collapse_junction, collapse_junction_tables,
+    expand_many_to_many, find_junction, find_junction_tables, materialize_junction,
+};
+#[cfg(feature = "ssr")]
+pub use layout_api::layout_api_router;
+pub use lint::{
+    LintContext, LintEngine, LintIssue, LintReport, LintRule, LintSettings, LintSink, LintTarget,
+    SOURCE_CHECKS, lint_sql, validate_and_lint_sql,
+};
+pub use minimap::{MINIMAP_PADDING, MinimapProjection, Viewport};
+pub use notation::{
+    DecorationCircle, DecorationLabel, EndDecoration, Notation, RelationshipEnd, edge_decorations,
+    end_decoration, relationship_ends,
+};
+pub use relationship_check::{
+    RelationshipIssue, RelationshipIssueKind, check_relationship, check_relationships,
 };
 pub use schema::*;
+pub use selection::{
+    Alignment, DistributeAxis, align_tables, delete_tables, distribute_tables, move_tables,
+    table_bounds, tables_in_rect,
+};
+pub use sql_completion::{
+    CompletionItem, CompletionKind, CompletionList, HoverInfo, complete_at, definition_at, hover_at,
+};
+pub use sql_formatter::{CommaStyle, FormatOptions, KeywordCase, format_sql};
 pub use sql_parser::{
-    ApplySqlResult, CanvasNotification, ErrorSeverity, NotificationType, SourcePosition,
-    SourceSpan, SqlParser, SqlValidationError, SqlValidationResult, UnderlineRange,
-    ValidationStats, apply_sql_to_graph, check_schema_sql, validate_sql, validate_sql_with_graph,
+    ApplySqlResult, CanvasNotification, CodeFix, ErrorSeverity, NotificationType, SourcePosition,
+    SourceSpan, SqlParser, SqlValidationError, SqlValidationResult, TextEdit, UnderlineRange,
+    ValidationStats, apply_sql_to_graph, apply_text_edits, check_schema_sql,
+    check_type_conversions, many_to_many_tables, validate_sql, validate_sql_with_graph,
+    validate_sql_with_lint,
 };
+pub use table_display::{TableDisplay, TableRows, displayed_table_height, set_table_display};
 pub use validation::{
-    ValidationError, ValidationLevel, ValidationResult, validate_column_name, validate_identifier,
-    validate_name, validate_table_name,
+    ValidationError, ValidationLevel, ValidationResult, closest_match, dialect_issue_severity,
+    dialects_reserving, identifier_length, is_reserved_in, max_identifier_length,
+    validate_column_name, validate_identifier, validate_identifier_for, validate_name,
+    validate_table_name,
 };
@@ -0,0 +1,323 @@
+//! Relationship notations for the canvas and exported images
+//!
+//! Every relationship end gets a cardinality (one or many) and an optionality.
+//! Optionality of the referenced end follows the nullability of the foreign key
+//! column: a nullable key means a row may reference nothing. The referencing
+//! ("many") end is always optional, since a referenced row need not be used.
+//!
+//! The notation decides how those ends are drawn: crow's foot glyphs or UML
+//! multiplicities. The default simple notation keeps the arrow with a `1:N`
+//! label.
+
+use petgraph::graph::EdgeIndex;
+use serde::{Deserialize, Serialize};
+
+use crate::core::schema::{RelationshipType, SchemaGraph};
+
+/// How relationship ends are drawn
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Notation {
+    /// Arrow to the target with a `1:N` label
+    #[default]
+    Simple,
+    /// Crow's foot with optional (circle) and mandatory (bar) markers
+    CrowsFoot,
+    /// UML multiplicities (`0..1`, `1`, `0..*`)
+    Uml,
+}
+
+impl Notation {
+    pub const ALL: [Notation; 3] = [Notation::Simple, Notation::CrowsFoot, Notation::Uml];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Notation::Simple => "Simple (1:N)",
+            Notation::CrowsFoot => "Crow's foot",
+            Notation::Uml => "UML",
+        }
+    }
+
+    /// Whether edges end in an arrow and carry the relationship type label
+    pub fn shows_arrow(&self) -> bool {
+        matches!(self, Notation::Simple)
+    }
+}
+
+/// Cardinality and optionality of one relationship end
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RelationshipEnd {
+    pub many: bool,
+    pub optional: bool,
+}
+
+impl RelationshipEnd {
+    /// UML multiplicity of the end
+    pub fn multiplicity(&self) -> &'static str {
+        match (self.many, self.optional) {
+            (false, false) => "1",
+            (false, true) => "0..1",
+            (true, false) => "1..*",
+            (true, true) => "0..*",
+        }
+    }
+}
+
+/// Ends of a relationship: (at the source table, at the target table)
+pub fn relationship_ends(
+    graph: &SchemaGraph,
+    edge: EdgeIndex,
+) -> Option<(RelationshipEnd, RelationshipEnd)> {
+    let (from, to) = graph.edge_endpoints(edge)?;
+    let rel = graph.edge_weight(edge)?;
+
+    // Ссылка по nullable ключу может никуда не указывать
+    let key_nullable = if rel.relationship_type.key_on_source() {
+        graph[from].find_column(&rel.from_column)
+    } else {
+        graph[to].find_column(&rel.to_column)
+    }
+    .is_none_or(|(_, column)| column.is_nullable);
+
+    let referencing = |many| RelationshipEnd {
+        many,
+        optional: true,
+    };
+    let referenced = RelationshipEnd {
+        many: false,
+        optional: key_nullable,
+    };
+    Some(match rel.relationship_type {
+        RelationshipType::ManyToOne => (referencing(true), referenced),
+        RelationshipType::OneToMany => (referenced, referencing(true)),
+        RelationshipType::OneToOne => (referencing(false), referenced),
+        RelationshipType::ManyToMany => (referencing(true), referencing(true)),
+    })
+}
+
+/// Circle of an end decoration
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecorationCircle {
+    pub x: f64,
+    pub y: f64,
+    pub r: f64,
+}
+
+/// Text of an end decoration, anchored at its middle
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecorationLabel {
+    pub text: String,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Shapes drawn at one end of a relationship, in canvas coordinates
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EndDecoration {
+    /// Stroked, unfilled SVG path data (bars, crow's foot); may be empty
+    pub path: String,
+    pub circle: Option<DecorationCircle>,
+    pub label: Option<DecorationLabel>,
+}
+
+/// Decoration of a relationship end touching its table at `tip`
+///
+/// `toward` is the next point of the edge path; glyphs are laid out from the
+/// table outwards along that direction.
+pub fn end_decoration(
+    notation: Notation,
+    end: RelationshipEnd,
+    tip: (f64, f64),
+    toward: (f64, f64),
+) -> EndDecoration {
+    let (dx, dy) = (toward.0 - tip.0, toward.1 - tip.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    // Вырожденный отрезок: считаем, что связь уходит вправо
+    let (ux, uy) = if length < f64::EPSILON {
+        (1.0, 0.0)
+    } else {
+        (dx / length, dy / length)
+    };
+    let (nx, ny) = (-uy, ux);
+    // Точка на расстоянии `along` от таблицы и `across` поперёк связи
+    let at = |along: f64, across: f64| {
+        (
+            tip.0 + ux * along + nx * across,
+            tip.1 + uy * along + ny * across,
+        )
+    };
+    let bar = |along: f64| {
+        let (a, b) = (at(along, -7.0), at(along, 7.0));
+        format!("M {} {} L {} {}", a.0, a.1, b.0, b.1)
+    };
+    let label = |text: &str| {
+        let (x, y) = at(16.0, -12.0);
+        Some(DecorationLabel {
+            text: text.to_string(),
+            x,
+            y,
+        })
+    };
+
+    match notation {
+        Notation::Simple => EndDecoration::default(),
+        Notation::CrowsFoot => {
+            let mut path = if end.many {
+                let (root, upper, lower) = (at(12.0, 0.0), at(0.0, -7.0), at(0.0, 7.0));
+                format!(
+                    "M {} {} L {} {} M {} {} L {} {} M {} {} L {} {}",
+                    root.0,
+                    root.1,
+                    upper.0,
+                    upper.1,
+                    root.0,
+                    root.1,
+                    tip.0,
+                    tip.1,
+                    root.0,
+                    root.1,
+                    lower.0,
+                    lower.1
+                )
+            } else {
+                bar(6.0)
+            };
+            let circle = if end.optional {
+                let (x, y) = at(21.0, 0.0);
+                Some(DecorationCircle { x, y, r: 4.0 })
+            } else {
+                path.push(' ');
+                path.push_str(&bar(if end.many { 16.0 } else { 12.0 }));
+                None
+            };
+            EndDecoration {
+                path,
+                circle,
+                label: None,
+            }
+        }
+        Notation::Uml => EndDecoration {
+            label: label(end.multiplicity()),
+            ..Default::default()
+        },
+    }
+}
+
+/// Decorations of both ends of `edge` drawn along `points`
+///
+/// `points` is the edge path from the source table to the target table.
+pub fn edge_decorations(
+    graph: &SchemaGraph,
+    edge: EdgeIndex,
+    notation: Notation,
+    points: &[(f64, f64)],
+) -> Option<(EndDecoration, EndDecoration)> {
+    if notation == Notation::Simple || points.len() < 2 {
+        return None;
+    }
+    let (source, target) = relationship_ends(graph, edge)?;
+    let last = points.len() - 1;
+    Some((
+        end_decoration(notation, source, points[0], points[1]),
+        end_decoration(notation, target, points[last], points[last - 1]),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Column, Relationship, TableNode};
+
+    /// posts.user_id -> users.id with the given nullability of `user_id`
+    fn posts_users(nullable: bool) -> (SchemaGraph, EdgeIndex) {
+        let mut graph = SchemaGraph::new();
+        let users = graph
+            .add_node(TableNode::new("users").add_column(Column::new("id", "INT").primary_key()));
+        let user_id = Column::new("user_id", "INT");
+        let user_id = if nullable {
+            user_id
+        } else {
+            user_id.not_null()
+        };
+        let posts = graph.add_node(TableNode::new("posts").add_column(user_id));
+        let edge = graph.add_edge(
+            posts,
+            users,
+            Relationship::new(
+                "fk_posts_user_id",
+                RelationshipType::ManyToOne,
+                "user_id",
+                "id",
+            ),
+        );
+        (graph, edge)
+    }
+
+    #[test]
+    fn test_optionality_follows_key_nullability() {
+        let (graph, edge) = posts_users(false);
+        let (posts, users) = relationship_ends(&graph, edge).unwrap();
+        assert_eq!(posts.multiplicity(), "0..*");
+        assert_eq!(users.multiplicity(), "1");
+
+        let (graph, edge) = posts_users(true);
+        let (_, users) = relationship_ends(&graph, edge).unwrap();
+        assert_eq!(users.multiplicity(), "0..1");
+    }
+
+    #[test]
+    fn test_one_to_many_key_on_target() {
+        let (mut graph, edge) = posts_users(false);
+        // Та же связь, записанная со стороны users
+        let (posts, users) = graph.edge_endpoints(edge).unwrap();
+        graph.remove_edge(edge);
+        let edge = graph.add_edge(
+            users,
+            posts,
+            Relationship::new(
+                "fk_posts_user_id",
+                RelationshipType::OneToMany,
+                "id",
+                "user_id",
+            ),
+        );
+
+        let (source, target) = relationship_ends(&graph, edge).unwrap();
+        assert_eq!((source.many, source.optional), (false, false));
+        assert_eq!((target.many, target.optional), (true, true));
+    }
+
+    #[test]
+    fn test_crows_foot_markers() {
+        let many = RelationshipEnd {
+            many: true,
+            optional: true,
+        };
+        let one = RelationshipEnd {
+            many: false,
+            optional: false,
+        };
+
+        // Связь уходит от таблицы вправо
+        let foot = end_decoration(Notation::CrowsFoot, many, (0.0, 0.0), (50.0, 0.0));
+        assert_eq!(foot.path.matches('M').count(), 3);
+        let circle = foot.circle.unwrap();
+        assert_eq!((circle.x, circle.y), (21.0, 0.0));
+
+        // Обязательная "одна": две черты, без кружка; влево - зеркально
+        let bars = end_decoration(Notation::CrowsFoot, one, (100.0, 0.0), (50.0, 0.0));
+        assert_eq!(bars.path, "M 94 7 L 94 -7 M 88 7 L 88 -7");
+        assert!(bars.circle.is_none());
+    }
+
+    #[test]
+    fn test_text_notations() {
+        let (graph, edge) = posts_users(true);
+        let points = [(200.0, 50.0), (150.0, 50.0), (100.0, 50.0)];
+
+        let (posts, users) = edge_decorations(&graph, edge, Notation::Uml, &points).unwrap();
+        assert_eq!(posts.label.unwrap().text, "0..*");
+        assert_eq!(users.label.unwrap().text, "0..1");
+
+        assert!(edge_decorations(&graph, edge, Notation::Simple, &points).is_none());
+    }
+}
@@ -0,0 +1,317 @@
+//! Relationship consistency analyzer
+//!
+//! Checks that a relationship makes sense for the columns it connects:
+//! - the key and the referenced column have compatible types
+//! - the referenced column is unique (a 1:1 must target a unique column)
+//! - the declared cardinality matches the key column (a unique N:1 key is really 1:1)
+//! - required (NOT NULL) keys do not default to NULL or reference nullable columns
+//!
+//! The analyzer only reports; callers decide whether an issue blocks the change.
+
+use super::sql_parser::SqlValidationError;
+use super::{Column, ErrorSeverity, Relationship, RelationshipType, SchemaGraph, TableNode};
+use petgraph::graph::EdgeIndex;
+use serde::{Deserialize, Serialize};
+
+/// Kind of relationship inconsistency
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RelationshipIssueKind {
+    /// Key and referenced column types are not compatible
+    TypeMismatch,
+    /// Referenced column is neither a primary key nor unique
+    TargetNotUnique,
+    /// Uniqueness of the key column contradicts the relationship type
+    CardinalityMismatch,
+    /// NOT NULL key that defaults to NULL or references a nullable column
+    NullabilityConflict,
+}
+
+impl RelationshipIssueKind {
+    /// Diagnostic code
+    pub fn code(&self) -> &'static str {
+        match self {
+            RelationshipIssueKind::TypeMismatch => "R001_FK_TYPE_MISMATCH",
+            RelationshipIssueKind::TargetNotUnique => "R002_TARGET_NOT_UNIQUE",
+            RelationshipIssueKind::CardinalityMismatch => "R003_CARDINALITY_MISMATCH",
+            RelationshipIssueKind::NullabilityConflict => "R004_NULLABILITY_CONFLICT",
+        }
+    }
+
+    pub fn severity(&self) -> ErrorSeverity {
+        match self {
+            RelationshipIssueKind::TypeMismatch => ErrorSeverity::Error,
+            _ => ErrorSeverity::Warning,
+        }
+    }
+}
+
+/// Inconsistency found in a relationship
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelationshipIssue {
+    pub kind: RelationshipIssueKind,
+    /// Relationship name
+    pub relationship: String,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+impl RelationshipIssue {
+    pub fn severity(&self) -> ErrorSeverity {
+        self.kind.severity()
+    }
+
+    pub fn to_diagnostic(&self) -> SqlValidationError {
+        let diagnostic = match self.severity() {
+            ErrorSeverity::Error => SqlValidationError::error(&self.message, self.kind.code()),
+            ErrorSeverity::Warning => SqlValidationError::warning(&self.message, self.kind.code()),
+            ErrorSeverity::Hint => SqlValidationError::hint(&self.message, self.kind.code()),
+        };
+        match &self.suggestion {
+            Some(suggestion) => diagnostic.with_suggestion(suggestion),
+            None => diagnostic,
+        }
+    }
+}
+
+impl std::fmt::Display for RelationshipIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Check a relationship between two tables (before or after it is added to the graph)
+///
+/// Missing columns are not reported here: `create_relationship` rejects them.
+pub fn check_relationship(
+    from_table: &TableNode,
+    to_table: &TableNode,
+    relationship: &Relationship,
+) -> Vec<RelationshipIssue> {
+    let (Some((_, from_column)), Some((_, to_column))) = (
+        from_table.find_column(&relationship.from_column),
+        to_table.find_column(&relationship.to_column),
+    ) else {
+        return Vec::new();
+    };
+
+    let rel_type = &relationship.relationship_type;
+    let from_name = format!("{}.{}", from_table.name, from_column.name);
+    let to_name = format!("{}.{}", to_table.name, to_column.name);
+    let mut issues = Vec::new();
+    let mut issue = |kind, message: String, suggestion: Option<String>| {
+        issues.push(RelationshipIssue {
+            kind,
+            relationship: relationship.name.clone(),
+            message,
+            suggestion,
+        });
+    };
+
+    if !from_column.is_type_compatible_with(to_column) {
+        issue(
+            RelationshipIssueKind::TypeMismatch,
+            format!(
+                "Relationship '{}': {} ({}) is not compatible with {} ({})",
+                relationship.name, from_name, from_column.data_type, to_name, to_column.data_type
+            ),
+            Some(format!(
+                "Change {} to a type compatible with {}",
+                from_name, to_column.data_type
+            )),
+        );
+    }
+
+    // Many-to-many goes through a junction table: no single key column to check
+    if *rel_type == RelationshipType::ManyToMany {
+        return issues;
+    }
+
+    let (key, key_name, referenced, referenced_name) = if rel_type.key_on_source() {
+        (from_column, &from_name, to_column, &to_name)
+    } else {
+        (to_column, &to_name, from_column, &from_name)
+    };
+    let is_unique = |column: &Column| column.is_primary_key || column.is_unique;
+
+    if !is_unique(referenced) {
+        let message = if *rel_type == RelationshipType::OneToOne {
+            format!(
+                "1:1 relationship '{}' targets {}, which is not unique",
+                relationship.name, referenced_name
+            )
+        } else {
+            format!(
+                "Relationship '{}' references {}, which is not unique",
+                relationship.name, referenced_name
+            )
+        };
+        issue(
+            RelationshipIssueKind::TargetNotUnique,
+            message,
+            Some(format!(
+                "Reference a primary key or mark {} as UNIQUE",
+                referenced_name
+            )),
+        );
+    }
+
+    match (rel_type, is_unique(key)) {
+        (RelationshipType::OneToOne, false) => issue(
+            RelationshipIssueKind::CardinalityMismatch,
+            format!(
+                "1:1 relationship '{}': {} is not unique, so it behaves as N:1",
+                relationship.name, key_name
+            ),
+            Some(format!(
+                "Mark {} as UNIQUE or change the type to N:1",
+                key_name
+            )),
+        ),
+        (RelationshipType::ManyToOne | RelationshipType::OneToMany, true) => issue(
+            RelationshipIssueKind::CardinalityMismatch,
+            format!(
+                "{} relationship '{}': {} is unique, so at most one row can reference each target",
+                rel_type, relationship.name, key_name
+            ),
+            Some("Change the type to 1:1 or drop the UNIQUE constraint".to_string()),
+        ),
+        _ => {}
+    }
+
+    if !key.is_nullable {
+        if key
+            .default_value
+            .as_deref()
+            .is_some_and(|d| d.trim().eq_ignore_ascii_case("null"))
+        {
+            issue(
+                RelationshipIssueKind::NullabilityConflict,
+                format!("{} is NOT NULL but defaults to NULL", key_name),
+                Some(format!("Remove the default or make {} nullable", key_name)),
+            );
+        }
+        if referenced.is_nullable && !referenced.is_primary_key {
+            issue(
+                RelationshipIssueKind::NullabilityConflict,
+                format!(
+                    "Required key {} references nullable column {}",
+                    key_name, referenced_name
+                ),
+                Some(format!("Make {} NOT NULL", referenced_name)),
+            );
+        }
+    }
+
+    issues
+}
+
+/// Check one relationship of the graph
+pub fn check_edge(graph: &SchemaGraph, edge: EdgeIndex) -> Vec<RelationshipIssue> {
+    let (Some((from, to)), Some(relationship)) =
+        (graph.edge_endpoints(edge), graph.edge_weight(edge))
+    else {
+        return Vec::new();
+    };
+    match (graph.node_weight(from), graph.node_weight(to)) {
+        (Some(from_table), Some(to_table)) => {
+            check_relationship(from_table, to_table, relationship)
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Check all relationships of the graph
+pub fn check_relationships(graph: &SchemaGraph) -> Vec<RelationshipIssue> {
+    graph
+        .edge_indices()
+        .flat_map(|edge| check_edge(graph, edge))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::create_demo_graph;
+
+    fn tables() -> (TableNode, TableNode) {
+        let users = TableNode::new("users")
+            .add_column(Column::new("id", "INT").primary_key())
+            .add_column(Column::new("email", "VARCHAR(255)").not_null());
+        let profiles = TableNode::new("profiles")
+            .add_column(Column::new("id", "INT").primary_key())
+            .add_column(Column::new("user_id", "BIGINT").not_null())
+            .add_column(Column::new("user_email", "VARCHAR(255)").not_null());
+        (users, profiles)
+    }
+
+    fn kinds(issues: &[RelationshipIssue]) -> Vec<RelationshipIssueKind> {
+        issues.iter().map(|i| i.kind).collect()
+    }
+
+    #[test]
+    fn test_consistent_relationships() {
+        let (users, profiles) = tables();
+        let rel = Relationship::new("fk", RelationshipType::ManyToOne, "user_id", "id");
+        assert!(check_relationship(&profiles, &users, &rel).is_empty());
+        assert!(check_relationships(&create_demo_graph()).is_empty());
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        let (users, profiles) = tables();
+        let rel = Relationship::new("fk", RelationshipType::ManyToOne, "user_email", "id");
+        let issues = check_relationship(&profiles, &users, &rel);
+        assert_eq!(kinds(&issues), vec![RelationshipIssueKind::TypeMismatch]);
+        assert_eq!(issues[0].severity(), ErrorSeverity::Error);
+        assert_eq!(issues[0].to_diagnostic().code, "R001_FK_TYPE_MISMATCH");
+    }
+
+    #[test]
+    fn test_cardinality_checks() {
+        let (users, mut profiles) = tables();
+
+        // 1:1 to a non-unique column, from a non-unique key
+        let rel = Relationship::new("fk", RelationshipType::OneToOne, "user_email", "email");
+        assert_eq!(
+            kinds(&check_relationship(&profiles, &users, &rel)),
+            vec![
+                RelationshipIssueKind::TargetNotUnique,
+                RelationshipIssueKind::CardinalityMismatch
+            ]
+        );
+
+        // N:1 from a unique column
+        profiles.columns[1].is_unique = true;
+        let rel = Relationship::new("fk", RelationshipType::ManyToOne, "user_id", "id");
+        assert_eq!(
+            kinds(&check_relationship(&profiles, &users, &rel)),
+            vec![RelationshipIssueKind::CardinalityMismatch]
+        );
+
+        // 1:N keeps the key on the target table
+        let rel = Relationship::new("fk", RelationshipType::OneToMany, "id", "user_id");
+        assert_eq!(
+            kinds(&check_relationship(&users, &profiles, &rel)),
+            vec![RelationshipIssueKind::CardinalityMismatch]
+        );
+    }
+
+    #[test]
+    fn test_nullability_conflicts() {
+        let (mut users, mut profiles) = tables();
+        users.columns[1].is_unique = true;
+        users.columns[1].is_nullable = true;
+        profiles.columns[2].default_value = Some("NULL".to_string());
+
+        let rel = Relationship::new("fk", RelationshipType::ManyToOne, "user_email", "email");
+        let issues = check_relationship(&profiles, &users, &rel);
+        assert_eq!(
+            kinds(&issues),
+            vec![
+                RelationshipIssueKind::NullabilityConflict,
+                RelationshipIssueKind::NullabilityConflict
+            ]
+        );
+        assert!(issues[0].message.contains("defaults to NULL"));
+    }
+}
@@ -5,6 +5,9 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use uuid::Uuid;
 
+use super::relationship_check::{RelationshipIssue, check_relationship};
+use super::sql_parser::ErrorSeverity;
+use super::table_display::TableDisplay;
 use super::validation;
 
 /// Стандартные типы данных MySQL
@@ -130,6 +133,12 @@ pub struct TableNode {
     pub columns: Vec<Column>,
     /// Позиция на канвасе (x, y)
     pub position: (f64, f64),
+    /// Закреплённая таблица не перемещается авто-раскладкой
+    #[serde(default)]
+    pub pinned: bool,
+    /// Уровень отображения колонок на канвасе
+    #[serde(default)]
+    pub display: TableDisplay,
 }
 
 impl TableNode {
@@ -139,6 +148,8 @@ impl TableNode {
             name: name.into(),
             columns: Vec::new(),
             position: (0.0, 0.0),
+            pinned: false,
+            display: TableDisplay::default(),
         }
     }
 
@@ -334,6 +345,9 @@ pub struct Relationship {
     pub from_column: String,
     /// Имя колонки в таблице-цели
     pub to_column: String,
+    /// Ортогональный маршрут связи на канвасе (пусто - маршрут не рассчитан)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub route: Vec<(f64, f64)>,
 }
 
 impl Relationship {
@@ -348,6 +362,7 @@ impl Relationship {
             relationship_type,
             from_column: from_column.into(),
             to_column: to_column.into(),
+            route: Vec::new(),
         }
     }
 }
@@ -365,6 +380,22 @@ pub enum RelationshipType {
     ManyToMany,
 }
 
+impl RelationshipType {
+    pub const ALL: [RelationshipType; 4] = [
+        RelationshipType::OneToOne,
+        RelationshipType::OneToMany,
+        RelationshipType::ManyToOne,
+        RelationshipType::ManyToMany,
+    ];
+
+    /// Whether the foreign key column lives in the source table of the edge
+    ///
+    /// For 1:N the key is on the "many" side, i.e. the target table.
+    pub fn key_on_source(&self) -> bool {
+        !matches!(self, RelationshipType::OneToMany)
+    }
+}
+
 impl std::fmt::Display for RelationshipType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -525,6 +556,16 @@ pub trait RelationshipOps {
         relationship: Relationship,
     ) -> Result<petgraph::graph::EdgeIndex, String>;
 
+    /// Создать связь с проверкой согласованности колонок
+    ///
+    /// Несовместимые типы отклоняются, остальные проблемы возвращаются как предупреждения
+    fn create_relationship_checked(
+        &mut self,
+        from_table: petgraph::graph::NodeIndex,
+        to_table: petgraph::graph::NodeIndex,
+        relationship: Relationship,
+    ) -> Result<(petgraph::graph::EdgeIndex, Vec<RelationshipIssue>), String>;
+
     /// Получить связь по индексу
     fn get_relationship(&self, edge_idx: petgraph::graph::EdgeIndex) -> Option<&Relationship>;
 
@@ -618,6 +659,29 @@ impl RelationshipOps for SchemaGraph {
         Ok(edge_idx)
     }
 
+    fn create_relationship_checked(
+        &mut self,
+        from_table: petgraph::graph::NodeIndex,
+        to_table: petgraph::graph::NodeIndex,
+        relationship: Relationship,
+    ) -> Result<(petgraph::graph::EdgeIndex, Vec<RelationshipIssue>), String> {
+        let issues = match (self.node_weight(from_table), self.node_weight(to_table)) {
+            (Some(from_node), Some(to_node)) => {
+                check_relationship(from_node, to_node, &relationship)
+            }
+            _ => Vec::new(),
+        };
+        if let Some(error) = issues
+            .iter()
+            .find(|issue| issue.severity() == ErrorSeverity::Error)
+        {
+            return Err(error.message.clone());
+        }
+
+        let edge_idx = self.create_relationship(from_table, to_table, relationship)?;
+        Ok((edge_idx, issues))
+    }
+
     fn get_relationship(&self, edge_idx: petgraph::graph::EdgeIndex) -> Option<&Relationship> {
         self.edge_weight(edge_idx)
     }
@@ -0,0 +1,311 @@
+//! Group operations on a selection of tables
+//!
+//! The canvas selects several tables (shift-click, marquee) and moves, aligns,
+//! distributes or deletes them at once. Every function mutates the graph and
+//! returns the [`GraphOperation`]s to broadcast as one batch.
+
+use crate::core::auto_layout::TABLE_WIDTH;
+use crate::core::liveshare::GraphOperation;
+use crate::core::schema::{SchemaGraph, TableOps};
+use crate::core::table_display::displayed_table_height;
+use petgraph::graph::NodeIndex;
+
+/// Edge or center line the selected tables are aligned to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alignment {
+    Left,
+    CenterX,
+    Right,
+    Top,
+    Middle,
+    Bottom,
+}
+
+impl Alignment {
+    pub const ALL: [Alignment; 6] = [
+        Alignment::Left,
+        Alignment::CenterX,
+        Alignment::Right,
+        Alignment::Top,
+        Alignment::Middle,
+        Alignment::Bottom,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Alignment::Left => "Align left",
+            Alignment::CenterX => "Align centers horizontally",
+            Alignment::Right => "Align right",
+            Alignment::Top => "Align top",
+            Alignment::Middle => "Align centers vertically",
+            Alignment::Bottom => "Align bottom",
+        }
+    }
+}
+
+/// Direction in which tables are spaced evenly
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DistributeAxis {
+    Horizontal,
+    Vertical,
+}
+
+/// Rendered bounds of a table at its display level: (left, top, right, bottom)
+pub fn table_bounds(graph: &SchemaGraph, table: NodeIndex) -> Option<(f64, f64, f64, f64)> {
+    let (x, y) = graph.node_weight(table)?.position;
+    Some((
+        x,
+        y,
+        x + TABLE_WIDTH,
+        y + displayed_table_height(graph, table),
+    ))
+}
+
+/// Tables touched by the rectangle between two corners (canvas coordinates)
+pub fn tables_in_rect(graph: &SchemaGraph, a: (f64, f64), b: (f64, f64)) -> Vec<NodeIndex> {
+    let (left, right) = (a.0.min(b.0), a.0.max(b.0));
+    let (top, bottom) = (a.1.min(b.1), a.1.max(b.1));
+    graph
+        .node_indices()
+        .filter(|&idx| {
+            table_bounds(graph, idx)
+                .is_some_and(|(l, t, r, b)| l <= right && r >= left && t <= bottom && b >= top)
+        })
+        .collect()
+}
+
+/// Set positions, returning `MoveTable` for the tables that actually moved
+fn set_positions(
+    graph: &mut SchemaGraph,
+    positions: impl IntoIterator<Item = (NodeIndex, (f64, f64))>,
+) -> Vec<GraphOperation> {
+    let mut ops = Vec::new();
+    for (idx, position) in positions {
+        if let Some(node) = graph.node_weight_mut(idx)
+            && node.position != position
+        {
+            node.position = position;
+            ops.push(GraphOperation::MoveTable {
+                node_id: idx.index() as u32,
+                table_uuid: node.uuid,
+                position,
+            });
+        }
+    }
+    ops
+}
+
+/// Move tables from their `origins` by the same offset
+pub fn move_tables(
+    graph: &mut SchemaGraph,
+    origins: &[(NodeIndex, (f64, f64))],
+    delta: (f64, f64),
+) -> Vec<GraphOperation> {
+    set_positions(
+        graph,
+        origins
+            .iter()
+            .map(|&(idx, (x, y))| (idx, (x + delta.0, y + delta.1))),
+    )
+}
+
+/// Align tables to the outermost edge (or the common center) of the selection
+pub fn align_tables(
+    graph: &mut SchemaGraph,
+    tables: &[NodeIndex],
+    alignment: Alignment,
+) -> Vec<GraphOperation> {
+    let bounds: Vec<_> = tables
+        .iter()
+        .filter_map(|&idx| table_bounds(graph, idx).map(|b| (idx, b)))
+        .collect();
+    if bounds.len() < 2 {
+        return Vec::new();
+    }
+    let min = |f: fn(&(f64, f64, f64, f64)) -> f64| {
+        bounds
+            .iter()
+            .map(|(_, b)| f(b))
+            .fold(f64::INFINITY, f64::min)
+    };
+    let max = |f: fn(&(f64, f64, f64, f64)) -> f64| {
+        bounds
+            .iter()
+            .map(|(_, b)| f(b))
+            .fold(f64::NEG_INFINITY, f64::max)
+    };
+    let (left, top) = (min(|b| b.0), min(|b| b.1));
+    let (right, bottom) = (max(|b| b.2), max(|b| b.3));
+
+    let positions: Vec<_> = bounds
+        .iter()
+        .map(|&(idx, (l, t, r, b))| {
+            let (width, height) = (r - l, b - t);
+            let position = match alignment {
+                Alignment::Left => (left, t),
+                Alignment::CenterX => ((left + right - width) / 2.0, t),
+                Alignment::Right => (right - width, t),
+                Alignment::Top => (l, top),
+                Alignment::Middle => (l, (top + bottom - height) / 2.0),
+                Alignment::Bottom => (l, bottom - height),
+            };
+            (idx, position)
+        })
+        .collect();
+    set_positions(graph, positions)
+}
+
+/// Space tables evenly between the two outermost ones
+///
+/// Gaps between neighbouring tables become equal; the first and the last
+/// table along the axis stay in place. Needs at least three tables.
+pub fn distribute_tables(
+    graph: &mut SchemaGraph,
+    tables: &[NodeIndex],
+    axis: DistributeAxis,
+) -> Vec<GraphOperation> {
+    // (начало, размер) вдоль оси
+    let mut spans: Vec<_> = tables
+        .iter()
+        .filter_map(|&idx| {
+            let (l, t, r, b) = table_bounds(graph, idx)?;
+            Some(match axis {
+                DistributeAxis::Horizontal => (idx, l, r - l),
+                DistributeAxis::Vertical => (idx, t, b - t),
+            })
+        })
+        .collect();
+    if spans.len() < 3 {
+        return Vec::new();
+    }
+    spans.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let (_, first_start, _) = spans[0];
+    let end = spans
+        .iter()
+        .map(|&(_, start, size)| start + size)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let occupied: f64 = spans.iter().map(|&(_, _, size)| size).sum();
+    let gap = (end - first_start - occupied) / (spans.len() - 1) as f64;
+
+    let mut cursor = first_start;
+    let mut positions = Vec::with_capacity(spans.len());
+    for &(idx, _, size) in &spans {
+        let (x, y) = graph[idx].position;
+        positions.push(match axis {
+            DistributeAxis::Horizontal => (idx, (cursor, y)),
+            DistributeAxis::Vertical => (idx, (x, cursor)),
+        });
+        cursor += size + gap;
+    }
+    set_positions(graph, positions)
+}
+
+/// Delete the tables together with their relationships
+pub fn delete_tables(graph: &mut SchemaGraph, tables: &[NodeIndex]) -> Vec<GraphOperation> {
+    let mut ops = Vec::new();
+    for &idx in tables {
+        let Some(table_uuid) = graph.node_weight(idx).map(|n| n.uuid) else {
+            continue;
+        };
+        if graph.delete_table(idx).is_ok() {
+            ops.push(GraphOperation::DeleteTable {
+                node_id: idx.index() as u32,
+                table_uuid,
+            });
+        }
+    }
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Column;
+
+    fn graph_with(positions: &[(f64, f64)]) -> (SchemaGraph, Vec<NodeIndex>) {
+        let mut graph = SchemaGraph::new();
+        let tables = positions
+            .iter()
+            .enumerate()
+            .map(|(i, &position)| {
+                let idx = graph.create_table(format!("t{}", i), position).unwrap();
+                graph[idx].columns.push(Column::new("id", "INT"));
+                idx
+            })
+            .collect();
+        (graph, tables)
+    }
+
+    #[test]
+    fn test_tables_in_rect_uses_table_bounds() {
+        let (graph, tables) = graph_with(&[(0.0, 0.0), (500.0, 0.0), (0.0, 800.0)]);
+
+        // Прямоугольник задевает только правый край первой таблицы
+        let hit = tables_in_rect(&graph, (270.0, 10.0), (300.0, 20.0));
+        assert_eq!(hit, vec![tables[0]]);
+
+        // Углы можно передавать в любом порядке
+        let hit = tables_in_rect(&graph, (900.0, 900.0), (-10.0, -10.0));
+        assert_eq!(hit.len(), 3);
+    }
+
+    #[test]
+    fn test_move_tables_keeps_relative_positions() {
+        let (mut graph, tables) = graph_with(&[(0.0, 0.0), (400.0, 100.0)]);
+        let origins: Vec<_> = tables.iter().map(|&t| (t, graph[t].position)).collect();
+
+        let ops = move_tables(&mut graph, &origins, (50.0, -20.0));
+
+        assert_eq!(ops.len(), 2);
+        assert_eq!(graph[tables[0]].position, (50.0, -20.0));
+        assert_eq!(graph[tables[1]].position, (450.0, 80.0));
+    }
+
+    #[test]
+    fn test_align_left_and_bottom() {
+        let (mut graph, tables) = graph_with(&[(100.0, 0.0), (300.0, 200.0), (50.0, 90.0)]);
+        graph[tables[1]].columns.push(Column::new("name", "TEXT"));
+
+        let ops = align_tables(&mut graph, &tables, Alignment::Left);
+        assert_eq!(ops.len(), 2, "the leftmost table does not move");
+        assert!(tables.iter().all(|&t| graph[t].position.0 == 50.0));
+
+        align_tables(&mut graph, &tables, Alignment::Bottom);
+        let bottoms: Vec<f64> = tables
+            .iter()
+            .map(|&t| table_bounds(&graph, t).unwrap().3)
+            .collect();
+        assert!(bottoms.iter().all(|&b| b == bottoms[0]));
+        assert!(graph[tables[1]].position.1 < graph[tables[0]].position.1);
+    }
+
+    #[test]
+    fn test_align_needs_two_tables() {
+        let (mut graph, tables) = graph_with(&[(100.0, 0.0)]);
+        assert!(align_tables(&mut graph, &tables, Alignment::Top).is_empty());
+    }
+
+    #[test]
+    fn test_distribute_equal_gaps() {
+        let (mut graph, tables) = graph_with(&[(0.0, 0.0), (1000.0, 0.0), (320.0, 50.0)]);
+
+        distribute_tables(&mut graph, &tables, DistributeAxis::Horizontal);
+
+        assert_eq!(graph[tables[0]].position, (0.0, 0.0));
+        assert_eq!(graph[tables[1]].position, (1000.0, 0.0));
+        // Зазоры по 220: 0..280, 500..780, 1000..1280
+        assert_eq!(graph[tables[2]].position, (500.0, 50.0));
+    }
+
+    #[test]
+    fn test_delete_tables() {
+        let (mut graph, tables) = graph_with(&[(0.0, 0.0), (400.0, 0.0), (800.0, 0.0)]);
+
+        let ops = delete_tables(&mut graph, &tables[..2]);
+
+        assert_eq!(ops.len(), 2);
+        assert_eq!(graph.node_count(), 1);
+        assert!(graph.node_weight(tables[2]).is_some());
+    }
+}
@@ -0,0 +1,706 @@
+//! Schema-aware completion, hover and go-to-definition for the SQL Source editor
+//!
+//! Works directly on the editor text, which may be unsaved or not parse yet:
+//! the table registry comes from [`SchemaValidator::from_source`], and the cursor
+//! context is detected from the [`sql_lexer`] tokens of the current statement.
+
+use super::SqlDialect;
+use super::sql_lexer::{self, CONSTRAINT_WORDS, TokenKind, is_ident_char};
+use super::sql_parser::{ColumnInfo, SchemaValidator, SourcePosition, SourceSpan, TableInfo};
+use serde::{Deserialize, Serialize};
+
+/// Kind of completion item
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompletionKind {
+    Table,
+    Column,
+    DataType,
+    Keyword,
+}
+
+/// Single completion suggestion
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompletionItem {
+    pub label: String,
+    pub kind: CompletionKind,
+    /// Short description shown next to the label (column type, column count, ...)
+    pub detail: Option<String>,
+    /// Text inserted in place of the typed prefix
+    pub insert_text: String,
+}
+
+/// Completion result for a cursor position
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompletionList {
+    /// Byte offset where the typed prefix starts (replaced on accept)
+    pub replace_start: usize,
+    /// Byte offset where the typed prefix ends (the cursor)
+    pub replace_end: usize,
+    pub items: Vec<CompletionItem>,
+}
+
+/// Hover summary for a table or column
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HoverInfo {
+    /// Heading, e.g. "Table users" or "Column users.email"
+    pub title: String,
+    /// Summary lines
+    pub lines: Vec<String>,
+    /// Range of the hovered identifier
+    pub span: SourceSpan,
+}
+
+/// DDL keywords offered when nothing more specific fits
+const KEYWORDS: &[&str] = &[
+    "CREATE TABLE",
+    "ALTER TABLE",
+    "DROP TABLE",
+    "IF NOT EXISTS",
+    "IF EXISTS",
+    "PRIMARY KEY",
+    "FOREIGN KEY",
+    "REFERENCES",
+    "CONSTRAINT",
+    "UNIQUE",
+    "NOT NULL",
+    "NULL",
+    "DEFAULT",
+    "CHECK",
+    "INDEX",
+    "ON DELETE",
+    "ON UPDATE",
+    "CASCADE",
+    "SET NULL",
+    "RESTRICT",
+    "NO ACTION",
+    "ADD COLUMN",
+    "DROP COLUMN",
+    "RENAME COLUMN",
+    "RENAME TO",
+    "ADD CONSTRAINT",
+];
+
+/// Simplified SQL token
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// Identifier or keyword (unquoted value)
+    Word { value: String, quoted: bool },
+    /// Single punctuation character
+    Punct(char),
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word { value, quoted: false } if value.eq_ignore_ascii_case(keyword))
+    }
+
+    fn word(&self) -> Option<&str> {
+        match self {
+            Token::Word { value, .. } => Some(value),
+            Token::Punct(_) => None,
+        }
+    }
+}
+
+/// What is expected at the cursor
+#[derive(Clone, Debug, PartialEq)]
+enum CursorContext {
+    /// Table name (after REFERENCES, ALTER TABLE, DROP TABLE)
+    TableName,
+    /// Column of the given table
+    ColumnOf(String),
+    /// Data type (after a column name)
+    DataType,
+    /// Anything else: keywords
+    Keyword,
+    /// Nothing sensible to offer (type arguments, new table name, ...)
+    Nothing,
+}
+
+fn is_quote_char(ch: char) -> bool {
+    ch == '`' || ch == '"'
+}
+
+/// Tokenize SQL text, skipping comments and string literals
+fn tokenize(text: &str) -> Vec<Token> {
+    sql_lexer::tokenize(text)
+        .into_iter()
+        .flat_map(|token| match token.kind {
+            TokenKind::Comment | TokenKind::String => Vec::new(),
+            TokenKind::Word | TokenKind::Number => vec![Token::Word {
+                value: token.text,
+                quoted: false,
+            }],
+            TokenKind::Quoted => vec![Token::Word {
+                value: token.unquoted(),
+                quoted: true,
+            }],
+            TokenKind::Punct | TokenKind::Operator => {
+                token.text.chars().map(Token::Punct).collect()
+            }
+        })
+        .collect()
+}
+
+/// Tokens of the statement that contains `offset`, up to `offset`
+fn statement_tokens(source: &str, offset: usize) -> Vec<Token> {
+    let tokens = tokenize(&source[..offset]);
+    match tokens.iter().rposition(|t| *t == Token::Punct(';')) {
+        Some(pos) => tokens[pos + 1..].to_vec(),
+        None => tokens,
+    }
+}
+
+/// Table that the statement defines or alters (CREATE TABLE x / ALTER TABLE x)
+fn statement_table(tokens: &[Token]) -> Option<String> {
+    let first = tokens.first()?;
+    if !first.is_keyword("CREATE") && !first.is_keyword("ALTER") {
+        return None;
+    }
+    let table_pos = tokens.iter().position(|t| t.is_keyword("TABLE"))?;
+    tokens[table_pos + 1..]
+        .iter()
+        .find(|t| {
+            !(t.is_keyword("IF")
+                || t.is_keyword("NOT")
+                || t.is_keyword("EXISTS")
+                || t.is_keyword("ONLY"))
+        })
+        .and_then(|t| t.word())
+        .map(str::to_string)
+}
+
+/// Index of the innermost unclosed '(' in `tokens`
+fn innermost_open_paren(tokens: &[Token]) -> Option<usize> {
+    let mut stack = Vec::new();
+    for (idx, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Punct('(') => stack.push(idx),
+            Token::Punct(')') => {
+                stack.pop();
+            }
+            _ => {}
+        }
+    }
+    stack.pop()
+}
+
+/// Detect what is expected after `tokens` (the statement text before the cursor word)
+fn cursor_context(tokens: &[Token]) -> CursorContext {
+    let current_table = statement_table(tokens);
+
+    if tokens.last().is_some_and(|t| t.is_keyword("REFERENCES")) {
+        return CursorContext::TableName;
+    }
+
+    if let Some(paren) = innermost_open_paren(tokens) {
+        let before = &tokens[..paren];
+
+        // REFERENCES table ( ...
+        if before.len() >= 2 && before[before.len() - 2].is_keyword("REFERENCES") {
+            return match before[before.len() - 1].word() {
+                Some(table) => CursorContext::ColumnOf(table.to_string()),
+                None => CursorContext::Nothing,
+            };
+        }
+
+        // PRIMARY KEY ( / FOREIGN KEY ( / UNIQUE ( / INDEX name ( ...
+        let is_key_list = before
+            .iter()
+            .rev()
+            .take(3)
+            .any(|t| t.is_keyword("KEY") || t.is_keyword("UNIQUE") || t.is_keyword("INDEX"));
+        if is_key_list {
+            return match current_table {
+                Some(table) => CursorContext::ColumnOf(table),
+                None => CursorContext::Nothing,
+            };
+        }
+
+        // CREATE TABLE name ( ... - inside the table body
+        let is_table_body = tokens.first().is_some_and(|t| t.is_keyword("CREATE"))
+            && innermost_open_paren(before).is_none()
+            && before.iter().any(|t| t.is_keyword("TABLE"));
+        if is_table_body {
+            let element: Vec<&Token> = tokens[paren + 1..]
+                .iter()
+                .rev()
+                .take_while(|t| **t != Token::Punct(','))
+                .collect();
+            return match element.as_slice() {
+                [] => CursorContext::Keyword,
+                [Token::Word { value, quoted }]
+                    if *quoted
+                        || !CONSTRAINT_WORDS
+                            .iter()
+                            .any(|w| value.eq_ignore_ascii_case(w)) =>
+                {
+                    CursorContext::DataType
+                }
+                _ => CursorContext::Keyword,
+            };
+        }
+
+        // Type arguments, function calls, CHECK expressions, ...
+        return CursorContext::Nothing;
+    }
+
+    let last = tokens.last();
+    let prev = tokens.len().checked_sub(2).map(|i| &tokens[i]);
+
+    // ALTER TABLE / DROP TABLE [IF EXISTS]
+    let after_table_keyword = tokens
+        .iter()
+        .rposition(|t| t.is_keyword("TABLE"))
+        .map(|pos| {
+            tokens[pos + 1..]
+                .iter()
+                .all(|t| t.is_keyword("IF") || t.is_keyword("EXISTS") || t.is_keyword("ONLY"))
+        })
+        .unwrap_or(false);
+    if after_table_keyword {
+        let first = tokens.first();
+        if first.is_some_and(|t| t.is_keyword("ALTER") || t.is_keyword("DROP")) {
+            return CursorContext::TableName;
+        }
+        return CursorContext::Nothing;
+    }
+
+    if tokens.first().is_some_and(|t| t.is_keyword("ALTER"))
+        && let Some(table) = current_table
+    {
+        // ALTER TABLE t DROP COLUMN | RENAME COLUMN | ALTER COLUMN | MODIFY | CHANGE
+        let column_follows = last.is_some_and(|t| {
+            t.is_keyword("MODIFY")
+                || t.is_keyword("CHANGE")
+                || (t.is_keyword("COLUMN")
+                    && prev.is_some_and(|p| {
+                        p.is_keyword("DROP")
+                            || p.is_keyword("RENAME")
+                            || p.is_keyword("ALTER")
+                            || p.is_keyword("MODIFY")
+                            || p.is_keyword("CHANGE")
+                    }))
+        });
+        if column_follows {
+            return CursorContext::ColumnOf(table);
+        }
+
+        // ALTER TABLE t ADD [COLUMN] name <type>
+        if let (Some(Token::Word { value, quoted }), Some(prev)) = (last, prev)
+            && (prev.is_keyword("ADD") || prev.is_keyword("COLUMN"))
+            && (*quoted
+                || !CONSTRAINT_WORDS
+                    .iter()
+                    .any(|w| value.eq_ignore_ascii_case(w)))
+            && !value.eq_ignore_ascii_case("COLUMN")
+        {
+            return CursorContext::DataType;
+        }
+    }
+
+    CursorContext::Keyword
+}
+
+/// Range of the identifier prefix that ends at `offset`
+fn prefix_start(source: &str, offset: usize) -> usize {
+    source[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, ch)| is_ident_char(*ch))
+        .last()
+        .map(|(idx, _)| idx)
+        .unwrap_or(offset)
+}
+
+/// Range of the identifier around `offset` (without quotes)
+fn word_at(source: &str, offset: usize) -> Option<(usize, usize)> {
+    let offset = offset.min(source.len());
+    if !source.is_char_boundary(offset) {
+        return None;
+    }
+    let start = prefix_start(source, offset);
+    let end = offset
+        + source[offset..]
+            .char_indices()
+            .find(|(_, ch)| !is_ident_char(*ch))
+            .map(|(idx, _)| idx)
+            .unwrap_or(source.len() - offset);
+    (start < end).then_some((start, end))
+}
+
+/// Keep items whose label starts with the typed prefix (case-insensitive)
+fn filter_items(mut items: Vec<CompletionItem>, prefix: &str) -> Vec<CompletionItem> {
+    let prefix_lower = prefix.to_lowercase();
+    items.retain(|item| item.label.to_lowercase().starts_with(&prefix_lower));
+    items
+}
+
+/// Columns declared so far in the body of an unfinished CREATE TABLE
+///
+/// Returns (name, type) pairs; used when the statement does not parse yet.
+fn body_columns(tokens: &[Token]) -> Vec<(String, Option<String>)> {
+    let Some(body) = tokens.iter().position(|t| *t == Token::Punct('(')) else {
+        return Vec::new();
+    };
+
+    let mut columns = Vec::new();
+    let mut depth = 0;
+    let mut element: Vec<&Token> = Vec::new();
+    let mut push_element = |element: &mut Vec<&Token>| {
+        if let Some(Token::Word { value, quoted }) = element.first()
+            && (*quoted
+                || !CONSTRAINT_WORDS
+                    .iter()
+                    .any(|w| value.eq_ignore_ascii_case(w)))
+        {
+            let data_type = element.get(1).and_then(|t| t.word()).map(str::to_uppercase);
+            columns.push((value.clone(), data_type));
+        }
+        element.clear();
+    };
+
+    for token in &tokens[body + 1..] {
+        match token {
+            Token::Punct('(') => depth += 1,
+            Token::Punct(')') if depth == 0 => break,
+            Token::Punct(')') => depth -= 1,
+            Token::Punct(',') if depth == 0 => {
+                push_element(&mut element);
+                continue;
+            }
+            _ => {}
+        }
+        element.push(token);
+    }
+    push_element(&mut element);
+
+    columns
+}
+
+/// Match the case of what the user is typing for keywords and types
+fn match_case(text: &str, prefix: &str) -> String {
+    let is_lowercase = !prefix.is_empty() && prefix.chars().all(|c| !c.is_uppercase());
+    if is_lowercase {
+        text.to_lowercase()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Completion suggestions at a byte offset in the editor text
+pub fn complete_at(source: &str, offset: usize, dialect: &SqlDialect) -> CompletionList {
+    let offset = offset.min(source.len());
+    if !source.is_char_boundary(offset) {
+        return CompletionList::default();
+    }
+
+    let start = prefix_start(source, offset);
+    let prefix = &source[start..offset];
+    // Close the quote if the user opened one before the prefix
+    let closing_quote = source[..start]
+        .chars()
+        .next_back()
+        .filter(|c| is_quote_char(*c))
+        .map(String::from)
+        .unwrap_or_default();
+
+    let tokens = statement_tokens(source, start - closing_quote.len());
+    let registry = SchemaValidator::from_source(source, dialect.clone());
+
+    let items = match cursor_context(&tokens) {
+        CursorContext::TableName => {
+            let mut tables: Vec<&TableInfo> = registry.tables().collect();
+            tables.sort_by(|a, b| a.name.cmp(&b.name));
+            tables
+                .into_iter()
+                .map(|table| CompletionItem {
+                    label: table.name.clone(),
+                    kind: CompletionKind::Table,
+                    detail: Some(format!("{} columns", table.columns.len())),
+                    insert_text: format!("{}{}", table.name, closing_quote),
+                })
+                .collect()
+        }
+        CursorContext::ColumnOf(table_name) => match registry.table(&table_name) {
+            Some(table) => table
+                .ordered_columns()
+                .map(|column| CompletionItem {
+                    label: column.name.clone(),
+                    kind: CompletionKind::Column,
+                    detail: Some(column_detail(column)),
+                    insert_text: format!("{}{}", column.name, closing_quote),
+                })
+                .collect(),
+            // The table being typed does not parse yet - use its body as written
+            None if tokens.first().is_some_and(|t| t.is_keyword("CREATE"))
+                && statement_table(&tokens).as_deref() == Some(table_name.as_str()) =>
+            {
+                body_columns(&tokens)
+                    .into_iter()
+                    .map(|(name, data_type)| CompletionItem {
+                        insert_text: format!("{}{}", name, closing_quote),
+                        label: name,
+                        kind: CompletionKind::Column,
+                        detail: data_type,
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        },
+        CursorContext::DataType => dialect
+            .data_types()
+            .iter()
+            .map(|data_type| CompletionItem {
+                label: data_type.to_string(),
+                kind: CompletionKind::DataType,
+                detail: Some(format!("{} type", dialect.label())),
+                insert_text: match_case(data_type, prefix),
+            })
+            .collect(),
+        CursorContext::Keyword => {
+            let mut keywords: Vec<&str> = KEYWORDS.to_vec();
+            if *dialect == SqlDialect::MySQL {
+                keywords.push("AUTO_INCREMENT");
+            }
+            keywords
+                .into_iter()
+                .map(|keyword| CompletionItem {
+                    label: keyword.to_string(),
+                    kind: CompletionKind::Keyword,
+                    detail: None,
+                    insert_text: match_case(keyword, prefix),
+                })
+                .collect()
+        }
+        CursorContext::Nothing => Vec::new(),
+    };
+
+    CompletionList {
+        replace_start: start,
+        replace_end: offset,
+        items: filter_items(items, prefix),
+    }
+}
+
+/// Short column description: type and flags
+fn column_detail(column: &ColumnInfo) -> String {
+    let mut detail = column.data_type.clone();
+    if column.is_primary_key {
+        detail.push_str(" PK");
+    } else {
+        if !column.is_nullable {
+            detail.push_str(" NOT NULL");
+        }
+        if column.is_unique {
+            detail.push_str(" UNIQUE");
+        }
+    }
+    detail
+}
+
+/// Symbol under the cursor, resolved against the registry
+enum Symbol<'a> {
+    Table(&'a TableInfo),
+    Column(&'a TableInfo, &'a ColumnInfo),
+}
+
+fn resolve_symbol<'a>(
+    registry: &'a SchemaValidator,
+    source: &str,
+    start: usize,
+    name: &str,
+) -> Option<Symbol<'a>> {
+    // Ignore the opening quote of a quoted identifier
+    let quote_len = source[..start]
+        .chars()
+        .next_back()
+        .filter(|c| is_quote_char(*c))
+        .map_or(0, char::len_utf8);
+    let tokens = statement_tokens(source, start - quote_len);
+    let tokens = &tokens[..];
+
+    match cursor_context(tokens) {
+        CursorContext::ColumnOf(table_name) => {
+            let table = registry.table(&table_name)?;
+            return table.column(name).map(|c| Symbol::Column(table, c));
+        }
+        CursorContext::TableName => {
+            return registry.table(name).map(Symbol::Table);
+        }
+        _ => {}
+    }
+
+    // Column of the table being defined/altered, otherwise a table name
+    if let Some(table) = statement_table(tokens).and_then(|t| registry.table(&t))
+        && let Some(column) = table.column(name)
+    {
+        return Some(Symbol::Column(table, column));
+    }
+    registry.table(name).map(Symbol::Table)
+}
+
+/// Hover summary for the table or column at a byte offset
+pub fn hover_at(source: &str, offset: usize, dialect: &SqlDialect) -> Option<HoverInfo> {
+    let (start, end) = word_at(source, offset)?;
+    let name = &source[start..end];
+    let registry = SchemaValidator::from_source(source, dialect.clone());
+
+    let span = SourceSpan::new(
+        SourcePosition::from_offset(source, start),
+        SourcePosition::from_offset(source, end),
+    );
+
+    match resolve_symbol(&registry, source, start, name)? {
+        Symbol::Table(table) => {
+            let mut lines: Vec<String> = table
+                .ordered_columns()
+                .map(|column| format!("{} {}", column.name, column_detail(column)))
+                .collect();
+            if let Some(pos) = table.source_position {
+                lines.push(format!("Defined at line {}", pos.start.line));
+            }
+            Some(HoverInfo {
+                title: format!("Table {} ({} columns)", table.name, table.columns.len()),
+                lines,
+                span,
+            })
+        }
+        Symbol::Column(table, column) => {
+            let mut lines = vec![column.data_type.clone()];
+            if column.is_primary_key {
+                lines.push("Primary key".to_string());
+            }
+            lines.push(if column.is_nullable {
+                "Nullable".to_string()
+            } else {
+                "Not null".to_string()
+            });
+            if column.is_unique {
+                lines.push("Unique".to_string());
+            }
+            Some(HoverInfo {
+                title: format!("Column {}.{}", table.name, column.name),
+                lines,
+                span,
+            })
+        }
+    }
+}
+
+/// Definition of the table or column referenced at a byte offset
+///
+/// For `REFERENCES users(id)` this is the name of `users` in its CREATE TABLE
+/// (or the `id` column definition).
+pub fn definition_at(source: &str, offset: usize, dialect: &SqlDialect) -> Option<SourceSpan> {
+    let (start, end) = word_at(source, offset)?;
+    let name = &source[start..end];
+    let registry = SchemaValidator::from_source(source, dialect.clone());
+
+    match resolve_symbol(&registry, source, start, name)? {
+        Symbol::Table(table) => table.source_position,
+        Symbol::Column(_, column) => column.source_position,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQL: &str = "CREATE TABLE users (\n    id INT PRIMARY KEY,\n    email VARCHAR(255) NOT NULL UNIQUE\n);\n\nCREATE TABLE posts (\n    id INT PRIMARY KEY,\n    user_id INT NOT NULL,\n    FOREIGN KEY (user_id) REFERENCES users(id)\n);\n";
+
+    fn labels(list: &CompletionList) -> Vec<&str> {
+        list.items.iter().map(|i| i.label.as_str()).collect()
+    }
+
+    #[test]
+    fn test_complete_tables_after_references() {
+        let source = format!(
+            "{}CREATE TABLE c (post_id INT, FOREIGN KEY (post_id) REFERENCES p",
+            SQL
+        );
+        let list = complete_at(&source, source.len(), &SqlDialect::MySQL);
+
+        assert_eq!(labels(&list), vec!["posts"]);
+        assert_eq!(list.replace_start, source.len() - 1);
+    }
+
+    #[test]
+    fn test_complete_columns_in_fk_clauses() {
+        let source = format!("{}CREATE TABLE c (user_id INT, FOREIGN KEY (", SQL);
+        let list = complete_at(&source, source.len(), &SqlDialect::MySQL);
+        assert_eq!(labels(&list), vec!["user_id"]);
+
+        let source = format!(
+            "{}CREATE TABLE c (user_id INT, FOREIGN KEY (user_id) REFERENCES users(",
+            SQL
+        );
+        let list = complete_at(&source, source.len(), &SqlDialect::MySQL);
+        assert_eq!(labels(&list), vec!["id", "email"]);
+    }
+
+    #[test]
+    fn test_complete_data_types_for_dialect() {
+        let source = "CREATE TABLE t (id INT, payload js";
+        let list = complete_at(source, source.len(), &SqlDialect::PostgreSQL);
+        assert_eq!(labels(&list), vec!["JSON", "JSONB"]);
+        assert_eq!(list.items[0].insert_text, "json");
+
+        let list = complete_at(source, source.len(), &SqlDialect::MySQL);
+        assert_eq!(labels(&list), vec!["JSON"]);
+    }
+
+    #[test]
+    fn test_complete_keywords_and_unfinished_text() {
+        // The last statement does not parse, earlier tables are still known
+        let source = format!("{}ALTER TABLE ", SQL);
+        let list = complete_at(&source, source.len(), &SqlDialect::MySQL);
+        assert_eq!(labels(&list), vec!["posts", "users"]);
+
+        let source = "CREATE TABLE t (id INT NOT NU";
+        let list = complete_at(source, source.len(), &SqlDialect::MySQL);
+        assert_eq!(labels(&list), vec!["NULL"]);
+
+        let source = "CREATE TABLE t (id INT def";
+        let list = complete_at(source, source.len(), &SqlDialect::MySQL);
+        assert_eq!(list.items[0].insert_text, "default");
+    }
+
+    #[test]
+    fn test_complete_quoted_identifier() {
+        let source = format!("{}ALTER TABLE `po", SQL);
+        let list = complete_at(&source, source.len(), &SqlDialect::MySQL);
+
+        assert_eq!(labels(&list), vec!["posts"]);
+        assert_eq!(list.items[0].insert_text, "posts`");
+    }
+
+    #[test]
+    fn test_hover_table_and_column() {
+        let offset = SQL.find("REFERENCES users").unwrap() + "REFERENCES ".len() + 1;
+        let hover = hover_at(SQL, offset, &SqlDialect::MySQL).unwrap();
+        assert_eq!(hover.title, "Table users (2 columns)");
+        assert!(
+            hover
+                .lines
+                .contains(&"email VARCHAR(255) NOT NULL UNIQUE".to_string())
+        );
+
+        let offset = SQL.find("user_id INT").unwrap();
+        let hover = hover_at(SQL, offset, &SqlDialect::MySQL).unwrap();
+        assert_eq!(hover.title, "Column posts.user_id");
+    }
+
+    #[test]
+    fn test_definition_from_fk_reference() {
+        let offset = SQL.find("REFERENCES users").unwrap() + "REFERENCES ".len();
+        let span = definition_at(SQL, offset, &SqlDialect::MySQL).unwrap();
+        assert_eq!(&SQL[span.start.offset..span.end.offset], "users");
+        assert_eq!(span.start.line, 1);
+
+        let offset = SQL.find("users(id)").unwrap() + "users(".len();
+        let span = definition_at(SQL, offset, &SqlDialect::MySQL).unwrap();
+        assert_eq!(&SQL[span.start.offset..span.end.offset], "id");
+        assert_eq!(span.start.line, 2);
+    }
+}
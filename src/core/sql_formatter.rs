@@ -0,0 +1,710 @@
+//! Deterministic SQL formatter
+//!
+//! Pretty-prints DDL with configurable keyword case, indentation, column alignment
+//! and comma style. It works on tokens rather than on the AST, so it also formats
+//! SQL the parser rejects and keeps every comment. Comments that precede a statement
+//! stay directly above it, which keeps the `-- Position:` annotations the importer
+//! reads attached to their CREATE TABLE.
+
+use serde::{Deserialize, Serialize};
+use sqlparser::keywords::ALL_KEYWORDS;
+
+use super::sql_lexer::{CONSTRAINT_WORDS, Token, TokenKind, tokenize};
+
+/// Case used for SQL keywords and built-in type names
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeywordCase {
+    #[default]
+    Upper,
+    Lower,
+    /// Keep keywords as written
+    Preserve,
+}
+
+impl KeywordCase {
+    pub const ALL: [KeywordCase; 3] = [
+        KeywordCase::Upper,
+        KeywordCase::Lower,
+        KeywordCase::Preserve,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeywordCase::Upper => "UPPER",
+            KeywordCase::Lower => "lower",
+            KeywordCase::Preserve => "Preserve",
+        }
+    }
+}
+
+/// Where commas go in CREATE TABLE bodies
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommaStyle {
+    /// `id INT,` (comma at the end of the line)
+    #[default]
+    Trailing,
+    /// `, id INT` (comma at the start of the next line)
+    Leading,
+}
+
+impl CommaStyle {
+    pub const ALL: [CommaStyle; 2] = [CommaStyle::Trailing, CommaStyle::Leading];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CommaStyle::Trailing => "Trailing",
+            CommaStyle::Leading => "Leading",
+        }
+    }
+}
+
+/// Formatter settings (stored per diagram)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FormatOptions {
+    pub keyword_case: KeywordCase,
+    /// Spaces per indentation level
+    pub indent: usize,
+    /// Align column names and types in CREATE TABLE bodies
+    pub align_columns: bool,
+    pub comma_style: CommaStyle,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            keyword_case: KeywordCase::Upper,
+            indent: 4,
+            align_columns: true,
+            comma_style: CommaStyle::Trailing,
+        }
+    }
+}
+
+/// Words after which the next word is an identifier
+const IDENT_AFTER: &[&str] = &[
+    "TABLE",
+    "REFERENCES",
+    "CONSTRAINT",
+    "INDEX",
+    "COLUMN",
+    "TO",
+    "EXISTS",
+    "ONLY",
+    "MODIFY",
+];
+
+/// Words that keep a space before an opening parenthesis
+const SPACE_BEFORE_PAREN: &[&str] = &[
+    "KEY", "UNIQUE", "CHECK", "AS", "IN", "VALUES", "USING", "EXISTS", "AND", "OR", "NOT",
+];
+
+/// Table options written as `NAME=value` (no spaces around `=`)
+const TABLE_OPTIONS: &[&str] = &[
+    "ENGINE",
+    "CHARSET",
+    "COLLATE",
+    "AUTO_INCREMENT",
+    "ROW_FORMAT",
+    "COMMENT",
+];
+
+/// Words that continue a data type (`INT UNSIGNED`, `DOUBLE PRECISION`, ...)
+const TYPE_SUFFIXES: &[&str] = &["UNSIGNED", "SIGNED", "ZEROFILL", "VARYING", "PRECISION"];
+
+fn is_keyword(word: &str) -> bool {
+    ALL_KEYWORDS
+        .binary_search(&word.to_uppercase().as_str())
+        .is_ok()
+}
+
+/// Statement tokens with identifier marks and final text
+struct Statement {
+    tokens: Vec<Token>,
+    /// Token is an identifier (never re-cased)
+    ident: Vec<bool>,
+}
+
+impl Statement {
+    fn new(tokens: Vec<Token>) -> Self {
+        let ident = mark_identifiers(&tokens);
+        Self { tokens, ident }
+    }
+
+    /// Token text with keyword case applied
+    fn text(&self, idx: usize, options: &FormatOptions) -> String {
+        let token = &self.tokens[idx];
+        if token.kind != TokenKind::Word || self.ident[idx] || !is_keyword(&token.text) {
+            return token.text.clone();
+        }
+        match options.keyword_case {
+            KeywordCase::Upper => token.text.to_uppercase(),
+            KeywordCase::Lower => token.text.to_lowercase(),
+            KeywordCase::Preserve => token.text.clone(),
+        }
+    }
+
+    /// Join code tokens in `indices` on one line
+    fn join(&self, indices: &[usize], options: &FormatOptions) -> String {
+        let mut out = String::new();
+        let mut prev: Option<usize> = None;
+        let mut prev_prev: Option<usize> = None;
+
+        for &idx in indices {
+            if let Some(p) = prev
+                && needs_space(
+                    prev_prev.map(|pp| &self.tokens[pp]),
+                    &self.tokens[p],
+                    &self.tokens[idx],
+                )
+            {
+                out.push(' ');
+            }
+            out.push_str(&self.text(idx, options));
+            prev_prev = prev;
+            prev = Some(idx);
+        }
+
+        out
+    }
+}
+
+/// Mark tokens that are identifiers, so keyword casing leaves them alone
+fn mark_identifiers(tokens: &[Token]) -> Vec<bool> {
+    let mut ident = vec![false; tokens.len()];
+    let code: Vec<usize> = (0..tokens.len())
+        .filter(|&i| tokens[i].kind != TokenKind::Comment)
+        .collect();
+    let body = table_body(tokens);
+
+    // Parenthesis stack: (is a column list, is the CREATE TABLE body)
+    let mut parens: Vec<(bool, bool)> = Vec::new();
+    let mut element_start = false;
+
+    for (j, &i) in code.iter().enumerate() {
+        let token = &tokens[i];
+        let prev = j.checked_sub(1).map(|p| code[p]);
+        let prev2 = j.checked_sub(2).map(|p| code[p]);
+        let next = code.get(j + 1).map(|&n| &tokens[n]);
+
+        if token.is("(") {
+            let is_list = prev
+                .is_some_and(|p| ident[p] || tokens[p].is_any_word(&["KEY", "UNIQUE", "INDEX"]));
+            let is_body = body.is_some_and(|(open, _)| open == i);
+            parens.push((is_list && !is_body, is_body));
+            element_start = is_body;
+            continue;
+        }
+        if token.is(")") {
+            parens.pop();
+            continue;
+        }
+        if token.is(",") {
+            element_start = parens.last().is_some_and(|(_, is_body)| *is_body);
+            continue;
+        }
+
+        let starts_element = std::mem::take(&mut element_start);
+        if token.kind == TokenKind::Quoted {
+            ident[i] = true;
+            continue;
+        }
+        if token.kind != TokenKind::Word {
+            continue;
+        }
+
+        let prev_token = prev.map(|p| &tokens[p]);
+        let in_column_list = parens.last().is_some_and(|(is_list, _)| *is_list);
+        let after_ident_word = prev_token.is_some_and(|p| p.is_any_word(IDENT_AFTER))
+            && !(token.is_any_word(&["IF", "ONLY"])
+                && next.is_some_and(|n| n.is_any_word(&["EXISTS", "NOT"])))
+            && !token.is_word("COLUMN");
+        let after_on = prev_token.is_some_and(|p| p.is_word("ON"))
+            && !token.is_any_word(&["DELETE", "UPDATE", "COMMIT"]);
+        let after_add = prev_token.is_some_and(|p| p.is_word("ADD"))
+            && !token.is_word("COLUMN")
+            && !token.is_any_word(CONSTRAINT_WORDS);
+        let after_change = prev2.is_some_and(|p| tokens[p].is_word("CHANGE"))
+            || prev_token.is_some_and(|p| p.is_word("CHANGE")) && !token.is_word("COLUMN");
+        let qualified = prev_token.is_some_and(|p| p.is(".")) || next.is_some_and(|n| n.is("."));
+        let column_name = starts_element && !token.is_any_word(CONSTRAINT_WORDS);
+
+        ident[i] = in_column_list
+            || after_ident_word
+            || after_on
+            || after_add
+            || after_change
+            || qualified
+            || column_name;
+    }
+
+    ident
+}
+
+/// Indices of the opening and closing parenthesis of a CREATE TABLE body
+fn table_body(tokens: &[Token]) -> Option<(usize, usize)> {
+    let code: Vec<usize> = (0..tokens.len())
+        .filter(|&i| tokens[i].kind != TokenKind::Comment)
+        .collect();
+    let first = tokens.get(*code.first()?)?;
+    if !first.is_word("CREATE") || !code.iter().take(5).any(|&i| tokens[i].is_word("TABLE")) {
+        return None;
+    }
+
+    let open = *code
+        .iter()
+        .take_while(|&&i| !tokens[i].is_word("AS"))
+        .find(|&&i| tokens[i].is("("))?;
+
+    let mut depth = 0;
+    for &i in code.iter().filter(|&&i| i >= open) {
+        if tokens[i].is("(") {
+            depth += 1;
+        } else if tokens[i].is(")") {
+            depth -= 1;
+            if depth == 0 {
+                return Some((open, i));
+            }
+        }
+    }
+    None
+}
+
+fn needs_space(prev_prev: Option<&Token>, prev: &Token, next: &Token) -> bool {
+    if next.is(",") || next.is(";") || next.is(")") || next.is(".") || prev.is("(") || prev.is(".")
+    {
+        return false;
+    }
+    if prev.is(":") || prev.is("::") || next.is(":") || next.is("::") {
+        return false;
+    }
+    if next.is("[") || prev.is("[") || next.is("]") {
+        return false;
+    }
+    // `ENGINE=InnoDB`
+    if (next.is("=") && prev.is_any_word(TABLE_OPTIONS))
+        || (prev.is("=") && prev_prev.is_some_and(|pp| pp.is_any_word(TABLE_OPTIONS)))
+    {
+        return false;
+    }
+    // Unary sign: `DEFAULT -1`, `(-1`
+    if (prev.is("-") || prev.is("+"))
+        && prev_prev.is_none_or(|pp| {
+            (matches!(pp.kind, TokenKind::Punct | TokenKind::Operator) && !pp.is(")"))
+                || (pp.kind == TokenKind::Word && is_keyword(&pp.text))
+        })
+    {
+        return false;
+    }
+    if next.is("(") {
+        return match prev.kind {
+            TokenKind::Word => prev.is_any_word(SPACE_BEFORE_PAREN),
+            TokenKind::Quoted => false,
+            _ => !prev.is("("),
+        };
+    }
+    true
+}
+
+/// Index just after the data type of a column definition (`code[1..]`)
+fn type_end(statement: &Statement, code: &[usize]) -> usize {
+    let tokens = &statement.tokens;
+    if code.len() < 2 || !matches!(tokens[code[1]].kind, TokenKind::Word | TokenKind::Quoted) {
+        return 1.min(code.len());
+    }
+
+    let mut end = 2;
+    while let Some(&next) = code.get(end) {
+        let token = &tokens[next];
+        if token.is("(") || token.is("[") {
+            let (open, close) = if token.is("(") {
+                ("(", ")")
+            } else {
+                ("[", "]")
+            };
+            let mut depth = 0;
+            while let Some(&i) = code.get(end) {
+                end += 1;
+                if tokens[i].is(open) {
+                    depth += 1;
+                } else if tokens[i].is(close) {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+            }
+        } else if token.is_any_word(TYPE_SUFFIXES) {
+            end += 1;
+        } else if token.is_any_word(&["WITH", "WITHOUT"])
+            && code
+                .get(end + 1)
+                .is_some_and(|&i| tokens[i].is_word("TIME"))
+            && code
+                .get(end + 2)
+                .is_some_and(|&i| tokens[i].is_word("ZONE"))
+        {
+            end += 3;
+        } else {
+            break;
+        }
+    }
+    end
+}
+
+/// One element of a CREATE TABLE body
+struct Element {
+    code: Vec<usize>,
+    leading_comments: Vec<String>,
+    trailing_comments: Vec<String>,
+}
+
+fn format_create_table(
+    statement: &Statement,
+    open: usize,
+    close: usize,
+    options: &FormatOptions,
+    out: &mut Vec<String>,
+) {
+    let tokens = &statement.tokens;
+    let indent = " ".repeat(options.indent);
+
+    // Header: everything before the body, comments go to the end of the line
+    let header: Vec<usize> = (0..=open)
+        .filter(|&i| tokens[i].kind != TokenKind::Comment)
+        .collect();
+    let mut header_line = statement.join(&header, options);
+    if let Some(paren) = header_line.strip_suffix('(') {
+        header_line = format!("{} (", paren.trim_end());
+    }
+    for i in (0..open).filter(|&i| tokens[i].kind == TokenKind::Comment) {
+        header_line.push(' ');
+        header_line.push_str(&tokens[i].text);
+    }
+
+    // Split the body into elements at top-level commas
+    let mut elements: Vec<Element> = Vec::new();
+    let mut current = Element {
+        code: Vec::new(),
+        leading_comments: Vec::new(),
+        trailing_comments: Vec::new(),
+    };
+    // Own-line comments wait for the next element (or the closing parenthesis)
+    let mut pending_comments: Vec<String> = Vec::new();
+    let mut depth = 0;
+    let mut after_comma = false;
+    for (i, token) in tokens.iter().enumerate().take(close).skip(open + 1) {
+        if token.kind == TokenKind::Comment {
+            if token.newline_before {
+                pending_comments.push(token.text.clone());
+            } else if !current.code.is_empty() {
+                current.trailing_comments.push(token.text.clone());
+            } else if let Some(prev) = elements.last_mut().filter(|_| after_comma) {
+                // `id INT, -- comment` belongs to the element before the comma
+                prev.trailing_comments.push(token.text.clone());
+            } else {
+                // `CREATE TABLE t ( -- comment`
+                header_line.push(' ');
+                header_line.push_str(&token.text);
+            }
+            continue;
+        }
+        current.leading_comments.append(&mut pending_comments);
+        if token.is("(") {
+            depth += 1;
+        } else if token.is(")") {
+            depth -= 1;
+        } else if token.is(",") && depth == 0 {
+            let element = std::mem::replace(
+                &mut current,
+                Element {
+                    code: Vec::new(),
+                    leading_comments: Vec::new(),
+                    trailing_comments: Vec::new(),
+                },
+            );
+            elements.push(element);
+            after_comma = true;
+            continue;
+        }
+        after_comma = false;
+        current.code.push(i);
+    }
+    let mut closing_comments = pending_comments;
+    if current.code.is_empty() {
+        closing_comments.splice(0..0, current.leading_comments.drain(..));
+    } else {
+        elements.push(current);
+    }
+
+    // Column definitions: name, type, rest (for alignment)
+    let rows: Vec<(Option<(String, String)>, String)> = elements
+        .iter()
+        .map(|element| {
+            let code = &element.code;
+            let is_column = code.first().is_some_and(|&i| statement.ident[i]);
+            if !is_column {
+                return (None, statement.join(code, options));
+            }
+            let end = type_end(statement, code);
+            let name = statement.join(&code[..1], options);
+            let data_type = statement.join(&code[1..end], options);
+            let rest = statement.join(&code[end..], options);
+            (Some((name, data_type)), rest)
+        })
+        .collect();
+
+    let name_width = rows
+        .iter()
+        .filter_map(|(column, _)| column.as_ref().map(|(n, _)| n.chars().count()))
+        .max()
+        .unwrap_or(0);
+    let type_width = rows
+        .iter()
+        .filter_map(|(column, _)| column.as_ref().map(|(_, t)| t.chars().count()))
+        .max()
+        .unwrap_or(0);
+
+    out.push(header_line);
+    let count = rows.len();
+    for (idx, ((column, rest), element)) in rows.into_iter().zip(&elements).enumerate() {
+        for comment in &element.leading_comments {
+            out.push(format!("{}{}", indent, comment));
+        }
+
+        let mut text = match column {
+            Some((name, data_type)) => {
+                let mut parts = vec![name, data_type, rest];
+                if options.align_columns {
+                    if !parts[2].is_empty() {
+                        parts[1] = pad(&parts[1], type_width);
+                    }
+                    if !parts[1].is_empty() {
+                        parts[0] = pad(&parts[0], name_width);
+                    }
+                }
+                parts
+                    .into_iter()
+                    .filter(|p| !p.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .trim_end()
+                    .to_string()
+            }
+            None => rest,
+        };
+
+        let is_last = idx + 1 == count;
+        text = match options.comma_style {
+            CommaStyle::Trailing if !is_last => format!("{}{},", indent, text),
+            CommaStyle::Trailing => format!("{}{}", indent, text),
+            CommaStyle::Leading if idx == 0 => format!("{}{}", indent, text),
+            CommaStyle::Leading => {
+                format!("{}, {}", " ".repeat(options.indent.saturating_sub(2)), text)
+            }
+        };
+        for comment in &element.trailing_comments {
+            text.push(' ');
+            text.push_str(comment);
+        }
+        out.push(text);
+    }
+    for comment in closing_comments {
+        out.push(format!("{}{}", indent, comment));
+    }
+
+    // Closing parenthesis, table options and comments
+    let tail: Vec<usize> = (close..tokens.len())
+        .filter(|&i| tokens[i].kind != TokenKind::Comment)
+        .collect();
+    let mut closing_line = statement.join(&tail, options);
+    for i in (close..tokens.len()).filter(|&i| tokens[i].kind == TokenKind::Comment) {
+        closing_line.push(' ');
+        closing_line.push_str(&tokens[i].text);
+    }
+    out.push(closing_line);
+}
+
+fn pad(text: &str, width: usize) -> String {
+    let len = text.chars().count();
+    format!("{}{}", text, " ".repeat(width.saturating_sub(len)))
+}
+
+/// Format SQL source
+///
+/// The result does not depend on the input layout (only on tokens, comments and
+/// where empty lines separate statements), so formatting is idempotent.
+pub fn format_sql(source: &str, options: &FormatOptions) -> String {
+    let tokens = tokenize(source);
+    let mut out: Vec<String> = Vec::new();
+    // Previous output item was a multi-line statement
+    let mut after_block = false;
+    let mut after_statement = false;
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = &tokens[i];
+
+        // Comment on the line of the previous statement's `;`
+        if token.kind == TokenKind::Comment && !token.newline_before && after_statement {
+            if let Some(last) = out.last_mut() {
+                last.push(' ');
+                last.push_str(&token.text);
+            }
+            i += 1;
+            continue;
+        }
+
+        if token.kind == TokenKind::Comment {
+            if !out.is_empty() && (token.blank_before || after_block) {
+                out.push(String::new());
+            }
+            out.push(token.text.clone());
+            after_block = false;
+            after_statement = false;
+            i += 1;
+            continue;
+        }
+
+        // Statement: up to and including `;`
+        let start = i;
+        while i < tokens.len() && !tokens[i].is(";") {
+            i += 1;
+        }
+        let end = (i + 1).min(tokens.len());
+        i = end;
+
+        let statement = Statement::new(tokens[start..end].to_vec());
+        let body = table_body(&statement.tokens);
+        let is_block = body.is_some();
+
+        if !out.is_empty()
+            && (statement.tokens[0].blank_before || after_block || (is_block && after_statement))
+        {
+            out.push(String::new());
+        }
+
+        match body {
+            Some((open, close)) => format_create_table(&statement, open, close, options, &mut out),
+            None => {
+                let code: Vec<usize> = (0..statement.tokens.len())
+                    .filter(|&t| statement.tokens[t].kind != TokenKind::Comment)
+                    .collect();
+                let mut line = statement.join(&code, options);
+                for t in (0..statement.tokens.len())
+                    .filter(|&t| statement.tokens[t].kind == TokenKind::Comment)
+                {
+                    line.push(' ');
+                    line.push_str(&statement.tokens[t].text);
+                }
+                out.push(line);
+            }
+        }
+        after_block = is_block;
+        after_statement = true;
+    }
+
+    if out.is_empty() {
+        return String::new();
+    }
+    let mut formatted = out.join("\n");
+    formatted.push('\n');
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_create_table_aligns_columns() {
+        let sql = "create table users (id int primary key, email varchar(255) not null, bio text);";
+        let formatted = format_sql(sql, &FormatOptions::default());
+
+        assert_eq!(
+            formatted,
+            "CREATE TABLE users (\n    id    INT          PRIMARY KEY,\n    email VARCHAR(255) NOT NULL,\n    bio   TEXT\n);\n"
+        );
+    }
+
+    #[test]
+    fn test_format_options() {
+        let sql = "CREATE TABLE `order` (`id` INT NOT NULL, `total` DECIMAL(10,2), PRIMARY KEY (`id`)) ENGINE=InnoDB;";
+        let options = FormatOptions {
+            keyword_case: KeywordCase::Lower,
+            indent: 2,
+            align_columns: false,
+            comma_style: CommaStyle::Leading,
+        };
+
+        assert_eq!(
+            format_sql(sql, &options),
+            "create table `order` (\n  `id` int not null\n, `total` decimal(10, 2)\n, primary key (`id`)\n) engine=InnoDB;\n"
+        );
+    }
+
+    #[test]
+    fn test_identifiers_keep_their_case() {
+        let sql = "CREATE TABLE Events (Name TEXT, Status INT, FOREIGN KEY (Status) REFERENCES Statuses(Id));\nALTER TABLE Events ADD COLUMN Date DATE;";
+        let options = FormatOptions {
+            keyword_case: KeywordCase::Lower,
+            ..Default::default()
+        };
+        let formatted = format_sql(sql, &options);
+
+        assert!(formatted.contains("create table Events ("));
+        assert!(formatted.contains("    Name   text,"));
+        assert!(formatted.contains("foreign key (Status) references Statuses(Id)"));
+        assert!(formatted.contains("alter table Events add column Date date;"));
+    }
+
+    #[test]
+    fn test_comments_are_preserved() {
+        let sql = "-- Database Schema\n\n-- Position: (100, 200)\ncreate table t ( -- header\n  id int, -- the id\n  -- own line\n  name text /* inline */\n  -- before close\n); -- after\nalter table t add x int;\n";
+        let formatted = format_sql(sql, &FormatOptions::default());
+
+        assert_eq!(
+            formatted,
+            "-- Database Schema\n\n-- Position: (100, 200)\nCREATE TABLE t ( -- header\n    id   INT, -- the id\n    -- own line\n    name TEXT /* inline */\n    -- before close\n); -- after\n\nALTER TABLE t ADD x INT;\n"
+        );
+    }
+
+    #[test]
+    fn test_format_is_idempotent_and_keeps_exporter_output_importable() {
+        use crate::core::{ExportOptions, SchemaExporter, SqlDialect, TableOps, create_demo_graph};
+
+        for dialect in SqlDialect::ALL {
+            let graph = create_demo_graph();
+            let options = ExportOptions {
+                sql_dialect: dialect.clone(),
+                ..Default::default()
+            };
+            let exported = SchemaExporter::export_sql(&graph, &options).unwrap();
+            let formatted = format_sql(&exported, &FormatOptions::default());
+            assert_eq!(format_sql(&formatted, &FormatOptions::default()), formatted);
+
+            let mut imported = crate::core::SchemaGraph::new();
+            let result = crate::core::apply_sql_to_graph(&formatted, dialect, &mut imported);
+            assert!(result.success, "{:?}", result.errors);
+
+            let original = graph.find_table_by_name("posts").unwrap();
+            let restored = imported.find_table_by_name("posts").unwrap();
+            assert_eq!(imported[restored].position, graph[original].position);
+            assert_eq!(imported.edge_count(), graph.edge_count());
+        }
+    }
+
+    #[test]
+    fn test_operators_and_literals() {
+        let sql = "CREATE TABLE t (n INT DEFAULT -1 CHECK (n>=-1), s TEXT DEFAULT 'it''s', c TEXT DEFAULT 'a'::text);";
+        let formatted = format_sql(sql, &FormatOptions::default());
+
+        assert!(
+            formatted.contains("DEFAULT -1 CHECK (n >= -1)"),
+            "{}",
+            formatted
+        );
+        assert!(formatted.contains("DEFAULT 'it''s'"), "{}", formatted);
+        assert!(formatted.contains("DEFAULT 'a'::TEXT"), "{}", formatted);
+    }
+}
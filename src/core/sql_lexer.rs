@@ -0,0 +1,214 @@
+//! SQL lexer shared by the formatter and the Source editor completion
+//!
+//! Works on any text, including SQL the parser rejects: comments, string
+//! literals and quoted identifiers are recognized, and every token records
+//! whether it starts a new line so formatting can keep blank lines.
+
+/// Words that start a table constraint rather than a column definition
+pub const CONSTRAINT_WORDS: &[&str] = &[
+    "CONSTRAINT",
+    "PRIMARY",
+    "FOREIGN",
+    "UNIQUE",
+    "KEY",
+    "INDEX",
+    "CHECK",
+    "FULLTEXT",
+    "SPATIAL",
+    "EXCLUDE",
+    "LIKE",
+];
+
+/// Kind of a lexed token
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Identifier or keyword
+    Word,
+    /// Quoted identifier (`` `name` `` or `"name"`), quotes included
+    Quoted,
+    /// String literal, quotes included
+    String,
+    Number,
+    /// Single punctuation character: `(`, `)`, `,`, `;`, `.`
+    Punct,
+    Operator,
+    /// `-- line` or `/* block */` comment
+    Comment,
+}
+
+/// Token with its source text and surrounding line breaks
+#[derive(Clone, Debug)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    /// Token starts on a new line
+    pub newline_before: bool,
+    /// At least one empty line precedes the token
+    pub blank_before: bool,
+}
+
+impl Token {
+    pub fn is(&self, punct: &str) -> bool {
+        matches!(self.kind, TokenKind::Punct | TokenKind::Operator) && self.text == punct
+    }
+
+    pub fn is_word(&self, word: &str) -> bool {
+        self.kind == TokenKind::Word && self.text.eq_ignore_ascii_case(word)
+    }
+
+    pub fn is_any_word(&self, words: &[&str]) -> bool {
+        self.kind == TokenKind::Word && words.iter().any(|w| self.text.eq_ignore_ascii_case(w))
+    }
+
+    /// Identifier value without quotes (an unterminated quote is allowed)
+    pub fn unquoted(&self) -> String {
+        if self.kind != TokenKind::Quoted {
+            return self.text.clone();
+        }
+        let mut chars = self.text.chars();
+        let Some(quote) = chars.next() else {
+            return String::new();
+        };
+        let inner = chars.as_str();
+        let inner = inner.strip_suffix(quote).unwrap_or(inner);
+        let doubled = format!("{quote}{quote}");
+        inner.replace(&doubled, &quote.to_string())
+    }
+}
+
+/// Character that may appear in an unquoted identifier
+pub fn is_ident_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_' || ch == '$'
+}
+
+fn is_operator_char(ch: char) -> bool {
+    "<>=!|&+-*/%^~:".contains(ch)
+}
+
+/// Split SQL text into tokens; never fails, unterminated quotes run to the end
+pub fn tokenize(source: &str) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut newlines = 0;
+
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch.is_whitespace() {
+            if ch == '\n' {
+                newlines += 1;
+            }
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        let kind = if ch == '-' && chars.get(i + 1) == Some(&'-') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            TokenKind::Comment
+        } else if ch == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i = (i + 2).min(chars.len());
+            TokenKind::Comment
+        } else if ch == '\'' || ch == '"' || ch == '`' {
+            i += 1;
+            while i < chars.len() {
+                if chars[i] == ch {
+                    // Doubled quote is an escaped quote
+                    if chars.get(i + 1) == Some(&ch) {
+                        i += 2;
+                        continue;
+                    }
+                    break;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(chars.len());
+            if ch == '\'' {
+                TokenKind::String
+            } else {
+                TokenKind::Quoted
+            }
+        } else if ch.is_ascii_digit() {
+            while i < chars.len() && (is_ident_char(chars[i]) || chars[i] == '.') {
+                i += 1;
+            }
+            TokenKind::Number
+        } else if is_ident_char(ch) {
+            while i < chars.len() && is_ident_char(chars[i]) {
+                i += 1;
+            }
+            TokenKind::Word
+        } else if is_operator_char(ch) {
+            i += 1;
+            while i < chars.len()
+                && is_operator_char(chars[i])
+                && !(chars[i] == '-' && chars.get(i + 1) == Some(&'-'))
+                && !(chars[i] == '/' && chars.get(i + 1) == Some(&'*'))
+                // Keep unary signs separate: `=-1`, `(-1`
+                && !(chars[i] == '-' || chars[i] == '+')
+            {
+                i += 1;
+            }
+            TokenKind::Operator
+        } else {
+            i += 1;
+            TokenKind::Punct
+        };
+
+        tokens.push(Token {
+            kind,
+            text: chars[start..i]
+                .iter()
+                .collect::<String>()
+                .trim_end()
+                .to_string(),
+            newline_before: newlines > 0 || tokens.is_empty(),
+            blank_before: newlines > 1,
+        });
+        newlines = 0;
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_kinds() {
+        let tokens = tokenize("CREATE TABLE `a``b` (x INT DEFAULT 'it''s' -- note\n);");
+        let kinds: Vec<TokenKind> = tokens.iter().map(|t| t.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::Word,
+                TokenKind::Word,
+                TokenKind::Quoted,
+                TokenKind::Punct,
+                TokenKind::Word,
+                TokenKind::Word,
+                TokenKind::Word,
+                TokenKind::String,
+                TokenKind::Comment,
+                TokenKind::Punct,
+                TokenKind::Punct,
+            ]
+        );
+        assert_eq!(tokens[2].unquoted(), "a`b");
+        assert!(tokens[9].newline_before);
+    }
+
+    #[test]
+    fn test_unterminated_quote() {
+        let tokens = tokenize("REFERENCES \"us");
+        assert_eq!(tokens[1].kind, TokenKind::Quoted);
+        assert_eq!(tokens[1].unquoted(), "us");
+    }
+}
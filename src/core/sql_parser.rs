@@ -8,7 +8,15 @@
 //! - Check function for validation on save
 //! - Apply SQL to graph with LiveShare synchronization
 
+use crate::core::export::TypeConversionWarning;
+use crate::core::junction::MANY_TO_MANY_MARKER;
+use crate::core::lint::{LintReport, LintSettings};
 use crate::core::liveshare::{ColumnData, GraphOperation};
+use crate::core::relationship_check::check_relationships;
+use crate::core::validation::{
+    closest_match, dialect_issue_severity, dialects_reserving, identifier_length,
+    identifier_length_unit, is_reserved_keyword, max_identifier_length,
+};
 use crate::core::{Column, ExportOptions, SchemaExporter, SchemaGraph, SqlDialect, TableNode};
 use petgraph::graph::NodeIndex;
 use serde::{Deserialize, Serialize};
@@ -89,6 +97,65 @@ impl SourcePosition {
     }
 }
 
+/// Byte offset of a 1-based (line, column) location as reported by sqlparser spans
+fn offset_at_location(source: &str, line: u64, column: u64) -> Option<usize> {
+    if line == 0 || column == 0 {
+        return None;
+    }
+
+    let mut current_line = 1;
+    let mut line_start = 0;
+    if line > 1 {
+        for (idx, ch) in source.char_indices() {
+            if ch == '\n' {
+                current_line += 1;
+                if current_line == line {
+                    line_start = idx + 1;
+                    break;
+                }
+            }
+        }
+        if current_line != line {
+            return None;
+        }
+    }
+
+    let line_text = &source[line_start..];
+    let column_offset = line_text
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .chain(std::iter::once(line_text.len()))
+        .nth(column as usize - 1)?;
+    Some(line_start + column_offset)
+}
+
+/// End of a column definition that starts before `from`
+///
+/// Scans to the next top-level ',' or ')' and returns the offset right after the
+/// last non-whitespace character before it.
+fn column_definition_end(source: &str, from: usize) -> usize {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut end = source.len();
+
+    for (idx, ch) in source[from..].char_indices() {
+        match (quote, ch) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(ch),
+            (None, '(') => depth += 1,
+            (None, ')') if depth > 0 => depth -= 1,
+            (None, ',' | ')') => {
+                end = from + idx;
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    from + source[from..end].trim_end().len()
+}
+
 /// Span in SQL source code (start and end positions)
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SourceSpan {
@@ -124,6 +191,9 @@ pub struct SqlValidationError {
     pub suggestion: Option<String>,
     /// Related information (e.g., where a referenced table is defined)
     pub related: Vec<RelatedInfo>,
+    /// Machine-applicable fixes (the first one is the preferred fix)
+    #[serde(default)]
+    pub fixes: Vec<CodeFix>,
 }
 
 /// Related information for an error
@@ -133,6 +203,80 @@ pub struct RelatedInfo {
     pub span: Option<SourceSpan>,
 }
 
+/// Text replacement in SQL source (the span offsets are byte offsets)
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextEdit {
+    pub span: SourceSpan,
+    pub new_text: String,
+}
+
+impl TextEdit {
+    /// Replace the text between two byte offsets
+    pub fn replace(source: &str, start: usize, end: usize, new_text: impl Into<String>) -> Self {
+        Self {
+            span: SourceSpan::new(
+                SourcePosition::from_offset(source, start),
+                SourcePosition::from_offset(source, end),
+            ),
+            new_text: new_text.into(),
+        }
+    }
+
+    /// Insert text at a byte offset
+    pub fn insert(source: &str, offset: usize, new_text: impl Into<String>) -> Self {
+        Self::replace(source, offset, offset, new_text)
+    }
+}
+
+/// Machine-applicable fix for a diagnostic
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeFix {
+    /// Short description for the "apply fix" action
+    pub title: String,
+    pub edits: Vec<TextEdit>,
+}
+
+impl CodeFix {
+    pub fn new(title: impl Into<String>, edits: Vec<TextEdit>) -> Self {
+        Self {
+            title: title.into(),
+            edits,
+        }
+    }
+
+    /// Apply this fix to the source it was computed for
+    pub fn apply(&self, source: &str) -> Result<String, String> {
+        apply_text_edits(source, &self.edits)
+    }
+}
+
+/// Apply non-overlapping text edits to source
+pub fn apply_text_edits(source: &str, edits: &[TextEdit]) -> Result<String, String> {
+    let mut sorted: Vec<&TextEdit> = edits.iter().collect();
+    sorted.sort_by_key(|e| (e.span.start.offset, e.span.end.offset));
+
+    let mut output = String::with_capacity(source.len());
+    let mut cursor = 0;
+    for edit in sorted {
+        let (start, end) = (edit.span.start.offset, edit.span.end.offset);
+        if start < cursor || end < start || end > source.len() {
+            return Err(format!(
+                "Edit at line {}, column {} overlaps another edit or is out of range",
+                edit.span.start.line, edit.span.start.column
+            ));
+        }
+        if !source.is_char_boundary(start) || !source.is_char_boundary(end) {
+            return Err("Edit does not fall on a character boundary".to_string());
+        }
+        output.push_str(&source[cursor..start]);
+        output.push_str(&edit.new_text);
+        cursor = end;
+    }
+    output.push_str(&source[cursor..]);
+
+    Ok(output)
+}
+
 impl SqlValidationError {
     pub fn error(message: impl Into<String>, code: impl Into<String>) -> Self {
         Self {
@@ -142,6 +286,7 @@ impl SqlValidationError {
             code: code.into(),
             suggestion: None,
             related: Vec::new(),
+            fixes: Vec::new(),
         }
     }
 
@@ -153,6 +298,7 @@ impl SqlValidationError {
             code: code.into(),
             suggestion: None,
             related: Vec::new(),
+            fixes: Vec::new(),
         }
     }
 
@@ -164,6 +310,7 @@ impl SqlValidationError {
             code: code.into(),
             suggestion: None,
             related: Vec::new(),
+            fixes: Vec::new(),
         }
     }
 
@@ -183,6 +330,11 @@ impl SqlValidationError {
         self
     }
 
+    pub fn with_fix(mut self, fix: CodeFix) -> Self {
+        self.fixes.push(fix);
+        self
+    }
+
     #[allow(dead_code)]
     pub fn with_related(mut self, message: impl Into<String>, span: Option<SourceSpan>) -> Self {
         self.related.push(RelatedInfo {
@@ -329,12 +481,50 @@ impl SqlValidationResult {
                         })
                     }),
                     "suggestion": d.suggestion,
+                    "fixes": d.fixes.iter().map(|fix| {
+                        serde_json::json!({
+                            "title": fix.title,
+                            "edits": fix.edits.iter().map(|edit| {
+                                serde_json::json!({
+                                    "start": { "line": edit.span.start.line, "column": edit.span.start.column },
+                                    "end": { "line": edit.span.end.line, "column": edit.span.end.column },
+                                    "new_text": edit.new_text,
+                                })
+                            }).collect::<Vec<_>>(),
+                        })
+                    }).collect::<Vec<_>>(),
                 })
             }).collect::<Vec<_>>(),
             "summary": self.format_summary()
         })
     }
 
+    /// Apply the preferred fix of every diagnostic ("fix all")
+    ///
+    /// Fixes that overlap an already accepted fix are skipped; re-validating the
+    /// result picks them up again.
+    pub fn fix_all(&self, source: &str) -> Result<String, String> {
+        let mut edits: Vec<TextEdit> = Vec::new();
+        for fix in self.diagnostics.iter().filter_map(|d| d.fixes.first()) {
+            let overlaps = fix.edits.iter().any(|edit| {
+                edits.iter().any(|accepted| {
+                    (edit.span.start.offset < accepted.span.end.offset
+                        && accepted.span.start.offset < edit.span.end.offset)
+                        || edit.span.start.offset == accepted.span.start.offset
+                })
+            });
+            if !overlaps {
+                edits.extend(fix.edits.iter().cloned());
+            }
+        }
+        apply_text_edits(source, &edits)
+    }
+
+    /// Whether any diagnostic has a machine-applicable fix
+    pub fn has_fixes(&self) -> bool {
+        self.diagnostics.iter().any(|d| !d.fixes.is_empty())
+    }
+
     fn format_summary(&self) -> String {
         if self.is_valid {
             format!("SQL is valid. Found {} tables.", self.stats.table_count)
@@ -465,6 +655,7 @@ impl SqlParser {
     }
 
     /// Calculate character offset from line and column
+    #[allow(clippy::explicit_counter_loop)]
     fn calculate_offset(&self, sql: &str, line: usize, column: usize) -> usize {
         let mut current_line = 1;
         let mut offset = 0;
@@ -517,34 +708,62 @@ impl SqlParser {
 // ============================================================================
 
 /// Semantic validator for database schemas
+///
+/// Besides validation, the validator keeps a registry of known tables and columns
+/// (with their positions in the source), which the Source editor uses for completion,
+/// hover and go-to-definition.
 pub struct SchemaValidator {
-    /// Known tables and their columns
+    /// Known tables and their columns (keyed by lowercase unquoted name)
     tables: HashMap<String, TableInfo>,
     /// Source SQL for position calculation
-    #[allow(dead_code)]
     source: String,
+    /// Offset of the currently registered statement in `source`
+    /// (non-zero when statements are parsed one by one)
+    base_offset: usize,
+    /// Dialect used for identifiers in suggested fixes
+    dialect: SqlDialect,
+    /// Lint settings for the convention checks (W004, H001); `None` skips them
+    lint_settings: Option<LintSettings>,
 }
 
-/// Information about a table for validation
+/// Information about a table known to the validator
 #[derive(Clone, Debug)]
-struct TableInfo {
+pub struct TableInfo {
+    /// Table name as written (without quotes)
+    pub name: String,
+    /// Columns keyed by lowercase name
     pub columns: HashMap<String, ColumnInfo>,
-    #[allow(dead_code)]
+    /// Lowercase column names in definition order
+    pub column_order: Vec<String>,
     pub primary_keys: Vec<String>,
-    #[allow(dead_code)]
+    /// Position of the table name in its CREATE TABLE statement
     pub source_position: Option<SourceSpan>,
 }
 
-/// Information about a column for validation
+impl TableInfo {
+    /// Columns in definition order
+    pub fn ordered_columns(&self) -> impl Iterator<Item = &ColumnInfo> {
+        self.column_order
+            .iter()
+            .filter_map(|key| self.columns.get(key))
+    }
+
+    /// Find column by name (case-insensitive, quotes ignored)
+    pub fn column(&self, name: &str) -> Option<&ColumnInfo> {
+        self.columns.get(&strip_quotes(name).to_lowercase())
+    }
+}
+
+/// Information about a column known to the validator
 #[derive(Clone, Debug)]
-struct ColumnInfo {
-    #[allow(dead_code)]
+pub struct ColumnInfo {
+    /// Column name as written (without quotes)
+    pub name: String,
     pub data_type: String,
-    #[allow(dead_code)]
     pub is_nullable: bool,
-    #[allow(dead_code)]
     pub is_primary_key: bool,
-    #[allow(dead_code)]
+    pub is_unique: bool,
+    /// Position of the column name in its definition
     pub source_position: Option<SourceSpan>,
 }
 
@@ -553,9 +772,34 @@ impl SchemaValidator {
         Self {
             tables: HashMap::new(),
             source: source.to_string(),
+            base_offset: 0,
+            dialect: SqlDialect::default(),
+            lint_settings: None,
         }
     }
 
+    /// Set the dialect used for identifiers in suggested fixes
+    pub fn with_dialect(mut self, dialect: SqlDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Run the convention checks (reserved keywords, missing primary keys)
+    /// that are enabled in `settings`
+    pub fn with_lint_settings(mut self, settings: LintSettings) -> Self {
+        self.lint_settings = Some(settings);
+        self
+    }
+
+    /// Effective severity of a convention check, `None` if it does not run
+    fn check_severity(&self, code: &str, default: ErrorSeverity) -> Option<ErrorSeverity> {
+        let settings = self.lint_settings.as_ref()?;
+        if !settings.is_enabled(code) {
+            return None;
+        }
+        Some(settings.severity.get(code).copied().unwrap_or(default))
+    }
+
     /// Build validator from existing schema graph
     pub fn from_graph(graph: &SchemaGraph, source: &str) -> Self {
         let mut validator = Self::new(source);
@@ -563,15 +807,20 @@ impl SchemaValidator {
         for node_idx in graph.node_indices() {
             if let Some(table) = graph.node_weight(node_idx) {
                 let mut columns = HashMap::new();
+                let mut column_order = Vec::new();
                 let mut primary_keys = Vec::new();
 
                 for col in &table.columns {
+                    let key = col.name.to_lowercase();
+                    column_order.push(key.clone());
                     columns.insert(
-                        col.name.to_lowercase(),
+                        key,
                         ColumnInfo {
+                            name: col.name.clone(),
                             data_type: col.data_type.clone(),
                             is_nullable: col.is_nullable,
                             is_primary_key: col.is_primary_key,
+                            is_unique: col.is_unique,
                             source_position: None,
                         },
                     );
@@ -583,7 +832,9 @@ impl SchemaValidator {
                 validator.tables.insert(
                     table.name.to_lowercase(),
                     TableInfo {
+                        name: table.name.clone(),
                         columns,
+                        column_order,
                         primary_keys,
                         source_position: None,
                     },
@@ -594,6 +845,236 @@ impl SchemaValidator {
         validator
     }
 
+    /// Build the table registry from (possibly unfinished) source text
+    ///
+    /// If the whole text does not parse, statements are parsed one by one and
+    /// every CREATE TABLE that parses is registered. This keeps completion and
+    /// hover working while the user is typing.
+    pub fn from_source(source: &str, dialect: SqlDialect) -> Self {
+        let parser = SqlParser::new(dialect.clone());
+        let mut validator = Self::new(source).with_dialect(dialect);
+
+        if let Ok(statements) = parser.parse(source) {
+            for stmt in &statements {
+                if let Statement::CreateTable(create_table) = stmt {
+                    validator.register_table_from_ast(create_table);
+                }
+            }
+            return validator;
+        }
+
+        let mut chunk_start = 0;
+        for chunk in source.split_inclusive(';') {
+            if let Ok(statements) = parser.parse(chunk) {
+                validator.base_offset = chunk_start;
+                for stmt in &statements {
+                    if let Statement::CreateTable(create_table) = stmt {
+                        validator.register_table_from_ast(create_table);
+                    }
+                }
+            }
+            chunk_start += chunk.len();
+        }
+        validator.base_offset = 0;
+
+        validator
+    }
+
+    /// All known tables
+    pub fn tables(&self) -> impl Iterator<Item = &TableInfo> {
+        self.tables.values()
+    }
+
+    /// Find table by name (case-insensitive, quotes ignored)
+    pub fn table(&self, name: &str) -> Option<&TableInfo> {
+        self.tables.get(&strip_quotes(name).to_lowercase())
+    }
+
+    /// Registry key for a table name from the AST
+    fn table_key(name: &ObjectName) -> String {
+        Self::display_name(name).to_lowercase()
+    }
+
+    /// Unquoted table name from the AST (last part of a qualified name)
+    fn display_name(name: &ObjectName) -> String {
+        name.0
+            .last()
+            .and_then(|part| part.as_ident())
+            .map(|ident| ident.value.clone())
+            .unwrap_or_else(|| strip_quotes(&name.to_string()))
+    }
+
+    /// Byte range of an identifier in the source (including quotes)
+    fn ident_range(&self, ident: &sqlparser::ast::Ident) -> Option<(usize, usize)> {
+        self.ident_span(ident)
+            .map(|span| (span.start.offset, span.end.offset))
+            .filter(|(start, end)| start < end)
+    }
+
+    /// Fix that replaces an identifier with another name, keeping its quoting
+    fn rename_fix(&self, ident: &sqlparser::ast::Ident, new_name: &str) -> Option<CodeFix> {
+        let (start, end) = self.ident_range(ident)?;
+        let new_text = match ident.quote_style {
+            Some(_) => self.dialect.quote_identifier(new_name),
+            None => new_name.to_string(),
+        };
+        Some(CodeFix::new(
+            format!("Change to '{}'", new_name),
+            vec![TextEdit::replace(&self.source, start, end, new_text)],
+        ))
+    }
+
+    /// Check an identifier against the reserved words and length limits of each dialect
+    ///
+    /// Severities come from `dialect_issue_severity`, as in the table and column editors.
+    fn check_identifier(
+        &self,
+        ident: &sqlparser::ast::Ident,
+        kind: &str,
+        result: &mut SqlValidationResult,
+    ) {
+        let span = self.ident_range(ident).map(|(start, end)| {
+            SourceSpan::new(
+                SourcePosition::from_offset(&self.source, start),
+                SourcePosition::from_offset(&self.source, end),
+            )
+        });
+        let diagnostic = |in_target: bool, message: String, code: &str| {
+            let mut diagnostic = SqlValidationError::warning(message, code);
+            diagnostic.severity = dialect_issue_severity(in_target);
+            match span {
+                Some(span) => diagnostic.with_span(span),
+                None => diagnostic,
+            }
+        };
+
+        for dialect in SqlDialect::ALL {
+            let Some(max) = max_identifier_length(&dialect) else {
+                continue;
+            };
+            let actual = identifier_length(&ident.value, &dialect);
+            if actual > max {
+                result.add_error(
+                    diagnostic(
+                        dialect == self.dialect,
+                        format!(
+                            "{} name '{}' is too long for {} ({} {}, max {})",
+                            kind,
+                            ident.value,
+                            dialect.label(),
+                            actual,
+                            identifier_length_unit(&dialect),
+                            max
+                        ),
+                        "W005_IDENTIFIER_TOO_LONG",
+                    )
+                    .with_suggestion(format!(
+                        "Use a name of at most {} {}",
+                        max,
+                        identifier_length_unit(&dialect)
+                    )),
+                );
+            }
+        }
+
+        if ident.quote_style.is_some() {
+            return;
+        }
+        let reserving = dialects_reserving(&ident.value);
+        let breaks_in = if !reserving.is_empty() {
+            reserving
+                .iter()
+                .map(|d| d.label())
+                .collect::<Vec<_>>()
+                .join(", ")
+        } else if is_reserved_keyword(&ident.value) {
+            "the SQL standard".to_string()
+        } else {
+            return;
+        };
+
+        let default_severity = dialect_issue_severity(reserving.contains(&self.dialect));
+        let Some(severity) = self.check_severity("W004_RESERVED_KEYWORD", default_severity) else {
+            return;
+        };
+
+        let quoted = self.dialect.quote_identifier(&ident.value);
+        let mut diagnostic = diagnostic(
+            reserving.contains(&self.dialect),
+            format!(
+                "{} name '{}' is a reserved keyword in {}",
+                kind, ident.value, breaks_in
+            ),
+            "W004_RESERVED_KEYWORD",
+        )
+        .with_suggestion(format!("Quote it as {} or choose another name", quoted));
+        diagnostic.severity = severity;
+        if let Some((start, end)) = self.ident_range(ident) {
+            diagnostic = diagnostic.with_fix(CodeFix::new(
+                format!("Quote as {}", quoted),
+                vec![TextEdit::replace(&self.source, start, end, quoted)],
+            ));
+        }
+        result.add_error(diagnostic);
+    }
+
+    /// Fix for a table without primary key: mark `id` as PK or add an `id` column
+    fn missing_primary_key_fix(
+        &self,
+        create_table: &sqlparser::ast::CreateTable,
+    ) -> Option<CodeFix> {
+        if let Some(id_column) = create_table
+            .columns
+            .iter()
+            .find(|c| c.name.value.eq_ignore_ascii_case("id"))
+        {
+            let (_, name_end) = self.ident_range(&id_column.name)?;
+            let end = column_definition_end(&self.source, name_end);
+            return Some(CodeFix::new(
+                "Make 'id' the primary key",
+                vec![TextEdit::insert(&self.source, end, " PRIMARY KEY")],
+            ));
+        }
+
+        let first = create_table.columns.first()?;
+        let (start, _) = self.ident_range(&first.name)?;
+        let line_start = self.source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let indent = &self.source[line_start..start];
+        let separator = if indent.trim().is_empty() {
+            format!(",\n{}", indent)
+        } else {
+            ", ".to_string()
+        };
+        let id_type = match self.dialect {
+            SqlDialect::MySQL => "INT",
+            SqlDialect::PostgreSQL | SqlDialect::SQLite => "INTEGER",
+        };
+        Some(CodeFix::new(
+            "Add 'id' primary key column",
+            vec![TextEdit::insert(
+                &self.source,
+                start,
+                format!(
+                    "{} {} PRIMARY KEY{}",
+                    self.dialect.quote_identifier("id"),
+                    id_type,
+                    separator
+                ),
+            )],
+        ))
+    }
+
+    /// Convert an identifier's parser span into a source span
+    fn ident_span(&self, ident: &sqlparser::ast::Ident) -> Option<SourceSpan> {
+        let local = &self.source[self.base_offset.min(self.source.len())..];
+        let start = offset_at_location(local, ident.span.start.line, ident.span.start.column)?;
+        let end = offset_at_location(local, ident.span.end.line, ident.span.end.column)?;
+        Some(SourceSpan::new(
+            SourcePosition::from_offset(&self.source, self.base_offset + start),
+            SourcePosition::from_offset(&self.source, self.base_offset + end),
+        ))
+    }
+
     /// Validate parsed statements semantically
     pub fn validate(&mut self, statements: &[Statement]) -> SqlValidationResult {
         let mut result = SqlValidationResult::new();
@@ -635,8 +1116,9 @@ impl SchemaValidator {
 
     /// Register a table from CREATE TABLE AST
     fn register_table_from_ast(&mut self, create_table: &sqlparser::ast::CreateTable) {
-        let table_name = create_table.name.to_string().to_lowercase();
+        let table_name = Self::table_key(&create_table.name);
         let mut columns = HashMap::new();
+        let mut column_order = Vec::new();
         let mut primary_keys = Vec::new();
 
         for column in &create_table.columns {
@@ -653,41 +1135,70 @@ impl SchemaValidator {
                 .iter()
                 .any(|opt| matches!(opt.option, ColumnOption::NotNull));
 
+            let is_unique = column
+                .options
+                .iter()
+                .any(|opt| matches!(opt.option, ColumnOption::Unique(_)));
+
             if is_pk {
                 primary_keys.push(column.name.value.clone());
             }
 
+            column_order.push(col_name.clone());
             columns.insert(
                 col_name,
                 ColumnInfo {
+                    name: column.name.value.clone(),
                     data_type: column.data_type.to_string(),
                     is_nullable,
                     is_primary_key: is_pk,
-                    source_position: None,
+                    is_unique,
+                    source_position: self.ident_span(&column.name),
                 },
             );
         }
 
-        // Check table constraints for primary keys
+        // Check table constraints for primary keys and unique columns
         for constraint in &create_table.constraints {
-            if let sqlparser::ast::TableConstraint::PrimaryKey(pk_constraint) = constraint {
-                for col in &pk_constraint.columns {
-                    // IndexColumn has an expr field, we need to extract column name from it
-                    let col_name = col.column.to_string();
-                    primary_keys.push(col_name.clone());
+            match constraint {
+                sqlparser::ast::TableConstraint::PrimaryKey(pk_constraint) => {
+                    for col in &pk_constraint.columns {
+                        // IndexColumn has an expr field, we need to extract column name from it
+                        let col_name = strip_quotes(&col.column.to_string());
+                        primary_keys.push(col_name.clone());
+                        if let Some(col_info) = columns.get_mut(&col_name.to_lowercase()) {
+                            col_info.is_primary_key = true;
+                            col_info.is_nullable = false;
+                        }
+                    }
+                }
+                sqlparser::ast::TableConstraint::Unique(unique_constraint)
+                    if unique_constraint.columns.len() == 1 =>
+                {
+                    let col_name = strip_quotes(&unique_constraint.columns[0].column.to_string());
                     if let Some(col_info) = columns.get_mut(&col_name.to_lowercase()) {
-                        col_info.is_primary_key = true;
+                        col_info.is_unique = true;
                     }
                 }
+                _ => {}
             }
         }
 
+        let source_position = create_table
+            .name
+            .0
+            .last()
+            .and_then(|part| part.as_ident())
+            .and_then(|ident| self.ident_span(ident));
+
         self.tables.insert(
             table_name,
             TableInfo {
+                name: Self::display_name(&create_table.name),
                 columns,
+                column_order,
                 primary_keys,
-                source_position: None,
+                source_position,
             },
         );
     }
@@ -698,12 +1209,17 @@ impl SchemaValidator {
         create_table: &sqlparser::ast::CreateTable,
         result: &mut SqlValidationResult,
     ) {
-        let table_name = create_table.name.to_string();
+        let table_name = Self::display_name(&create_table.name);
+
+        if let Some(ident) = create_table.name.0.last().and_then(|part| part.as_ident()) {
+            self.check_identifier(ident, "Table", result);
+        }
 
         // Check column names
         let mut seen_columns: HashSet<String> = HashSet::new();
         for column in &create_table.columns {
             let col_name = column.name.value.to_lowercase();
+            self.check_identifier(&column.name, "Column", result);
 
             // Check for duplicate column names
             if !seen_columns.insert(col_name.clone()) {
@@ -723,6 +1239,38 @@ impl SchemaValidator {
             self.validate_data_type(&column.data_type, result);
         }
 
+        // Tables without a primary key
+        let has_primary_key = self
+            .tables
+            .get(&Self::table_key(&create_table.name))
+            .is_some_and(|t| !t.primary_keys.is_empty());
+        let missing_key_severity =
+            self.check_severity("H001_MISSING_PRIMARY_KEY", ErrorSeverity::Hint);
+        if let Some(severity) = missing_key_severity
+            && !has_primary_key
+            && !create_table.columns.is_empty()
+        {
+            let mut hint = SqlValidationError::hint(
+                format!("Table '{}' has no primary key", table_name),
+                "H001_MISSING_PRIMARY_KEY",
+            )
+            .with_suggestion("Add a PRIMARY KEY so rows can be identified and referenced");
+            hint.severity = severity;
+            if let Some(span) = create_table
+                .name
+                .0
+                .last()
+                .and_then(|part| part.as_ident())
+                .and_then(|ident| self.ident_span(ident))
+            {
+                hint = hint.with_span(span);
+            }
+            if let Some(fix) = self.missing_primary_key_fix(create_table) {
+                hint = hint.with_fix(fix);
+            }
+            result.add_error(hint);
+        }
+
         // Validate foreign key constraints
         for constraint in &create_table.constraints {
             if let sqlparser::ast::TableConstraint::ForeignKey(fk_constraint) = constraint {
@@ -746,8 +1294,8 @@ impl SchemaValidator {
         to_columns: &[sqlparser::ast::Ident],
         result: &mut SqlValidationResult,
     ) {
-        let to_table_name = to_table.to_string().to_lowercase();
-        let to_table_str = to_table.to_string();
+        let to_table_name = Self::table_key(to_table);
+        let to_table_str = Self::display_name(to_table);
 
         // Check if referenced table exists
         if !self.tables.contains_key(&to_table_name) {
@@ -765,6 +1313,13 @@ impl SchemaValidator {
             } else if let Some(span) = self.find_foreign_key_position(from_table, &to_table_str) {
                 error = error.with_span(span);
             }
+            let table_names: Vec<&str> = self.tables.values().map(|t| t.name.as_str()).collect();
+            if let Some(ident) = to_table.0.last().and_then(|part| part.as_ident())
+                && let Some(closest) = closest_match(&to_table_str, table_names)
+                && let Some(fix) = self.rename_fix(ident, closest)
+            {
+                error = error.with_fix(fix);
+            }
             result.add_error(error);
             result.stats.relationship_count += 1;
             return;
@@ -794,9 +1349,19 @@ impl SchemaValidator {
                         .join(", ")
                 ));
                 // Try to find position of column reference
-                if let Some(span) = self.find_position_in_source(&col.value) {
+                if let Some(span) = self
+                    .ident_span(col)
+                    .or_else(|| self.find_position_in_source(&col.value))
+                {
                     error = error.with_span(span);
                 }
+                if let Some(closest) = closest_match(
+                    &col.value,
+                    table_info.ordered_columns().map(|c| c.name.as_str()),
+                ) && let Some(fix) = self.rename_fix(col, closest)
+                {
+                    error = error.with_fix(fix);
+                }
                 result.add_error(error);
             }
         }
@@ -817,6 +1382,7 @@ impl SchemaValidator {
     }
 
     /// Validate data type
+    #[allow(clippy::collapsible_match)]
     fn validate_data_type(&self, data_type: &DataType, result: &mut SqlValidationResult) {
         // Check for common data type issues
         match data_type {
@@ -854,8 +1420,8 @@ impl SchemaValidator {
         operations: &[AlterTableOperation],
         result: &mut SqlValidationResult,
     ) {
-        let name = table_name.to_string().to_lowercase();
-        let table_name_str = table_name.to_string();
+        let name = Self::table_key(table_name);
+        let table_name_str = Self::display_name(table_name);
 
         // Check if table exists
         if !self.tables.contains_key(&name) {
@@ -916,6 +1482,19 @@ impl SchemaValidator {
                         ));
                     }
                 }
+                // Внешний ключ из ALTER TABLE проверяется так же, как в CREATE TABLE
+                AlterTableOperation::AddConstraint {
+                    constraint: sqlparser::ast::TableConstraint::ForeignKey(fk_constraint),
+                    ..
+                } => {
+                    self.validate_foreign_key(
+                        &table_name_str,
+                        &fk_constraint.foreign_table,
+                        &fk_constraint.columns,
+                        &fk_constraint.referred_columns,
+                        result,
+                    );
+                }
                 _ => {}
             }
         }
@@ -924,8 +1503,8 @@ impl SchemaValidator {
     /// Validate DROP statement
     fn validate_drop(&self, names: &[ObjectName], result: &mut SqlValidationResult) {
         for name in names {
-            let table_name = name.to_string().to_lowercase();
-            let table_name_str = name.to_string();
+            let table_name = Self::table_key(name);
+            let table_name_str = Self::display_name(name);
             if !self.tables.contains_key(&table_name) {
                 let mut warning = SqlValidationError::warning(
                     format!("Dropping non-existent table '{}'", name),
@@ -966,6 +1545,7 @@ impl SchemaValidator {
         // Search for "ALTER TABLE `table_name`" or "ALTER TABLE table_name"
         let patterns = [
             format!("ALTER TABLE `{}`", table_name),
+            format!("ALTER TABLE \"{}\"", table_name),
             format!("ALTER TABLE {}", table_name),
         ];
 
@@ -985,6 +1565,7 @@ impl SchemaValidator {
         // Search for patterns and return position of table name only
         let patterns = [
             (format!("references `{}`", table_lower), 12), // "REFERENCES `" = 12 chars
+            (format!("references \"{}\"", table_lower), 12), // "REFERENCES \"" = 12 chars
             (format!("references {}", table_lower), 11),   // "REFERENCES " = 11 chars
         ];
 
@@ -1009,14 +1590,16 @@ impl SchemaValidator {
         ];
 
         // First try to find ALTER TABLE ... ADD CONSTRAINT ... FOREIGN KEY ... REFERENCES to_table
-        let alter_pattern = format!("ALTER TABLE `{}` ADD CONSTRAINT", from_table);
-        let alter_pattern2 = format!("ALTER TABLE {} ADD CONSTRAINT", from_table);
+        let alter_patterns = [
+            format!("ALTER TABLE `{}` ADD CONSTRAINT", from_table),
+            format!("ALTER TABLE \"{}\" ADD CONSTRAINT", from_table),
+            format!("ALTER TABLE {} ADD CONSTRAINT", from_table),
+        ];
 
-        if let Some(span) = self.find_position_in_source(&alter_pattern) {
-            return Some(span);
-        }
-        if let Some(span) = self.find_position_in_source(&alter_pattern2) {
-            return Some(span);
+        for pattern in &alter_patterns {
+            if let Some(span) = self.find_position_in_source(pattern) {
+                return Some(span);
+            }
         }
 
         for pattern in &patterns {
@@ -1031,6 +1614,7 @@ impl SchemaValidator {
     fn find_drop_table_position(&self, table_name: &str) -> Option<SourceSpan> {
         let patterns = [
             format!("DROP TABLE `{}`", table_name),
+            format!("DROP TABLE \"{}\"", table_name),
             format!("DROP TABLE {}", table_name),
         ];
 
@@ -1049,6 +1633,28 @@ impl SchemaValidator {
 
 /// Full SQL validation combining syntax and semantic checks
 pub fn validate_sql(sql: &str, dialect: SqlDialect) -> SqlValidationResult {
+    let validator = SchemaValidator::new(sql).with_dialect(dialect.clone());
+    validate_sql_with_validator(sql, dialect, validator)
+}
+
+/// Full SQL validation plus the convention checks enabled in the lint settings
+/// (reserved keywords, missing primary keys)
+pub fn validate_sql_with_lint(
+    sql: &str,
+    dialect: SqlDialect,
+    settings: &LintSettings,
+) -> SqlValidationResult {
+    let validator = SchemaValidator::new(sql)
+        .with_dialect(dialect.clone())
+        .with_lint_settings(settings.clone());
+    validate_sql_with_validator(sql, dialect, validator)
+}
+
+fn validate_sql_with_validator(
+    sql: &str,
+    dialect: SqlDialect,
+    mut validator: SchemaValidator,
+) -> SqlValidationResult {
     let parser = SqlParser::new(dialect);
 
     // First, validate syntax
@@ -1059,7 +1665,6 @@ pub fn validate_sql(sql: &str, dialect: SqlDialect) -> SqlValidationResult {
     }
 
     // Then, validate semantics
-    let mut validator = SchemaValidator::new(sql);
     let semantic_result = validator.validate(&result.statements);
 
     // Merge results
@@ -1080,7 +1685,7 @@ pub fn validate_sql_with_graph(
     dialect: SqlDialect,
     graph: &SchemaGraph,
 ) -> SqlValidationResult {
-    let parser = SqlParser::new(dialect);
+    let parser = SqlParser::new(dialect.clone());
 
     // First, validate syntax
     let mut result = parser.validate_syntax(sql);
@@ -1090,7 +1695,7 @@ pub fn validate_sql_with_graph(
     }
 
     // Then, validate semantics with existing graph context
-    let mut validator = SchemaValidator::from_graph(graph, sql);
+    let mut validator = SchemaValidator::from_graph(graph, sql).with_dialect(dialect);
     let semantic_result = validator.validate(&result.statements);
 
     // Merge results
@@ -1126,7 +1731,100 @@ pub fn check_schema_sql(graph: &SchemaGraph, dialect: SqlDialect) -> SqlValidati
         }
     };
 
-    validate_sql(&sql, dialect)
+    let mut result = validate_sql(&sql, dialect.clone());
+    for warning in check_type_conversions(graph, &dialect, &sql).diagnostics {
+        result.add_error(warning);
+    }
+    for issue in check_relationships(graph) {
+        result.add_error(issue.to_diagnostic());
+    }
+    result.is_valid = result.stats.error_count == 0;
+    result
+}
+
+/// Report column types that do not map cleanly to the target dialect
+///
+/// `source` is the DDL rendered for that dialect; it is used to point each warning
+/// at the converted type in the source view.
+pub fn check_type_conversions(
+    graph: &SchemaGraph,
+    dialect: &SqlDialect,
+    source: &str,
+) -> SqlValidationResult {
+    let mut result = SqlValidationResult::new();
+
+    for warning in SchemaExporter::type_conversion_warnings(graph, dialect) {
+        let mut diagnostic = SqlValidationError::warning(
+            format!(
+                "Column '{}.{}': {} is rendered as {} in {}",
+                warning.table,
+                warning.column,
+                warning.original_type,
+                warning.converted_type,
+                dialect.label()
+            ),
+            "W003_TYPE_CONVERSION",
+        )
+        .with_suggestion(warning.message.clone());
+
+        if let Some(span) = find_column_type_position(source, dialect, &warning) {
+            diagnostic = diagnostic.with_span(span);
+        }
+        result.add_error(diagnostic);
+    }
+
+    result.is_valid = result.stats.error_count == 0;
+    result
+}
+
+/// Find the converted type of a column inside its CREATE TABLE statement
+fn find_column_type_position(
+    source: &str,
+    dialect: &SqlDialect,
+    warning: &TypeConversionWarning,
+) -> Option<SourceSpan> {
+    // Formatted SQL may use another keyword case, aligned columns and `DECIMAL(10, 2)`
+    let lower = source.to_ascii_lowercase();
+    let table_header = format!(
+        "create table {}",
+        dialect
+            .quote_identifier(&warning.table)
+            .to_ascii_lowercase()
+    );
+    let column_name = dialect
+        .quote_identifier(&warning.column)
+        .to_ascii_lowercase();
+
+    let table_offset = lower.find(&table_header)?;
+    let column_offset = table_offset + lower[table_offset..].find(&format!("{} ", column_name))?;
+    let after_name = column_offset + column_name.len();
+    let type_start =
+        after_name + (source[after_name..].len() - source[after_name..].trim_start().len());
+
+    // Match the type text, ignoring whitespace differences
+    let mut type_end = type_start;
+    let mut source_chars = source[type_start..].char_indices().peekable();
+    for expected in warning
+        .converted_type
+        .chars()
+        .filter(|c| !c.is_whitespace())
+    {
+        while source_chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        match source_chars.next() {
+            Some((idx, c)) if c.eq_ignore_ascii_case(&expected) => {
+                type_end = type_start + idx + c.len_utf8();
+            }
+            _ => {
+                type_end = type_start + warning.converted_type.len();
+                break;
+            }
+        }
+    }
+
+    Some(SourceSpan::new(
+        SourcePosition::from_offset(source, type_start),
+        SourcePosition::from_offset(source, type_end.min(source.len())),
+    ))
 }
 
 // ============================================================================
@@ -1261,7 +1959,6 @@ impl CanvasNotification {
         }
     }
 
-    #[allow(dead_code)]
     pub fn info(title: impl Into<String>, message: impl Into<String>) -> Self {
         Self {
             notification_type: NotificationType::Info,
@@ -1303,6 +2000,27 @@ impl CanvasNotification {
         }
     }
 
+    /// Summary of a lint run (success if there are no issues)
+    pub fn from_lint_report(report: &LintReport) -> Self {
+        if report.is_empty() {
+            return Self::success("Lint Passed", "Schema follows all enabled lint rules");
+        }
+        let errors = report.count(ErrorSeverity::Error);
+        let warnings = report.count(ErrorSeverity::Warning);
+        let hints = report.count(ErrorSeverity::Hint);
+        let message = format!(
+            "{} errors, {} warnings, {} hints. Check the source editor for details.",
+            errors, warnings, hints
+        );
+        if errors > 0 {
+            Self::error("Lint Issues", message)
+        } else if warnings > 0 {
+            Self::warning("Lint Issues", message)
+        } else {
+            Self::info("Lint Hints", message)
+        }
+    }
+
     pub fn from_validation_result(result: &SqlValidationResult) -> Self {
         if result.is_valid {
             if result.has_warnings() {
@@ -1395,6 +2113,37 @@ fn strip_quotes(name: &str) -> String {
         .to_string()
 }
 
+/// Names of the tables marked as exported N:M relationships
+///
+/// The marker comment precedes `CREATE TABLE`, possibly with other comments
+/// (such as the position) in between.
+pub fn many_to_many_tables(sql: &str) -> Vec<String> {
+    let lines: Vec<&str> = sql.lines().collect();
+    let mut tables = Vec::new();
+
+    for (line_idx, line) in lines.iter().enumerate() {
+        let line_lower = line.trim_start().to_lowercase();
+        let Some(rest) = line_lower.strip_prefix("create table") else {
+            continue;
+        };
+        let marked = lines[..line_idx]
+            .iter()
+            .rev()
+            .map(|l| l.trim())
+            .take_while(|l| l.starts_with("--"))
+            .any(|l| l == MANY_TO_MANY_MARKER);
+        if marked
+            && let Some(name) = strip_quotes(rest.trim())
+                .split(|c: char| c.is_whitespace() || c == '(')
+                .next()
+                .map(strip_quotes)
+        {
+            tables.push(name);
+        }
+    }
+    tables
+}
+
 /// Parse position from SQL comment like "-- Position: (100.0, 200.0)"
 fn parse_position_from_sql(sql: &str, table_name: &str) -> Option<(f64, f64)> {
     // Find the CREATE TABLE statement for this table
@@ -1484,6 +2233,13 @@ pub fn apply_sql_to_graph(
     // Clear existing graph and rebuild from SQL
     // This ensures the graph matches the SQL exactly
     let old_table_count = graph.node_count();
+    // Удаление старых таблиц тоже операции: их рассылают участникам и откатывают в истории
+    for idx in graph.node_indices() {
+        graph_ops.push(GraphOperation::DeleteTable {
+            node_id: idx.index() as u32,
+            table_uuid: graph[idx].uuid,
+        });
+    }
     graph.clear();
 
     // Track table name to node index mapping
@@ -1787,6 +2543,11 @@ pub fn apply_sql_to_graph(
         }
     }
 
+    // Relationship consistency (types, cardinality, nullability)
+    for issue in check_relationships(graph) {
+        warnings.push(issue.message);
+    }
+
     if old_table_count > 0 && graph.node_count() > 0 {
         applied.insert(
             0,
@@ -1955,6 +2716,29 @@ ALTER TABLE `orders` ADD CONSTRAINT `fk_orders_users` FOREIGN KEY (`user_id`) RE
         );
     }
 
+    #[test]
+    fn test_alter_table_foreign_key_is_validated() {
+        let sql = r#"
+CREATE TABLE users (id INT PRIMARY KEY);
+CREATE TABLE orders (id INT PRIMARY KEY, user_id INT);
+ALTER TABLE `orders` ADD CONSTRAINT `fk_orders_users` FOREIGN KEY (`user_id`) REFERENCES `users`(`id`);
+ALTER TABLE `orders` ADD CONSTRAINT `fk_orders_owner` FOREIGN KEY (`user_id`) REFERENCES `users`(`owner_id`);
+        "#;
+        let result = validate_sql(sql, SqlDialect::MySQL);
+
+        let codes: Vec<&str> = result.diagnostics.iter().map(|d| d.code.as_str()).collect();
+        assert!(!codes.contains(&"E003_UNKNOWN_TABLE"), "{:?}", codes);
+        assert_eq!(
+            codes
+                .iter()
+                .filter(|&&c| c == "E004_UNKNOWN_COLUMN")
+                .count(),
+            1,
+            "{:?}",
+            codes
+        );
+    }
+
     #[test]
     fn test_foreign_key_valid() {
         let sql = r#"
@@ -2041,6 +2825,28 @@ ALTER TABLE `orders` ADD CONSTRAINT `fk_orders_users` FOREIGN KEY (`user_id`) RE
         assert_eq!(graph.node_count(), 0);
     }
 
+    #[test]
+    fn test_apply_sql_emits_delete_for_replaced_tables() {
+        let mut graph = SchemaGraph::new();
+        apply_sql_to_graph(
+            "CREATE TABLE users (id INT PRIMARY KEY);",
+            SqlDialect::MySQL,
+            &mut graph,
+        );
+        let old_uuid = graph.node_weights().next().unwrap().uuid;
+
+        let result = apply_sql_to_graph(
+            "CREATE TABLE users (id INT PRIMARY KEY);",
+            SqlDialect::MySQL,
+            &mut graph,
+        );
+
+        assert!(matches!(
+            result.graph_ops.first(),
+            Some(GraphOperation::DeleteTable { table_uuid, .. }) if *table_uuid == old_uuid
+        ));
+    }
+
     #[test]
     fn test_apply_sql_invalid() {
         let mut graph = SchemaGraph::new();
@@ -2110,6 +2916,35 @@ ALTER TABLE `orders` ADD CONSTRAINT `fk_orders_users` FOREIGN KEY (`user_id`) RE
         assert_eq!(graph.edge_count(), 1); // One foreign key relationship
     }
 
+    #[test]
+    fn test_apply_sql_reports_relationship_consistency() {
+        let mut graph = SchemaGraph::new();
+        let sql = r#"
+            CREATE TABLE users (
+                id INT NOT NULL,
+                email VARCHAR(255),
+                PRIMARY KEY (id)
+            );
+            CREATE TABLE posts (
+                id INT NOT NULL,
+                author_email VARCHAR(255) NOT NULL,
+                PRIMARY KEY (id),
+                CONSTRAINT fk_posts_author FOREIGN KEY (author_email) REFERENCES users(email)
+            );
+        "#;
+        let result = apply_sql_to_graph(sql, SqlDialect::MySQL, &mut graph);
+
+        assert!(result.success);
+        assert_eq!(graph.edge_count(), 1);
+        assert!(result.warnings.iter().any(|w| w.contains("not unique")));
+        assert!(
+            result
+                .warnings
+                .iter()
+                .any(|w| w.contains("references nullable column users.email"))
+        );
+    }
+
     #[test]
     fn test_apply_sql_with_alter_table_foreign_key() {
         let mut graph = SchemaGraph::new();
@@ -2361,4 +3196,213 @@ ALTER TABLE `users` ADD CONSTRAINT `fk_users_id_posts` FOREIGN KEY (`id`) REFERE
         assert!(table.is_some());
         assert_eq!(table.unwrap().name, "new_table");
     }
+
+    #[test]
+    fn test_postgresql_roundtrip() {
+        use crate::core::TableOps;
+
+        let mut graph = crate::core::create_demo_graph();
+        let options = ExportOptions {
+            sql_dialect: SqlDialect::PostgreSQL,
+            include_positions: true,
+            ..Default::default()
+        };
+        let exported = SchemaExporter::export_sql(&graph, &options).unwrap();
+
+        let validation = validate_sql(&exported, SqlDialect::PostgreSQL);
+        assert!(validation.is_valid, "{}", validation.format_for_display());
+
+        let result = apply_sql_to_graph(&exported, SqlDialect::PostgreSQL, &mut graph);
+        assert!(result.success, "{:?}", result.errors);
+        assert_eq!(graph.node_count(), 3);
+        assert!(graph.find_table_by_name("users").is_some());
+    }
+
+    #[test]
+    fn test_check_type_conversions_points_at_type() {
+        use crate::core::TableOps;
+
+        let mut graph = SchemaGraph::new();
+        let idx = graph.create_table("orders", (0.0, 0.0)).unwrap();
+        graph[idx]
+            .columns
+            .push(Column::new("id", "INT").primary_key());
+        graph[idx]
+            .columns
+            .push(Column::new("status", "ENUM('new','paid')"));
+
+        let options = ExportOptions {
+            sql_dialect: SqlDialect::PostgreSQL,
+            ..Default::default()
+        };
+        let sql = SchemaExporter::export_sql(&graph, &options).unwrap();
+        let result = check_type_conversions(&graph, &SqlDialect::PostgreSQL, &sql);
+
+        assert_eq!(result.stats.warning_count, 1);
+        let diag = &result.diagnostics[0];
+        assert_eq!(diag.code, "W003_TYPE_CONVERSION");
+        let span = diag.span.as_ref().unwrap();
+        assert_eq!(&sql[span.start.offset..span.end.offset], "VARCHAR(255)");
+
+        let schema_check = check_schema_sql(&graph, SqlDialect::PostgreSQL);
+        assert!(schema_check.is_valid);
+        assert_eq!(schema_check.stats.warning_count, 1);
+    }
+
+    fn first_fix<'a>(result: &'a SqlValidationResult, code: &str) -> &'a CodeFix {
+        result
+            .diagnostics
+            .iter()
+            .find(|d| d.code == code)
+            .and_then(|d| d.fixes.first())
+            .unwrap_or_else(|| panic!("no fix for {}", code))
+    }
+
+    #[test]
+    fn test_fix_unknown_referenced_column_and_table() {
+        let sql = "CREATE TABLE users (id INT PRIMARY KEY);\nCREATE TABLE posts (id INT PRIMARY KEY, user_id INT, FOREIGN KEY (user_id) REFERENCES users(idd));\nCREATE TABLE comments (id INT PRIMARY KEY, post_id INT, FOREIGN KEY (post_id) REFERENCES `post`(id));";
+        let result = validate_sql(sql, SqlDialect::MySQL);
+        assert!(!result.is_valid);
+
+        let column_fix = first_fix(&result, "E004_UNKNOWN_COLUMN");
+        assert_eq!(column_fix.title, "Change to 'id'");
+        let table_fix = first_fix(&result, "E003_UNKNOWN_TABLE");
+        assert_eq!(table_fix.edits[0].new_text, "`posts`");
+
+        let fixed = result.fix_all(sql).unwrap();
+        assert!(fixed.contains("REFERENCES users(id)"));
+        assert!(fixed.contains("REFERENCES `posts`(id)"));
+        assert!(validate_sql(&fixed, SqlDialect::MySQL).is_valid);
+    }
+
+    #[test]
+    fn test_fix_reserved_keyword_quotes_identifier() {
+        let sql = "CREATE TABLE events (id INT PRIMARY KEY, date DATE)";
+        let result = validate_sql_with_lint(sql, SqlDialect::PostgreSQL, &LintSettings::default());
+
+        let fix = first_fix(&result, "W004_RESERVED_KEYWORD");
+        let fixed = fix.apply(sql).unwrap();
+        assert_eq!(
+            fixed,
+            "CREATE TABLE events (id INT PRIMARY KEY, \"date\" DATE)"
+        );
+    }
+
+    #[test]
+    fn test_reserved_keyword_depends_on_dialect() {
+        let sql = "CREATE TABLE user (id INT PRIMARY KEY)";
+        let find = |result: &SqlValidationResult, code: &str| {
+            result.diagnostics.iter().find(|d| d.code == code).cloned()
+        };
+
+        let result = validate_sql_with_lint(sql, SqlDialect::MySQL, &LintSettings::default());
+        let diag = find(&result, "W004_RESERVED_KEYWORD").unwrap();
+        assert_eq!(diag.severity, ErrorSeverity::Hint);
+        assert_eq!(
+            diag.message,
+            "Table name 'user' is a reserved keyword in PostgreSQL"
+        );
+        assert_eq!(diag.fixes[0].edits[0].new_text, "`user`");
+
+        let sql = format!(
+            "CREATE TABLE t (id INT PRIMARY KEY, \"{}\" INT)",
+            "c".repeat(64)
+        );
+        let result = validate_sql_with_lint(&sql, SqlDialect::PostgreSQL, &LintSettings::default());
+        let diag = find(&result, "W005_IDENTIFIER_TOO_LONG").unwrap();
+        assert_eq!(diag.severity, ErrorSeverity::Warning);
+        assert!(
+            diag.message
+                .contains("too long for PostgreSQL (64 bytes, max 63)")
+        );
+        assert!(find(&result, "W004_RESERVED_KEYWORD").is_none());
+    }
+
+    #[test]
+    fn test_fix_missing_primary_key() {
+        let sql = "CREATE TABLE tags (\n    id INT NOT NULL,\n    label VARCHAR(50)\n)";
+        let result = validate_sql_with_lint(sql, SqlDialect::MySQL, &LintSettings::default());
+        let fixed = first_fix(&result, "H001_MISSING_PRIMARY_KEY")
+            .apply(sql)
+            .unwrap();
+        assert!(fixed.contains("id INT NOT NULL PRIMARY KEY,"));
+
+        let sql = "CREATE TABLE tags (\n    label VARCHAR(50)\n)";
+        let result = validate_sql_with_lint(sql, SqlDialect::MySQL, &LintSettings::default());
+        let fixed = first_fix(&result, "H001_MISSING_PRIMARY_KEY")
+            .apply(sql)
+            .unwrap();
+        assert_eq!(
+            fixed,
+            "CREATE TABLE tags (\n    `id` INT PRIMARY KEY,\n    label VARCHAR(50)\n)"
+        );
+        assert_eq!(
+            validate_sql_with_lint(&fixed, SqlDialect::MySQL, &LintSettings::default())
+                .stats
+                .hint_count,
+            0
+        );
+    }
+
+    #[test]
+    fn test_convention_checks_follow_lint_settings() {
+        let sql = "CREATE TABLE user (name TEXT)";
+        let codes = |result: &SqlValidationResult| {
+            result
+                .diagnostics
+                .iter()
+                .map(|d| (d.code.clone(), d.severity))
+                .collect::<Vec<_>>()
+        };
+
+        // Plain validation does not run the convention checks
+        assert!(
+            validate_sql(sql, SqlDialect::PostgreSQL)
+                .diagnostics
+                .is_empty()
+        );
+
+        let mut settings = LintSettings::default();
+        let result = validate_sql_with_lint(sql, SqlDialect::PostgreSQL, &settings);
+        assert_eq!(
+            codes(&result),
+            vec![
+                ("W004_RESERVED_KEYWORD".to_string(), ErrorSeverity::Warning),
+                ("H001_MISSING_PRIMARY_KEY".to_string(), ErrorSeverity::Hint),
+            ]
+        );
+
+        settings.set_enabled("H001_MISSING_PRIMARY_KEY", false);
+        settings.set_severity("W004_RESERVED_KEYWORD", Some(ErrorSeverity::Hint));
+        let result = validate_sql_with_lint(sql, SqlDialect::PostgreSQL, &settings);
+        assert_eq!(
+            codes(&result),
+            vec![("W004_RESERVED_KEYWORD".to_string(), ErrorSeverity::Hint)]
+        );
+    }
+
+    #[test]
+    fn test_format_for_llm_includes_fixes() {
+        let sql = "CREATE TABLE users (id INT PRIMARY KEY);\nCREATE TABLE posts (id INT PRIMARY KEY, user_id INT, FOREIGN KEY (user_id) REFERENCES users(uid));";
+        let json = validate_sql(sql, SqlDialect::MySQL).format_for_llm();
+
+        let fix = &json["diagnostics"][0]["fixes"][0];
+        assert_eq!(fix["title"], "Change to 'id'");
+        assert_eq!(fix["edits"][0]["new_text"], "id");
+        assert_eq!(fix["edits"][0]["start"]["line"], 2);
+    }
+
+    #[test]
+    fn test_apply_text_edits_rejects_overlap() {
+        let source = "abcdef";
+        let edits = vec![
+            TextEdit::replace(source, 1, 4, "X"),
+            TextEdit::replace(source, 3, 5, "Y"),
+        ];
+        assert!(apply_text_edits(source, &edits).is_err());
+        assert_eq!(
+            apply_text_edits(source, &edits[..1]).unwrap(),
+            "aXef".to_string()
+        );
+    }
 }
@@ -0,0 +1,249 @@
+//! Table display levels: how much of a table the canvas shows
+//!
+//! Big tables can be shown with all columns, with key columns only (primary,
+//! unique and relationship columns), as a name-only header, or collapsed to a
+//! `+N columns` row. The level is stored per table in the diagram document and
+//! drives every table geometry: rendered height, auto-layout spacing and the
+//! rows relationships attach to. A relationship on a hidden column attaches to
+//! the `+N columns` row, or to the header when the table shows its name only.
+
+use std::collections::HashSet;
+
+use petgraph::Direction;
+use petgraph::graph::NodeIndex;
+use serde::{Deserialize, Serialize};
+
+use crate::core::auto_layout::{TABLE_HEADER_HEIGHT, estimated_table_height};
+use crate::core::liveshare::GraphOperation;
+use crate::core::schema::SchemaGraph;
+
+/// Maximum height of the column list (`max-h-96`), longer lists scroll
+pub const MAX_COLUMN_LIST_HEIGHT: f64 = 384.0;
+
+/// How much of a table is drawn
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TableDisplay {
+    /// Every column
+    #[default]
+    Full,
+    /// Primary key, unique and relationship columns
+    Keys,
+    /// Header only
+    NameOnly,
+    /// Header and a `+N columns` row
+    Collapsed,
+}
+
+impl TableDisplay {
+    pub const ALL: [TableDisplay; 4] = [
+        TableDisplay::Full,
+        TableDisplay::Keys,
+        TableDisplay::NameOnly,
+        TableDisplay::Collapsed,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TableDisplay::Full => "All columns",
+            TableDisplay::Keys => "Keys only",
+            TableDisplay::NameOnly => "Name only",
+            TableDisplay::Collapsed => "Collapsed",
+        }
+    }
+
+    /// Next level, in [`ALL`](Self::ALL) order (the table header button cycles through them)
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|d| d == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Rows of a table as drawn on the canvas
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableRows {
+    pub display: TableDisplay,
+    /// Indices of the shown columns, in column order
+    pub columns: Vec<usize>,
+    /// Number of columns not shown
+    pub hidden: usize,
+}
+
+impl TableRows {
+    /// Rows of `table` at its display level (empty if the table does not exist)
+    pub fn of(graph: &SchemaGraph, table: NodeIndex) -> Self {
+        let Some(node) = graph.node_weight(table) else {
+            return Self::default();
+        };
+        let columns: Vec<usize> = match node.display {
+            TableDisplay::Full => (0..node.columns.len()).collect(),
+            TableDisplay::Keys => {
+                let related = relationship_columns(graph, table);
+                node.columns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| {
+                        c.is_primary_key || c.is_unique || related.contains(c.name.as_str())
+                    })
+                    .map(|(i, _)| i)
+                    .collect()
+            }
+            TableDisplay::NameOnly | TableDisplay::Collapsed => Vec::new(),
+        };
+        Self {
+            display: node.display,
+            hidden: node.columns.len() - columns.len(),
+            columns,
+        }
+    }
+
+    /// Whether a `+N columns` row closes the column list
+    pub fn has_summary_row(&self) -> bool {
+        self.hidden > 0 && self.display != TableDisplay::NameOnly
+    }
+
+    /// Rows of the column list, including the `+N columns` row
+    pub fn row_count(&self) -> usize {
+        self.columns.len() + usize::from(self.has_summary_row())
+    }
+
+    /// Row showing the column at `index`, or the `+N columns` row for hidden columns
+    ///
+    /// `None` when the column has no row to attach to (name-only tables).
+    pub fn row_of(&self, index: usize) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|&i| i == index)
+            .or_else(|| self.has_summary_row().then_some(self.columns.len()))
+    }
+
+    /// Rendered height of the table
+    ///
+    /// Long column lists scroll, so the height stops at [`MAX_COLUMN_LIST_HEIGHT`].
+    pub fn height(&self) -> f64 {
+        if self.display == TableDisplay::NameOnly {
+            TABLE_HEADER_HEIGHT
+        } else {
+            estimated_table_height(self.row_count())
+                .min(TABLE_HEADER_HEIGHT + MAX_COLUMN_LIST_HEIGHT)
+        }
+    }
+}
+
+/// Rendered height of `table` at its display level
+pub fn displayed_table_height(graph: &SchemaGraph, table: NodeIndex) -> f64 {
+    TableRows::of(graph, table).height()
+}
+
+/// Columns of `table` used by its relationships
+fn relationship_columns(graph: &SchemaGraph, table: NodeIndex) -> HashSet<&str> {
+    let outgoing = graph
+        .edges_directed(table, Direction::Outgoing)
+        .map(|e| e.weight().from_column.as_str());
+    let incoming = graph
+        .edges_directed(table, Direction::Incoming)
+        .map(|e| e.weight().to_column.as_str());
+    outgoing.chain(incoming).collect()
+}
+
+/// Set the display level of `tables`; returns operations for the changed tables
+pub fn set_table_display(
+    graph: &mut SchemaGraph,
+    tables: &[NodeIndex],
+    display: TableDisplay,
+) -> Vec<GraphOperation> {
+    tables
+        .iter()
+        .filter_map(|&idx| {
+            let node = graph.node_weight_mut(idx)?;
+            if node.display == display {
+                return None;
+            }
+            node.display = display;
+            Some(GraphOperation::SetTableDisplay {
+                node_id: idx.index() as u32,
+                table_uuid: node.uuid,
+                display,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{TableOps, create_demo_graph};
+
+    fn demo_table(graph: &SchemaGraph, name: &str) -> NodeIndex {
+        graph.find_table_by_name(name).unwrap()
+    }
+
+    #[test]
+    fn test_keys_show_key_and_relationship_columns() {
+        let mut graph = create_demo_graph();
+        let posts = demo_table(&graph, "posts");
+        set_table_display(&mut graph, &[posts], TableDisplay::Keys);
+
+        let rows = TableRows::of(&graph, posts);
+        let names: Vec<&str> = rows
+            .columns
+            .iter()
+            .map(|&i| graph[posts].columns[i].name.as_str())
+            .collect();
+        assert!(names.contains(&"id"));
+        assert!(names.contains(&"user_id"));
+        assert!(!names.contains(&"title"));
+        assert_eq!(rows.hidden, graph[posts].columns.len() - names.len());
+        assert_eq!(rows.row_count(), names.len() + 1);
+    }
+
+    #[test]
+    fn test_hidden_columns_attach_to_summary_row() {
+        let mut graph = create_demo_graph();
+        let users = demo_table(&graph, "users");
+        let column_count = graph[users].columns.len();
+
+        set_table_display(&mut graph, &[users], TableDisplay::Collapsed);
+        let collapsed = TableRows::of(&graph, users);
+        assert_eq!(collapsed.hidden, column_count);
+        assert_eq!(collapsed.row_of(0), Some(0));
+        assert_eq!(collapsed.height(), estimated_table_height(1));
+
+        set_table_display(&mut graph, &[users], TableDisplay::NameOnly);
+        let name_only = TableRows::of(&graph, users);
+        assert_eq!(name_only.row_of(0), None);
+        assert_eq!(name_only.height(), TABLE_HEADER_HEIGHT);
+
+        set_table_display(&mut graph, &[users], TableDisplay::Full);
+        assert_eq!(
+            displayed_table_height(&graph, users),
+            estimated_table_height(column_count)
+        );
+    }
+
+    #[test]
+    fn test_long_column_lists_scroll() {
+        let mut graph = SchemaGraph::new();
+        let mut node = crate::core::TableNode::new("wide");
+        node.columns = (0..40)
+            .map(|i| crate::core::Column::new(format!("column_{}", i), "INT"))
+            .collect();
+        let wide = graph.add_node(node);
+        assert_eq!(
+            displayed_table_height(&graph, wide),
+            TABLE_HEADER_HEIGHT + MAX_COLUMN_LIST_HEIGHT
+        );
+    }
+
+    #[test]
+    fn test_set_table_display_reports_changed_tables() {
+        let mut graph = create_demo_graph();
+        let tables: Vec<NodeIndex> = graph.node_indices().collect();
+
+        let ops = set_table_display(&mut graph, &tables[..1], TableDisplay::Collapsed);
+        assert_eq!(ops.len(), 1);
+        // Уже свёрнутая таблица не даёт операции
+        let ops = set_table_display(&mut graph, &tables, TableDisplay::Collapsed);
+        assert_eq!(ops.len(), tables.len() - 1);
+        assert_eq!(TableDisplay::Collapsed.next(), TableDisplay::Full);
+    }
+}
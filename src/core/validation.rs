@@ -2,6 +2,7 @@
 //!
 //! Implements standard database naming rules compatible with MySQL, PostgreSQL, and other RDBMS.
 
+use super::{ErrorSeverity, SqlDialect};
 use std::collections::HashSet;
 use std::sync::LazyLock;
 
@@ -12,7 +13,8 @@ pub const MAX_IDENTIFIER_LENGTH: usize = 64;
 pub const MIN_IDENTIFIER_LENGTH: usize = 1;
 
 /// SQL reserved keywords that cannot be used as identifiers without quoting
-/// This is a combined list from MySQL, PostgreSQL, and SQL standard
+/// This is a combined list from MySQL, PostgreSQL, and SQL standard,
+/// used when no target dialect is known (see `validate_identifier_for`)
 static RESERVED_KEYWORDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
     [
         // SQL Standard
@@ -183,6 +185,446 @@ static RESERVED_KEYWORDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
     .collect()
 });
 
+/// Words that MySQL 8 reserves (non-reserved keywords like DATE or TEXT are allowed)
+static MYSQL_RESERVED: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    [
+        "ACCESSIBLE",
+        "ADD",
+        "ALL",
+        "ALTER",
+        "ANALYZE",
+        "AND",
+        "AS",
+        "ASC",
+        "ASENSITIVE",
+        "BEFORE",
+        "BETWEEN",
+        "BIGINT",
+        "BINARY",
+        "BLOB",
+        "BOTH",
+        "BY",
+        "CALL",
+        "CASCADE",
+        "CASE",
+        "CHANGE",
+        "CHAR",
+        "CHARACTER",
+        "CHECK",
+        "COLLATE",
+        "COLUMN",
+        "CONDITION",
+        "CONSTRAINT",
+        "CONTINUE",
+        "CONVERT",
+        "CREATE",
+        "CROSS",
+        "CUBE",
+        "CUME_DIST",
+        "CURRENT_DATE",
+        "CURRENT_TIME",
+        "CURRENT_TIMESTAMP",
+        "CURRENT_USER",
+        "CURSOR",
+        "DATABASE",
+        "DATABASES",
+        "DAY_HOUR",
+        "DAY_MICROSECOND",
+        "DAY_MINUTE",
+        "DAY_SECOND",
+        "DEC",
+        "DECIMAL",
+        "DECLARE",
+        "DEFAULT",
+        "DELAYED",
+        "DELETE",
+        "DENSE_RANK",
+        "DESC",
+        "DESCRIBE",
+        "DETERMINISTIC",
+        "DISTINCT",
+        "DISTINCTROW",
+        "DIV",
+        "DOUBLE",
+        "DROP",
+        "DUAL",
+        "EACH",
+        "ELSE",
+        "ELSEIF",
+        "EMPTY",
+        "ENCLOSED",
+        "ESCAPED",
+        "EXCEPT",
+        "EXISTS",
+        "EXIT",
+        "EXPLAIN",
+        "FALSE",
+        "FETCH",
+        "FIRST_VALUE",
+        "FLOAT",
+        "FLOAT4",
+        "FLOAT8",
+        "FOR",
+        "FORCE",
+        "FOREIGN",
+        "FROM",
+        "FULLTEXT",
+        "FUNCTION",
+        "GENERATED",
+        "GET",
+        "GRANT",
+        "GROUP",
+        "GROUPING",
+        "GROUPS",
+        "HAVING",
+        "HIGH_PRIORITY",
+        "HOUR_MICROSECOND",
+        "HOUR_MINUTE",
+        "HOUR_SECOND",
+        "IF",
+        "IGNORE",
+        "IN",
+        "INDEX",
+        "INFILE",
+        "INNER",
+        "INOUT",
+        "INSENSITIVE",
+        "INSERT",
+        "INT",
+        "INT1",
+        "INT2",
+        "INT3",
+        "INT4",
+        "INT8",
+        "INTEGER",
+        "INTERSECT",
+        "INTERVAL",
+        "INTO",
+        "IS",
+        "ITERATE",
+        "JOIN",
+        "JSON_TABLE",
+        "KEY",
+        "KEYS",
+        "KILL",
+        "LAG",
+        "LAST_VALUE",
+        "LATERAL",
+        "LEAD",
+        "LEADING",
+        "LEAVE",
+        "LEFT",
+        "LIKE",
+        "LIMIT",
+        "LINEAR",
+        "LINES",
+        "LOAD",
+        "LOCALTIME",
+        "LOCALTIMESTAMP",
+        "LOCK",
+        "LONG",
+        "LONGBLOB",
+        "LONGTEXT",
+        "LOOP",
+        "LOW_PRIORITY",
+        "MATCH",
+        "MAXVALUE",
+        "MEDIUMBLOB",
+        "MEDIUMINT",
+        "MEDIUMTEXT",
+        "MIDDLEINT",
+        "MINUTE_MICROSECOND",
+        "MINUTE_SECOND",
+        "MOD",
+        "MODIFIES",
+        "NATURAL",
+        "NOT",
+        "NO_WRITE_TO_BINLOG",
+        "NTH_VALUE",
+        "NTILE",
+        "NULL",
+        "NUMERIC",
+        "OF",
+        "ON",
+        "OPTIMIZE",
+        "OPTION",
+        "OPTIONALLY",
+        "OR",
+        "ORDER",
+        "OUT",
+        "OUTER",
+        "OUTFILE",
+        "OVER",
+        "PARTITION",
+        "PERCENT_RANK",
+        "PRECISION",
+        "PRIMARY",
+        "PROCEDURE",
+        "PURGE",
+        "RANGE",
+        "RANK",
+        "READ",
+        "READS",
+        "READ_WRITE",
+        "REAL",
+        "RECURSIVE",
+        "REFERENCES",
+        "REGEXP",
+        "RELEASE",
+        "RENAME",
+        "REPEAT",
+        "REPLACE",
+        "REQUIRE",
+        "RESIGNAL",
+        "RESTRICT",
+        "RETURN",
+        "REVOKE",
+        "RIGHT",
+        "RLIKE",
+        "ROW",
+        "ROWS",
+        "ROW_NUMBER",
+        "SCHEMA",
+        "SCHEMAS",
+        "SECOND_MICROSECOND",
+        "SELECT",
+        "SENSITIVE",
+        "SEPARATOR",
+        "SET",
+        "SHOW",
+        "SIGNAL",
+        "SMALLINT",
+        "SPATIAL",
+        "SPECIFIC",
+        "SQL",
+        "SQLEXCEPTION",
+        "SQLSTATE",
+        "SQLWARNING",
+        "SQL_BIG_RESULT",
+        "SQL_CALC_FOUND_ROWS",
+        "SQL_SMALL_RESULT",
+        "SSL",
+        "STARTING",
+        "STORED",
+        "STRAIGHT_JOIN",
+        "SYSTEM",
+        "TABLE",
+        "TERMINATED",
+        "THEN",
+        "TINYBLOB",
+        "TINYINT",
+        "TINYTEXT",
+        "TO",
+        "TRAILING",
+        "TRIGGER",
+        "TRUE",
+        "UNDO",
+        "UNION",
+        "UNIQUE",
+        "UNLOCK",
+        "UNSIGNED",
+        "UPDATE",
+        "USAGE",
+        "USE",
+        "USING",
+        "UTC_DATE",
+        "UTC_TIME",
+        "UTC_TIMESTAMP",
+        "VALUES",
+        "VARBINARY",
+        "VARCHAR",
+        "VARCHARACTER",
+        "VARYING",
+        "VIRTUAL",
+        "WHEN",
+        "WHERE",
+        "WHILE",
+        "WINDOW",
+        "WITH",
+        "WRITE",
+        "XOR",
+        "YEAR_MONTH",
+        "ZEROFILL",
+    ]
+    .into_iter()
+    .collect()
+});
+
+/// Words that PostgreSQL reserves, including those allowed only as function or type names
+static POSTGRES_RESERVED: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    [
+        "ALL",
+        "ANALYSE",
+        "ANALYZE",
+        "AND",
+        "ANY",
+        "ARRAY",
+        "AS",
+        "ASC",
+        "ASYMMETRIC",
+        "AUTHORIZATION",
+        "BINARY",
+        "BOTH",
+        "CASE",
+        "CAST",
+        "CHECK",
+        "COLLATE",
+        "COLLATION",
+        "COLUMN",
+        "CONCURRENTLY",
+        "CONSTRAINT",
+        "CREATE",
+        "CROSS",
+        "CURRENT_CATALOG",
+        "CURRENT_DATE",
+        "CURRENT_ROLE",
+        "CURRENT_SCHEMA",
+        "CURRENT_TIME",
+        "CURRENT_TIMESTAMP",
+        "CURRENT_USER",
+        "DEFAULT",
+        "DEFERRABLE",
+        "DESC",
+        "DISTINCT",
+        "DO",
+        "ELSE",
+        "END",
+        "EXCEPT",
+        "FALSE",
+        "FETCH",
+        "FOR",
+        "FOREIGN",
+        "FREEZE",
+        "FROM",
+        "FULL",
+        "GRANT",
+        "GROUP",
+        "HAVING",
+        "ILIKE",
+        "IN",
+        "INITIALLY",
+        "INNER",
+        "INTERSECT",
+        "INTO",
+        "IS",
+        "ISNULL",
+        "JOIN",
+        "LATERAL",
+        "LEADING",
+        "LEFT",
+        "LIKE",
+        "LIMIT",
+        "LOCALTIME",
+        "LOCALTIMESTAMP",
+        "NATURAL",
+        "NOT",
+        "NOTNULL",
+        "NULL",
+        "OFFSET",
+        "ON",
+        "ONLY",
+        "OR",
+        "ORDER",
+        "OUTER",
+        "OVERLAPS",
+        "PLACING",
+        "PRIMARY",
+        "REFERENCES",
+        "RETURNING",
+        "RIGHT",
+        "SELECT",
+        "SESSION_USER",
+        "SIMILAR",
+        "SOME",
+        "SYMMETRIC",
+        "SYSTEM_USER",
+        "TABLE",
+        "TABLESAMPLE",
+        "THEN",
+        "TO",
+        "TRAILING",
+        "TRUE",
+        "UNION",
+        "UNIQUE",
+        "USER",
+        "USING",
+        "VARIADIC",
+        "VERBOSE",
+        "WHEN",
+        "WHERE",
+        "WINDOW",
+        "WITH",
+    ]
+    .into_iter()
+    .collect()
+});
+
+/// Keywords SQLite does not accept as bare identifiers
+static SQLITE_RESERVED: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    [
+        "ADD",
+        "ALL",
+        "ALTER",
+        "AND",
+        "AS",
+        "AUTOINCREMENT",
+        "BETWEEN",
+        "CASE",
+        "CHECK",
+        "COLLATE",
+        "COMMIT",
+        "CONSTRAINT",
+        "CREATE",
+        "DEFAULT",
+        "DEFERRABLE",
+        "DELETE",
+        "DISTINCT",
+        "DROP",
+        "ELSE",
+        "ESCAPE",
+        "EXCEPT",
+        "EXISTS",
+        "FOREIGN",
+        "FROM",
+        "GROUP",
+        "HAVING",
+        "IN",
+        "INDEX",
+        "INSERT",
+        "INTERSECT",
+        "INTO",
+        "IS",
+        "ISNULL",
+        "JOIN",
+        "LIMIT",
+        "NOT",
+        "NOTHING",
+        "NOTNULL",
+        "NULL",
+        "ON",
+        "OR",
+        "ORDER",
+        "PRIMARY",
+        "REFERENCES",
+        "RETURNING",
+        "SELECT",
+        "SET",
+        "TABLE",
+        "THEN",
+        "TO",
+        "TRANSACTION",
+        "UNION",
+        "UNIQUE",
+        "UPDATE",
+        "USING",
+        "VALUES",
+        "WHEN",
+        "WHERE",
+    ]
+    .into_iter()
+    .collect()
+});
+
 /// Validation error types
 #[derive(Debug, Clone, PartialEq)]
 pub enum ValidationError {
@@ -198,6 +640,18 @@ pub enum ValidationError {
     StartsWithUnderscore,
     /// Identifier is a reserved keyword
     ReservedKeyword { keyword: String },
+    /// Identifier is reserved in some dialects and has to be quoted there
+    ReservedInDialects {
+        keyword: String,
+        dialects: Vec<String>,
+    },
+    /// Identifier exceeds the length limit of a dialect
+    ExceedsDialectLimit {
+        dialect: String,
+        max: usize,
+        actual: usize,
+        unit: &'static str,
+    },
     /// Identifier contains only underscores/digits
     NoLetters,
     /// Identifier contains consecutive underscores
@@ -235,6 +689,26 @@ impl std::fmt::Display for ValidationError {
             ValidationError::ReservedKeyword { keyword } => {
                 write!(f, "'{}' is a reserved SQL keyword", keyword)
             }
+            ValidationError::ReservedInDialects { keyword, dialects } => {
+                write!(
+                    f,
+                    "'{}' is reserved in {} and must be quoted there",
+                    keyword,
+                    dialects.join(", ")
+                )
+            }
+            ValidationError::ExceedsDialectLimit {
+                dialect,
+                max,
+                actual,
+                unit,
+            } => {
+                write!(
+                    f,
+                    "Name is too long for {} ({} {}, max {})",
+                    dialect, actual, unit, max
+                )
+            }
             ValidationError::NoLetters => {
                 write!(f, "Name must contain at least one letter")
             }
@@ -270,6 +744,8 @@ pub struct ValidationResult {
     pub errors: Vec<ValidationError>,
     /// Warnings that are recommended to fix
     pub warnings: Vec<ValidationError>,
+    /// Informational notes (e.g. issues in dialects other than the target)
+    pub hints: Vec<ValidationError>,
 }
 
 impl ValidationResult {
@@ -293,6 +769,18 @@ impl ValidationResult {
         self.warnings.push(warning);
     }
 
+    pub fn add_hint(&mut self, hint: ValidationError) {
+        self.hints.push(hint);
+    }
+
+    fn add(&mut self, severity: ErrorSeverity, error: ValidationError) {
+        match severity {
+            ErrorSeverity::Error => self.add_error(error),
+            ErrorSeverity::Warning => self.add_warning(error),
+            ErrorSeverity::Hint => self.add_hint(error),
+        }
+    }
+
     /// Convert to Result, returning first error if any
     pub fn to_result(&self) -> Result<(), ValidationError> {
         if let Some(error) = self.errors.first() {
@@ -308,6 +796,7 @@ impl ValidationResult {
             .iter()
             .map(|e| format!("Error: {}", e))
             .chain(self.warnings.iter().map(|w| format!("Warning: {}", w)))
+            .chain(self.hints.iter().map(|h| format!("Hint: {}", h)))
             .collect()
     }
 }
@@ -331,6 +820,25 @@ pub fn validate_identifier(name: &str, level: ValidationLevel) -> ValidationResu
         });
     }
 
+    check_characters(trimmed, &mut result);
+
+    // Standard and Strict: Check reserved keywords
+    if level == ValidationLevel::Standard || level == ValidationLevel::Strict {
+        let upper = trimmed.to_uppercase();
+        if RESERVED_KEYWORDS.contains(upper.as_str()) {
+            result.add_error(ValidationError::ReservedKeyword {
+                keyword: trimmed.to_string(),
+            });
+        }
+    }
+
+    check_style(trimmed, level, &mut result);
+
+    result
+}
+
+/// Character rules shared by all validation levels
+fn check_characters(trimmed: &str, result: &mut ValidationResult) {
     // Check for invalid characters (only ASCII alphanumeric and underscore allowed)
     let invalid_chars: Vec<char> = trimmed
         .chars()
@@ -353,17 +861,10 @@ pub fn validate_identifier(name: &str, level: ValidationLevel) -> ValidationResu
     if !trimmed.chars().any(|c| c.is_ascii_alphabetic()) {
         result.add_error(ValidationError::NoLetters);
     }
+}
 
-    // Standard and Strict: Check reserved keywords
-    if level == ValidationLevel::Standard || level == ValidationLevel::Strict {
-        let upper = trimmed.to_uppercase();
-        if RESERVED_KEYWORDS.contains(upper.as_str()) {
-            result.add_error(ValidationError::ReservedKeyword {
-                keyword: trimmed.to_string(),
-            });
-        }
-    }
-
+/// Underscore style rules: errors in Strict mode, warnings otherwise
+fn check_style(trimmed: &str, level: ValidationLevel, result: &mut ValidationResult) {
     // Strict: Additional style checks
     if level == ValidationLevel::Strict {
         // Starts with underscore
@@ -394,10 +895,121 @@ pub fn validate_identifier(name: &str, level: ValidationLevel) -> ValidationResu
             result.add_warning(ValidationError::ConsecutiveUnderscores);
         }
     }
+}
+
+/// Validates an identifier for a target dialect
+///
+/// Unlike `validate_identifier`, keyword and length checks use per-dialect rules,
+/// with severities from [`dialect_issue_severity`]: neither blocks a name, since
+/// exported SQL quotes every identifier.
+pub fn validate_identifier_for(
+    name: &str,
+    level: ValidationLevel,
+    dialect: &SqlDialect,
+) -> ValidationResult {
+    let mut result = ValidationResult::new();
+
+    let trimmed = name.trim();
+    if trimmed.is_empty() {
+        result.add_error(ValidationError::Empty);
+        return result;
+    }
+
+    // Target dialect first, then the others
+    let mut dialects = vec![dialect.clone()];
+    dialects.extend(SqlDialect::ALL.into_iter().filter(|d| d != dialect));
+    for d in &dialects {
+        if let Some(max) = max_identifier_length(d) {
+            let actual = identifier_length(trimmed, d);
+            if actual > max {
+                let error = ValidationError::ExceedsDialectLimit {
+                    dialect: d.label().to_string(),
+                    max,
+                    actual,
+                    unit: identifier_length_unit(d),
+                };
+                result.add(dialect_issue_severity(d == dialect), error);
+            }
+        }
+    }
+
+    check_characters(trimmed, &mut result);
+
+    if level != ValidationLevel::Minimal {
+        let reserving = dialects_reserving(trimmed);
+        if !reserving.is_empty() {
+            let error = ValidationError::ReservedInDialects {
+                keyword: trimmed.to_string(),
+                dialects: reserving.iter().map(|d| d.label().to_string()).collect(),
+            };
+            result.add(dialect_issue_severity(reserving.contains(dialect)), error);
+        }
+    }
+
+    check_style(trimmed, level, &mut result);
 
     result
 }
 
+/// Severity of a reserved word or length limit problem in one dialect
+///
+/// Shared by the table/column editors (`validate_identifier_for`) and the SQL
+/// validator (W004, W005): a warning when the target dialect is affected, a hint
+/// when only other dialects are (the schema may still be exported there).
+pub fn dialect_issue_severity(in_target: bool) -> ErrorSeverity {
+    if in_target {
+        ErrorSeverity::Warning
+    } else {
+        ErrorSeverity::Hint
+    }
+}
+
+/// Maximum identifier length of a dialect (None if the dialect has no practical limit)
+///
+/// PostgreSQL truncates names longer than 63 bytes (NAMEDATALEN - 1),
+/// MySQL rejects names longer than 64 characters.
+pub fn max_identifier_length(dialect: &SqlDialect) -> Option<usize> {
+    match dialect {
+        SqlDialect::MySQL => Some(MAX_IDENTIFIER_LENGTH),
+        SqlDialect::PostgreSQL => Some(63),
+        SqlDialect::SQLite => None,
+    }
+}
+
+/// Identifier length as the dialect counts it (bytes for PostgreSQL, characters otherwise)
+pub fn identifier_length(name: &str, dialect: &SqlDialect) -> usize {
+    match dialect {
+        SqlDialect::PostgreSQL => name.len(),
+        _ => name.chars().count(),
+    }
+}
+
+/// Unit of `identifier_length` ("bytes" or "chars")
+pub fn identifier_length_unit(dialect: &SqlDialect) -> &'static str {
+    match dialect {
+        SqlDialect::PostgreSQL => "bytes",
+        _ => "chars",
+    }
+}
+
+/// Check if a name is a reserved word in the given dialect
+pub fn is_reserved_in(name: &str, dialect: &SqlDialect) -> bool {
+    let upper = name.to_uppercase();
+    match dialect {
+        SqlDialect::MySQL => MYSQL_RESERVED.contains(upper.as_str()),
+        SqlDialect::PostgreSQL => POSTGRES_RESERVED.contains(upper.as_str()),
+        SqlDialect::SQLite => SQLITE_RESERVED.contains(upper.as_str()),
+    }
+}
+
+/// Dialects in which the name is a reserved word (in `SqlDialect::ALL` order)
+pub fn dialects_reserving(name: &str) -> Vec<SqlDialect> {
+    SqlDialect::ALL
+        .into_iter()
+        .filter(|dialect| is_reserved_in(name, dialect))
+        .collect()
+}
+
 /// Simple validation function that returns Result<(), String>
 /// Uses Standard validation level
 pub fn validate_name(name: &str) -> Result<(), String> {
@@ -487,10 +1099,53 @@ pub fn suggest_valid_name(name: &str, prefix: &str) -> String {
     }
 }
 
+/// Levenshtein distance between two strings (case-insensitive)
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+/// Find the candidate closest to `name` (for "did you mean" fixes)
+/// Returns None if nothing is close enough to be a plausible typo
+pub fn closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(2);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_closest_match() {
+        let columns = ["id", "user_id", "email"];
+        assert_eq!(closest_match("user_idd", columns), Some("user_id"));
+        assert_eq!(closest_match("emial", columns), Some("email"));
+        assert_eq!(closest_match("ID", columns), Some("id"));
+        assert_eq!(closest_match("created_at", columns), None);
+    }
+
     #[test]
     fn test_valid_identifiers() {
         assert!(validate_name("users").is_ok());
@@ -633,4 +1288,73 @@ mod tests {
             "Name cannot start with a digit"
         );
     }
+
+    #[test]
+    fn test_dialect_reserved_words() {
+        assert!(is_reserved_in("user", &SqlDialect::PostgreSQL));
+        assert!(!is_reserved_in("user", &SqlDialect::MySQL));
+        assert!(!is_reserved_in("date", &SqlDialect::MySQL));
+        assert_eq!(
+            dialects_reserving("order"),
+            vec![
+                SqlDialect::MySQL,
+                SqlDialect::PostgreSQL,
+                SqlDialect::SQLite
+            ]
+        );
+        assert_eq!(dialects_reserving("Keys"), vec![SqlDialect::MySQL]);
+        assert!(dialects_reserving("users").is_empty());
+    }
+
+    #[test]
+    fn test_validate_identifier_for_reserved() {
+        // Reserved only in PostgreSQL: a hint for MySQL
+        let result = validate_identifier_for("user", ValidationLevel::Standard, &SqlDialect::MySQL);
+        assert!(result.is_valid() && !result.has_warnings());
+        assert_eq!(
+            result.hints[0].to_string(),
+            "'user' is reserved in PostgreSQL and must be quoted there"
+        );
+
+        // Reserved in the target dialect: a warning, the name is still accepted
+        let result =
+            validate_identifier_for("user", ValidationLevel::Standard, &SqlDialect::PostgreSQL);
+        assert!(result.is_valid());
+        assert_eq!(result.warnings.len(), 1);
+
+        let result = validate_identifier_for("user", ValidationLevel::Minimal, &SqlDialect::MySQL);
+        assert!(result.is_valid() && result.hints.is_empty());
+
+        // Non-reserved keyword in every dialect
+        let result = validate_identifier_for("date", ValidationLevel::Standard, &SqlDialect::MySQL);
+        assert!(result.is_valid() && !result.has_warnings() && result.hints.is_empty());
+    }
+
+    #[test]
+    fn test_validate_identifier_for_length() {
+        let name = "a".repeat(64);
+        let result = validate_identifier_for(&name, ValidationLevel::Standard, &SqlDialect::MySQL);
+        assert!(result.is_valid());
+        assert_eq!(
+            result.hints,
+            vec![ValidationError::ExceedsDialectLimit {
+                dialect: "PostgreSQL".to_string(),
+                max: 63,
+                actual: 64,
+                unit: "bytes",
+            }]
+        );
+
+        let result =
+            validate_identifier_for(&name, ValidationLevel::Standard, &SqlDialect::PostgreSQL);
+        assert_eq!(
+            result.warnings[0].to_string(),
+            "Name is too long for PostgreSQL (64 bytes, max 63)"
+        );
+
+        let name = "a".repeat(100);
+        let result = validate_identifier_for(&name, ValidationLevel::Standard, &SqlDialect::SQLite);
+        assert!(result.is_valid());
+        assert_eq!(result.hints.len(), 2);
+    }
 }
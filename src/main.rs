@@ -15,6 +15,7 @@ async fn main() {
     };
     use archischema::core::diagrams::{DiagramApiState, diagram_api_router};
     use archischema::core::folders::{FolderApiState, folder_api_router};
+    use archischema::core::layout_api::layout_api_router;
     use archischema::core::liveshare::{
         LiveshareState, init_jwt_service, liveshare_router, ws_handler,
     };
@@ -185,7 +186,9 @@ async fn main() {
         // REST API for room management
         .merge(liveshare_api)
         // AI API for chat completions
-        .merge(ai_api);
+        .merge(ai_api)
+        // Auto-layout of large diagrams off the browser's main thread
+        .merge(layout_api_router());
 
     // Merge auth API if available
     if let Some(auth_router) = auth_api {
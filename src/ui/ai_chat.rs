@@ -5,14 +5,15 @@
 //! - Ask: Read-only, can only analyze the schema
 //! - Write: Full access, can modify the schema
 
-use crate::core::SchemaGraph;
 use crate::core::ai_config::{AiConfig, AiMode, ChatMessage, MessageRole};
 #[cfg(not(feature = "ssr"))]
 use crate::core::ai_config::{FunctionCall, StreamChunk, ToolCall};
 #[cfg(not(feature = "ssr"))]
-use crate::core::{ToolExecutor, ToolRequest};
+use crate::core::liveshare::GraphOperation;
+use crate::core::{DiagramSettings, SchemaGraph};
 #[cfg(not(feature = "ssr"))]
-use crate::ui::liveshare_client::{ConnectionState, try_use_liveshare_context};
+use crate::core::{LayoutConfig, LayoutScope, ToolExecutor, ToolRequest, auto_layout_with_config};
+use crate::ui::history::use_history_context;
 use crate::ui::markdown::Markdown;
 use crate::ui::{Icon, icons};
 use leptos::prelude::*;
@@ -31,7 +32,19 @@ pub fn AiChatPanel(
     is_open: RwSignal<bool>,
     /// Schema graph for tool execution
     _graph: RwSignal<SchemaGraph>,
+    /// Diagram settings (target SQL dialect for schema tools)
+    #[prop(optional)]
+    settings: Option<RwSignal<DiagramSettings>>,
 ) -> impl IntoView {
+    // Tools are executed only on the client
+    #[cfg(feature = "ssr")]
+    let _ = settings;
+
+    // All edits of one answer are sent to LiveShare and undone together
+    let history = use_history_context();
+    #[cfg(feature = "ssr")]
+    let _ = history;
+
     // Chat state
     let (messages, set_messages) = signal::<Vec<ChatMessage>>(Vec::new());
     let (input_value, set_input_value) = signal(String::new());
@@ -232,6 +245,11 @@ pub fn AiChatPanel(
                             current_messages.push(assistant_message.clone());
                             set_messages.update(|msgs| msgs.push(assistant_message));
 
+                            // Таблицы, созданные AI, раскладываются после выполнения всех вызовов
+                            let mut created_tables = Vec::new();
+                            let before = _graph.get_untracked();
+                            let mut gesture_ops = Vec::new();
+
                             // Execute each tool call
                             for tool_call in &tool_calls {
                                 let tool_name = &tool_call.function.name;
@@ -249,6 +267,8 @@ pub fn AiChatPanel(
                                         | "create_relationship"
                                         | "delete_relationship"
                                         | "apply_sql"
+                                        | "apply_inferred_foreign_keys"
+                                        | "auto_layout"
                                 );
 
                                 let tool_result = if is_write_op && !current_config.mode.can_write()
@@ -267,22 +287,29 @@ pub fn AiChatPanel(
                                         parameters: params,
                                     };
 
+                                    let diagram_settings =
+                                        settings.map(|s| s.get_untracked()).unwrap_or_default();
+
                                     // Execute on graph (need to update signal)
-                                    let result = _graph
-                                        .try_update(|g| ToolExecutor::execute(g, &tool_request));
+                                    let result = _graph.try_update(|g| {
+                                        ToolExecutor::execute_with_settings(
+                                            g,
+                                            &diagram_settings,
+                                            &tool_request,
+                                        )
+                                    });
 
                                     match result {
                                         Some(response) => {
-                                            // Send graph operations to LiveShare for sync
-                                            if !response.graph_ops.is_empty() {
-                                                if let Some(liveshare_ctx) = try_use_liveshare_context() {
-                                                    if liveshare_ctx.connection_state.with_untracked(|v| *v) == ConnectionState::Connected {
-                                                        for op in &response.graph_ops {
-                                                            liveshare_ctx.send_graph_op(op.clone());
-                                                        }
+                                            gesture_ops.extend(response.graph_ops.iter().cloned());
+                                            created_tables.extend(response.graph_ops.iter().filter_map(
+                                                |op| match op {
+                                                    GraphOperation::CreateTable { table_uuid, .. } => {
+                                                        Some(*table_uuid)
                                                     }
-                                                }
-                                            }
+                                                    _ => None,
+                                                },
+                                            ));
                                             serde_json::to_string(&response)
                                                 .unwrap_or_else(|_| "Error serializing response".to_string())
                                         }
@@ -296,6 +323,29 @@ pub fn AiChatPanel(
                                 current_messages.push(tool_response_msg.clone());
                             }
 
+                            // Новые таблицы ставим рядом со связанными, не сдвигая остальные
+                            if !created_tables.is_empty() {
+                                let algorithm = settings
+                                    .map(|s| s.get_untracked().layout)
+                                    .unwrap_or_default();
+                                let layout_ops = _graph
+                                    .try_update(|g| {
+                                        let tables = g
+                                            .node_indices()
+                                            .filter(|&idx| created_tables.contains(&g[idx].uuid))
+                                            .collect();
+                                        let config = LayoutConfig {
+                                            algorithm,
+                                            scope: LayoutScope::Tables(tables),
+                                            ..Default::default()
+                                        };
+                                        auto_layout_with_config(g, &config)
+                                    })
+                                    .unwrap_or_default();
+                                gesture_ops.extend(layout_ops);
+                            }
+                            history.commit(before, gesture_ops);
+
                             // Make another streaming API call with tool results
                             let tools = build_tool_definitions(current_config.mode);
                             let follow_up_request = ChatRequest {
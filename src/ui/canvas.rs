@@ -1,11 +1,33 @@
-use crate::core::{SchemaGraph, TableOps, auto_layout, create_demo_graph};
+use crate::core::auto_layout::TABLE_WIDTH;
+use crate::core::{
+    Alignment, ClipboardFragment, DEFAULT_FOCUS_DEPTH, DetailLevel, DiagramSettings,
+    DistributeAxis, EndDecoration, ExportFormat, ExportOptions, FocusMode, ImpactReport,
+    LayoutConfig, LayoutScope, MAX_FOCUS_DEPTH, PaletteCommand, PaletteItem, RelationshipType,
+    RoutingConfig, SchemaGraph, TableDisplay, TableOps, TableRows, Viewport, align_tables,
+    analyze_tables_impact, apply_routes, auto_layout_with_config, collapse_junction,
+    column_anchor_y, compatible_columns, connect_columns, create_demo_graph, cull_edges,
+    cull_tables, delete_tables, distribute_tables, edge_decorations, find_junction,
+    materialize_junction, paste_fragment, render_area, route_is_current, route_label_position,
+    route_local_edges, route_svg_path, set_table_display, stale_edges, table_bounds,
+    table_outlines,
+};
+#[cfg(not(feature = "ssr"))]
+use crate::core::{
+    ConnectTarget, LayoutJob, LayoutRequest, LayoutResult, TablePositions, apply_layout,
+    column_at_y, foreign_key_column, move_tables, suggest_relationship_type, tables_in_rect,
+};
 use crate::ui::ai_chat::{AiChatButton, AiChatPanel};
 use crate::ui::auth::UserMenu;
+use crate::ui::command_palette::CommandPalette;
+use crate::ui::connect_dialog::{ConnectDialog, PendingConnection};
+use crate::ui::history::{HistoryContext, provide_history_context, use_history_context};
+use crate::ui::impact_dialog::ImpactConfirmDialog;
 #[cfg(not(feature = "ssr"))]
 use crate::ui::liveshare_client::{
     ColumnData, GraphStateSnapshot, RelationshipData, RelationshipSnapshot, TableSnapshot,
 };
 use crate::ui::liveshare_client::{ConnectionState, GraphOperation, use_liveshare_context};
+use crate::ui::minimap::Minimap;
 use crate::ui::notifications::{NotificationManager, NotificationsContainer};
 use crate::ui::remote_cursors::{CursorTracker, RemoteCursors};
 use crate::ui::settings_modal::{SettingsButton, SettingsModal};
@@ -16,9 +38,70 @@ use crate::ui::{Icon, icons};
 use leptos::prelude::*;
 use leptos::{html, web_sys};
 use petgraph::graph::{EdgeIndex, NodeIndex};
+use std::collections::{HashMap, HashSet};
+
+/// Time budget of one auto-layout slice, so the canvas keeps responding
+#[cfg(not(feature = "ssr"))]
+const LAYOUT_SLICE_MS: f64 = 12.0;
+
+/// Duration of the animated jump to a table or relationship
+#[cfg(not(feature = "ssr"))]
+const PAN_ANIMATION_MS: f64 = 300.0;
+
+/// Run a layout job a slice per frame, reporting progress
+///
+/// Returns `None` when `cancelled` is set before the job finishes.
 #[cfg(not(feature = "ssr"))]
-use std::collections::HashMap;
-use std::collections::HashSet;
+async fn run_layout_job(
+    mut job: LayoutJob,
+    progress: RwSignal<Option<f64>>,
+    cancelled: RwSignal<bool>,
+) -> Option<LayoutResult> {
+    loop {
+        let slice_start = js_sys::Date::now();
+        while js_sys::Date::now() - slice_start < LAYOUT_SLICE_MS {
+            if job.step() {
+                return Some(job.finish());
+            }
+        }
+        progress.set(Some(job.progress()));
+        // Отдаём управление браузеру между порциями
+        gloo_timers::future::TimeoutFuture::new(0).await;
+        if cancelled.get_untracked() {
+            return None;
+        }
+    }
+}
+
+/// Calculate a layout on the server, for layouts that cannot run step by step
+///
+/// Aborting `signal` cancels the request.
+#[cfg(not(feature = "ssr"))]
+async fn request_server_layout(
+    request: &LayoutRequest,
+    signal: Option<&web_sys::AbortSignal>,
+) -> Result<TablePositions, String> {
+    use gloo_net::http::Request;
+
+    if !request.within_limits() {
+        return Err(format!(
+            "Diagrams over {} tables or {} relationships cannot be arranged with this layout",
+            LayoutRequest::MAX_TABLES,
+            LayoutRequest::MAX_RELATIONSHIPS
+        ));
+    }
+    let response = Request::post("/api/layout")
+        .abort_signal(signal)
+        .json(request)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+    if !response.ok() {
+        return Err(format!("Server error: {}", response.status()));
+    }
+    response.json().await.map_err(|e| e.to_string())
+}
 
 /// Helper function to dispatch a save event to the parent component
 #[cfg(not(feature = "ssr"))]
@@ -41,9 +124,49 @@ fn dispatch_save_event(_reason: &str) {
     // No-op on server
 }
 
+/// Move keyboard focus out of text fields when the canvas is clicked
+#[cfg(not(feature = "ssr"))]
+fn blur_active_element() {
+    use wasm_bindgen::JsCast;
+
+    if let Some(element) = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.active_element())
+        .and_then(|e| e.dyn_into::<web_sys::HtmlElement>().ok())
+    {
+        let _ = element.blur();
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn blur_active_element() {}
+
+/// Whether keyboard focus is in a text field, which handles Ctrl+Z itself
+#[cfg(not(feature = "ssr"))]
+fn is_text_input_focused() -> bool {
+    use wasm_bindgen::JsCast;
+
+    let Some(element) = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.active_element())
+    else {
+        return false;
+    };
+    let tag = element.tag_name().to_lowercase();
+    tag == "input"
+        || tag == "textarea"
+        || tag == "select"
+        || element
+            .dyn_ref::<web_sys::HtmlElement>()
+            .is_some_and(|e| e.is_content_editable())
+}
+
 #[component]
 pub fn SchemaCanvas(
     graph: RwSignal<SchemaGraph>,
+    /// Per-diagram settings (target SQL dialect, etc.), persisted with the diagram
+    #[prop(optional)]
+    settings: Option<RwSignal<DiagramSettings>>,
     /// Diagram name (editable)
     #[prop(optional)]
     diagram_name: Option<RwSignal<String>>,
@@ -60,11 +183,21 @@ pub fn SchemaCanvas(
     // Get LiveShare context for sync
     let liveshare_ctx = use_liveshare_context();
 
+    let settings = settings.unwrap_or_else(|| RwSignal::new(DiagramSettings::default()));
+    // Editors deep in the sidebar read the target dialect from context
+    provide_context(settings);
+
+    // Undo/redo of local edits, shared with the sidebar and editors
+    let history = provide_history_context(graph);
+
     // Notification manager for canvas notifications
     let notification_manager = NotificationManager::new();
 
     // Состояние для drag & drop
     let (_dragging_node, set_dragging_node) = signal::<Option<(NodeIndex, f64, f64)>>(None);
+    // Перетаскиваемые таблицы и их позиции до начала перетаскивания;
+    // первая - таблица под курсором, остальные - выделенная вместе с ней группа
+    let drag_group = StoredValue::new(Vec::<(NodeIndex, (f64, f64))>::new());
 
     // Track which nodes are currently being remotely dragged (for UI purposes)
     // This is a RwSignal so it can be used in reactive contexts
@@ -78,6 +211,9 @@ pub fn SchemaCanvas(
     #[allow(unused_variables)]
     let (pan_y, set_pan_y) = signal(0.0_f64);
 
+    // Размер видимой области канваса в пикселях (миникарта, вьюпорт для LiveShare)
+    let viewport_size = RwSignal::new((1280.0_f64, 800.0_f64));
+
     // Состояние для панорамирования средней кнопкой мыши
     #[allow(unused_variables)]
     let (panning, set_panning) = signal::<Option<(f64, f64)>>(None);
@@ -93,8 +229,44 @@ pub fn SchemaCanvas(
 
     // State for highlighted edges (when clicking on edge or table)
     let highlighted_edges: RwSignal<HashSet<EdgeIndex>> = RwSignal::new(HashSet::new());
-    // State for selected table (to highlight all its edges)
-    let selected_table: RwSignal<Option<NodeIndex>> = RwSignal::new(None);
+    // Selected tables (highlight their edges, shift-click / marquee add more)
+    let selected_tables: RwSignal<HashSet<NodeIndex>> = RwSignal::new(HashSet::new());
+    // Рамка выделения: начало в координатах окна и признак добавления (Shift)
+    #[allow(unused_variables)]
+    let (marquee_start, set_marquee_start) = signal::<Option<(f64, f64, bool)>>(None);
+    let marquee_end = RwSignal::new((0.0_f64, 0.0_f64));
+    // Клик, завершающий рамку, не должен сбрасывать выделение
+    let suppress_canvas_click = StoredValue::new(false);
+    // Удаление выделенных таблиц, ожидающее подтверждения
+    let pending_selection_delete = RwSignal::new(None::<(Vec<NodeIndex>, ImpactReport)>);
+    // Зависимости удаления, ожидающего подтверждения (подсвечиваются на канвасе)
+    let impact_preview = RwSignal::new(None::<ImpactReport>);
+    // Затронутые таблицы и колонки по имени таблицы
+    let impact_marks = Memo::new(move |_| {
+        impact_preview.with(|report| {
+            let mut marks: HashMap<String, Vec<String>> = HashMap::new();
+            if let Some(report) = report {
+                for table in report.tables() {
+                    marks.entry(table).or_default();
+                }
+                for (table, column) in report.columns() {
+                    marks.entry(table).or_default().push(column);
+                }
+            }
+            marks
+        })
+    });
+    // Протягивание связи от колонки: таблица и колонка-источник
+    let connect_drag = RwSignal::new(None::<(NodeIndex, String)>);
+    // Конец линии протягивания в координатах канваса
+    let connect_cursor = RwSignal::new((0.0_f64, 0.0_f64));
+    // Отпущенная связь, ожидающая выбора типа
+    let pending_connection = RwSignal::new(None::<PendingConnection>);
+    // Колонки других таблиц, совместимые по типу с протягиваемой
+    let connect_targets = Memo::new(move |_| {
+        let (table, column) = connect_drag.get()?;
+        Some(graph.with(|g| compatible_columns(g, table, &column)))
+    });
     // Track if mouse moved during drag (to prevent selection on drag)
     let was_dragged: RwSignal<bool> = RwSignal::new(false);
 
@@ -104,6 +276,246 @@ pub fn SchemaCanvas(
     // Мемоизация индексов рёбер
     let edge_indices = Memo::new(move |_| graph.with(|g| g.edge_indices().collect::<Vec<_>>()));
 
+    // Режим фокуса: на канвасе только таблицы в N шагах от выбранной
+    let focus: RwSignal<Option<FocusMode>> = RwSignal::new(None);
+    let focus_tables = Memo::new(move |_| {
+        let mode = focus.get()?;
+        Some(graph.with(|g| mode.visible_tables(g)))
+    });
+    let is_visible = move |idx: NodeIndex| {
+        focus_tables.with_untracked(|visible| visible.as_ref().is_none_or(|v| v.contains(&idx)))
+    };
+    let visible_node_indices = Memo::new(move |_| {
+        let all = node_indices.get();
+        focus_tables.with(|visible| match visible {
+            Some(visible) => all
+                .into_iter()
+                .filter(|idx| visible.contains(idx))
+                .collect(),
+            None => all,
+        })
+    });
+    let visible_edge_indices = Memo::new(move |_| {
+        let all = edge_indices.get();
+        focus_tables.with(|visible| match visible {
+            Some(visible) => graph.with(|g| {
+                all.into_iter()
+                    .filter(|&edge| {
+                        g.edge_endpoints(edge).is_some_and(|(from, to)| {
+                            visible.contains(&from) && visible.contains(&to)
+                        })
+                    })
+                    .collect()
+            }),
+            None => all,
+        })
+    });
+
+    // Виртуализация: рисуется только то, что пересекает видимую область (с запасом);
+    // списки сравниваются мемо, поэтому панорамирование без смены состава не перерисовывает канвас
+    let detail_level = Memo::new(move |_| DetailLevel::for_zoom(zoom.get()));
+    let cull_area = Memo::new(move |_| {
+        let zoom = zoom.get();
+        let viewport =
+            Viewport::from_transform((pan_x.get(), pan_y.get()), zoom, viewport_size.get());
+        render_area(&viewport, zoom)
+    });
+    let rendered_node_indices = Memo::new(move |_| {
+        let area = cull_area.get();
+        visible_node_indices.with(|tables| graph.with(|g| cull_tables(g, &area, tables)))
+    });
+    let rendered_edge_indices = Memo::new(move |_| {
+        let area = cull_area.get();
+        visible_edge_indices.with(|edges| graph.with(|g| cull_edges(g, &area, edges)))
+    });
+
+    // Фокус снимается, если таблица фокуса удалена или выделена таблица вне фокуса
+    // (вставка, новая таблица, переход из палитры)
+    Effect::new(move |_| {
+        let Some(visible) = focus_tables.get() else {
+            return;
+        };
+        if visible.is_empty()
+            || selected_tables.with(|s| s.iter().any(|idx| !visible.contains(idx)))
+        {
+            focus.set(None);
+        }
+    });
+
+    // Удалённые таблицы (в том числе другими участниками) выпадают из выделения
+    Effect::new(move |_| {
+        let existing = node_indices.get();
+        if selected_tables.with_untracked(|s| s.iter().any(|idx| !existing.contains(idx))) {
+            selected_tables.update(|s| s.retain(|idx| existing.contains(idx)));
+        }
+    });
+
+    // Выделение видно участникам LiveShare через awareness
+    Effect::new(move |_| {
+        let mut selected: Vec<String> = selected_tables.with(|s| {
+            graph.with_untracked(|g| {
+                s.iter()
+                    .filter_map(|&idx| g.node_weight(idx).map(|n| n.uuid.to_string()))
+                    .collect()
+            })
+        });
+        selected.sort();
+        liveshare_ctx.set_local_selection(selected);
+    });
+
+    // Таблицы, выделенные другими участниками: UUID -> цвет участника
+    let remote_selections = Memo::new(move |_| {
+        liveshare_ctx.remote_users.with(|users| {
+            users
+                .iter()
+                .flat_map(|user| {
+                    user.selected_nodes
+                        .iter()
+                        .map(|uuid| (uuid.clone(), user.color.clone()))
+                })
+                .collect::<HashMap<String, String>>()
+        })
+    });
+
+    let delete_selection = move |tables: Vec<NodeIndex>| {
+        let before = graph.get_untracked();
+        let ops = graph
+            .try_update(|g| delete_tables(g, &tables))
+            .unwrap_or_default();
+        history.commit(before, ops);
+        selected_tables.set(HashSet::new());
+        highlighted_edges.set(HashSet::new());
+        dispatch_save_event("tables_deleted");
+    };
+
+    // Удаление выделенных таблиц: при наличии зависимостей вне выделения - подтверждение
+    let request_delete_selection = move || {
+        let mut tables: Vec<NodeIndex> =
+            selected_tables.with_untracked(|s| s.iter().copied().collect());
+        if tables.is_empty() {
+            return;
+        }
+        tables.sort();
+        let report = graph
+            .with_untracked(|g| analyze_tables_impact(g, &tables).ok())
+            .filter(|r| !r.is_empty());
+        match report {
+            Some(report) => {
+                highlighted_edges.set(report.edges());
+                impact_preview.set(Some(report.clone()));
+                pending_selection_delete.set(Some((tables, report)));
+            }
+            None => delete_selection(tables),
+        }
+    };
+
+    // Копирование выделения: DDL и JSON-фрагмент одним текстом в системный буфер обмена
+    let copy_selection = move || {
+        let mut tables: Vec<NodeIndex> =
+            selected_tables.with_untracked(|s| s.iter().copied().collect());
+        if tables.is_empty() {
+            return;
+        }
+        tables.sort();
+        let fragment = graph.with_untracked(|g| ClipboardFragment::from_tables(g, &tables));
+        let options = settings.with_untracked(|s| ExportOptions {
+            format: ExportFormat::Sql,
+            sql_dialect: s.dialect.clone(),
+            sql_format: Some(s.sql_format.clone()),
+            ..Default::default()
+        });
+        match fragment.to_clipboard_text(&options) {
+            Ok(text) => {
+                #[cfg(not(feature = "ssr"))]
+                if let Some(window) = web_sys::window() {
+                    let _ = window.navigator().clipboard().write_text(&text);
+                }
+                #[cfg(feature = "ssr")]
+                let _ = text;
+                notification_manager.info(
+                    "Copied",
+                    format!("{} table(s) copied to the clipboard", fragment.tables.len()),
+                );
+            }
+            Err(e) => notification_manager.error("Cannot copy tables", e),
+        }
+    };
+
+    // Вставка скопированных таблиц (из этой или другой диаграммы) с выделением копий
+    #[allow(unused_variables)]
+    let paste_text = move |text: String| {
+        let fragment = match ClipboardFragment::from_clipboard_text(&text) {
+            Ok(fragment) if !fragment.is_empty() => fragment,
+            Ok(_) => return,
+            Err(e) => {
+                notification_manager.warning("Nothing to paste", e);
+                return;
+            }
+        };
+        let before = graph.get_untracked();
+        // Вставляем в копию, чтобы ошибка не оставила граф наполовину изменённым
+        let mut updated = before.clone();
+        match paste_fragment(&mut updated, &fragment) {
+            Ok((tables, ops)) => {
+                graph.set(updated);
+                history.commit(before, ops);
+                highlighted_edges.set(HashSet::new());
+                selected_tables.set(tables.into_iter().collect());
+                dispatch_save_event("tables_pasted");
+            }
+            Err(e) => notification_manager.error("Cannot paste tables", e),
+        }
+    };
+
+    // Геометрия, от которой зависят маршруты связей (без самих маршрутов)
+    let route_geometry = Memo::new(move |_| {
+        graph.with(|g| {
+            let tables: Vec<_> = g
+                .node_indices()
+                .filter_map(|idx| {
+                    let node = g.node_weight(idx)?;
+                    let columns: Vec<String> =
+                        node.columns.iter().map(|c| c.name.clone()).collect();
+                    // Строки таблицы зависят от уровня отображения и признаков PK,
+                    // UNIQUE и участия колонок в связях
+                    Some((idx, node.position, columns, TableRows::of(g, idx)))
+                })
+                .collect();
+            let edges: Vec<_> = g
+                .edge_indices()
+                .filter_map(|idx| {
+                    let (from, to) = g.edge_endpoints(idx)?;
+                    let edge = g.edge_weight(idx)?;
+                    Some((
+                        idx,
+                        from,
+                        to,
+                        edge.from_column.clone(),
+                        edge.to_column.clone(),
+                    ))
+                })
+                .collect();
+            (tables, edges)
+        })
+    });
+
+    // Контуры таблиц, для которых посчитаны маршруты
+    let routed_outlines = StoredValue::new(HashMap::new());
+
+    // Перестраиваем устаревшие маршруты связей, когда таблицы не перетаскиваются
+    Effect::new(move |_| {
+        route_geometry.track();
+        if _dragging_node.get().is_some() || remote_dragging_nodes.with(|n| !n.is_empty()) {
+            return;
+        }
+        let routes = graph.with_untracked(|g| {
+            let stale = routed_outlines.with_value(|outlines| stale_edges(g, outlines));
+            routed_outlines.set_value(table_outlines(g));
+            route_local_edges(g, &RoutingConfig::default(), &stale)
+        });
+        graph.maybe_update(|g| apply_routes(g, routes));
+    });
+
     // Listen for remote graph operations from LiveShare
     // NOTE: We use handler.forget() here which technically leaks memory, but:
     // 1. These are global event listeners that live for the entire app lifetime
@@ -449,6 +861,8 @@ pub fn SchemaCanvas(
                             );
 
                             apply_graph_state(graph_clone, state);
+                            // Локальная история не применима к состоянию сессии
+                            history.clear();
 
                             // Show notification if local data was replaced
                             if had_local_data {
@@ -503,13 +917,6 @@ pub fn SchemaCanvas(
         });
     }
 
-    // Helper function to send graph op when connected
-    let send_graph_op = move |op: GraphOperation| {
-        if liveshare_ctx.connection_state.with_untracked(|v| *v) == ConnectionState::Connected {
-            liveshare_ctx.send_graph_op(op);
-        }
-    };
-
     // Глобальный обработчик перемещения мыши
     #[cfg(not(feature = "ssr"))]
     {
@@ -574,12 +981,16 @@ pub fn SchemaCanvas(
                 // Mark that we've moved (dragged)
                 was_dragged.set(true);
 
-                // Используем batch для минимизации реактивных пересчётов
+                // Используем batch для минимизации реактивных пересчётов;
+                // выделенная группа сдвигается вместе с таблицей под курсором
                 batch(move || {
-                    graph.update(|g| {
-                        if let Some(node) = g.node_weight_mut(node_idx) {
-                            node.position = (new_x, new_y);
-                        }
+                    drag_group.with_value(|group| {
+                        let Some(&(_, origin)) = group.first() else {
+                            return;
+                        };
+                        graph.update(|g| {
+                            move_tables(g, group, (new_x - origin.0, new_y - origin.1));
+                        });
                     });
                 });
 
@@ -597,7 +1008,39 @@ pub fn SchemaCanvas(
                     {
                         liveshare_ctx.send_table_drag_end(node_idx.index() as u32, position);
                     }
+                    let group = drag_group.get_value();
+                    if let Some(&(_, origin)) = group.first()
+                        && origin != position
+                    {
+                        let ops: Vec<GraphOperation> = graph.with_untracked(|g| {
+                            group
+                                .iter()
+                                .filter_map(|&(idx, _)| {
+                                    let node = g.node_weight(idx)?;
+                                    Some(GraphOperation::MoveTable {
+                                        node_id: idx.index() as u32,
+                                        table_uuid: node.uuid,
+                                        position: node.position,
+                                    })
+                                })
+                                .collect()
+                        });
+                        let mut before = graph.get_untracked();
+                        for &(idx, origin) in &group {
+                            if let Some(node) = before.node_weight_mut(idx) {
+                                node.position = origin;
+                            }
+                        }
+                        // Одна таблица уже отправлена как TableDragEnd - только записываем
+                        // в историю; группа рассылается одной пачкой MoveTable
+                        if group.len() > 1 {
+                            history.commit(before, ops);
+                        } else {
+                            history.record(before, ops);
+                        }
+                    }
                 }
+                drag_group.set_value(Vec::new());
                 set_dragging_node.set(None);
 
                 // Trigger save after drag ends
@@ -620,11 +1063,167 @@ pub fn SchemaCanvas(
         });
     }
 
+    // Плавный переход: точка канваса `center` оказывается в середине экрана
+    #[cfg(not(feature = "ssr"))]
+    let pan_animation = StoredValue::new(0_u64);
+    let animate_to = move |center: (f64, f64)| {
+        let target =
+            Viewport::pan_to_center(center, zoom.get_untracked(), viewport_size.get_untracked());
+        #[cfg(not(feature = "ssr"))]
+        {
+            // Новый переход отменяет предыдущий
+            pan_animation.update_value(|id| *id += 1);
+            let id = pan_animation.get_value();
+            let start = (pan_x.get_untracked(), pan_y.get_untracked());
+            let started = js_sys::Date::now();
+            leptos::task::spawn_local(async move {
+                loop {
+                    gloo_timers::future::TimeoutFuture::new(16).await;
+                    if pan_animation.get_value() != id {
+                        return;
+                    }
+                    let t = ((js_sys::Date::now() - started) / PAN_ANIMATION_MS).min(1.0);
+                    let eased = 1.0 - (1.0 - t).powi(3);
+                    set_pan_x.set(start.0 + (target.0 - start.0) * eased);
+                    set_pan_y.set(start.1 + (target.1 - start.1) * eased);
+                    if t >= 1.0 {
+                        return;
+                    }
+                }
+            });
+        }
+        #[cfg(feature = "ssr")]
+        {
+            set_pan_x.set(target.0);
+            set_pan_y.set(target.1);
+        }
+    };
+    let table_center = move |node_idx: NodeIndex| {
+        graph.with_untracked(|g| {
+            let (left, top, right, bottom) = table_bounds(g, node_idx)?;
+            Some(((left + right) / 2.0, (top + bottom) / 2.0))
+        })
+    };
+
     // Обработчик фокуса на таблице из сайдбара
-    let handle_table_focus = Callback::new(move |_node_idx: NodeIndex| {
-        // TODO: Центрировать таблицу на канвасе
+    let handle_table_focus = Callback::new(move |node_idx: NodeIndex| {
+        if let Some(center) = table_center(node_idx) {
+            if !is_visible(node_idx) {
+                focus.set(None);
+            }
+            animate_to(center);
+        }
     });
 
+    // Вход в режим фокуса (или перенос фокуса на другую таблицу с той же глубиной)
+    let enter_focus = move |node_idx: NodeIndex| {
+        let depth = focus
+            .get_untracked()
+            .map_or(DEFAULT_FOCUS_DEPTH, |mode| mode.depth);
+        focus.set(Some(FocusMode::new(node_idx).with_depth(depth)));
+        highlighted_edges.set(HashSet::new());
+        selected_tables.set(HashSet::from([node_idx]));
+        if let Some(center) = table_center(node_idx) {
+            animate_to(center);
+        }
+    };
+
+    // Связь, протянутая между колонками, с выбранным в окне типом
+    let confirm_connection = move |relationship_type: RelationshipType| {
+        let Some(connection) = pending_connection.get_untracked() else {
+            return;
+        };
+        pending_connection.set(None);
+        let before = graph.get_untracked();
+        let result = graph.try_update(|g| {
+            connect_columns(
+                g,
+                connection.table,
+                &connection.column,
+                connection.target,
+                &connection.target_column,
+                relationship_type,
+            )
+        });
+        match result {
+            Some(Ok((edge, issues, ops))) => {
+                history.commit(before, ops);
+                selected_tables.set(HashSet::new());
+                highlighted_edges.set(HashSet::from([edge]));
+                if !issues.is_empty() {
+                    let messages: Vec<String> = issues.iter().map(|i| i.to_string()).collect();
+                    notification_manager.warning("Relationship created", messages.join("; "));
+                }
+                dispatch_save_event("relationship_created");
+            }
+            Some(Err(e)) => notification_manager.error("Cannot create relationship", e),
+            None => {}
+        }
+    };
+
+    // Переход к результату командной палитры с подсветкой
+    let focus_palette_item = move |item: PaletteItem| {
+        editor_mode.set(EditorMode::Visual);
+        match item {
+            PaletteItem::Table(node_idx)
+            | PaletteItem::Column {
+                table: node_idx, ..
+            } => {
+                let Some(center) = table_center(node_idx) else {
+                    return;
+                };
+                highlighted_edges.set(HashSet::new());
+                selected_tables.set(HashSet::from([node_idx]));
+                animate_to(center);
+            }
+            PaletteItem::Relationship(edge_idx) => {
+                let Some((from, to)) = graph.with_untracked(|g| g.edge_endpoints(edge_idx)) else {
+                    return;
+                };
+                let (Some(a), Some(b)) = (table_center(from), table_center(to)) else {
+                    return;
+                };
+                if !is_visible(from) || !is_visible(to) {
+                    focus.set(None);
+                }
+                selected_tables.set(HashSet::new());
+                highlighted_edges.set(HashSet::from([edge_idx]));
+                animate_to(((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0));
+            }
+            PaletteItem::Command(_) => {}
+        }
+    };
+
+    // Новая таблица в середине видимой области
+    let create_table_in_view = move || {
+        let (center_x, center_y) = Viewport::from_transform(
+            (pan_x.get_untracked(), pan_y.get_untracked()),
+            zoom.get_untracked(),
+            viewport_size.get_untracked(),
+        )
+        .center();
+        let position = (center_x - TABLE_WIDTH / 2.0, center_y - 40.0);
+        let before = graph.get_untracked();
+        let node_idx = graph.write().create_table_auto(position);
+        let (name, uuid) = graph.with_untracked(|g| {
+            g.node_weight(node_idx)
+                .map(|n| (n.name.clone(), n.uuid))
+                .unwrap_or_default()
+        });
+        history.commit(
+            before,
+            vec![GraphOperation::CreateTable {
+                node_id: node_idx.index() as u32,
+                table_uuid: uuid,
+                name,
+                position,
+            }],
+        );
+        editor_mode.set(EditorMode::Visual);
+        selected_tables.set(HashSet::from([node_idx]));
+        dispatch_save_event("table_created");
+    };
+
     // Обработчики для зума и панорамирования
     #[cfg(not(feature = "ssr"))]
     {
@@ -644,6 +1243,66 @@ pub fn SchemaCanvas(
         > = Rc::new(RefCell::new(None));
         let pan_closures_for_effect = pan_closures.clone();
 
+        // Размер канваса: при монтировании и при изменении размера окна
+        let resize_closure: Rc<RefCell<Option<Closure<dyn Fn()>>>> = Rc::new(RefCell::new(None));
+        Effect::new(move || {
+            let Some(canvas_element) = canvas_ref.get() else {
+                return;
+            };
+            let Some(window) = web_sys::window() else {
+                return;
+            };
+            // Снимаем обработчик прошлого запуска, иначе они копятся при каждом монтировании
+            if let Some(old_handler) = resize_closure.borrow_mut().take() {
+                let _ = window.remove_event_listener_with_callback(
+                    "resize",
+                    old_handler.as_ref().unchecked_ref(),
+                );
+            }
+            let measure = move || {
+                let (width, height) = (
+                    canvas_element.client_width(),
+                    canvas_element.client_height(),
+                );
+                // Скрытый канвас (режим Source) имеет нулевой размер
+                if width > 0 && height > 0 {
+                    viewport_size.set((width as f64, height as f64));
+                }
+            };
+            measure();
+            let resize_handler = Closure::<dyn Fn()>::new(measure);
+            let _ = window.add_event_listener_with_callback(
+                "resize",
+                resize_handler.as_ref().unchecked_ref(),
+            );
+            *resize_closure.borrow_mut() = Some(resize_handler);
+        });
+
+        // Вьюпорт для миникарт участников LiveShare; отправка с задержкой,
+        // чтобы не слать сообщение на каждый шаг панорамирования
+        let peer_count = Memo::new(move |_| liveshare_ctx.remote_users.with(|u| u.len()));
+        let viewport_timer = StoredValue::new(None::<leptos::leptos_dom::helpers::TimeoutHandle>);
+        Effect::new(move || {
+            let (zoom, pan, size) = (zoom.get(), (pan_x.get(), pan_y.get()), viewport_size.get());
+            // Новые участники сразу получают текущий вьюпорт
+            peer_count.track();
+            if liveshare_ctx.connection_state.get() != ConnectionState::Connected {
+                return;
+            }
+            if let Some(handle) = viewport_timer.get_value() {
+                handle.clear();
+            }
+            let handle = set_timeout_with_handle(
+                move || {
+                    let center = Viewport::from_transform(pan, zoom, size).center();
+                    liveshare_ctx.send_viewport(center, zoom, size);
+                },
+                std::time::Duration::from_millis(150),
+            )
+            .ok();
+            viewport_timer.set_value(handle);
+        });
+
         // Эффект для настройки обработчиков на элементе канваса
         Effect::new(move || {
             let Some(canvas_element) = canvas_ref.get() else {
@@ -741,7 +1400,207 @@ pub fn SchemaCanvas(
             *pan_closures_for_effect.borrow_mut() = Some((move_closure, up_closure));
         });
 
-        // Обработчик клавиатуры для зума (Ctrl + "+"/"-")
+        // Рамка выделения левой кнопкой по пустому месту канваса
+        let marquee_closures: Rc<
+            RefCell<
+                Option<(
+                    Closure<dyn Fn(web_sys::MouseEvent)>,
+                    Closure<dyn Fn(web_sys::MouseEvent)>,
+                )>,
+            >,
+        > = Rc::new(RefCell::new(None));
+        let marquee_closures_for_effect = marquee_closures.clone();
+
+        Effect::new(move || {
+            let marquee_state = marquee_start.get();
+
+            let document = web_sys::window()
+                .and_then(|w| w.document())
+                .expect("no document");
+
+            if let Some((old_move, old_up)) = marquee_closures_for_effect.borrow_mut().take() {
+                let _ = document.remove_event_listener_with_callback(
+                    "mousemove",
+                    old_move.as_ref().unchecked_ref(),
+                );
+                let _ = document.remove_event_listener_with_callback(
+                    "mouseup",
+                    old_up.as_ref().unchecked_ref(),
+                );
+            }
+
+            let Some((start_x, start_y, additive)) = marquee_state else {
+                return;
+            };
+
+            let move_closure = Closure::new(move |ev: web_sys::MouseEvent| {
+                ev.prevent_default();
+                marquee_end.set((ev.client_x() as f64, ev.client_y() as f64));
+            });
+
+            let up_closure = Closure::new(move |ev: web_sys::MouseEvent| {
+                set_marquee_start.set(None);
+                let (end_x, end_y) = (ev.client_x() as f64, ev.client_y() as f64);
+                // Без заметного движения это обычный клик по канвасу
+                if (end_x - start_x).abs() < 4.0 && (end_y - start_y).abs() < 4.0 {
+                    return;
+                }
+
+                let current_zoom = zoom.with_untracked(|v| *v);
+                let current_pan_x = pan_x.with_untracked(|v| *v);
+                let current_pan_y = pan_y.with_untracked(|v| *v);
+                let to_canvas = |x: f64, y: f64| {
+                    (
+                        (x - current_pan_x) / current_zoom,
+                        (y - current_pan_y) / current_zoom,
+                    )
+                };
+                let mut hit = graph.with_untracked(|g| {
+                    tables_in_rect(g, to_canvas(start_x, start_y), to_canvas(end_x, end_y))
+                });
+                hit.retain(|&idx| is_visible(idx));
+                selected_tables.update(|selected| {
+                    if !additive {
+                        selected.clear();
+                    }
+                    selected.extend(hit);
+                });
+                highlighted_edges.set(HashSet::new());
+                suppress_canvas_click.set_value(true);
+            });
+
+            document
+                .add_event_listener_with_callback(
+                    "mousemove",
+                    move_closure.as_ref().unchecked_ref(),
+                )
+                .unwrap();
+
+            document
+                .add_event_listener_with_callback("mouseup", up_closure.as_ref().unchecked_ref())
+                .unwrap();
+
+            *marquee_closures_for_effect.borrow_mut() = Some((move_closure, up_closure));
+        });
+
+        // Протягивание связи от ручки колонки до колонки другой таблицы
+        let connect_closures: Rc<
+            RefCell<
+                Option<(
+                    Closure<dyn Fn(web_sys::MouseEvent)>,
+                    Closure<dyn Fn(web_sys::MouseEvent)>,
+                )>,
+            >,
+        > = Rc::new(RefCell::new(None));
+        let connect_closures_for_effect = connect_closures.clone();
+
+        Effect::new(move || {
+            let drag_state = connect_drag.get();
+
+            let document = web_sys::window()
+                .and_then(|w| w.document())
+                .expect("no document");
+
+            if let Some((old_move, old_up)) = connect_closures_for_effect.borrow_mut().take() {
+                let _ = document.remove_event_listener_with_callback(
+                    "mousemove",
+                    old_move.as_ref().unchecked_ref(),
+                );
+                let _ = document.remove_event_listener_with_callback(
+                    "mouseup",
+                    old_up.as_ref().unchecked_ref(),
+                );
+            }
+
+            let Some((table, column)) = drag_state else {
+                return;
+            };
+
+            let to_canvas = move |ev: &web_sys::MouseEvent| {
+                let current_zoom = zoom.with_untracked(|v| *v);
+                (
+                    (ev.client_x() as f64 - pan_x.with_untracked(|v| *v)) / current_zoom,
+                    (ev.client_y() as f64 - pan_y.with_untracked(|v| *v)) / current_zoom,
+                )
+            };
+
+            let move_closure = Closure::new(move |ev: web_sys::MouseEvent| {
+                ev.prevent_default();
+                connect_cursor.set(to_canvas(&ev));
+            });
+
+            let up_closure = Closure::new(move |ev: web_sys::MouseEvent| {
+                connect_drag.set(None);
+                let (x, y) = to_canvas(&ev);
+                let position = (ev.client_x() as f64, ev.client_y() as f64);
+                let dropped = graph.with_untracked(|g| {
+                    // Верхняя таблица под курсором - последняя отрисованная
+                    let target = rendered_node_indices
+                        .get_untracked()
+                        .into_iter()
+                        .rev()
+                        .filter(|&idx| idx != table)
+                        .find(|&idx| {
+                            table_bounds(g, idx).is_some_and(|(left, top, right, bottom)| {
+                                x >= left && x <= right && y >= top && y <= bottom
+                            })
+                        })?;
+                    let (_, source) = g[table].find_column(&column)?;
+                    let node = &g[target];
+                    let existing = column_at_y(g, target, y)
+                        .map(|i| &node.columns[i])
+                        .filter(|c| source.is_type_compatible_with(c));
+                    let source_label = format!("{}.{}", g[table].name, column);
+                    // Мимо совместимой колонки - предлагаем создать внешний ключ
+                    let pending = match existing {
+                        Some(target_column) => PendingConnection {
+                            table,
+                            column: column.clone(),
+                            target,
+                            target_column: ConnectTarget::Column(target_column.name.clone()),
+                            source_label,
+                            target_label: format!("{}.{}", node.name, target_column.name),
+                            new_column_type: None,
+                            suggested: suggest_relationship_type(source, target_column),
+                            position,
+                        },
+                        None => {
+                            let new_column = foreign_key_column(g, table, &column, target)?;
+                            PendingConnection {
+                                table,
+                                column: column.clone(),
+                                target,
+                                target_column: ConnectTarget::NewColumn,
+                                source_label,
+                                target_label: format!("{}.{}", node.name, new_column.name),
+                                suggested: suggest_relationship_type(source, &new_column),
+                                new_column_type: Some(new_column.data_type),
+                                position,
+                            }
+                        }
+                    };
+                    Some(pending)
+                });
+                suppress_canvas_click.set_value(true);
+                pending_connection.set(dropped);
+            });
+
+            document
+                .add_event_listener_with_callback(
+                    "mousemove",
+                    move_closure.as_ref().unchecked_ref(),
+                )
+                .unwrap();
+
+            document
+                .add_event_listener_with_callback("mouseup", up_closure.as_ref().unchecked_ref())
+                .unwrap();
+
+            *connect_closures_for_effect.borrow_mut() = Some((move_closure, up_closure));
+        });
+
+        // Обработчик клавиатуры: зум (Ctrl + "+"/"-"), отмена (Ctrl+Z, Ctrl+Shift+Z / Ctrl+Y),
+        // копирование и вставка таблиц (Ctrl+C / Ctrl+V)
         Effect::new(move || {
             let document = web_sys::window()
                 .and_then(|w| w.document())
@@ -765,6 +1624,57 @@ pub fn SchemaCanvas(
                             *z = new_zoom;
                         });
                     }
+                    // Удаление и сброс выделения (в полях ввода клавиши работают как обычно)
+                    else if (ev.key() == "Delete" || ev.key() == "Backspace")
+                        && !is_text_input_focused()
+                        && pending_selection_delete.with_untracked(|p| p.is_none())
+                        && selected_tables.with_untracked(|s| !s.is_empty())
+                    {
+                        ev.prevent_default();
+                        request_delete_selection();
+                    } else if ev.key() == "Escape" && !is_text_input_focused() {
+                        // Esc без выделения выходит из режима фокуса
+                        if selected_tables.with_untracked(|s| s.is_empty()) {
+                            focus.set(None);
+                        } else {
+                            selected_tables.set(HashSet::new());
+                        }
+                    }
+                    // Отмена и повтор; в полях ввода работает их собственная отмена
+                    else if (ev.ctrl_key() || ev.meta_key()) && !is_text_input_focused() {
+                        let key = ev.key().to_lowercase();
+                        if key == "z" && !ev.shift_key() {
+                            ev.prevent_default();
+                            history.undo();
+                        } else if (key == "z" && ev.shift_key()) || key == "y" {
+                            ev.prevent_default();
+                            history.redo();
+                        } else if key == "c" && selected_tables.with_untracked(|s| !s.is_empty()) {
+                            ev.prevent_default();
+                            copy_selection();
+                        } else if key == "v" {
+                            ev.prevent_default();
+                            let clipboard = web_sys::window()
+                                .expect("no window")
+                                .navigator()
+                                .clipboard();
+                            leptos::task::spawn_local(async move {
+                                match wasm_bindgen_futures::JsFuture::from(clipboard.read_text())
+                                    .await
+                                {
+                                    Ok(value) => {
+                                        if let Some(text) = value.as_string() {
+                                            paste_text(text);
+                                        }
+                                    }
+                                    Err(_) => notification_manager.error(
+                                        "Cannot paste tables",
+                                        "Clipboard access was denied by the browser",
+                                    ),
+                                }
+                            });
+                        }
+                    }
                 },
             );
 
@@ -794,6 +1704,8 @@ pub fn SchemaCanvas(
                 diagram_name=diagram_name
                 is_demo=is_demo
                 on_name_change=on_name_change
+                highlighted_edges=highlighted_edges
+                impact_preview=impact_preview
             />
 
             // Source Editor (показывается в режиме Source)
@@ -805,7 +1717,12 @@ pub fn SchemaCanvas(
                         "hidden"
                     }
                 }>
-                    <SourceEditor graph=graph readonly=false />
+                    <SourceEditor
+                        graph=graph
+                        settings=settings
+                        readonly=false
+                        on_notification=Callback::new(move |n| notification_manager.notify(n))
+                    />
                 </div>
             </Show>
 
@@ -826,14 +1743,26 @@ pub fn SchemaCanvas(
                         ev.stop_propagation();
                         set_panning.set(Some((ev.client_x() as f64, ev.client_y() as f64)));
                     }
+                    // Левая кнопка по пустому месту - рамка выделения (таблицы останавливают всплытие)
+                    else if ev.button() == 0 {
+                        ev.prevent_default();
+                        blur_active_element();
+                        let start = (ev.client_x() as f64, ev.client_y() as f64);
+                        marquee_end.set(start);
+                        set_marquee_start.set(Some((start.0, start.1, ev.shift_key())));
+                    }
                 }
                 on:click=move |ev: web_sys::MouseEvent| {
+                    if suppress_canvas_click.get_value() {
+                        suppress_canvas_click.set_value(false);
+                        return;
+                    }
                     // Clear selection when clicking on empty canvas (not on table or edge)
                     // The event target should be the canvas itself or the grid
-                    if ev.button() == 0 {
+                    if ev.button() == 0 && !ev.shift_key() {
                         // Only clear if we didn't click on a table or edge (they stop propagation)
                         highlighted_edges.set(HashSet::new());
-                        selected_table.set(None);
+                        selected_tables.set(HashSet::new());
                     }
                 }
                 on:contextmenu=move |ev: web_sys::MouseEvent| {
@@ -869,64 +1798,50 @@ pub fn SchemaCanvas(
                     >
                         // Рендерим связи - используем мемоизированные индексы
                         {move || {
+                        if detail_level.get() == DetailLevel::Compact {
+                            // Мелкий масштаб: все связи одним путём, без подписей и стрелок
+                            let batched = rendered_edge_indices.with(|edges| {
+                                graph.with(|g| {
+                                    edges
+                                        .iter()
+                                        .filter_map(|&edge_idx| edge_geometry(g, edge_idx).map(|geometry| geometry.path))
+                                        .collect::<Vec<_>>()
+                                        .join(" ")
+                                })
+                            });
+                            return view! {
+                                <path
+                                    d=batched
+                                    class="stroke-current text-gray-500 dark:text-gray-400"
+                                    stroke-width="1.5"
+                                    vector-effect="non-scaling-stroke"
+                                    fill="none"
+                                    style="pointer-events: none;"
+                                />
+                            }
+                            .into_any();
+                        }
                         let current_highlighted = highlighted_edges.get();
-                        let current_selected_table = selected_table.get();
+                        let current_selected = selected_tables.get();
+                        let current_notation = settings.with(|s| s.notation);
+                        let show_arrow = current_notation.shows_arrow();
 
-                        edge_indices.get()
+                        rendered_edge_indices.get()
                             .into_iter()
                             .filter_map(|edge_idx| {
                                 graph.with(|g| {
                                     let (from_idx, to_idx) = g.edge_endpoints(edge_idx)?;
-                                    let from_node = g.node_weight(from_idx)?;
-                                    let to_node = g.node_weight(to_idx)?;
                                     let edge = g.edge_weight(edge_idx)?;
-
-                                    let (from_x, from_y) = from_node.position;
-                                    let (to_x, to_y) = to_node.position;
-
-                                    // Константы для расчёта позиции стрелок
-                                    const NODE_WIDTH: f64 = 280.0;
-                                    const HEADER_HEIGHT: f64 = 48.0;
-                                    const ROW_HEIGHT: f64 = 36.0;
-                                    const PADDING_TOP: f64 = 8.0;
-                                    const GAP: f64 = 30.0;
-
-                                    // Находим индекс колонки в исходной таблице
-                                    let from_col_idx = from_node.columns.iter()
-                                        .position(|col| col.name == edge.from_column)
-                                        .unwrap_or(0);
-
-                                    // Находим индекс колонки в целевой таблице
-                                    let to_col_idx = to_node.columns.iter()
-                                        .position(|col| col.name == edge.to_column)
-                                        .unwrap_or(0);
-
-                                    // Вычисляем Y координаты для конкретных колонок
-                                    let from_col_y = from_y + HEADER_HEIGHT + PADDING_TOP
-                                        + (from_col_idx as f64 * ROW_HEIGHT) + (ROW_HEIGHT / 2.0);
-                                    let to_col_y = to_y + HEADER_HEIGHT + PADDING_TOP
-                                        + (to_col_idx as f64 * ROW_HEIGHT) + (ROW_HEIGHT / 2.0);
-
-                                    // Определяем границы таблиц
-                                    let from_right = from_x + NODE_WIDTH;
-                                    let from_left = from_x;
-                                    let to_right = to_x + NODE_WIDTH;
-                                    let to_left = to_x;
-
-                                    // Умная логика выбора пути стрелки
-                                    let (_start_x, _start_y, _end_x, _end_y, text_x, text_y, path_data) =
-                                        calculate_edge_path(
-                                            from_x, from_y, to_x, to_y,
-                                            from_col_y, to_col_y,
-                                            from_left, from_right, to_left, to_right,
-                                            NODE_WIDTH, GAP
-                                        );
+                                    let EdgeGeometry { path: path_data, label_x: text_x, label_y: text_y, points } =
+                                        edge_geometry(g, edge_idx)?;
+                                    let decorations = edge_decorations(g, edge_idx, current_notation, &points);
 
                                     let rel_type = edge.relationship_type.to_string();
 
                                     // Check if this edge should be highlighted (skip rendering here if highlighted - will be in top layer)
                                     let is_highlighted = current_highlighted.contains(&edge_idx)
-                                        || current_selected_table.map(|t| t == from_idx || t == to_idx).unwrap_or(false);
+                                        || current_selected.contains(&from_idx)
+                                        || current_selected.contains(&to_idx);
 
                                     // Clone path_data for the invisible click target
                                     let path_data_clone = path_data.clone();
@@ -946,7 +1861,7 @@ pub fn SchemaCanvas(
                                                     let mut new_set = HashSet::new();
                                                     new_set.insert(edge_idx);
                                                     highlighted_edges.set(new_set);
-                                                    selected_table.set(None);
+                                                    selected_tables.set(HashSet::new());
                                                 }
                                             />
                                             // Visible path (dimmed if highlighted, since highlighted version is on top layer)
@@ -955,26 +1870,38 @@ pub fn SchemaCanvas(
                                                 class="stroke-current text-gray-500 dark:text-gray-400"
                                                 stroke-width="2"
                                                 fill="none"
-                                                marker-end="url(#arrowhead)"
+                                                marker-end=show_arrow.then_some("url(#arrowhead)")
                                                 style="pointer-events: none;"
                                                 style:opacity=if is_highlighted { "0.3" } else { "1" }
                                             />
-                                            <text
-                                                x=text_x
-                                                y=text_y
-                                                class="fill-current text-gray-500 dark:text-gray-400 select-none"
-                                                font-size="12"
-                                                text-anchor="start"
-                                                style="pointer-events: none;"
-                                                style:opacity=if is_highlighted { "0.3" } else { "1" }
-                                            >
-                                                {rel_type}
-                                            </text>
+                                            {show_arrow.then(|| view! {
+                                                <text
+                                                    x=text_x
+                                                    y=text_y
+                                                    class="fill-current text-gray-500 dark:text-gray-400 select-none"
+                                                    font-size="12"
+                                                    text-anchor="start"
+                                                    style="pointer-events: none;"
+                                                    style:opacity=if is_highlighted { "0.3" } else { "1" }
+                                                >
+                                                    {rel_type}
+                                                </text>
+                                            })}
+                                            {decorations.map(|(source, target)| view! {
+                                                <g
+                                                    style="pointer-events: none;"
+                                                    style:opacity=if is_highlighted { "0.3" } else { "1" }
+                                                >
+                                                    {decoration_view(source, false)}
+                                                    {decoration_view(target, false)}
+                                                </g>
+                                            })}
                                         </g>
                                     })
                                 })
                             })
                             .collect_view()
+                            .into_any()
                         }}
                     </g>
                 </svg>
@@ -997,27 +1924,46 @@ pub fn SchemaCanvas(
                     let current_dragging = _dragging_node.get();
                     // Get which tables are being remotely dragged (for disabling CSS transitions)
                     let current_remote_dragging = remote_dragging_nodes.get();
-                    let current_selected_table = selected_table.get();
+                    let current_selected = selected_tables.get();
+                    let current_remote_selections = remote_selections.get();
+
+                    let compact = detail_level.get() == DetailLevel::Compact;
+                    let current_connect_targets = connect_targets.get();
+                    let connect_source = connect_drag.with(|d| d.as_ref().map(|(table, _)| *table));
+                    let current_impact = impact_marks.get();
 
-                    node_indices.get()
+                    rendered_node_indices.get()
                         .into_iter()
                         .filter_map(|idx| {
                             // Используем with вместо get для избежания клонирования всего графа
                             graph.with(|g| {
                                 g.node_weight(idx).map(|node| {
                                     let node_clone = node.clone();
+                                    let rows = TableRows::of(g, idx);
                                     let node_idx = idx;
                                     // Check if this specific node is being dragged locally or remotely
                                     let is_local_dragging = current_dragging.map(|(drag_idx, _, _)| drag_idx == idx).unwrap_or(false);
                                     let is_remote_dragging = current_remote_dragging.contains(&(idx.index() as u32));
                                     let is_dragging = is_local_dragging || is_remote_dragging;
-                                    let is_selected = current_selected_table == Some(idx);
+                                    let is_selected = current_selected.contains(&idx);
+                                    let remote_selection_color =
+                                        current_remote_selections.get(&node.uuid.to_string()).cloned();
+                                    // Таблица-источник при протягивании не затемняется
+                                    let connect_targets_for_table = current_connect_targets
+                                        .as_ref()
+                                        .filter(|_| connect_source != Some(idx))
+                                        .map(|targets| targets.get(&idx).cloned().unwrap_or_default());
+                                    let affected_columns = current_impact.get(&node.name).cloned();
 
                                     view! {
                                         <TableNodeView
                                             node=node_clone
+                                            rows=rows
                                             is_being_dragged=is_dragging
                                             is_selected=is_selected
+                                            affected_columns=affected_columns
+                                            compact=compact
+                                            remote_selection_color=remote_selection_color
                                             on_mouse_down=Callback::new(move |ev: web_sys::MouseEvent| {
                                                 if ev.button() != 0 {
                                                     return;
@@ -1046,6 +1992,19 @@ pub fn SchemaCanvas(
                                                         let offset_x = canvas_mouse_x - x;
                                                         let offset_y = canvas_mouse_y - y;
 
+                                                        // Таблица из выделения тащит за собой всю группу
+                                                        let mut group = vec![(node_idx, (x, y))];
+                                                        selected_tables.with_untracked(|selected| {
+                                                            if selected.len() > 1 && selected.contains(&node_idx) {
+                                                                group.extend(
+                                                                    selected
+                                                                        .iter()
+                                                                        .filter(|&&other| other != node_idx)
+                                                                        .filter_map(|&other| g.node_weight(other).map(|n| (other, n.position))),
+                                                                );
+                                                            }
+                                                        });
+                                                        drag_group.set_value(group);
                                                         set_dragging_node.set(Some((node_idx, offset_x, offset_y)));
 
                                                         // Send TableDragStart to attach cursor to table
@@ -1065,11 +2024,52 @@ pub fn SchemaCanvas(
                                                 ev.stop_propagation();
                                                 // Only select if we didn't drag
                                                 if !was_dragged.with_untracked(|v| *v) {
-                                                    // Select this table and highlight all its edges
-                                                    selected_table.set(Some(node_idx));
+                                                    // Shift-click toggles the table in the selection,
+                                                    // a plain click selects only this table
+                                                    if ev.shift_key() {
+                                                        selected_tables.update(|selected| {
+                                                            if !selected.remove(&node_idx) {
+                                                                selected.insert(node_idx);
+                                                            }
+                                                        });
+                                                    } else {
+                                                        selected_tables.set(HashSet::from([node_idx]));
+                                                    }
                                                     highlighted_edges.set(HashSet::new());
                                                 }
                                             })
+                                            on_focus=Callback::new(move |_| enter_focus(node_idx))
+                                            on_connect_start=Callback::new(move |(column, ev): (String, web_sys::MouseEvent)| {
+                                                let current_zoom = zoom.with_untracked(|v| *v);
+                                                connect_cursor.set((
+                                                    (ev.client_x() as f64 - pan_x.with_untracked(|v| *v)) / current_zoom,
+                                                    (ev.client_y() as f64 - pan_y.with_untracked(|v| *v)) / current_zoom,
+                                                ));
+                                                connect_drag.set(Some((node_idx, column)));
+                                            })
+                                            connect_targets=connect_targets_for_table
+                                            on_toggle_pin=Callback::new(move |_| {
+                                                let before = graph.get_untracked();
+                                                let toggled = graph.try_update(|g| {
+                                                    let node = g.node_weight_mut(node_idx)?;
+                                                    node.pinned = !node.pinned;
+                                                    Some((node.uuid, node.pinned))
+                                                });
+                                                if let Some(Some((table_uuid, pinned))) = toggled {
+                                                    history.commit(before, vec![GraphOperation::SetTablePinned {
+                                                        node_id: node_idx.index() as u32,
+                                                        table_uuid,
+                                                        pinned,
+                                                    }]);
+                                                }
+                                            })
+                                            on_display_change=Callback::new(move |display| {
+                                                let before = graph.get_untracked();
+                                                let ops = graph.try_update(|g| set_table_display(g, &[node_idx], display)).unwrap_or_default();
+                                                if !ops.is_empty() {
+                                                    history.commit(before, ops);
+                                                }
+                                            })
                                             />
                                         }
                                     })
@@ -1117,9 +2117,11 @@ pub fn SchemaCanvas(
                         // Рендерим ТОЛЬКО выделенные связи
                         {move || {
                         let current_highlighted = highlighted_edges.get();
-                        let current_selected_table = selected_table.get();
+                        let current_selected = selected_tables.get();
+                        let current_notation = settings.with(|s| s.notation);
+                        let show_arrow = current_notation.shows_arrow();
 
-                        edge_indices.get()
+                        rendered_edge_indices.get()
                             .into_iter()
                             .filter_map(|edge_idx| {
                                 graph.with(|g| {
@@ -1127,50 +2129,17 @@ pub fn SchemaCanvas(
 
                                     // Only render if highlighted
                                     let is_highlighted = current_highlighted.contains(&edge_idx)
-                                        || current_selected_table.map(|t| t == from_idx || t == to_idx).unwrap_or(false);
+                                        || current_selected.contains(&from_idx)
+                                        || current_selected.contains(&to_idx);
 
                                     if !is_highlighted {
                                         return None;
                                     }
 
-                                    let from_node = g.node_weight(from_idx)?;
-                                    let to_node = g.node_weight(to_idx)?;
                                     let edge = g.edge_weight(edge_idx)?;
-
-                                    let (from_x, from_y) = from_node.position;
-                                    let (to_x, to_y) = to_node.position;
-
-                                    const NODE_WIDTH: f64 = 280.0;
-                                    const HEADER_HEIGHT: f64 = 48.0;
-                                    const ROW_HEIGHT: f64 = 36.0;
-                                    const PADDING_TOP: f64 = 8.0;
-                                    const GAP: f64 = 30.0;
-
-                                    let from_col_idx = from_node.columns.iter()
-                                        .position(|col| col.name == edge.from_column)
-                                        .unwrap_or(0);
-
-                                    let to_col_idx = to_node.columns.iter()
-                                        .position(|col| col.name == edge.to_column)
-                                        .unwrap_or(0);
-
-                                    let from_col_y = from_y + HEADER_HEIGHT + PADDING_TOP
-                                        + (from_col_idx as f64 * ROW_HEIGHT) + (ROW_HEIGHT / 2.0);
-                                    let to_col_y = to_y + HEADER_HEIGHT + PADDING_TOP
-                                        + (to_col_idx as f64 * ROW_HEIGHT) + (ROW_HEIGHT / 2.0);
-
-                                    let from_right = from_x + NODE_WIDTH;
-                                    let from_left = from_x;
-                                    let to_right = to_x + NODE_WIDTH;
-                                    let to_left = to_x;
-
-                                    let (_start_x, _start_y, _end_x, _end_y, text_x, text_y, path_data) =
-                                        calculate_edge_path(
-                                            from_x, from_y, to_x, to_y,
-                                            from_col_y, to_col_y,
-                                            from_left, from_right, to_left, to_right,
-                                            NODE_WIDTH, GAP
-                                        );
+                                    let EdgeGeometry { path: path_data, label_x: text_x, label_y: text_y, points } =
+                                        edge_geometry(g, edge_idx)?;
+                                    let decorations = edge_decorations(g, edge_idx, current_notation, &points);
 
                                     let rel_type = edge.relationship_type.to_string();
                                     let path_data_glow = path_data.clone();
@@ -1193,34 +2162,230 @@ pub fn SchemaCanvas(
                                                 fill="none"
                                                 stroke-dasharray="10 10"
                                                 class="animated-edge"
-                                                marker-end="url(#arrowhead-white)"
+                                                marker-end=show_arrow.then_some("url(#arrowhead-white)")
                                             />
                                             // Relationship type label
-                                            <text
-                                                x=text_x
-                                                y=text_y
-                                                fill="white"
-                                                font-size="13"
-                                                font-weight="bold"
-                                                text-anchor="start"
-                                                style="text-shadow: 0 0 4px rgba(0,0,0,0.8);"
-                                            >
-                                                {rel_type}
-                                            </text>
+                                            {show_arrow.then(|| view! {
+                                                <text
+                                                    x=text_x
+                                                    y=text_y
+                                                    fill="white"
+                                                    font-size="13"
+                                                    font-weight="bold"
+                                                    text-anchor="start"
+                                                    style="text-shadow: 0 0 4px rgba(0,0,0,0.8);"
+                                                >
+                                                    {rel_type}
+                                                </text>
+                                            })}
+                                            {decorations.map(|(source, target)| view! {
+                                                {decoration_view(source, true)}
+                                                {decoration_view(target, true)}
+                                            })}
                                         </g>
                                     })
                                 })
                             })
                             .collect_view()
                         }}
+
+                        // Протягиваемая связь: от колонки-источника до курсора
+                        {move || {
+                            let (table, column) = connect_drag.get()?;
+                            let (cursor_x, cursor_y) = connect_cursor.get();
+                            let (start_x, start_y) = graph.with(|g| {
+                                let (left, _, right, _) = table_bounds(g, table)?;
+                                let x = if cursor_x < (left + right) / 2.0 { left } else { right };
+                                Some((x, column_anchor_y(g, table, &column)))
+                            })?;
+                            Some(view! {
+                                <line
+                                    x1=start_x
+                                    y1=start_y
+                                    x2=cursor_x
+                                    y2=cursor_y
+                                    stroke="#3b82f6"
+                                    stroke-width="2"
+                                    stroke-dasharray="6 4"
+                                />
+                                <circle cx=cursor_x cy=cursor_y r="4" fill="#3b82f6" />
+                            })
+                        }}
                     </g>
                 </svg>
 
+                // Действия над связями N:M: промежуточная таблица <-> ребро
+                <JunctionActions
+                    graph=graph
+                    highlighted_edges=highlighted_edges
+                    selected_tables=selected_tables
+                    notification_manager=notification_manager
+                    on_graph_ops=Callback::new(move |_ops: Vec<GraphOperation>| {
+                        dispatch_save_event("junction_changed");
+                    })
+                />
+
+                // Групповые действия над несколькими выделенными таблицами
+                <SelectionActions
+                    graph=graph
+                    selected_tables=selected_tables
+                    settings=settings
+                    on_delete=Callback::new(move |_| request_delete_selection())
+                    on_copy=Callback::new(move |_| copy_selection())
+                />
+
+                // Рамка выделения (координаты окна)
+                {move || marquee_start.get().map(|(start_x, start_y, _)| {
+                    let (end_x, end_y) = marquee_end.get();
+                    view! {
+                        <div
+                            class="fixed pointer-events-none border border-blue-500 bg-blue-500/10"
+                            style:left=format!("{}px", start_x.min(end_x))
+                            style:top=format!("{}px", start_y.min(end_y))
+                            style:width=format!("{}px", (end_x - start_x).abs())
+                            style:height=format!("{}px", (end_y - start_y).abs())
+                            style:z-index="150"
+                        ></div>
+                    }
+                })}
+
+                <ConnectDialog
+                    pending=pending_connection
+                    on_confirm=Callback::new(confirm_connection)
+                />
+
+                <ImpactConfirmDialog
+                    report=Signal::derive(move || {
+                        pending_selection_delete.with(|p| p.as_ref().map(|(_, r)| r.clone()))
+                    })
+                    on_confirm=move || {
+                        if let Some((tables, _)) = pending_selection_delete.get_untracked() {
+                            pending_selection_delete.set(None);
+                            impact_preview.set(None);
+                            delete_selection(tables);
+                        }
+                    }
+                    on_cancel=move || {
+                        pending_selection_delete.set(None);
+                        impact_preview.set(None);
+                        highlighted_edges.set(HashSet::new());
+                    }
+                />
+
                 // Settings button (правый верхний угол) and AI Chat button
                 {
                     let settings_open = RwSignal::new(false);
                     let initial_room_id = RwSignal::new(String::new());
                     let ai_chat_open = RwSignal::new(false);
+                    // Доля выполненной авто-раскладки, пока она идёт
+                    let layout_progress: RwSignal<Option<f64>> = RwSignal::new(None);
+                    let layout_cancelled = RwSignal::new(false);
+                    // Раскладка считается на сервере: доля выполненного неизвестна
+                    let layout_on_server = RwSignal::new(false);
+                    // Отмена запроса к серверу
+                    #[cfg(not(feature = "ssr"))]
+                    let layout_abort = StoredValue::new_local(None::<web_sys::AbortController>);
+                    let cancel_auto_layout = move || {
+                        layout_cancelled.set(true);
+                        #[cfg(not(feature = "ssr"))]
+                        if let Some(controller) = layout_abort.get_value() {
+                            controller.abort();
+                        }
+                    };
+                    let settings_tab = RwSignal::new("diagram");
+                    let palette_open = RwSignal::new(false);
+
+                    // Авто-раскладка с алгоритмом из настроек диаграммы, порциями по кадрам
+                    let start_auto_layout = move || {
+                        #[cfg(not(feature = "ssr"))]
+                        {
+                            if layout_progress.get_untracked().is_some() {
+                                return;
+                            }
+                            let config = LayoutConfig {
+                                algorithm: settings.with_untracked(|s| s.layout),
+                                ..Default::default()
+                            };
+                            // Раскладка считается по снимку, к графу применяется по UUID таблиц
+                            let snapshot = graph.get_untracked();
+                            let runs_in_steps = LayoutJob::runs_in_steps(&snapshot, &config);
+                            layout_cancelled.set(false);
+                            layout_on_server.set(!runs_in_steps);
+                            layout_progress.set(Some(0.0));
+                            leptos::task::spawn_local(async move {
+                                let positions = if runs_in_steps {
+                                    let job = LayoutJob::new(&snapshot, &config);
+                                    run_layout_job(job, layout_progress, layout_cancelled)
+                                        .await
+                                        .map(|layout| Ok(TablePositions::of(&snapshot, &layout)))
+                                } else {
+                                    // Остальные раскладки не делятся на шаги - считаем на сервере
+                                    let request = LayoutRequest {
+                                        graph: snapshot,
+                                        algorithm: config.algorithm,
+                                    };
+                                    let controller = web_sys::AbortController::new().ok();
+                                    layout_abort.set_value(controller.clone());
+                                    let result = request_server_layout(
+                                        &request,
+                                        controller.as_ref().map(|c| c.signal()).as_ref(),
+                                    )
+                                    .await;
+                                    layout_abort.set_value(None);
+                                    (!layout_cancelled.get_untracked()).then_some(result)
+                                };
+                                layout_progress.set(None);
+                                let positions = match positions {
+                                    Some(Ok(positions)) => positions,
+                                    Some(Err(e)) => {
+                                        notification_manager.error("Auto layout failed", e);
+                                        return;
+                                    }
+                                    None => return,
+                                };
+                                let layout = graph.with_untracked(|g| positions.resolve(g));
+                                let before = graph.get_untracked();
+                                let ops = graph
+                                    .try_update(|g| apply_layout(g, &layout))
+                                    .unwrap_or_default();
+                                // Sync moved tables to LiveShare (pinned tables stay in place)
+                                history.commit(before, ops);
+                            });
+                        }
+                    };
+
+                    let on_palette_select = Callback::new(move |item: PaletteItem| match item {
+                        PaletteItem::Command(PaletteCommand::NewTable) => create_table_in_view(),
+                        PaletteItem::Command(PaletteCommand::AutoLayout) => {
+                            editor_mode.set(EditorMode::Visual);
+                            start_auto_layout();
+                        }
+                        PaletteItem::Command(PaletteCommand::Export) => {
+                            settings_tab.set("export");
+                            settings_open.set(true);
+                        }
+                        PaletteItem::Command(PaletteCommand::ToggleSourceView) => {
+                            editor_mode.update(|mode| {
+                                *mode = match mode {
+                                    EditorMode::Visual => EditorMode::Source,
+                                    EditorMode::Source => EditorMode::Visual,
+                                }
+                            });
+                        }
+                        PaletteItem::Command(PaletteCommand::TableDisplay(display)) => {
+                            let before = graph.get_untracked();
+                            let ops = graph
+                                .try_update(|g| {
+                                    let tables: Vec<NodeIndex> = g.node_indices().collect();
+                                    set_table_display(g, &tables, display)
+                                })
+                                .unwrap_or_default();
+                            if !ops.is_empty() {
+                                history.commit(before, ops);
+                            }
+                        }
+                        item => focus_palette_item(item),
+                    });
 
                     // Auto-open settings and connect when there's a pending room from URL
                     #[cfg(not(feature = "ssr"))]
@@ -1266,43 +2431,100 @@ pub fn SchemaCanvas(
                             is_open=settings_open
                             initial_room_id=initial_room_id
                             graph=graph
+                            settings=settings
                             diagram_name=diagram_name.map(|s| s.with_untracked(|v| v.clone()))
                             diagram_id=diagram_id
                             is_demo=is_demo
                             on_name_change=on_name_change
+                            active_tab=settings_tab
+                            focus=focus
                         />
+                        <CommandPalette is_open=palette_open graph=graph on_select=on_palette_select />
+                        // Навигация режима фокуса
+                        <Show when=move || editor_mode.get() == EditorMode::Visual>
+                            <FocusBreadcrumb
+                                graph=graph
+                                focus=focus
+                                focus_tables=focus_tables
+                                on_export=Callback::new(move |_| {
+                                    settings_tab.set("export");
+                                    settings_open.set(true);
+                                })
+                            />
+                        </Show>
+                        // Миникарта (слева от кнопки настроек)
+                        <Show when=move || editor_mode.get() == EditorMode::Visual>
+                            <Minimap
+                                graph=graph
+                                zoom=zoom
+                                pan_x=pan_x
+                                pan_y=pan_y
+                                set_pan_x=set_pan_x
+                                set_pan_y=set_pan_y
+                                viewport_size=viewport_size
+                            />
+                        </Show>
+                        // Undo / Redo (above auto layout button in bottom-right)
+                        <div
+                            class="fixed bottom-52 right-4 z-40 flex flex-col w-12 bg-theme-surface border border-theme-primary overflow-hidden theme-transition"
+                            style="border-radius: 12px; box-shadow: var(--shadow-lg);"
+                        >
+                            <button
+                                class="flex items-center justify-center h-10 text-theme-secondary hover:text-theme-accent transition-colors disabled:opacity-40"
+                                disabled=move || !history.can_undo.get()
+                                on:click=move |_| history.undo()
+                                title="Undo (Ctrl+Z)"
+                            >
+                                <Icon name=icons::UNDO class="w-5 h-5"/>
+                            </button>
+                            <button
+                                class="flex items-center justify-center h-10 border-t border-theme-primary text-theme-secondary hover:text-theme-accent transition-colors disabled:opacity-40"
+                                disabled=move || !history.can_redo.get()
+                                on:click=move |_| history.redo()
+                                title="Redo (Ctrl+Shift+Z)"
+                            >
+                                <Icon name=icons::REDO class="w-5 h-5"/>
+                            </button>
+                        </div>
                         // Auto Layout button (above AI chat button in bottom-right)
                         <button
-                            class="fixed bottom-36 right-4 z-40 flex items-center justify-center w-12 h-12 bg-theme-surface border border-theme-primary text-theme-secondary hover:text-theme-accent hover:border-theme-accent theme-transition transition-colors"
+                            class="fixed bottom-36 right-4 z-40 flex items-center justify-center w-12 h-12 bg-theme-surface border border-theme-primary text-theme-secondary hover:text-theme-accent hover:border-theme-accent theme-transition transition-colors disabled:opacity-50"
                             style="border-radius: 12px; box-shadow: var(--shadow-lg);"
-                            on:click=move |_| {
-                                // Apply auto layout
-                                graph.update(|g| {
-                                    auto_layout(g);
-                                });
-                                // Sync all table positions to LiveShare
-                                if liveshare_ctx.connection_state.with_untracked(|v| *v) == ConnectionState::Connected {
-                                    graph.with_untracked(|g| {
-                                        for node_idx in g.node_indices() {
-                                            if let Some(node) = g.node_weight(node_idx) {
-                                                liveshare_ctx.send_graph_op(GraphOperation::MoveTable {
-                                                    node_id: node_idx.index() as u32,
-                                                    table_uuid: node.uuid,
-                                                    position: node.position,
-                                                });
-                                            }
-                                        }
-                                    });
-                                }
-                            }
-                            title="Auto Layout - Arrange tables automatically based on relationships"
+                            disabled=move || layout_progress.get().is_some()
+                            on:click=move |_| start_auto_layout()
+                            title="Auto Layout - Arrange tables automatically based on relationships (pinned tables stay in place)"
                         >
                             <Icon name=icons::SPARKLES class="w-6 h-6"/>
                         </button>
+                        // Ход долгой раскладки с возможностью отмены
+                        <Show when=move || layout_progress.get().is_some()>
+                            <div
+                                class="fixed bottom-36 right-20 z-40 flex items-center gap-3 h-12 px-4 bg-theme-surface border border-theme-primary text-theme-secondary text-sm"
+                                style="border-radius: 12px; box-shadow: var(--shadow-lg);"
+                            >
+                                <Icon name=icons::LOADER class="w-4 h-4 animate-spin"/>
+                                <span>
+                                    {move || {
+                                        if layout_on_server.get() {
+                                            "Arranging tables…".to_string()
+                                        } else {
+                                            format!("Arranging tables… {:.0}%", layout_progress.get().unwrap_or(0.0) * 100.0)
+                                        }
+                                    }}
+                                </span>
+                                <button
+                                    class="text-theme-muted hover:text-theme-primary"
+                                    title="Cancel auto layout"
+                                    on:click=move |_| cancel_auto_layout()
+                                >
+                                    <Icon name=icons::X class="w-4 h-4"/>
+                                </button>
+                            </div>
+                        </Show>
                         // AI Chat button (above settings button in bottom-right)
                         <AiChatButton is_open=ai_chat_open />
                         // AI Chat panel
-                        <AiChatPanel is_open=ai_chat_open _graph=graph />
+                        <AiChatPanel is_open=ai_chat_open _graph=graph settings=settings />
                     }
                 }
 
@@ -1332,16 +2554,17 @@ pub fn SchemaCanvas(
                                         <button
                                             class="w-full px-6 py-3 btn-theme-primary rounded-lg font-medium shadow-sm hover:shadow-md transition-all duration-200 flex items-center justify-center gap-2"
                                             on:click=move |_| {
+                                                let before = graph.get_untracked();
                                                 let node_idx = graph.write().create_table_auto((400.0, 300.0));
                                                 let (name, uuid) = graph.with(|g| {
                                                     g.node_weight(node_idx).map(|n| (n.name.clone(), n.uuid)).unwrap_or_default()
                                                 });
-                                                send_graph_op(GraphOperation::CreateTable {
+                                                history.commit(before, vec![GraphOperation::CreateTable {
                                                     node_id: node_idx.index() as u32,
                                                     table_uuid: uuid,
                                                     name,
                                                     position: (400.0, 300.0),
-                                                });
+                                                }]);
                                             }
                                         >
                                             <Icon name=icons::PLUS class="w-5 h-5"/>
@@ -1351,6 +2574,7 @@ pub fn SchemaCanvas(
                                             class="w-full px-6 py-3 text-theme-secondary bg-theme-tertiary rounded-lg font-medium shadow-sm hover:shadow-md transition-all duration-200 flex items-center justify-center gap-2 theme-transition"
                                             on:click=move |_| {
                                                 graph.set(create_demo_graph());
+                                                history.clear();
                                             }
                                         >
                                             <Icon name=icons::TABLE class="w-5 h-5"/>
@@ -1373,6 +2597,487 @@ pub fn SchemaCanvas(
     }
 }
 
+/// Floating actions for the current selection: materialize a selected N:M edge
+/// into a junction table, or collapse a selected pure junction table into an N:M edge
+#[component]
+fn JunctionActions(
+    graph: RwSignal<SchemaGraph>,
+    highlighted_edges: RwSignal<HashSet<EdgeIndex>>,
+    selected_tables: RwSignal<HashSet<NodeIndex>>,
+    notification_manager: NotificationManager,
+    /// Called with the applied operations after a conversion (already sent and recorded)
+    on_graph_ops: Callback<Vec<GraphOperation>>,
+) -> impl IntoView {
+    let history = use_history_context();
+
+    let many_to_many_edge = Memo::new(move |_| {
+        let edges = highlighted_edges.get();
+        let edge = *edges.iter().next().filter(|_| edges.len() == 1)?;
+        graph.with(|g| {
+            g.edge_weight(edge)
+                .filter(|rel| rel.relationship_type == RelationshipType::ManyToMany)
+                .map(|_| edge)
+        })
+    });
+    let junction_table = Memo::new(move |_| {
+        let node = selected_tables.with(|s| s.iter().next().copied().filter(|_| s.len() == 1))?;
+        graph.with(|g| find_junction(g, node).map(|j| (node, j.name)))
+    });
+
+    let materialize = move |_| {
+        let Some(edge) = many_to_many_edge.get_untracked() else {
+            return;
+        };
+        let before = graph.get_untracked();
+        match graph.try_update(|g| materialize_junction(g, edge)) {
+            Some(Ok((node, ops))) => {
+                highlighted_edges.set(HashSet::new());
+                selected_tables.set(HashSet::from([node]));
+                history.commit(before, ops.clone());
+                on_graph_ops.run(ops);
+            }
+            Some(Err(e)) => notification_manager.error("Cannot create junction table", e),
+            None => {}
+        }
+    };
+    let collapse = move |_| {
+        let Some((node, _)) = junction_table.get_untracked() else {
+            return;
+        };
+        let before = graph.get_untracked();
+        match graph.try_update(|g| collapse_junction(g, node)) {
+            Some(Ok((edge, ops))) => {
+                selected_tables.set(HashSet::new());
+                highlighted_edges.set(HashSet::from([edge]));
+                history.commit(before, ops.clone());
+                on_graph_ops.run(ops);
+            }
+            Some(Err(e)) => notification_manager.error("Cannot collapse junction table", e),
+            None => {}
+        }
+    };
+
+    view! {
+        <Show when=move || many_to_many_edge.get().is_some() || junction_table.get().is_some()>
+            <div
+                class="absolute top-4 left-1/2 -translate-x-1/2 z-40 flex items-center gap-2 px-3 py-2 bg-theme-surface border border-theme-primary text-sm theme-transition"
+                style="border-radius: 12px; box-shadow: var(--shadow-lg);"
+                on:click=|ev: web_sys::MouseEvent| ev.stop_propagation()
+            >
+                {move || match junction_table.get() {
+                    Some((_, name)) => view! {
+                        <span class="text-theme-secondary">
+                            "'" {name} "' only links two tables"
+                        </span>
+                        <button class="btn-theme-primary px-3 py-1 rounded-md flex items-center gap-1" on:click=collapse>
+                            <Icon name=icons::COLLAPSE class="w-4 h-4"/>
+                            "Collapse to N:M"
+                        </button>
+                    }
+                        .into_any(),
+                    None => view! {
+                        <span class="text-theme-secondary">"N:M relationship"</span>
+                        <button class="btn-theme-primary px-3 py-1 rounded-md flex items-center gap-1" on:click=materialize>
+                            <Icon name=icons::EXPAND class="w-4 h-4"/>
+                            "Create junction table"
+                        </button>
+                    }
+                        .into_any(),
+                }}
+            </div>
+        </Show>
+    }
+}
+
+/// Apply a group operation to the selected tables as one undo step
+fn apply_to_selection(
+    graph: RwSignal<SchemaGraph>,
+    selected_tables: RwSignal<HashSet<NodeIndex>>,
+    history: HistoryContext,
+    change: impl FnOnce(&mut SchemaGraph, &HashSet<NodeIndex>) -> Vec<GraphOperation>,
+) {
+    let selected = selected_tables.get_untracked();
+    let before = graph.get_untracked();
+    let ops = graph
+        .try_update(|g| change(g, &selected))
+        .unwrap_or_default();
+    if !ops.is_empty() {
+        history.commit(before, ops);
+        dispatch_save_event("selection_arranged");
+    }
+}
+
+/// Breadcrumb shown in focus mode: exit, hop depth and export of the focused tables
+#[component]
+fn FocusBreadcrumb(
+    graph: RwSignal<SchemaGraph>,
+    focus: RwSignal<Option<FocusMode>>,
+    focus_tables: Memo<Option<HashSet<NodeIndex>>>,
+    /// Open the export settings (export is limited to the focused tables)
+    on_export: Callback<()>,
+) -> impl IntoView {
+    let table_name = move || {
+        let mode = focus.get()?;
+        graph.with(|g| g.node_weight(mode.table).map(|n| n.name.clone()))
+    };
+    let depth = move || focus.get().map_or(0, |mode| mode.depth);
+    let set_depth = move |depth: usize| {
+        focus.update(|mode| {
+            if let Some(mode) = mode {
+                *mode = mode.with_depth(depth);
+            }
+        });
+    };
+    let button_class = "px-2 py-1 rounded-md text-theme-secondary hover:bg-theme-tertiary hover:text-theme-primary disabled:opacity-40 disabled:pointer-events-none";
+
+    view! {
+        <Show when=move || focus.get().is_some()>
+            <div
+                class="fixed bottom-4 left-1/2 -translate-x-1/2 z-40 flex items-center gap-1 px-3 py-2 bg-theme-surface border border-theme-primary text-sm theme-transition"
+                style="border-radius: 12px; box-shadow: var(--shadow-lg);"
+                on:click=|ev: web_sys::MouseEvent| ev.stop_propagation()
+                on:mousedown=|ev: web_sys::MouseEvent| ev.stop_propagation()
+            >
+                <button class=button_class title="Show all tables (Esc)" on:click=move |_| focus.set(None)>
+                    "All tables"
+                </button>
+                <Icon name=icons::CHEVRON_RIGHT class="w-4 h-4 text-theme-muted"/>
+                <span class="flex items-center gap-1 px-1 font-medium text-theme-primary">
+                    <Icon name=icons::EYE class="w-4 h-4"/>
+                    {table_name}
+                </span>
+                <span class="text-xs text-theme-muted ml-2">
+                    {move || {
+                        let count = focus_tables.with(|t| t.as_ref().map_or(0, |t| t.len()));
+                        format!("{} tables within", count)
+                    }}
+                </span>
+                <button
+                    class=button_class
+                    title="Fewer hops"
+                    disabled=move || depth() == 0
+                    on:click=move |_| set_depth(depth().saturating_sub(1))
+                >
+                    "−"
+                </button>
+                <span class="w-14 text-center text-theme-primary tabular-nums">
+                    {move || match depth() {
+                        1 => "1 hop".to_string(),
+                        n => format!("{} hops", n),
+                    }}
+                </span>
+                <button
+                    class=button_class
+                    title="More hops"
+                    disabled=move || { depth() >= MAX_FOCUS_DEPTH }
+                    on:click=move |_| set_depth(depth() + 1)
+                >
+                    "+"
+                </button>
+                <button
+                    class="p-1.5 rounded-md text-theme-secondary hover:text-theme-primary ml-2"
+                    title="Export the focused tables as SQL, JSON or image"
+                    on:click=move |_| on_export.run(())
+                >
+                    <Icon name=icons::ARROW_DOWN_TO_LINE class="w-4 h-4"/>
+                </button>
+                <button
+                    class="p-1.5 rounded-md text-theme-secondary hover:text-theme-primary"
+                    title="Exit focus mode (Esc)"
+                    on:click=move |_| focus.set(None)
+                >
+                    <Icon name=icons::X class="w-4 h-4"/>
+                </button>
+            </div>
+        </Show>
+    }
+}
+
+/// Toolbar for a selection of several tables: align, distribute, layout, copy, delete
+#[component]
+fn SelectionActions(
+    graph: RwSignal<SchemaGraph>,
+    selected_tables: RwSignal<HashSet<NodeIndex>>,
+    settings: RwSignal<DiagramSettings>,
+    /// Request deletion of the selected tables
+    on_delete: Callback<()>,
+    /// Copy the selected tables to the clipboard
+    on_copy: Callback<()>,
+) -> impl IntoView {
+    let history = use_history_context();
+    let count = Memo::new(move |_| selected_tables.with(|s| s.len()));
+
+    // Порядок выделения не важен, но операции должны быть детерминированы
+    let sorted = |selected: &HashSet<NodeIndex>| {
+        let mut tables: Vec<NodeIndex> = selected.iter().copied().collect();
+        tables.sort();
+        tables
+    };
+    let align = move |alignment: Alignment| {
+        apply_to_selection(graph, selected_tables, history, |g, selected| {
+            align_tables(g, &sorted(selected), alignment)
+        });
+    };
+    let distribute = move |axis: DistributeAxis| {
+        apply_to_selection(graph, selected_tables, history, |g, selected| {
+            distribute_tables(g, &sorted(selected), axis)
+        });
+    };
+    let set_display = move |display: TableDisplay| {
+        apply_to_selection(graph, selected_tables, history, |g, selected| {
+            set_table_display(g, &sorted(selected), display)
+        });
+    };
+    // Общий уровень отображения выделенных таблиц (None - уровни разные)
+    let common_display = Memo::new(move |_| {
+        let selected = selected_tables.get();
+        graph.with(|g| {
+            let mut displays = selected
+                .iter()
+                .filter_map(|&idx| g.node_weight(idx).map(|n| n.display));
+            let first = displays.next()?;
+            displays.all(|d| d == first).then_some(first)
+        })
+    });
+    let layout_selection = move |_| {
+        apply_to_selection(graph, selected_tables, history, |g, selected| {
+            let config = LayoutConfig {
+                algorithm: settings.with_untracked(|s| s.layout),
+                scope: LayoutScope::Tables(selected.clone()),
+                ..Default::default()
+            };
+            auto_layout_with_config(g, &config)
+        });
+    };
+
+    let alignment_label = |alignment: Alignment| match alignment {
+        Alignment::Left => "Left",
+        Alignment::CenterX => "Center",
+        Alignment::Right => "Right",
+        Alignment::Top => "Top",
+        Alignment::Middle => "Middle",
+        Alignment::Bottom => "Bottom",
+    };
+    let button_class = "px-2 py-1 rounded-md text-theme-secondary hover:bg-theme-tertiary hover:text-theme-primary disabled:opacity-40 disabled:pointer-events-none";
+
+    view! {
+        <Show when=move || { count.get() > 1 }>
+            <div
+                class="absolute top-4 left-1/2 -translate-x-1/2 z-40 flex items-center gap-1 px-3 py-2 bg-theme-surface border border-theme-primary text-sm theme-transition"
+                style="border-radius: 12px; box-shadow: var(--shadow-lg);"
+                on:click=|ev: web_sys::MouseEvent| ev.stop_propagation()
+                on:mousedown=|ev: web_sys::MouseEvent| ev.stop_propagation()
+            >
+                <span class="text-theme-secondary font-medium mr-2">
+                    {move || format!("{} tables", count.get())}
+                </span>
+                <span class="text-xs text-theme-muted">"Align"</span>
+                {Alignment::ALL
+                    .into_iter()
+                    .map(|alignment| view! {
+                        <button class=button_class title=alignment.label() on:click=move |_| align(alignment)>
+                            {alignment_label(alignment)}
+                        </button>
+                    })
+                    .collect_view()}
+                <span class="text-xs text-theme-muted ml-2">"Distribute"</span>
+                <button
+                    class=button_class
+                    title="Equal horizontal gaps (3+ tables)"
+                    disabled=move || count.get() < 3
+                    on:click=move |_| distribute(DistributeAxis::Horizontal)
+                >
+                    "Horizontal"
+                </button>
+                <button
+                    class=button_class
+                    title="Equal vertical gaps (3+ tables)"
+                    disabled=move || count.get() < 3
+                    on:click=move |_| distribute(DistributeAxis::Vertical)
+                >
+                    "Vertical"
+                </button>
+                <span class="text-xs text-theme-muted ml-2">"Columns"</span>
+                <select
+                    class="input-theme px-2 py-1 rounded-md text-sm"
+                    title="Columns shown by the selected tables"
+                    on:change=move |ev| {
+                        let value = event_target_value(&ev);
+                        if let Some(display) = TableDisplay::ALL
+                            .into_iter()
+                            .find(|d| d.label() == value)
+                        {
+                            set_display(display);
+                        }
+                    }
+                >
+                    <option value="" disabled=true selected=move || common_display.get().is_none()>
+                        "Mixed"
+                    </option>
+                    {TableDisplay::ALL
+                        .into_iter()
+                        .map(|display| view! {
+                            <option
+                                value=display.label()
+                                selected=move || common_display.get() == Some(display)
+                            >
+                                {display.label()}
+                            </option>
+                        })
+                        .collect_view()}
+                </select>
+                <button
+                    class="btn-theme-primary px-3 py-1 rounded-md flex items-center gap-1 ml-2"
+                    title="Auto layout only the selected tables (pinned tables stay in place)"
+                    on:click=layout_selection
+                >
+                    <Icon name=icons::SPARKLES class="w-4 h-4"/>
+                    "Layout selection"
+                </button>
+                <button
+                    class="p-1.5 rounded-md text-theme-secondary hover:text-theme-primary"
+                    title="Copy selected tables with their relationships (Ctrl+C)"
+                    on:click=move |_| on_copy.run(())
+                >
+                    <Icon name=icons::DOCUMENT_DUPLICATE class="w-4 h-4"/>
+                </button>
+                <button
+                    class="p-1.5 rounded-md text-theme-secondary hover:text-red-500"
+                    title="Delete selected tables (Delete)"
+                    on:click=move |_| on_delete.run(())
+                >
+                    <Icon name=icons::TRASH class="w-4 h-4"/>
+                </button>
+                <button
+                    class="p-1.5 rounded-md text-theme-secondary hover:text-theme-primary"
+                    title="Clear selection (Esc)"
+                    on:click=move |_| selected_tables.set(HashSet::new())
+                >
+                    <Icon name=icons::X class="w-4 h-4"/>
+                </button>
+            </div>
+        </Show>
+    }
+}
+
+/// Значки нотации на конце связи; подсвеченные связи рисуются белым
+fn decoration_view(decoration: EndDecoration, highlighted: bool) -> impl IntoView {
+    let (stroke_class, fill_class) = if highlighted {
+        ("stroke-white", "fill-white")
+    } else {
+        (
+            "stroke-current text-gray-500 dark:text-gray-400",
+            "fill-current text-gray-500 dark:text-gray-400",
+        )
+    };
+    let EndDecoration {
+        path,
+        circle,
+        label,
+    } = decoration;
+    view! {
+        {(!path.is_empty()).then(|| view! {
+            <path d=path class=stroke_class stroke-width="2" fill="none" />
+        })}
+        {circle.map(|circle| view! {
+            <circle
+                cx=circle.x
+                cy=circle.y
+                r=circle.r
+                class=stroke_class
+                stroke="currentColor"
+                stroke-width="2"
+                // Пустой кружок закрывает линию цветом фона
+                style="fill: var(--bg-canvas);"
+            />
+        })}
+        {label.map(|label| view! {
+            <text
+                x=label.x
+                y=label.y
+                class=format!("{} select-none", fill_class)
+                font-size="12"
+                font-weight=if highlighted { "bold" } else { "normal" }
+                text-anchor="middle"
+                dominant-baseline="middle"
+            >
+                {label.text}
+            </text>
+        })}
+    }
+}
+
+/// Путь связи на канвасе
+struct EdgeGeometry {
+    /// Данные SVG-пути
+    path: String,
+    label_x: f64,
+    label_y: f64,
+    /// Точки пути от таблицы-источника к целевой; концевые отрезки задают
+    /// направление значков нотации
+    points: Vec<(f64, f64)>,
+}
+
+/// Путь SVG и позиция подписи связи
+///
+/// Используется сохранённый ортогональный маршрут, пока он привязан к текущему
+/// положению таблиц (во время перетаскивания - упрощённый путь).
+fn edge_geometry(g: &SchemaGraph, edge_idx: EdgeIndex) -> Option<EdgeGeometry> {
+    let edge = g.edge_weight(edge_idx)?;
+    if route_is_current(g, edge_idx)
+        && let Some((label_x, label_y)) = route_label_position(&edge.route)
+    {
+        return Some(EdgeGeometry {
+            path: route_svg_path(&edge.route),
+            label_x,
+            label_y,
+            points: edge.route.clone(),
+        });
+    }
+
+    let (from_idx, to_idx) = g.edge_endpoints(edge_idx)?;
+    let from_node = g.node_weight(from_idx)?;
+    let to_node = g.node_weight(to_idx)?;
+    let (from_x, from_y) = from_node.position;
+    let (to_x, to_y) = to_node.position;
+
+    const GAP: f64 = 30.0;
+
+    // Y координаты конкретных колонок
+    let from_col_y = column_anchor_y(g, from_idx, &edge.from_column);
+    let to_col_y = column_anchor_y(g, to_idx, &edge.to_column);
+
+    // Умная логика выбора пути стрелки
+    let (start_x, start_y, end_x, end_y, label_x, label_y, path) = calculate_edge_path(
+        from_x,
+        from_y,
+        to_x,
+        to_y,
+        from_col_y,
+        to_col_y,
+        from_x,
+        from_x + TABLE_WIDTH,
+        to_x,
+        to_x + TABLE_WIDTH,
+        TABLE_WIDTH,
+        GAP,
+    );
+    // Концы упрощённого пути всегда горизонтальны и уходят от таблицы наружу
+    let outward = |x: f64, left: f64| if x == left { -1.0 } else { 1.0 };
+    let points = vec![
+        (start_x, start_y),
+        (start_x + outward(start_x, from_x), start_y),
+        (end_x + outward(end_x, to_x), end_y),
+        (end_x, end_y),
+    ];
+    Some(EdgeGeometry {
+        path,
+        label_x,
+        label_y,
+        points,
+    })
+}
+
 /// Вычисляет путь SVG для рёбра графа с оптимизированной логикой
 /// Возвращает: (start_x, start_y, end_x, end_y, label_x, label_y, path_data)
 #[inline]
@@ -1605,6 +3310,48 @@ fn apply_remote_graph_op(graph: RwSignal<SchemaGraph>, op: GraphOperation) {
                 }
             });
         }
+        GraphOperation::SetTablePinned {
+            node_id,
+            table_uuid,
+            pinned,
+        } => {
+            graph.update(|g| {
+                // Try to find by UUID first, then fall back to node_id
+                let idx = find_node_by_uuid(g, table_uuid).or_else(|| {
+                    let idx = NodeIndex::new(node_id as usize);
+                    if g.node_weight(idx).is_some() {
+                        Some(idx)
+                    } else {
+                        None
+                    }
+                });
+
+                if let Some(node) = idx.and_then(|idx| g.node_weight_mut(idx)) {
+                    node.pinned = pinned;
+                }
+            });
+        }
+        GraphOperation::SetTableDisplay {
+            node_id,
+            table_uuid,
+            display,
+        } => {
+            graph.update(|g| {
+                // Try to find by UUID first, then fall back to node_id
+                let idx = find_node_by_uuid(g, table_uuid).or_else(|| {
+                    let idx = NodeIndex::new(node_id as usize);
+                    if g.node_weight(idx).is_some() {
+                        Some(idx)
+                    } else {
+                        None
+                    }
+                });
+
+                if let Some(node) = idx.and_then(|idx| g.node_weight_mut(idx)) {
+                    node.display = display;
+                }
+            });
+        }
         GraphOperation::AddColumn {
             node_id,
             table_uuid,
@@ -1792,6 +3539,8 @@ fn apply_graph_state(graph: RwSignal<SchemaGraph>, state: GraphStateSnapshot) {
 
             // Preserve the UUID from the snapshot
             node.uuid = table.table_uuid;
+            node.pinned = table.pinned;
+            node.display = table.display;
 
             // Add columns
             for col_data in table.columns {
@@ -1881,6 +3630,8 @@ fn create_graph_snapshot(graph: RwSignal<SchemaGraph>) -> GraphStateSnapshot {
                         name: node.name.clone(),
                         position: node.position,
                         columns,
+                        pinned: node.pinned,
+                        display: node.display,
                         version: 0,
                         last_modified_at: 0,
                         is_deleted: false,
@@ -1,8 +1,10 @@
-use crate::core::{Column, MySqlDataType, RelationshipOps, RelationshipType, SchemaGraph};
-use crate::ui::liveshare_client::{
-    ColumnData, ConnectionState, GraphOperation, RelationshipData, use_liveshare_context,
+use crate::core::{
+    Column, DiagramSettings, MySqlDataType, Relationship, RelationshipOps, RelationshipType,
+    SchemaGraph, TableNode, ValidationLevel, check_relationship, validate_identifier_for,
 };
-use crate::ui::{ErrorMessage, Icon, icons};
+use crate::ui::history::use_history_context;
+use crate::ui::liveshare_client::{ColumnData, GraphOperation, RelationshipData};
+use crate::ui::{DialectNameNotes, ErrorMessage, Icon, icons};
 use leptos::prelude::*;
 use petgraph::graph::NodeIndex;
 use petgraph::visit::EdgeRef;
@@ -53,6 +55,8 @@ pub fn ColumnEditor(
     #[prop(optional)]
     current_table: Option<NodeIndex>,
 ) -> impl IntoView {
+    let history = use_history_context();
+
     // Состояние формы
     let (name, set_name) = signal(column.as_ref().map(|c| c.name.clone()).unwrap_or_default());
     let initial_data_type = column
@@ -110,7 +114,22 @@ pub fn ColumnEditor(
 
     let available_types = MySqlDataType::all_types();
 
-    let handle_save = move |_| {
+    // Проверка имени с учётом целевого диалекта диаграммы (зарезервированные слова, длина)
+    let diagram_settings = use_context::<RwSignal<DiagramSettings>>();
+    let dialect_check = move || {
+        let dialect = diagram_settings
+            .map(|s| s.with(|v| v.dialect.clone()))
+            .unwrap_or_default();
+        validate_identifier_for(&name.get(), ValidationLevel::Standard, &dialect)
+    };
+    let quoted_name = move || {
+        diagram_settings
+            .map(|s| s.with(|v| v.dialect.clone()))
+            .unwrap_or_default()
+            .quote_identifier(name.get().trim())
+    };
+
+    let handle_save = move || {
         let name_value = name.get();
         let data_type_value = data_type.get();
 
@@ -119,6 +138,10 @@ pub fn ColumnEditor(
             set_error.set(Some(e));
             return;
         }
+        if let Err(e) = dialect_check().to_result() {
+            set_error.set(Some(e.to_string()));
+            return;
+        }
 
         if let Err(e) = Column::validate_data_type(&data_type_value) {
             set_error.set(Some(e));
@@ -171,10 +194,33 @@ pub fn ColumnEditor(
                 }
             }
 
-            // Подготавливаем данные для LiveShare sync
-            let liveshare_ctx = use_liveshare_context();
-            let is_connected =
-                liveshare_ctx.connection_state.with_untracked(|v| *v) == ConnectionState::Connected;
+            let before = g.get_untracked();
+            let table_uuid = before
+                .node_weight(current_node)
+                .map(|n| n.uuid)
+                .unwrap_or_else(uuid::Uuid::new_v4);
+            let col_data = ColumnData {
+                name: new_column.name.clone(),
+                data_type: new_column.data_type.clone(),
+                is_primary_key: new_column.is_primary_key,
+                is_nullable: new_column.is_nullable,
+                is_unique: new_column.is_unique,
+                default_value: new_column.default_value.clone(),
+                foreign_key: None,
+            };
+            let mut ops = vec![match column_index {
+                Some(idx) => GraphOperation::UpdateColumn {
+                    node_id: current_node.index() as u32,
+                    table_uuid,
+                    column_index: idx,
+                    column: col_data,
+                },
+                None => GraphOperation::AddColumn {
+                    node_id: current_node.index() as u32,
+                    table_uuid,
+                    column: col_data,
+                },
+            }];
 
             // Клонируем данные для использования в closure
             let name_value_clone = name_value.clone();
@@ -187,7 +233,7 @@ pub fn ColumnEditor(
             let column_to_save = new_column.clone();
 
             // Все изменения графа в одном update() - и колонка, и FK связь
-            g.update(move |graph_mut| {
+            g.update(|graph_mut| {
                 use crate::core::Relationship;
 
                 // 1. Сохраняем колонку в таблицу
@@ -213,6 +259,9 @@ pub fn ColumnEditor(
 
                 for edge_id in edges_to_remove {
                     graph_mut.remove_edge(edge_id);
+                    ops.push(GraphOperation::DeleteRelationship {
+                        edge_id: edge_id.index() as u32,
+                    });
                 }
 
                 // 3. Создаём новую FK связь, если нужно
@@ -230,62 +279,28 @@ pub fn ColumnEditor(
                         to_col.clone(),
                     );
 
-                    if let Ok(edge_idx) =
-                        graph_mut.create_relationship(current_node, target_node, relationship)
-                    {
-                        // Send LiveShare sync for relationship
-                        if is_connected {
-                            liveshare_ctx.send_graph_op(GraphOperation::CreateRelationship {
-                                edge_id: edge_idx.index() as u32,
-                                from_node: current_node.index() as u32,
-                                to_node: target_node.index() as u32,
-                                relationship: RelationshipData {
-                                    name: rel_name,
-                                    relationship_type: fk_rel_type.to_string(),
-                                    from_column: from_col,
-                                    to_column: to_col,
-                                },
-                            });
-                        }
+                    if let Ok((edge_idx, _)) = graph_mut.create_relationship_checked(
+                        current_node,
+                        target_node,
+                        relationship,
+                    ) {
+                        ops.push(GraphOperation::CreateRelationship {
+                            edge_id: edge_idx.index() as u32,
+                            from_node: current_node.index() as u32,
+                            to_node: target_node.index() as u32,
+                            relationship: RelationshipData {
+                                name: rel_name,
+                                relationship_type: fk_rel_type.to_string(),
+                                from_column: from_col,
+                                to_column: to_col,
+                            },
+                        });
                     }
                 }
             });
 
-            // Send LiveShare sync for column
-            if is_connected {
-                let col_data = ColumnData {
-                    name: new_column.name.clone(),
-                    data_type: new_column.data_type.clone(),
-                    is_primary_key: new_column.is_primary_key,
-                    is_nullable: new_column.is_nullable,
-                    is_unique: new_column.is_unique,
-                    default_value: new_column.default_value.clone(),
-                    foreign_key: None,
-                };
-
-                if let (Some(g), Some(current_node)) = (graph, current_table) {
-                    let table_uuid = g.with(|g| {
-                        g.node_weight(current_node)
-                            .map(|n| n.uuid)
-                            .unwrap_or_else(uuid::Uuid::new_v4)
-                    });
-
-                    if let Some(idx) = column_index {
-                        liveshare_ctx.send_graph_op(GraphOperation::UpdateColumn {
-                            node_id: current_node.index() as u32,
-                            table_uuid,
-                            column_index: idx,
-                            column: col_data,
-                        });
-                    } else {
-                        liveshare_ctx.send_graph_op(GraphOperation::AddColumn {
-                            node_id: current_node.index() as u32,
-                            table_uuid,
-                            column: col_data,
-                        });
-                    }
-                }
-            }
+            // Колонка и её FK связь - один шаг истории
+            history.commit(before, ops);
         }
 
         // Trigger save after column changes
@@ -303,6 +318,8 @@ pub fn ColumnEditor(
         on_save.run(());
     };
 
+    let save = Callback::new(move |_: ()| handle_save());
+
     let form_content = view! {
         <div class=if inline {
             ""
@@ -344,6 +361,11 @@ pub fn ColumnEditor(
                             set_error.set(None);
                         }
                     />
+                    <DialectNameNotes
+                        result=Signal::derive(dialect_check)
+                        quoted=Signal::derive(quoted_name)
+                        on_quote=save
+                    />
                 </div>
 
                 // Тип данных
@@ -613,6 +635,43 @@ pub fn ColumnEditor(
                                                         "For Many-to-Many relationships, create a junction table"
                                                     </p>
                                                 </div>
+
+                                                // Предупреждения о согласованности связи (кардинальность, NULL)
+                                                {move || {
+                                                    let (Some(target_idx), Some(target_col), Some(current_node)) =
+                                                        (fk_target_table.get(), fk_target_column.get(), current_table)
+                                                    else {
+                                                        return Vec::new();
+                                                    };
+                                                    let mut column = Column::new(name.get(), data_type.get());
+                                                    column.is_primary_key = is_primary_key.get();
+                                                    column.is_nullable = is_nullable.get() && !column.is_primary_key;
+                                                    column.is_unique = is_unique.get();
+                                                    let default = default_value.get();
+                                                    column.default_value = (!default.is_empty()).then_some(default);
+                                                    let relationship = Relationship::new(
+                                                        "fk",
+                                                        fk_relationship_type.get(),
+                                                        column.name.clone(),
+                                                        target_col,
+                                                    );
+                                                    g.with(|graph_val| {
+                                                        let (Some(current), Some(target)) =
+                                                            (graph_val.node_weight(current_node), graph_val.node_weight(target_idx))
+                                                        else {
+                                                            return Vec::new();
+                                                        };
+                                                        let source = TableNode::new(current.name.clone()).add_column(column);
+                                                        check_relationship(&source, target, &relationship)
+                                                    })
+                                                    .into_iter()
+                                                    .map(|issue| view! {
+                                                        <p class="text-xs text-theme-warning">
+                                                            "⚠ " {issue.message}
+                                                        </p>
+                                                    })
+                                                    .collect_view()
+                                                }}
                                             </div>
                                         }
                                             .into_any()
@@ -657,7 +716,7 @@ pub fn ColumnEditor(
                     </button>
                     <button
                         class="btn-primary"
-                        on:click=handle_save
+                        on:click=move |_| save.run(())
                     >
                         <Icon name=icons::CHECK class="icon-btn"/>
                         "Save"
@@ -0,0 +1,202 @@
+//! Ctrl+K command palette
+//!
+//! Fuzzy search over tables, columns, relationships and editor commands.
+//! The canvas decides what selecting a result does (jump to the element or run
+//! the command); this component only searches and reports the choice.
+
+use leptos::prelude::*;
+use leptos::{html, web_sys};
+
+use crate::core::{DEFAULT_PALETTE_LIMIT, PaletteItem, PaletteMatch, SchemaGraph, search_palette};
+use crate::ui::{Icon, Kbd, KeyboardHint, KeyboardHints, icons};
+
+#[component]
+pub fn CommandPalette(
+    /// Whether the palette is open (Ctrl+K toggles it)
+    is_open: RwSignal<bool>,
+    graph: RwSignal<SchemaGraph>,
+    /// Called with the chosen result; the palette closes itself
+    on_select: Callback<PaletteItem>,
+) -> impl IntoView {
+    let query = RwSignal::new(String::new());
+    let active = RwSignal::new(0_usize);
+    let input_ref = NodeRef::<html::Input>::new();
+
+    let results = Memo::new(move |_| {
+        if !is_open.get() {
+            return Vec::new();
+        }
+        query.with(|q| graph.with(|g| search_palette(g, q, DEFAULT_PALETTE_LIMIT)))
+    });
+
+    // Ctrl+K / Cmd+K открывает палитру из любого места, в том числе из полей ввода
+    #[cfg(not(feature = "ssr"))]
+    {
+        use leptos::ev::keydown;
+
+        let handle_keydown = window_event_listener(keydown, move |ev| {
+            if (ev.ctrl_key() || ev.meta_key()) && ev.key().to_lowercase() == "k" {
+                ev.prevent_default();
+                is_open.update(|open| *open = !*open);
+            }
+        });
+        on_cleanup(move || drop(handle_keydown));
+    }
+
+    // При открытии - чистый запрос и фокус в поле поиска
+    Effect::new(move |_| {
+        if is_open.get() {
+            query.set(String::new());
+            active.set(0);
+            if let Some(input) = input_ref.get() {
+                let _ = input.focus();
+            }
+        }
+    });
+
+    let choose = move |item: PaletteItem| {
+        is_open.set(false);
+        on_select.run(item);
+    };
+
+    let handle_keydown = move |ev: web_sys::KeyboardEvent| {
+        let count = results.with_untracked(|r| r.len());
+        match ev.key().as_str() {
+            "ArrowDown" if count > 0 => {
+                ev.prevent_default();
+                active.update(|i| *i = (*i + 1) % count);
+            }
+            "ArrowUp" if count > 0 => {
+                ev.prevent_default();
+                active.update(|i| *i = (*i + count - 1) % count);
+            }
+            "Enter" => {
+                ev.prevent_default();
+                let item = results
+                    .with_untracked(|r| r.get(active.get_untracked()).map(|m| m.item.clone()));
+                if let Some(item) = item {
+                    choose(item);
+                }
+            }
+            "Escape" => {
+                ev.prevent_default();
+                is_open.set(false);
+            }
+            _ => {}
+        }
+    };
+
+    view! {
+        <Show when=move || is_open.get()>
+            <div
+                class="fixed inset-0 z-[200] flex items-start justify-center pt-24 backdrop-theme"
+                on:mousedown=move |_| is_open.set(false)
+            >
+                <div
+                    class="w-full max-w-xl bg-theme-surface border border-theme-primary overflow-hidden theme-transition"
+                    style="border-radius: 12px; box-shadow: var(--shadow-xl);"
+                    on:mousedown=|ev: web_sys::MouseEvent| ev.stop_propagation()
+                >
+                    <div class="flex items-center gap-2 px-4 py-3 border-b border-theme-primary">
+                        <Icon name=icons::SEARCH class="w-4 h-4 text-theme-muted"/>
+                        <input
+                            node_ref=input_ref
+                            type="text"
+                            class="flex-1 bg-transparent outline-none text-theme-primary"
+                            placeholder="Search tables, columns, relationships and commands"
+                            prop:value=move || query.get()
+                            on:input=move |ev| {
+                                query.set(event_target_value(&ev));
+                                active.set(0);
+                            }
+                            on:keydown=handle_keydown
+                        />
+                        <Kbd key="Esc".to_string()/>
+                    </div>
+                    <div class="max-h-96 overflow-y-auto py-1">
+                        {move || {
+                            let results = results.get();
+                            if results.is_empty() {
+                                return view! {
+                                    <div class="px-4 py-6 text-sm text-center text-theme-muted">
+                                        "Nothing found"
+                                    </div>
+                                }
+                                    .into_any();
+                            }
+                            results
+                                .into_iter()
+                                .enumerate()
+                                .map(|(index, result)| {
+                                    let item = result.item.clone();
+                                    view! {
+                                        <button
+                                            class=move || {
+                                                if active.get() == index {
+                                                    "w-full flex items-center gap-3 px-4 py-2 text-left bg-theme-tertiary"
+                                                } else {
+                                                    "w-full flex items-center gap-3 px-4 py-2 text-left"
+                                                }
+                                            }
+                                            on:mousemove=move |_| active.set(index)
+                                            on:click=move |_| choose(item.clone())
+                                        >
+                                            <PaletteRow result=result/>
+                                        </button>
+                                    }
+                                })
+                                .collect_view()
+                                .into_any()
+                        }}
+                    </div>
+                    <div class="px-4 py-2 border-t border-theme-primary text-xs text-theme-muted">
+                        <KeyboardHints hints=vec![
+                            KeyboardHint::new("↑↓", "to navigate"),
+                            KeyboardHint::new("Enter", "to open"),
+                            KeyboardHint::new("Ctrl+K", "to toggle"),
+                        ]/>
+                    </div>
+                </div>
+            </div>
+        </Show>
+    }
+}
+
+/// One result: kind icon, label with matched characters highlighted, detail
+#[component]
+fn PaletteRow(result: PaletteMatch) -> impl IntoView {
+    let icon = match result.item {
+        PaletteItem::Command(_) => icons::LIGHTNING,
+        PaletteItem::Table(_) => icons::TABLE,
+        PaletteItem::Column { .. } => icons::MENU,
+        PaletteItem::Relationship(_) => icons::LINK,
+    };
+
+    // Соседние символы с одинаковой подсветкой объединяем в один span
+    let mut segments: Vec<(String, bool)> = Vec::new();
+    for (i, ch) in result.label.chars().enumerate() {
+        let matched = result.highlights.contains(&i);
+        match segments.last_mut() {
+            Some((text, last)) if *last == matched => text.push(ch),
+            _ => segments.push((ch.to_string(), matched)),
+        }
+    }
+
+    view! {
+        <Icon name=icon class="w-4 h-4 text-theme-muted shrink-0"/>
+        <span class="text-sm text-theme-primary truncate">
+            {segments
+                .into_iter()
+                .map(|(text, matched)| {
+                    if matched {
+                        view! { <span class="text-theme-accent font-semibold">{text}</span> }
+                            .into_any()
+                    } else {
+                        view! { <span>{text}</span> }.into_any()
+                    }
+                })
+                .collect_view()}
+        </span>
+        <span class="ml-auto text-xs text-theme-muted truncate">{result.detail}</span>
+    }
+}
@@ -1,5 +1,6 @@
 //! Reusable message components for displaying errors, warnings, success messages, etc.
 
+use crate::core::{ValidationError, ValidationResult};
 use crate::ui::{Icon, icons};
 use leptos::prelude::*;
 
@@ -116,3 +117,67 @@ pub fn WarningMessageStatic(
         </div>
     }
 }
+
+/// Dialect notes for a table or column name (reserved words, length limits)
+///
+/// Warnings concern the diagram's dialect, hints the other dialects. A name reserved
+/// in the diagram's dialect gets a "Quote it" action: exported SQL quotes every
+/// identifier, so the name can be kept as is.
+#[component]
+pub fn DialectNameNotes(
+    /// Result of `validate_identifier_for`
+    #[prop(into)]
+    result: Signal<ValidationResult>,
+    /// The name quoted for the diagram's dialect
+    #[prop(into)]
+    quoted: Signal<String>,
+    /// Keep the reserved name, quoted in SQL
+    #[prop(into)]
+    on_quote: Callback<()>,
+) -> impl IntoView {
+    let is_dialect_note = |note: &ValidationError| {
+        matches!(
+            note,
+            ValidationError::ReservedInDialects { .. }
+                | ValidationError::ExceedsDialectLimit { .. }
+        )
+    };
+
+    move || {
+        let result = result.get();
+        let warnings = result
+            .warnings
+            .into_iter()
+            .filter(is_dialect_note)
+            .map(|warning| {
+                let reserved = matches!(warning, ValidationError::ReservedInDialects { .. });
+                view! {
+                    <p class="text-xs text-theme-warning mt-1">
+                        "⚠ " {warning.to_string()}
+                        {reserved.then(|| view! {
+                            <button
+                                type="button"
+                                class="ml-2 underline"
+                                title="Exported SQL quotes the name"
+                                on:click=move |_| on_quote.run(())
+                            >
+                                {move || format!("Quote it as {}", quoted.get())}
+                            </button>
+                        })}
+                    </p>
+                }
+            })
+            .collect_view();
+        let hints = result
+            .hints
+            .into_iter()
+            .filter(is_dialect_note)
+            .map(|hint| {
+                view! {
+                    <p class="text-xs text-theme-muted mt-1">{hint.to_string()}</p>
+                }
+            })
+            .collect_view();
+        view! { {warnings} {hints} }
+    }
+}
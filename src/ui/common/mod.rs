@@ -29,8 +29,8 @@ pub use keyboard::{
     SubmitCancelHints,
 };
 pub use message::{
-    ErrorMessage, ErrorMessageStatic, InfoMessage, SuccessMessage, SuccessMessageStatic,
-    WarningMessage, WarningMessageStatic,
+    DialectNameNotes, ErrorMessage, ErrorMessageStatic, InfoMessage, SuccessMessage,
+    SuccessMessageStatic, WarningMessage, WarningMessageStatic,
 };
 pub use modal::{AlertDialog, BaseModal, ConfirmDialog};
 pub use spinner::{
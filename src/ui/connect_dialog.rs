@@ -0,0 +1,115 @@
+//! Relationship type picker shown after a drag-to-connect drop
+
+use leptos::prelude::*;
+use leptos::web_sys;
+use petgraph::graph::NodeIndex;
+
+use crate::core::{ConnectTarget, RelationshipType};
+use crate::ui::{Icon, icons};
+
+/// A dropped connection waiting for its relationship type
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingConnection {
+    pub table: NodeIndex,
+    pub column: String,
+    pub target: NodeIndex,
+    pub target_column: ConnectTarget,
+    /// `users.id`
+    pub source_label: String,
+    /// `posts.user_id`, for a new column the proposed name
+    pub target_label: String,
+    /// Type of the column to be created (only for [`ConnectTarget::NewColumn`])
+    pub new_column_type: Option<String>,
+    pub suggested: RelationshipType,
+    /// Drop point in window coordinates
+    pub position: (f64, f64),
+}
+
+#[component]
+pub fn ConnectDialog(
+    pending: RwSignal<Option<PendingConnection>>,
+    /// Called with the chosen relationship type; the canvas applies the connection
+    on_confirm: Callback<RelationshipType>,
+) -> impl IntoView {
+    // Esc отменяет, пока выбор открыт
+    #[cfg(not(feature = "ssr"))]
+    {
+        use leptos::ev::keydown;
+
+        let handle_keydown = window_event_listener(keydown, move |ev| {
+            if ev.key() == "Escape" && pending.with_untracked(|p| p.is_some()) {
+                ev.prevent_default();
+                pending.set(None);
+            }
+        });
+        on_cleanup(move || drop(handle_keydown));
+    }
+
+    let type_label = |rel_type: &RelationshipType| match rel_type {
+        RelationshipType::OneToOne => "One to one",
+        RelationshipType::OneToMany => "One to many",
+        RelationshipType::ManyToOne => "Many to one",
+        RelationshipType::ManyToMany => "Many to many",
+    };
+
+    move || {
+        pending.get().map(|connection| {
+            let (left, top) = connection.position;
+            view! {
+                // Клик мимо окна - отмена
+                <div
+                    class="fixed inset-0 z-[160]"
+                    on:mousedown=move |_| pending.set(None)
+                ></div>
+                <div
+                    class="fixed z-[170] w-72 p-3 bg-theme-surface border border-theme-primary text-sm theme-transition"
+                    style=format!(
+                        "left: {}px; top: {}px; border-radius: 12px; box-shadow: var(--shadow-xl);",
+                        left + 8.0,
+                        top + 8.0
+                    )
+                    on:mousedown=|ev: web_sys::MouseEvent| ev.stop_propagation()
+                    on:click=|ev: web_sys::MouseEvent| ev.stop_propagation()
+                >
+                    <div class="flex items-center gap-2 mb-1 font-medium text-theme-primary">
+                        <Icon name=icons::LINK class="w-4 h-4 text-theme-muted"/>
+                        <span class="truncate">{connection.source_label.clone()}</span>
+                        <span class="text-theme-muted">"→"</span>
+                        <span class="truncate">{connection.target_label.clone()}</span>
+                    </div>
+                    {connection.new_column_type.clone().map(|data_type| view! {
+                        <p class="mb-2 text-xs text-theme-muted">
+                            "Creates column " {connection.target_label.clone()} " (" {data_type} ")"
+                        </p>
+                    })}
+                    <div class="grid grid-cols-2 gap-1 mt-2">
+                        {RelationshipType::ALL
+                            .into_iter()
+                            .map(|rel_type| {
+                                let class = if rel_type == connection.suggested {
+                                    "btn-theme-primary px-2 py-1.5 rounded-md text-left"
+                                } else {
+                                    "px-2 py-1.5 rounded-md text-left text-theme-secondary hover:bg-theme-tertiary hover:text-theme-primary"
+                                };
+                                let label = rel_type.to_string();
+                                let description = type_label(&rel_type);
+                                view! {
+                                    <button class=class on:click=move |_| on_confirm.run(rel_type.clone())>
+                                        <span class="font-semibold mr-1">{label}</span>
+                                        <span class="text-xs opacity-80">{description}</span>
+                                    </button>
+                                }
+                            })
+                            .collect_view()}
+                    </div>
+                    <button
+                        class="mt-2 w-full px-2 py-1 rounded-md text-xs text-theme-muted hover:text-theme-primary"
+                        on:click=move |_| pending.set(None)
+                    >
+                        "Cancel (Esc)"
+                    </button>
+                </div>
+            }
+        })
+    }
+}
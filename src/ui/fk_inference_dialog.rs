@@ -0,0 +1,185 @@
+use crate::core::{
+    DEFAULT_MIN_CONFIDENCE, FkCandidate, SchemaGraph, accept_fk_candidates, infer_foreign_keys,
+};
+use crate::ui::history::use_history_context;
+use crate::ui::liveshare_client::GraphOperation;
+use crate::ui::{ErrorMessage, Icon, icons};
+use leptos::prelude::*;
+
+/// Диалог просмотра предложенных внешних ключей
+///
+/// Кандидаты с уверенностью не ниже [`DEFAULT_MIN_CONFIDENCE`] отмечены заранее.
+#[component]
+pub fn FkInferenceDialog(
+    graph: RwSignal<SchemaGraph>,
+    /// Callback после принятия кандидатов (операции уже отправлены и записаны в историю)
+    #[prop(into)]
+    on_accept: Callback<Vec<GraphOperation>>,
+    /// Callback при отмене
+    #[prop(into)]
+    on_cancel: Callback<()>,
+) -> impl IntoView {
+    let history = use_history_context();
+
+    // Кандидаты вычисляются один раз при открытии диалога
+    let candidates = StoredValue::new(graph.with_untracked(infer_foreign_keys));
+    let selected = RwSignal::new(candidates.with_value(|list| {
+        list.iter()
+            .map(|c| c.confidence >= DEFAULT_MIN_CONFIDENCE)
+            .collect::<Vec<_>>()
+    }));
+    let (error, set_error) = signal::<Option<String>>(None);
+
+    let selected_count = move || selected.with(|s| s.iter().filter(|&&v| v).count());
+
+    let toggle = move |index: usize| {
+        selected.update(|s| {
+            if let Some(v) = s.get_mut(index) {
+                *v = !*v;
+            }
+        });
+    };
+
+    let set_all = move |value: bool| {
+        selected.update(|s| s.iter_mut().for_each(|v| *v = value));
+    };
+
+    let handle_accept = move || {
+        let chosen: Vec<FkCandidate> = candidates.with_value(|list| {
+            selected.with_untracked(|s| {
+                list.iter()
+                    .zip(s.iter())
+                    .filter(|(_, on)| **on)
+                    .map(|(c, _)| c.clone())
+                    .collect()
+            })
+        });
+        if chosen.is_empty() {
+            return;
+        }
+
+        let before = graph.get_untracked();
+        let mut result = None;
+        graph.update(|g| result = Some(accept_fk_candidates(g, &chosen)));
+        let Some(result) = result else {
+            return;
+        };
+
+        // Если не создано ни одной связи - остаёмся в диалоге и показываем ошибки
+        if result.graph_ops.is_empty() {
+            set_error.set(Some(result.errors.join("; ")));
+        } else {
+            history.commit(before, result.graph_ops.clone());
+            on_accept.run(result.graph_ops);
+        }
+    };
+
+    let confidence_class = |confidence: f32| {
+        if confidence >= 0.85 {
+            "text-green-500"
+        } else if confidence >= DEFAULT_MIN_CONFIDENCE {
+            "text-yellow-500"
+        } else {
+            "text-theme-muted"
+        }
+    };
+
+    view! {
+        <div class="h-full flex flex-col space-y-4">
+            // Заголовок
+            <div>
+                <h3 class="title-lg">"Infer Relationships"</h3>
+                <p class="subtitle">
+                    "Foreign keys suggested from column names and types"
+                </p>
+            </div>
+
+            {move || {
+                if candidates.with_value(|c| c.is_empty()) {
+                    view! {
+                        <div class="card-info text-sm text-theme-muted flex items-start">
+                            <Icon name=icons::INFORMATION_CIRCLE class="icon-text flex-shrink-0"/>
+                            <span>"No candidates found. Columns like customer_id or customerId are matched to tables by name."</span>
+                        </div>
+                    }
+                        .into_any()
+                } else {
+                    view! {
+                        <div class="flex items-center justify-between text-xs text-theme-muted">
+                            <span>{move || format!("{} selected", selected_count())}</span>
+                            <div class="space-x-3">
+                                <button class="text-theme-accent hover:underline" on:click=move |_| set_all(true)>
+                                    "Select all"
+                                </button>
+                                <button class="text-theme-accent hover:underline" on:click=move |_| set_all(false)>
+                                    "Clear"
+                                </button>
+                            </div>
+                        </div>
+                    }
+                        .into_any()
+                }
+            }}
+
+            // Список кандидатов
+            <div class="flex-1 overflow-y-auto space-y-2">
+                {candidates
+                    .get_value()
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, candidate)| {
+                        let percent = (candidate.confidence * 100.0).round() as u32;
+                        let class = confidence_class(candidate.confidence);
+                        let reasons = candidate.reasons.join(", ");
+                        view! {
+                            <label class="flex items-start p-3 rounded-lg border border-theme-primary bg-theme-secondary hover:border-theme-accent theme-transition cursor-pointer">
+                                <input
+                                    type="checkbox"
+                                    class="mt-1 mr-3"
+                                    prop:checked=move || selected.with(|s| s.get(index).copied().unwrap_or(false))
+                                    on:change=move |_| toggle(index)
+                                />
+                                <div class="flex-1 min-w-0">
+                                    <div class="flex items-center justify-between text-sm">
+                                        <span class="font-mono text-theme-primary truncate">
+                                            {format!("{}.{}", candidate.from_table, candidate.from_column)}
+                                        </span>
+                                        <span class=format!("ml-2 text-xs font-semibold {}", class)>
+                                            {format!("{}%", percent)}
+                                        </span>
+                                    </div>
+                                    <div class="text-xs text-theme-secondary font-mono truncate">
+                                        {format!(
+                                            "→ {}.{} ({})",
+                                            candidate.to_table,
+                                            candidate.to_column,
+                                            candidate.relationship_type,
+                                        )}
+                                    </div>
+                                    <div class="mt-1 text-xs text-theme-muted">{reasons}</div>
+                                </div>
+                            </label>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+
+            <ErrorMessage error=error/>
+
+            // Кнопки действий
+            <div class="flex items-center justify-center space-x-3 divider-top pt-4">
+                <button class="btn-secondary px-5 py-2.5" on:click=move |_| on_cancel.run(())>
+                    "Cancel"
+                </button>
+                <button
+                    class="btn-primary px-6 py-2.5"
+                    on:click=move |_| handle_accept()
+                    disabled=move || selected_count() == 0
+                >
+                    <Icon name=icons::CHECK class="icon-btn"/>
+                    {move || format!("Accept {}", selected_count())}
+                </button>
+            </div>
+        </div>
+    }
+}
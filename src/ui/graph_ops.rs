@@ -5,6 +5,8 @@
 
 use leptos::prelude::WithUntracked;
 
+use crate::core::TableDisplay;
+
 use crate::ui::liveshare_client::{
     ColumnData, ConnectionState, GraphOperation, LiveShareContext, RelationshipData,
 };
@@ -74,6 +76,29 @@ impl GraphOpsSender {
         });
     }
 
+    /// Send a SetTablePinned operation
+    pub fn set_table_pinned(&self, node_idx: NodeIndex, table_uuid: uuid::Uuid, pinned: bool) {
+        self.send(GraphOperation::SetTablePinned {
+            node_id: node_idx.index() as u32,
+            table_uuid,
+            pinned,
+        });
+    }
+
+    /// Send a SetTableDisplay operation
+    pub fn set_table_display(
+        &self,
+        node_idx: NodeIndex,
+        table_uuid: uuid::Uuid,
+        display: TableDisplay,
+    ) {
+        self.send(GraphOperation::SetTableDisplay {
+            node_id: node_idx.index() as u32,
+            table_uuid,
+            display,
+        });
+    }
+
     /// Send an AddColumn operation
     pub fn add_column(&self, node_idx: NodeIndex, table_uuid: uuid::Uuid, column: ColumnData) {
         self.send(GraphOperation::AddColumn {
@@ -0,0 +1,133 @@
+//! Undo/redo for the editor
+//!
+//! Provides `HistoryContext`, shared by every component that edits the graph.
+//! A component snapshots the graph before a gesture, mutates it, and commits
+//! the gesture's operations: they are sent to LiveShare and recorded as one
+//! undo entry. Undo and redo broadcast their own operations the same way.
+
+use leptos::prelude::*;
+
+use crate::core::{SchemaGraph, UndoHistory};
+use crate::ui::liveshare_client::{GraphOperation, LiveShareContext, use_liveshare_context};
+
+#[cfg(not(feature = "ssr"))]
+fn dispatch_save_event(reason: &str) {
+    use wasm_bindgen::JsValue;
+
+    if let Some(window) = web_sys::window() {
+        let init = web_sys::CustomEventInit::new();
+        init.set_detail(&JsValue::from_str(reason));
+        if let Ok(event) =
+            web_sys::CustomEvent::new_with_event_init_dict("diagram-save-requested", &init)
+        {
+            let _ = window.dispatch_event(&event);
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn dispatch_save_event(_reason: &str) {
+    // No-op on server
+}
+
+/// Undo/redo state of the diagram being edited
+#[derive(Clone, Copy)]
+pub struct HistoryContext {
+    history: StoredValue<UndoHistory>,
+    graph: RwSignal<SchemaGraph>,
+    liveshare: LiveShareContext,
+    /// Whether there is something to undo
+    pub can_undo: RwSignal<bool>,
+    /// Whether there is something to redo
+    pub can_redo: RwSignal<bool>,
+}
+
+impl HistoryContext {
+    pub fn new(graph: RwSignal<SchemaGraph>, liveshare: LiveShareContext) -> Self {
+        Self {
+            history: StoredValue::new(UndoHistory::new()),
+            graph,
+            liveshare,
+            can_undo: RwSignal::new(false),
+            can_redo: RwSignal::new(false),
+        }
+    }
+
+    /// Send a finished gesture to LiveShare and record it for undo
+    ///
+    /// `before` is the graph as it was before the gesture started.
+    pub fn commit(&self, before: SchemaGraph, ops: Vec<GraphOperation>) {
+        for op in &ops {
+            self.liveshare.send_graph_op(op.clone());
+        }
+        self.record(before, ops);
+    }
+
+    /// Record a gesture whose operations were already sent (e.g. table drag)
+    pub fn record(&self, before: SchemaGraph, ops: Vec<GraphOperation>) {
+        self.graph.with_untracked(|after| {
+            self.history
+                .update_value(|history| history.record(&before, after, ops));
+        });
+        self.sync_flags();
+    }
+
+    /// Undo the last local gesture
+    pub fn undo(&self) {
+        self.step(|history, graph| history.undo(graph));
+    }
+
+    /// Redo the last undone gesture
+    pub fn redo(&self) {
+        self.step(|history, graph| history.redo(graph));
+    }
+
+    /// Forget the history (another diagram or session state was loaded)
+    pub fn clear(&self) {
+        self.history.update_value(|history| history.clear());
+        self.sync_flags();
+    }
+
+    fn step(
+        &self,
+        action: impl FnOnce(&mut UndoHistory, &mut SchemaGraph) -> Option<Vec<GraphOperation>>,
+    ) {
+        let mut graph = self.graph.get_untracked();
+        let Some(ops) = self
+            .history
+            .try_update_value(|history| action(history, &mut graph))
+            .flatten()
+        else {
+            return;
+        };
+        self.sync_flags();
+        if ops.is_empty() {
+            return;
+        }
+        self.graph.set(graph);
+        for op in ops {
+            self.liveshare.send_graph_op(op);
+        }
+        dispatch_save_event("history");
+    }
+
+    fn sync_flags(&self) {
+        let (can_undo, can_redo) = self
+            .history
+            .with_value(|history| (history.can_undo(), history.can_redo()));
+        self.can_undo.set(can_undo);
+        self.can_redo.set(can_redo);
+    }
+}
+
+/// Create the history for `graph` and provide it to child components
+pub fn provide_history_context(graph: RwSignal<SchemaGraph>) -> HistoryContext {
+    let ctx = HistoryContext::new(graph, use_liveshare_context());
+    provide_context(ctx);
+    ctx
+}
+
+/// Use the history context from the component tree
+pub fn use_history_context() -> HistoryContext {
+    expect_context::<HistoryContext>()
+}
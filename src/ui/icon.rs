@@ -88,4 +88,8 @@ pub mod icons {
     pub const ARROW_LEFT: &str = "arrow-left";
     pub const PANEL_LEFT_CLOSE: &str = "panel-left-close";
     pub const PANEL_LEFT_OPEN: &str = "panel-left-open";
+    pub const PIN: &str = "pin";
+    pub const UNDO: &str = "undo";
+    pub const REDO: &str = "redo";
+    pub const LINK: &str = "link";
 }
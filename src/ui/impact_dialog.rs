@@ -0,0 +1,79 @@
+use crate::core::{ImpactKind, ImpactReport};
+use crate::ui::{BaseModal, Icon, icons};
+use leptos::prelude::*;
+
+/// Подтверждение удаления с перечнем зависимых объектов
+#[component]
+pub fn ImpactConfirmDialog(
+    /// Отчёт о зависимостях; диалог открыт, пока он задан
+    #[prop(into)]
+    report: Signal<Option<ImpactReport>>,
+    /// Callback при подтверждении удаления
+    #[prop(into)]
+    on_confirm: Callback<()>,
+    /// Callback при отмене
+    #[prop(into)]
+    on_cancel: Callback<()>,
+) -> impl IntoView {
+    let is_open = Signal::derive(move || report.with(|r| r.is_some()));
+
+    let kind_icon = |kind: ImpactKind| match kind {
+        ImpactKind::Relationship => icons::LIGHTNING,
+        ImpactKind::OrphanedKey | ImpactKind::DependentKey => icons::KEY,
+        ImpactKind::Index => icons::SORT,
+        ImpactKind::JunctionTable => icons::TABLE,
+    };
+
+    view! {
+        <BaseModal
+            title="Confirm deletion".to_string()
+            is_open=is_open
+            on_close=on_cancel
+            max_width="max-w-lg"
+        >
+            <div class="space-y-4">
+                <p class="text-theme-secondary">
+                    {move || report.with(|r| r.as_ref().map(|r| r.summary()).unwrap_or_default())}
+                </p>
+                <p class="text-xs text-theme-muted">
+                    "Affected relationships are highlighted on the canvas."
+                </p>
+
+                <ul class="max-h-72 overflow-y-auto space-y-1.5">
+                    {move || {
+                        report
+                            .get()
+                            .map(|r| r.items)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|item| {
+                                let indent = format!("padding-left: {}rem", (item.depth - 1) as f32 * 1.25);
+                                view! {
+                                    <li class="flex items-start text-sm" style=indent>
+                                        <Icon name=kind_icon(item.kind) class="icon-text text-theme-muted flex-shrink-0 mt-0.5"/>
+                                        <div class="min-w-0">
+                                            <span class="text-xs font-medium text-theme-muted mr-1">
+                                                {item.kind.label()}
+                                            </span>
+                                            <span class="text-theme-primary break-words">{item.description}</span>
+                                        </div>
+                                    </li>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </ul>
+
+                <div class="flex items-center justify-end gap-2 divider-top pt-4">
+                    <button class="btn-secondary" on:click=move |_| on_cancel.run(())>
+                        "Cancel"
+                    </button>
+                    <button class="btn-danger" on:click=move |_| on_confirm.run(())>
+                        <Icon name=icons::TRASH class="icon-btn"/>
+                        "Delete anyway"
+                    </button>
+                </div>
+            </div>
+        </BaseModal>
+    }
+}
@@ -0,0 +1,203 @@
+use crate::core::{
+    DEFAULT_MAX_JOIN_PATHS, DiagramSettings, JoinPath, SchemaGraph, find_join_paths, join_query_sql,
+};
+use crate::ui::{Icon, icons};
+use leptos::prelude::*;
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use std::collections::HashSet;
+
+/// Панель поиска пути соединения между таблицами
+///
+/// Выбранный путь подсвечивается на канвасе, для него генерируется SELECT
+/// с JOIN-ами в диалекте диаграммы.
+#[component]
+pub fn JoinPathPanel(
+    graph: RwSignal<SchemaGraph>,
+    /// Подсвеченные связи на канвасе
+    highlighted_edges: RwSignal<HashSet<EdgeIndex>>,
+) -> impl IntoView {
+    let settings = use_context::<RwSignal<DiagramSettings>>();
+    let (filter, set_filter) = signal(String::new());
+    // Порядок выбора важен: первая выбранная таблица идёт во FROM
+    let selected_tables = RwSignal::new(Vec::<NodeIndex>::new());
+    let active_path = RwSignal::new(0usize);
+
+    let paths = Memo::new(move |_| {
+        let tables = selected_tables.get();
+        if tables.len() < 2 {
+            return Ok(Vec::new());
+        }
+        graph.with(|g| find_join_paths(g, &tables, DEFAULT_MAX_JOIN_PATHS))
+    });
+
+    let current_path = move || -> Option<JoinPath> {
+        paths.with(|p| {
+            p.as_ref()
+                .ok()
+                .and_then(|list| list.get(active_path.get()).cloned())
+        })
+    };
+
+    let sql = move || {
+        let dialect = settings
+            .map(|s| s.with(|s| s.dialect.clone()))
+            .unwrap_or_default();
+        current_path().map(|path| join_query_sql(&path, &dialect))
+    };
+
+    // Подсветка выбранного пути на канвасе
+    Effect::new(move |_| {
+        let edges: HashSet<EdgeIndex> = current_path()
+            .map(|path| path.edges().into_iter().collect())
+            .unwrap_or_default();
+        highlighted_edges.set(edges);
+    });
+    on_cleanup(move || highlighted_edges.set(HashSet::new()));
+
+    let toggle_table = move |node: NodeIndex| {
+        selected_tables.update(|tables| {
+            if let Some(pos) = tables.iter().position(|&t| t == node) {
+                tables.remove(pos);
+            } else {
+                tables.push(node);
+            }
+        });
+        active_path.set(0);
+    };
+
+    let copy_sql = move |_| {
+        #[cfg(not(feature = "ssr"))]
+        if let (Some(sql), Some(window)) = (sql(), web_sys::window()) {
+            let _ = window.navigator().clipboard().write_text(&sql);
+        }
+    };
+
+    let tables = Memo::new(move |_| {
+        let query = filter.get().to_lowercase();
+        graph.with(|g| {
+            let mut list: Vec<(NodeIndex, String)> = g
+                .node_indices()
+                .filter_map(|idx| g.node_weight(idx).map(|t| (idx, t.name.clone())))
+                .filter(|(_, name)| query.is_empty() || name.to_lowercase().contains(&query))
+                .collect();
+            list.sort_by(|a, b| a.1.cmp(&b.1));
+            list
+        })
+    });
+
+    view! {
+        <div class="h-full flex flex-col space-y-4">
+            // Заголовок
+            <div>
+                <h3 class="title-lg">"Join Path"</h3>
+                <p class="subtitle">
+                    "Select two or more tables to find how they connect"
+                </p>
+            </div>
+
+            // Выбор таблиц
+            <div class="space-y-2">
+                <input
+                    type="text"
+                    class="input-base input-sm"
+                    placeholder="Filter tables..."
+                    prop:value=move || filter.get()
+                    on:input=move |ev| set_filter.set(event_target_value(&ev))
+                />
+                <div class="max-h-48 overflow-y-auto rounded-lg border border-theme-primary divide-y divide-theme-primary">
+                    <For
+                        each=move || tables.get()
+                        key=|(idx, name)| (*idx, name.clone())
+                        children=move |(idx, name)| {
+                            let order = move || {
+                                selected_tables
+                                    .with(|t| t.iter().position(|&n| n == idx))
+                                    .map(|pos| (pos + 1).to_string())
+                                    .unwrap_or_default()
+                            };
+                            view! {
+                                <label class="flex items-center px-3 py-1.5 text-sm cursor-pointer hover:bg-theme-tertiary theme-transition">
+                                    <input
+                                        type="checkbox"
+                                        class="mr-2"
+                                        prop:checked=move || selected_tables.with(|t| t.contains(&idx))
+                                        on:change=move |_| toggle_table(idx)
+                                    />
+                                    <span class="flex-1 font-mono text-theme-primary truncate">{name}</span>
+                                    <span class="text-xs text-theme-muted">{order}</span>
+                                </label>
+                            }
+                        }
+                    />
+                </div>
+            </div>
+
+            // Найденные пути
+            <div class="flex-1 overflow-y-auto space-y-2">
+                {move || {
+                    paths.with(|result| match result {
+                        Err(err) => view! {
+                            <div class="card-info text-sm text-theme-muted flex items-start">
+                                <Icon name=icons::ALERT_CIRCLE class="icon-text flex-shrink-0"/>
+                                <span>{err.clone()}</span>
+                            </div>
+                        }
+                            .into_any(),
+                        Ok(list) if list.is_empty() => ().into_any(),
+                        Ok(list) => list
+                            .iter()
+                            .enumerate()
+                            .map(|(index, path)| {
+                                let route = std::iter::once(path.root_table.clone())
+                                    .chain(path.steps.iter().map(|s| s.joined_table.clone()))
+                                    .collect::<Vec<_>>()
+                                    .join(" → ");
+                                let label = if index == 0 {
+                                    format!("Shortest · {} joins", path.len())
+                                } else {
+                                    format!("Alternative · {} joins", path.len())
+                                };
+                                view! {
+                                    <button
+                                        class=move || {
+                                            if active_path.get() == index {
+                                                "w-full text-left p-3 rounded-lg border border-theme-accent bg-theme-secondary theme-transition"
+                                            } else {
+                                                "w-full text-left p-3 rounded-lg border border-theme-primary hover:border-theme-accent theme-transition"
+                                            }
+                                        }
+                                        on:click=move |_| active_path.set(index)
+                                    >
+                                        <div class="text-xs text-theme-muted">{label}</div>
+                                        <div class="text-sm font-mono text-theme-primary break-words">{route}</div>
+                                    </button>
+                                }
+                            })
+                            .collect_view()
+                            .into_any(),
+                    })
+                }}
+
+                // SQL выбранного пути
+                {move || {
+                    sql().map(|sql| {
+                        view! {
+                            <div class="relative">
+                                <pre class="p-3 rounded-lg bg-theme-tertiary text-xs font-mono text-theme-primary overflow-x-auto whitespace-pre">
+                                    {sql}
+                                </pre>
+                                <button
+                                    class="absolute top-2 right-2 btn-secondary px-2 py-1 text-xs"
+                                    title="Copy SQL"
+                                    on:click=copy_sql
+                                >
+                                    <Icon name=icons::DOCUMENT_DUPLICATE class="w-3.5 h-3.5"/>
+                                </button>
+                            </div>
+                        }
+                    })
+                }}
+            </div>
+        </div>
+    }
+}
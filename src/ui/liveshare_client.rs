@@ -26,6 +26,7 @@ fn now_ms() -> f64 {
 }
 
 // Import auth context for getting username
+use crate::core::Viewport;
 use crate::ui::auth::use_auth_context;
 
 // Re-export protocol types for convenience
@@ -38,6 +39,10 @@ pub use crate::core::liveshare::{
 /// User ID type
 pub type UserId = Uuid;
 
+/// Screen size assumed for peers that do not report the size of their viewport
+#[cfg(not(feature = "ssr"))]
+const DEFAULT_VIEWPORT_SIZE: (f64, f64) = (1280.0, 800.0);
+
 /// Connection state for the LiveShare client
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ConnectionState {
@@ -71,6 +76,8 @@ pub struct RemoteUser {
     pub is_active: bool,
     /// Table being dragged by this user (node_id, offset_x, offset_y)
     pub dragging_table: Option<(u32, f64, f64)>,
+    /// Visible part of the canvas (from `UserViewport` messages)
+    pub viewport: Option<Viewport>,
 }
 
 impl RemoteUser {
@@ -83,6 +90,7 @@ impl RemoteUser {
             selected_nodes: vec![],
             is_active: true,
             dragging_table: None,
+            viewport: None,
         }
     }
 
@@ -161,6 +169,8 @@ pub struct LiveShareContext {
     pub error: RwSignal<Option<String>>,
     /// Local cursor position (to send to others)
     pub local_cursor: RwSignal<Option<(f64, f64)>>,
+    /// Locally selected tables (UUIDs, sent to others with the cursor)
+    pub local_selection: RwSignal<Vec<String>>,
     /// Pending room to join from URL (e.g., /?room=uuid)
     pub pending_join_room: RwSignal<Option<String>>,
     /// Synchronization status
@@ -199,6 +209,7 @@ impl LiveShareContext {
             remote_users: RwSignal::new(vec![]),
             error: RwSignal::new(None),
             local_cursor: RwSignal::new(None),
+            local_selection: RwSignal::new(vec![]),
             pending_join_room: RwSignal::new(None),
             sync_status: RwSignal::new(SyncStatus::Idle),
             pending_updates: RwSignal::new(0),
@@ -476,6 +487,18 @@ impl LiveShareContext {
         self.local_cursor.set(cursor);
     }
 
+    /// Update the local table selection and show it to others
+    pub fn set_local_selection(&self, selected_nodes: Vec<String>) {
+        if self
+            .local_selection
+            .with_untracked(|v| *v == selected_nodes)
+        {
+            return;
+        }
+        self.local_selection.set(selected_nodes.clone());
+        self.send_awareness(self.local_cursor.get_untracked(), selected_nodes);
+    }
+
     /// Send a Yjs update to the server
     #[allow(dead_code)]
     pub fn send_update(&self, update: Vec<u8>) {
@@ -531,6 +554,26 @@ impl LiveShareContext {
         // No-op on server
     }
 
+    /// Send the local viewport (center in canvas coordinates, size in screen pixels)
+    #[cfg(not(feature = "ssr"))]
+    pub fn send_viewport(&self, center: (f64, f64), zoom: f64, size: (f64, f64)) {
+        if self.connection_state.get_untracked() != ConnectionState::Connected {
+            return;
+        }
+        let msg = ClientMessage::UserViewport {
+            center,
+            zoom,
+            size: Some(size),
+        };
+        send_message(&msg);
+    }
+
+    /// Send viewport stub for SSR
+    #[cfg(feature = "ssr")]
+    pub fn send_viewport(&self, _center: (f64, f64), _zoom: f64, _size: (f64, f64)) {
+        // No-op on server
+    }
+
     /// Send graph state response to a specific user
     #[cfg(not(feature = "ssr"))]
     pub fn send_graph_state_response(&self, target_user_id: UserId, state: GraphStateSnapshot) {
@@ -884,12 +927,21 @@ fn handle_message(ctx: &LiveShareContext, text: &str) {
             }
         }
         ServerMessage::UserViewport {
-            user_id: _,
-            center: _,
-            zoom: _,
+            user_id,
+            center,
+            zoom,
+            size,
         } => {
-            // TODO: Handle user viewport updates for optimization
-            // This would be used to only send updates for visible elements
+            // Update remote user's viewport (shown on the minimap)
+            if user_id != ctx.user_id.with_untracked(|v| *v) && zoom > 0.0 {
+                let viewport =
+                    Viewport::from_center(center, zoom, size.unwrap_or(DEFAULT_VIEWPORT_SIZE));
+                ctx.remote_users.update(|users| {
+                    if let Some(user) = users.iter_mut().find(|u| u.user_id == user_id) {
+                        user.viewport = Some(viewport);
+                    }
+                });
+            }
         }
         ServerMessage::TableDragStart {
             user_id,
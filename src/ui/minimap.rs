@@ -0,0 +1,196 @@
+//! Minimap overlay for the canvas
+//!
+//! Shows every table and relationship scaled down, the current viewport and the
+//! viewports of LiveShare peers. Clicking or dragging on the minimap pans the
+//! canvas so that the point under the cursor is centered.
+
+use leptos::prelude::*;
+use leptos::web_sys;
+
+use crate::core::{MinimapProjection, SchemaGraph, Viewport, table_bounds};
+use crate::ui::liveshare_client::use_liveshare_context;
+
+/// Size of the minimap in pixels
+const MINIMAP_SIZE: (f64, f64) = (200.0, 120.0);
+
+#[component]
+pub fn Minimap(
+    graph: RwSignal<SchemaGraph>,
+    zoom: ReadSignal<f64>,
+    pan_x: ReadSignal<f64>,
+    pan_y: ReadSignal<f64>,
+    set_pan_x: WriteSignal<f64>,
+    set_pan_y: WriteSignal<f64>,
+    /// Size of the visible canvas area in screen pixels
+    viewport_size: RwSignal<(f64, f64)>,
+) -> impl IntoView {
+    let liveshare = use_liveshare_context();
+
+    let local_viewport = Memo::new(move |_| {
+        Viewport::from_transform((pan_x.get(), pan_y.get()), zoom.get(), viewport_size.get())
+    });
+
+    // Вьюпорты участников: (цвет, вьюпорт), цвет тот же, что у их курсоров
+    let peer_viewports = Memo::new(move |_| {
+        liveshare.remote_users.with(|users| {
+            users
+                .iter()
+                .filter_map(|user| {
+                    let viewport = user.viewport?;
+                    Some((user.color.clone(), viewport))
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+
+    // Проекция замораживается на время перетаскивания, иначе она смещается
+    // вместе с вьюпортом и рамка "убегает" от курсора
+    let frozen = RwSignal::new(None::<MinimapProjection>);
+    let projection = Memo::new(move |_| {
+        if let Some(projection) = frozen.get() {
+            return projection;
+        }
+        let mut viewports = vec![local_viewport.get()];
+        peer_viewports.with(|peers| viewports.extend(peers.iter().map(|(_, v)| *v)));
+        graph.with(|g| MinimapProjection::fit(g, &viewports, MINIMAP_SIZE))
+    });
+
+    let center_on = move |ev: &web_sys::MouseEvent| {
+        let point = projection
+            .get_untracked()
+            .to_canvas((ev.offset_x() as f64, ev.offset_y() as f64));
+        let (x, y) =
+            Viewport::pan_to_center(point, zoom.get_untracked(), viewport_size.get_untracked());
+        set_pan_x.set(x);
+        set_pan_y.set(y);
+    };
+
+    let tables = move || {
+        let projection = projection.get();
+        graph.with(|g| {
+            g.node_indices()
+                .filter_map(|table| table_bounds(g, table))
+                .map(|(l, t, r, b)| {
+                    let rect = projection.project(&Viewport {
+                        x: l,
+                        y: t,
+                        width: r - l,
+                        height: b - t,
+                    });
+                    view! {
+                        <rect
+                            x=rect.x
+                            y=rect.y
+                            width=rect.width.max(1.0)
+                            height=rect.height.max(1.0)
+                            rx="1"
+                            style="fill: var(--text-muted); opacity: 0.6;"
+                        />
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    let relationships = move || {
+        let projection = projection.get();
+        graph.with(|g| {
+            g.edge_indices()
+                .filter_map(|edge| {
+                    let (from, to) = g.edge_endpoints(edge)?;
+                    let center = |idx| {
+                        let (l, t, r, b) = table_bounds(g, idx)?;
+                        Some(projection.to_minimap(((l + r) / 2.0, (t + b) / 2.0)))
+                    };
+                    let ((x1, y1), (x2, y2)) = (center(from)?, center(to)?);
+                    Some(view! {
+                        <line
+                            x1=x1
+                            y1=y1
+                            x2=x2
+                            y2=y2
+                            stroke-width="1"
+                            style="stroke: var(--text-tertiary); opacity: 0.5;"
+                        />
+                    })
+                })
+                .collect_view()
+        })
+    };
+
+    let peers = move || {
+        let projection = projection.get();
+        peer_viewports
+            .get()
+            .into_iter()
+            .map(|(color, viewport)| {
+                let rect = projection.project(&viewport);
+                view! {
+                    <rect
+                        x=rect.x
+                        y=rect.y
+                        width=rect.width
+                        height=rect.height
+                        fill="none"
+                        stroke=color
+                        stroke-width="1.5"
+                        stroke-dasharray="4 2"
+                    />
+                }
+            })
+            .collect_view()
+    };
+
+    let local = move || {
+        let rect = projection.get().project(&local_viewport.get());
+        view! {
+            <rect
+                x=rect.x
+                y=rect.y
+                width=rect.width
+                height=rect.height
+                stroke-width="1.5"
+                style="fill: var(--accent-light); fill-opacity: 0.35; stroke: var(--accent-primary);"
+            />
+        }
+    };
+
+    view! {
+        <div
+            class="fixed bottom-4 right-20 z-40 bg-theme-surface border border-theme-primary overflow-hidden theme-transition"
+            style="border-radius: 12px; box-shadow: var(--shadow-lg);"
+            title="Minimap - click or drag to navigate"
+            on:click=|ev: web_sys::MouseEvent| ev.stop_propagation()
+        >
+            <svg
+                width=MINIMAP_SIZE.0
+                height=MINIMAP_SIZE.1
+                class="block cursor-pointer select-none"
+                on:mousedown=move |ev: web_sys::MouseEvent| {
+                    if ev.button() != 0 {
+                        return;
+                    }
+                    ev.prevent_default();
+                    ev.stop_propagation();
+                    frozen.set(Some(projection.get_untracked()));
+                    center_on(&ev);
+                }
+                on:mousemove=move |ev: web_sys::MouseEvent| {
+                    if frozen.with_untracked(|f| f.is_some()) {
+                        center_on(&ev);
+                    }
+                }
+                on:mouseup=move |_| frozen.set(None)
+                on:mouseleave=move |_| frozen.set(None)
+            >
+                // Содержимое не перехватывает события: offset_x/offset_y считаются от svg
+                <g style="pointer-events: none;">
+                    {relationships}
+                    {tables}
+                    {peers}
+                    {local}
+                </g>
+            </svg>
+        </div>
+    }
+}
@@ -4,12 +4,19 @@ pub mod auth;
 pub mod auth_utils;
 pub mod canvas;
 pub mod column_editor;
+pub mod command_palette;
 pub mod common;
+pub mod connect_dialog;
+pub mod fk_inference_dialog;
 pub mod graph_ops;
+pub mod history;
 pub mod icon;
+pub mod impact_dialog;
+pub mod join_path_panel;
 pub mod liveshare_client;
 pub mod liveshare_panel;
 pub mod markdown;
+pub mod minimap;
 pub mod new_table_dialog;
 pub mod notifications;
 pub mod pages;
@@ -30,18 +37,25 @@ pub use auth::{
 };
 pub use canvas::SchemaCanvas;
 pub use column_editor::ColumnEditor;
+pub use command_palette::CommandPalette;
 pub use common::{
     AlertDialog, BaseModal, Button, ButtonGroup, ButtonSize, ButtonVariant, CheckboxField,
-    ConfirmDialog, CreateCancelHints, ErrorMessage, ErrorMessageStatic, FormField, IconButton,
-    InfoMessage, Kbd, KeyboardHint, KeyboardHintWithIcon, KeyboardHints, SaveCancelHints,
-    SelectField, SubmitCancelButtons, SubmitCancelHints, SuccessMessage, SuccessMessageStatic,
-    TextAreaField, WarningMessage, WarningMessageStatic,
+    ConfirmDialog, CreateCancelHints, DialectNameNotes, ErrorMessage, ErrorMessageStatic,
+    FormField, IconButton, InfoMessage, Kbd, KeyboardHint, KeyboardHintWithIcon, KeyboardHints,
+    SaveCancelHints, SelectField, SubmitCancelButtons, SubmitCancelHints, SuccessMessage,
+    SuccessMessageStatic, TextAreaField, WarningMessage, WarningMessageStatic,
 };
+pub use connect_dialog::{ConnectDialog, PendingConnection};
+pub use fk_inference_dialog::FkInferenceDialog;
 pub use graph_ops::{GraphOpsSender, use_graph_ops};
+pub use history::{HistoryContext, provide_history_context, use_history_context};
 pub use icon::{Icon, icons};
+pub use impact_dialog::ImpactConfirmDialog;
+pub use join_path_panel::JoinPathPanel;
 pub use liveshare_client::{LiveShareContext, provide_liveshare_context, use_liveshare_context};
 pub use liveshare_panel::LiveSharePanel;
 pub use markdown::Markdown;
+pub use minimap::Minimap;
 pub use new_table_dialog::{CreateTableResult, NewTableData, NewTableDialog};
 pub use notifications::{NotificationItem, NotificationManager, NotificationsContainer};
 pub use pages::{
@@ -7,7 +7,7 @@ use leptos::prelude::*;
 use leptos::task::spawn_local;
 use leptos_router::hooks::{use_params_map, use_query_map};
 
-use crate::core::SchemaGraph;
+use crate::core::{DiagramDocument, DiagramSettings, SchemaGraph};
 use crate::ui::auth::{AuthState, use_auth_context};
 use crate::ui::canvas::SchemaCanvas;
 use crate::ui::liveshare_client::use_liveshare_context;
@@ -86,6 +86,7 @@ pub fn EditorPage() -> impl IntoView {
 
     // Diagram state
     let graph = RwSignal::new(SchemaGraph::new());
+    let settings = RwSignal::new(DiagramSettings::default());
     let diagram_name = RwSignal::new(String::from("Untitled Diagram"));
     let loading = RwSignal::new(true);
     let error = RwSignal::new(None::<String>);
@@ -140,9 +141,10 @@ pub fn EditorPage() -> impl IntoView {
                     Ok(diagram) => {
                         diagram_name.set(diagram.name);
                         if let Some(schema) = diagram.schema_data
-                            && let Ok(parsed) = serde_json::from_value(schema)
+                            && let Ok(document) = DiagramDocument::from_value(schema)
                         {
-                            graph.set(parsed);
+                            settings.set(document.settings);
+                            graph.set(document.graph);
                         }
                     }
                     Err(e) => {
@@ -194,7 +196,7 @@ pub fn EditorPage() -> impl IntoView {
             save_status.set(SaveStatus::Saving);
 
             let id_clone = id.clone();
-            let graph_clone = serde_json::to_string(&graph.get_untracked()).unwrap_or_default();
+            let graph_clone = current_document_json(graph, settings);
 
             leptos::logging::log!("Saving diagram: {}", reason);
 
@@ -226,12 +228,9 @@ pub fn EditorPage() -> impl IntoView {
         Effect::new(move |_| {
             let save_callback = save_callback.clone();
             let last_state = last_save_state.clone();
-            let graph_signal = graph;
-
             // Setup interval for 1 minute (60000ms)
             let interval = Interval::new(60_000, move || {
-                let current_state =
-                    serde_json::to_string(&graph_signal.get_untracked()).unwrap_or_default();
+                let current_state = current_document_json(graph, settings);
 
                 // Only save if graph has changed since last save
                 if *last_state.borrow() != current_state {
@@ -314,6 +313,7 @@ pub fn EditorPage() -> impl IntoView {
             <Show when=move || !loading.get()>
                 <SchemaCanvas
                     graph=graph
+                    settings=settings
                     diagram_name=diagram_name
                     diagram_id=diagram_id.get()
                     is_demo=is_demo.get()
@@ -324,6 +324,15 @@ pub fn EditorPage() -> impl IntoView {
     }
 }
 
+/// Serialize graph + diagram settings as the persisted document
+fn current_document_json(
+    graph: RwSignal<SchemaGraph>,
+    settings: RwSignal<DiagramSettings>,
+) -> String {
+    let document = DiagramDocument::new(graph.get_untracked(), settings.get_untracked());
+    document.to_json().unwrap_or_default()
+}
+
 #[component]
 fn LoadingOverlay() -> impl IntoView {
     view! {
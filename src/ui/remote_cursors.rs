@@ -344,7 +344,8 @@ pub fn CursorTracker(
                 let last = *last_update_move.borrow();
                 if now - last >= 20.0 {
                     if let Some((x, y)) = *last_position_move.borrow() {
-                        ctx_move.send_awareness(Some((x, y)), vec![]);
+                        ctx_move
+                            .send_awareness(Some((x, y)), ctx_move.local_selection.get_untracked());
                     }
                     *last_update_move.borrow_mut() = now;
                 }
@@ -353,7 +354,7 @@ pub fn CursorTracker(
             let ctx_leave = ctx;
             let mouseleave = Closure::wrap(Box::new(move |_: web_sys::MouseEvent| {
                 // Send None cursor when mouse leaves the window
-                ctx_leave.send_awareness(None, vec![]);
+                ctx_leave.send_awareness(None, ctx_leave.local_selection.get_untracked());
             }) as Box<dyn FnMut(web_sys::MouseEvent)>);
 
             let _ = document
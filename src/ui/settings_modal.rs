@@ -3,10 +3,14 @@
 //! Provides a centered modal with:
 //! - Backdrop blur effect
 //! - Diagram settings (info, rename, LiveShare, delete)
-//! - Canvas settings (theme switcher)
+//! - Canvas settings (theme switcher, auto-layout algorithm, relationship notation)
 //! - Export settings
+//! - Lint rules (enable/disable, severity overrides)
 
-use crate::core::{ExportFormat, ExportOptions, SchemaExporter, SchemaGraph, SqlDialect};
+use crate::core::{
+    DiagramSettings, ErrorSeverity, ExportFormat, ExportOptions, FocusMode, LayoutAlgorithm,
+    LintContext, LintEngine, Notation, SOURCE_CHECKS, SchemaExporter, SchemaGraph,
+};
 use crate::ui::liveshare_client::{ConnectionState, LiveShareContext, use_liveshare_context};
 use crate::ui::theme::{ThemeMode, use_theme_context};
 use crate::ui::{Icon, icons};
@@ -457,7 +461,7 @@ fn ThemeSelector() -> impl IntoView {
 
 /// Canvas/Theme tab content component
 #[component]
-fn CanvasTab() -> impl IntoView {
+fn CanvasTab(settings: Option<RwSignal<DiagramSettings>>) -> impl IntoView {
     view! {
         <div style="display: flex; flex-direction: column; gap: 20px;">
             // Theme section
@@ -466,15 +470,75 @@ fn CanvasTab() -> impl IntoView {
                 <ThemeSelector/>
             </div>
 
-            // Additional canvas settings placeholder
-            <div class="bg-theme-secondary theme-transition" style="padding: 16px; border-radius: 12px;">
-                <div class="flex items-center" style="gap: 12px;">
-                    <Icon name=icons::INFORMATION_CIRCLE class="w-5 h-5 text-theme-muted" />
-                    <p class="text-theme-tertiary" style="font-size: 14px;">
-                        "More canvas settings coming soon..."
+            // Auto layout section
+            {settings.map(|settings| view! {
+                <div>
+                    <h3 class="text-theme-primary" style="font-size: 16px; font-weight: 600; margin-bottom: 4px;">"Auto Layout"</h3>
+                    <p class="text-theme-tertiary" style="font-size: 13px; margin-bottom: 12px;">
+                        "Layered places referenced tables above the tables that point to them"
                     </p>
+                    <select
+                        class="input-theme"
+                        style="width: 100%; padding: 8px 12px; border-radius: 8px; font-size: 14px;"
+                        on:change=move |ev| {
+                            let value = event_target_value(&ev);
+                            if let Some(algorithm) = LayoutAlgorithm::ALL
+                                .into_iter()
+                                .find(|a| a.label() == value)
+                            {
+                                settings.update(|s| s.layout = algorithm);
+                            }
+                        }
+                    >
+                        {LayoutAlgorithm::ALL
+                            .into_iter()
+                            .map(|algorithm| view! {
+                                <option
+                                    value=algorithm.label()
+                                    selected=move || settings.with(|s| s.layout == algorithm)
+                                >
+                                    {algorithm.label()}
+                                </option>
+                            })
+                            .collect_view()}
+                    </select>
                 </div>
-            </div>
+            })}
+
+            // Relationship notation section
+            {settings.map(|settings| view! {
+                <div>
+                    <h3 class="text-theme-primary" style="font-size: 16px; font-weight: 600; margin-bottom: 4px;">"Relationship Notation"</h3>
+                    <p class="text-theme-tertiary" style="font-size: 13px; margin-bottom: 12px;">
+                        "Used on the canvas and in exported images; optionality follows foreign key nullability"
+                    </p>
+                    <select
+                        class="input-theme"
+                        style="width: 100%; padding: 8px 12px; border-radius: 8px; font-size: 14px;"
+                        on:change=move |ev| {
+                            let value = event_target_value(&ev);
+                            if let Some(notation) = Notation::ALL
+                                .into_iter()
+                                .find(|n| n.label() == value)
+                            {
+                                settings.update(|s| s.notation = notation);
+                            }
+                        }
+                    >
+                        {Notation::ALL
+                            .into_iter()
+                            .map(|notation| view! {
+                                <option
+                                    value=notation.label()
+                                    selected=move || settings.with(|s| s.notation == notation)
+                                >
+                                    {notation.label()}
+                                </option>
+                            })
+                            .collect_view()}
+                    </select>
+                </div>
+            })}
         </div>
     }
 }
@@ -532,6 +596,7 @@ fn ExportFormatSelector(
     let set_sql = Callback::new(move |_: ()| set_export_format.set("sql"));
     let set_json = Callback::new(move |_: ()| set_export_format.set("json"));
     let set_csv = Callback::new(move |_: ()| set_export_format.set("csv"));
+    let set_svg = Callback::new(move |_: ()| set_export_format.set("svg"));
 
     view! {
         <div class="bg-theme-secondary theme-transition" style="padding: 16px; border-radius: 12px; margin-bottom: 16px;">
@@ -560,6 +625,13 @@ fn ExportFormatSelector(
                     icon_name=icons::FILE
                     on_click=set_csv
                 />
+                <ExportFormatButton
+                    format_value="svg"
+                    current_format=current_format
+                    label="Image"
+                    icon_name=icons::CAMERA
+                    on_click=set_svg
+                />
             </div>
         </div>
     }.into_any()
@@ -567,10 +639,29 @@ fn ExportFormatSelector(
 
 /// Export tab content component
 #[component]
-fn ExportTab(graph: Option<RwSignal<SchemaGraph>>) -> impl IntoView {
+fn ExportTab(
+    graph: Option<RwSignal<SchemaGraph>>,
+    settings: Option<RwSignal<DiagramSettings>>,
+    focus: Option<RwSignal<Option<FocusMode>>>,
+) -> impl IntoView {
+    // SQL export uses the diagram's target dialect
+    let dialect = move || {
+        settings
+            .map(|s| s.with(|v| v.dialect.clone()))
+            .unwrap_or_default()
+    };
     let (export_format, set_export_format) = signal("sql");
     let (_export_result, set_export_result) = signal::<Option<String>>(None);
     let (export_filename, set_export_filename) = signal(String::from("schema"));
+    // В режиме фокуса по умолчанию экспортируются только таблицы фокуса
+    let focused_only = RwSignal::new(true);
+    let active_focus = move || focus.and_then(|f| f.get()).filter(|_| graph.is_some());
+    let focused_count = move || {
+        active_focus()
+            .zip(graph)
+            .map(|(f, g)| g.with(|g| f.visible_tables(g).len()))
+            .unwrap_or(0)
+    };
 
     view! {
         <div style="display: flex; flex-direction: column; gap: 20px;">
@@ -601,6 +692,23 @@ fn ExportTab(graph: Option<RwSignal<SchemaGraph>>) -> impl IntoView {
                     </div>
                 </div>
 
+                // Focus scope
+                <Show when=move || active_focus().is_some()>
+                    <label
+                        class="bg-theme-secondary theme-transition flex items-center cursor-pointer"
+                        style="padding: 16px; border-radius: 12px; margin-bottom: 16px; gap: 12px;"
+                    >
+                        <input
+                            type="checkbox"
+                            prop:checked=move || focused_only.get()
+                            on:change=move |ev| focused_only.set(event_target_checked(&ev))
+                        />
+                        <span class="text-theme-secondary" style="font-size: 14px;">
+                            {move || format!("Only the {} tables in focus", focused_count())}
+                        </span>
+                    </label>
+                </Show>
+
                 // Export button
                 {move || {
                     if let Some(g) = graph {
@@ -619,14 +727,20 @@ fn ExportTab(graph: Option<RwSignal<SchemaGraph>>) -> impl IntoView {
                                             format: match format {
                                                 "json" => ExportFormat::Json,
                                                 "csv" => ExportFormat::Csv,
+                                                "svg" => ExportFormat::Svg,
                                                 _ => ExportFormat::Sql,
                                             },
-                                            sql_dialect: SqlDialect::MySQL,
+                                            sql_dialect: dialect(),
                                             include_positions: true,
                                             include_drop_statements: false,
                                             pretty_print: true,
+                                            sql_format: settings.map(|s| s.with(|v| v.sql_format.clone())),
+                                            notation: settings.map(|s| s.with(|v| v.notation)).unwrap_or_default(),
                                         };
-                                        SchemaExporter::export(graph, &options)
+                                        match active_focus().filter(|_| focused_only.get_untracked()) {
+                                            Some(focus) => SchemaExporter::export(&focus.subgraph(graph), &options),
+                                            None => SchemaExporter::export(graph, &options),
+                                        }
                                     });
 
                                     match result {
@@ -639,6 +753,7 @@ fn ExportTab(graph: Option<RwSignal<SchemaGraph>>) -> impl IntoView {
                                                 let mime_type = match format {
                                                     "json" => "application/json",
                                                     "csv" => "text/csv",
+                                                    "svg" => "image/svg+xml",
                                                     _ => "text/plain",
                                                 };
                                                 let full_filename = format!("{}.{}", filename, format);
@@ -697,14 +812,16 @@ fn ExportTab(graph: Option<RwSignal<SchemaGraph>>) -> impl IntoView {
                             {move || match export_format.get() {
                                 "json" => "JSON Format",
                                 "csv" => "CSV Format",
+                                "svg" => "Image Format",
                                 _ => "SQL Format",
                             }}
                         </p>
                         <p class="text-theme-tertiary" style="font-size: 13px; line-height: 1.5;">
                             {move || match export_format.get() {
-                                "json" => "Structured format with tables, columns, relationships and positions. Ideal for backup and programmatic access.",
-                                "csv" => "Tabular format with separate sections for tables, columns and relationships. Good for spreadsheet analysis.",
-                                _ => "DDL statements (CREATE TABLE) compatible with MySQL. Ready for database deployment.",
+                                "json" => "Structured format with tables, columns, relationships and positions. Ideal for backup and programmatic access.".to_string(),
+                                "csv" => "Tabular format with separate sections for tables, columns and relationships. Good for spreadsheet analysis.".to_string(),
+                                "svg" => "SVG picture of the diagram with tables at their canvas positions. Scales without loss for docs and slides.".to_string(),
+                                _ => format!("DDL statements (CREATE TABLE) compatible with {}. Ready for database deployment.", dialect().label()),
                             }}
                         </p>
                     </div>
@@ -714,6 +831,113 @@ fn ExportTab(graph: Option<RwSignal<SchemaGraph>>) -> impl IntoView {
     }
 }
 
+/// Lint tab content - per-diagram rule toggles and severity overrides
+#[component]
+fn LintTab(
+    graph: Option<RwSignal<SchemaGraph>>,
+    settings: Option<RwSignal<DiagramSettings>>,
+) -> impl IntoView {
+    let Some(settings) = settings else {
+        return view! {
+            <div class="bg-theme-secondary theme-transition" style="padding: 16px; border-radius: 12px; text-align: center;">
+                <p class="text-theme-tertiary" style="font-size: 14px;">"No diagram loaded"</p>
+            </div>
+        }
+        .into_any();
+    };
+
+    let engine = LintEngine::default();
+    let rules: Vec<(&'static str, &'static str, ErrorSeverity)> = engine
+        .rules()
+        .map(|rule| (rule.id(), rule.description(), rule.default_severity()))
+        .chain(SOURCE_CHECKS.iter().copied())
+        .collect();
+
+    // Issue counts per rule for the current diagram
+    let report = Memo::new(move |_| {
+        let lint = settings.with(|s| s.lint.clone());
+        let context = LintContext::new(settings.with(|s| s.dialect.clone()));
+        graph
+            .map(|g| g.with(|g| LintEngine::default().run_with_context(g, &lint, &context)))
+            .unwrap_or_default()
+    });
+
+    view! {
+        <div style="display: flex; flex-direction: column; gap: 12px;">
+            <div>
+                <h3 class="text-theme-primary" style="font-size: 16px; font-weight: 600; margin-bottom: 4px;">"Lint Rules"</h3>
+                <p class="text-theme-tertiary" style="font-size: 13px;">
+                    {move || match report.with(|r| r.issues.len()) {
+                        0 => "No issues in this diagram".to_string(),
+                        n => format!("{} issues in this diagram (shown in the source editor)", n),
+                    }}
+                </p>
+            </div>
+            {rules
+                .into_iter()
+                .map(|(id, description, default_severity)| {
+                    let enabled = move || settings.with(|s| s.lint.is_enabled(id));
+                    let issue_count = move || report.with(|r| r.issues.iter().filter(|i| i.rule_id == id).count());
+                    view! {
+                        <div class="bg-theme-secondary theme-transition" style="padding: 12px; border-radius: 12px; display: flex; flex-direction: column; gap: 8px;">
+                            <label class="flex items-center" style="gap: 8px; cursor: pointer;">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=enabled
+                                    on:change=move |ev| {
+                                        let checked = event_target_checked(&ev);
+                                        settings.update(|s| s.lint.set_enabled(id, checked));
+                                    }
+                                />
+                                <span class="text-theme-primary" style="font-size: 14px; font-weight: 500; flex: 1;">{description}</span>
+                                <span class="text-theme-muted" style="font-size: 12px;">
+                                    {move || match issue_count() {
+                                        0 => String::new(),
+                                        n => n.to_string(),
+                                    }}
+                                </span>
+                            </label>
+                            <div class="flex items-center justify-between" style="gap: 8px;">
+                                <code class="text-theme-muted" style="font-size: 11px;">{id}</code>
+                                <select
+                                    class="input-theme"
+                                    style="padding: 4px 8px; border-radius: 6px; font-size: 12px;"
+                                    prop:disabled=move || !enabled()
+                                    on:change=move |ev| {
+                                        let severity = match event_target_value(&ev).as_str() {
+                                            "error" => Some(ErrorSeverity::Error),
+                                            "warning" => Some(ErrorSeverity::Warning),
+                                            "hint" => Some(ErrorSeverity::Hint),
+                                            _ => None,
+                                        };
+                                        settings.update(|s| s.lint.set_severity(id, severity));
+                                    }
+                                >
+                                    <option value="default" selected=move || settings.with(|s| !s.lint.severity.contains_key(id))>
+                                        {format!("Default ({})", default_severity)}
+                                    </option>
+                                    {[ErrorSeverity::Error, ErrorSeverity::Warning, ErrorSeverity::Hint]
+                                        .into_iter()
+                                        .map(|severity| view! {
+                                            <option
+                                                value=severity.to_string()
+                                                selected=move || settings.with(|s| s.lint.severity.get(id) == Some(&severity))
+                                            >
+                                                {severity.to_string()}
+                                            </option>
+                                        })
+                                        .collect_view()}
+                                </select>
+                            </div>
+                        </div>
+                    }
+                })
+                .collect_view()}
+        </div>
+    }
+    .into_any()
+}
+
 /// Diagram tab content - info, rename, liveshare, and delete functionality
 #[component]
 fn DiagramTab(
@@ -1227,6 +1451,9 @@ pub fn SettingsModal(
     /// Schema graph for export functionality
     #[prop(optional)]
     graph: Option<RwSignal<SchemaGraph>>,
+    /// Diagram settings (SQL dialect for export)
+    #[prop(optional)]
+    settings: Option<RwSignal<DiagramSettings>>,
     /// Diagram name for diagram tab
     #[prop(default = None)]
     diagram_name: Option<String>,
@@ -1239,6 +1466,12 @@ pub fn SettingsModal(
     /// Callback when diagram name changes
     #[prop(default = None)]
     on_name_change: Option<Callback<String>>,
+    /// Active tab ("diagram", "canvas", "export", "lint"), to open the modal on a given tab
+    #[prop(optional)]
+    active_tab: Option<RwSignal<&'static str>>,
+    /// Canvas focus mode; while active, export can be limited to the focused tables
+    #[prop(optional)]
+    focus: Option<RwSignal<Option<FocusMode>>>,
 ) -> impl IntoView {
     // Get the LiveShare context
     let ctx = use_liveshare_context();
@@ -1266,7 +1499,7 @@ pub fn SettingsModal(
     let mode = RwSignal::new("join");
 
     // Active settings tab
-    let (active_tab, set_active_tab) = signal("diagram");
+    let active_tab = active_tab.unwrap_or_else(|| RwSignal::new("diagram"));
 
     // Close modal handler
     let close_modal = move |_| {
@@ -1327,9 +1560,10 @@ pub fn SettingsModal(
                     >
                         {
                             let current_tab = Memo::new(move |_| active_tab.get());
-                            let set_diagram = Callback::new(move |_: ()| set_active_tab.set("diagram"));
-                            let set_canvas = Callback::new(move |_: ()| set_active_tab.set("canvas"));
-                            let set_export = Callback::new(move |_: ()| set_active_tab.set("export"));
+                            let set_diagram = Callback::new(move |_: ()| active_tab.set("diagram"));
+                            let set_canvas = Callback::new(move |_: ()| active_tab.set("canvas"));
+                            let set_export = Callback::new(move |_: ()| active_tab.set("export"));
+                            let set_lint = Callback::new(move |_: ()| active_tab.set("lint"));
                             view! {
                                 <SettingsTabButton
                                     tab_value="diagram"
@@ -1352,6 +1586,13 @@ pub fn SettingsModal(
                                     icon_path="M4 16v1a3 3 0 003 3h10a3 3 0 003-3v-1m-4-8l-4-4m0 0L8 8m4-4v12"
                                     on_click=set_export
                                 />
+                                <SettingsTabButton
+                                    tab_value="lint"
+                                    current_tab=current_tab
+                                    label="Lint"
+                                    icon_path="M9 12l2 2 4-4m5.618-4.016A11.955 11.955 0 0112 2.944a11.955 11.955 0 01-8.618 3.04A12.02 12.02 0 003 9c0 5.591 3.824 10.29 9 11.622 5.176-1.332 9-6.03 9-11.622 0-1.042-.133-2.052-.382-3.016z"
+                                    on_click=set_lint
+                                />
                             }
                         }
                     </div>
@@ -1381,12 +1622,17 @@ pub fn SettingsModal(
 
                         // Canvas tab content
                         <Show when=move || active_tab.get() == "canvas">
-                            <CanvasTab/>
+                            <CanvasTab settings=settings/>
                         </Show>
 
                         // Export tab content
                         <Show when=move || active_tab.get() == "export">
-                            <ExportTab graph=graph/>
+                            <ExportTab graph=graph settings=settings focus=focus/>
+                        </Show>
+
+                        // Lint tab content
+                        <Show when=move || active_tab.get() == "lint">
+                            <LintTab graph=graph settings=settings/>
                         </Show>
                     </div>
                 </div>
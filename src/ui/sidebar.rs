@@ -1,16 +1,22 @@
-use crate::core::{Column, SchemaGraph, TableOps};
+use crate::core::{
+    Column, ImpactReport, ImpactTarget, SchemaGraph, TableOps, analyze_impact,
+    delete_column_with_dependents,
+};
 use crate::ui::column_editor::ColumnEditor;
+use crate::ui::fk_inference_dialog::FkInferenceDialog;
+use crate::ui::history::use_history_context;
 use crate::ui::icon::{Icon, icons};
-use crate::ui::liveshare_client::{
-    ColumnData, ConnectionState, GraphOperation, use_liveshare_context,
-};
+use crate::ui::impact_dialog::ImpactConfirmDialog;
+use crate::ui::join_path_panel::JoinPathPanel;
+use crate::ui::liveshare_client::{ColumnData, GraphOperation};
 use crate::ui::new_table_dialog::{CreateTableResult, NewTableData, NewTableDialog};
 use crate::ui::source_editor::{EditorMode, EditorModeSwitcher};
 use crate::ui::table_editor::TableEditor;
 use leptos::prelude::*;
 use leptos::web_sys;
 use leptos_router::components::A;
-use petgraph::graph::NodeIndex;
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use std::collections::HashSet;
 
 /// Diagram name editor component - extracted to reduce nesting depth
 #[component]
@@ -100,10 +106,19 @@ fn DiagramNameEditor(
     }
 }
 
+/// Удаление, ожидающее подтверждения
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum PendingDelete {
+    Table(NodeIndex),
+    Column(NodeIndex, usize),
+}
+
 #[derive(Clone, Debug, PartialEq)]
 enum EditingMode {
     None,
     CreatingTable,
+    InferringRelationships,
+    FindingJoinPath,
     EditingColumn(NodeIndex, Option<usize>),
     EditingTable(NodeIndex),
 }
@@ -125,10 +140,11 @@ pub fn Sidebar(
     /// Callback when diagram name changes
     #[prop(default = None)]
     on_name_change: Option<Callback<String>>,
+    /// Highlighted relationships on the canvas (join path preview)
+    highlighted_edges: RwSignal<HashSet<EdgeIndex>>,
+    /// Dependents of a pending delete, highlighted on the canvas
+    impact_preview: RwSignal<Option<ImpactReport>>,
 ) -> impl IntoView {
-    // Get LiveShare context for sync
-    let liveshare_ctx = use_liveshare_context();
-
     let set_is_collapsed = is_collapsed;
     let (search_query, set_search_query) = signal(String::new());
     let (expanded_tables, set_expanded_tables) = signal::<Vec<NodeIndex>>(Vec::new());
@@ -136,13 +152,76 @@ pub fn Sidebar(
     // Состояние для редактора (колонка или таблица)
     let (editing_mode, set_editing_mode) = signal(EditingMode::None);
 
-    // Helper to send graph operation when connected
-    let send_graph_op = move |op: GraphOperation| {
-        if liveshare_ctx.connection_state.with_untracked(|v| *v) == ConnectionState::Connected {
-            liveshare_ctx.send_graph_op(op);
+    // Изменения отправляются участникам и записываются в историю отмены
+    let history = use_history_context();
+
+    // Удаление с проверкой зависимостей: при наличии зависимых объектов
+    // показываем подтверждение и подсвечиваем затронутые связи
+    let pending_delete = RwSignal::new(None::<(PendingDelete, ImpactReport)>);
+
+    let perform_delete = move |target: PendingDelete| {
+        match target {
+            PendingDelete::Table(node_idx) => {
+                let table_uuid = graph.with_untracked(|g| {
+                    g.node_weight(node_idx)
+                        .map(|n| n.uuid)
+                        .unwrap_or_else(uuid::Uuid::new_v4)
+                });
+                let before = graph.get_untracked();
+                graph.update(|g| {
+                    let _ = g.delete_table(node_idx);
+                });
+                history.commit(
+                    before,
+                    vec![GraphOperation::DeleteTable {
+                        node_id: node_idx.index() as u32,
+                        table_uuid,
+                    }],
+                );
+            }
+            PendingDelete::Column(node_idx, column_index) => {
+                let before = graph.get_untracked();
+                let mut ops = Vec::new();
+                graph.update(|g| {
+                    if let Ok(result) = delete_column_with_dependents(g, node_idx, column_index) {
+                        ops = result;
+                    }
+                });
+                history.commit(before, ops);
+            }
+        }
+        set_editing_mode.set(EditingMode::None);
+    };
+
+    let request_delete = move |target: PendingDelete| {
+        let impact_target = match target {
+            PendingDelete::Table(node_idx) => Some(ImpactTarget::Table(node_idx)),
+            PendingDelete::Column(node_idx, column_index) => graph.with_untracked(|g| {
+                g.node_weight(node_idx)
+                    .and_then(|n| n.columns.get(column_index))
+                    .map(|c| ImpactTarget::Column(node_idx, c.name.clone()))
+            }),
+        };
+        let report = impact_target
+            .and_then(|t| graph.with_untracked(|g| analyze_impact(g, &t).ok()))
+            .filter(|r| !r.is_empty());
+
+        match report {
+            Some(report) => {
+                highlighted_edges.set(report.edges());
+                impact_preview.set(Some(report.clone()));
+                pending_delete.set(Some((target, report)));
+            }
+            None => perform_delete(target),
         }
     };
 
+    let close_impact = move || {
+        pending_delete.set(None);
+        impact_preview.set(None);
+        highlighted_edges.set(HashSet::new());
+    };
+
     // Мемоизация статистики для предотвращения повторных подсчетов
     let total_tables = Memo::new(move |_| graph.with(|g| g.node_count()));
     let total_columns = Memo::new(move |_| {
@@ -282,23 +361,10 @@ pub fn Sidebar(
 
                                                     on_delete=move || {
                                                         if let Some(idx) = col_idx {
-                                                            graph
-                                                                .update(|g| {
-                                                                    if let Some(node) = g.node_weight_mut(node_idx) && idx < node.columns.len() {
-                                                                        node.columns.remove(idx);
-                                                                    }
-                                                                });
-                                                            // Send sync op
-                                                            let table_uuid = graph.with(|g| {
-                                                                g.node_weight(node_idx).map(|n| n.uuid).unwrap_or_else(uuid::Uuid::new_v4)
-                                                            });
-                                                            send_graph_op(GraphOperation::DeleteColumn {
-                                                                node_id: node_idx.index() as u32,
-                                                                table_uuid,
-                                                                column_index: idx,
-                                                            });
+                                                            request_delete(PendingDelete::Column(node_idx, idx));
+                                                        } else {
+                                                            set_editing_mode.set(EditingMode::None);
                                                         }
-                                                        set_editing_mode.set(EditingMode::None);
                                                     }
                                                 />
                                             </div>
@@ -338,18 +404,7 @@ pub fn Sidebar(
                                                     }
 
                                                     on_delete=move || {
-                                                        graph.update(|g| {
-                                                            let _ = g.delete_table(node_idx);
-                                                        });
-                                                        // Send sync op
-                                                        let table_uuid = graph.with(|g| {
-                                                            g.node_weight(node_idx).map(|n| n.uuid).unwrap_or_else(uuid::Uuid::new_v4)
-                                                        });
-                                                        send_graph_op(GraphOperation::DeleteTable {
-                                                            node_id: node_idx.index() as u32,
-                                                            table_uuid,
-                                                        });
-                                                        set_editing_mode.set(EditingMode::None);
+                                                        request_delete(PendingDelete::Table(node_idx));
                                                     }
                                                 />
                                             </div>
@@ -389,6 +444,7 @@ pub fn Sidebar(
                                                         let pk_type = data.pk_type.clone();
 
                                                         // Создаём таблицу
+                                                        let before = graph.get_untracked();
                                                         let result = graph.write().create_table(&table_name, position);
 
                                                         match result {
@@ -406,15 +462,15 @@ pub fn Sidebar(
                                                                 let table_uuid = graph.with(|g| {
                                                                     g.node_weight(new_node_idx).map(|n| n.uuid).unwrap_or_else(uuid::Uuid::new_v4)
                                                                 });
-                                                                send_graph_op(GraphOperation::CreateTable {
+                                                                let create_op = GraphOperation::CreateTable {
                                                                     node_id: new_node_idx.index() as u32,
                                                                     table_uuid,
                                                                     name: table_name,
                                                                     position,
-                                                                });
+                                                                };
 
-                                                                // Отправляем операцию добавления первичного ключа
-                                                                send_graph_op(GraphOperation::AddColumn {
+                                                                // Вместе с добавлением первичного ключа - один шаг истории
+                                                                let add_pk_op = GraphOperation::AddColumn {
                                                                     node_id: new_node_idx.index() as u32,
                                                                     table_uuid,
                                                                     column: ColumnData {
@@ -426,7 +482,8 @@ pub fn Sidebar(
                                                                         default_value: None,
                                                                         foreign_key: None,
                                                                     },
-                                                                });
+                                                                };
+                                                                history.commit(before, vec![create_op, add_pk_op]);
 
                                                                 // Раскрываем таблицу в списке
                                                                 set_expanded_tables.update(|expanded| {
@@ -454,6 +511,64 @@ pub fn Sidebar(
                                     }
                                         .into_any()
                                 }
+                                EditingMode::InferringRelationships => {
+                                    // Режим просмотра предложенных связей
+                                    view! {
+                                        <div class="flex-1 flex flex-col overflow-hidden">
+                                            // Хлебные крошки - вся панель кликабельна
+                                            <button
+                                                class="w-full px-6 py-3 border-b border-theme-primary bg-theme-secondary hover:bg-theme-tertiary theme-transition text-left cursor-pointer"
+                                                on:click=move |_| set_editing_mode.set(EditingMode::None)
+                                            >
+                                                <div class="flex items-center text-sm text-theme-accent font-medium">
+                                                    <Icon name=icons::CHEVRON_LEFT class="w-4 h-4 mr-1"/>
+                                                    "Back to tables"
+                                                </div>
+                                                <div class="mt-1 text-xs text-theme-muted">
+                                                    "Infer Relationships"
+                                                </div>
+                                            </button>
+
+                                            <div class="flex-1 overflow-y-auto px-6 py-4 bg-theme-surface theme-transition">
+                                                <FkInferenceDialog
+                                                    graph=graph
+                                                    on_accept=Callback::new(move |_ops: Vec<GraphOperation>| {
+                                                        set_editing_mode.set(EditingMode::None);
+                                                    })
+                                                    on_cancel=move || {
+                                                        set_editing_mode.set(EditingMode::None);
+                                                    }
+                                                />
+                                            </div>
+                                        </div>
+                                    }
+                                        .into_any()
+                                }
+                                EditingMode::FindingJoinPath => {
+                                    // Режим поиска пути соединения таблиц
+                                    view! {
+                                        <div class="flex-1 flex flex-col overflow-hidden">
+                                            // Хлебные крошки - вся панель кликабельна
+                                            <button
+                                                class="w-full px-6 py-3 border-b border-theme-primary bg-theme-secondary hover:bg-theme-tertiary theme-transition text-left cursor-pointer"
+                                                on:click=move |_| set_editing_mode.set(EditingMode::None)
+                                            >
+                                                <div class="flex items-center text-sm text-theme-accent font-medium">
+                                                    <Icon name=icons::CHEVRON_LEFT class="w-4 h-4 mr-1"/>
+                                                    "Back to tables"
+                                                </div>
+                                                <div class="mt-1 text-xs text-theme-muted">
+                                                    "Join Path"
+                                                </div>
+                                            </button>
+
+                                            <div class="flex-1 overflow-y-auto px-6 py-4 bg-theme-surface theme-transition">
+                                                <JoinPathPanel graph=graph highlighted_edges=highlighted_edges/>
+                                            </div>
+                                        </div>
+                                    }
+                                        .into_any()
+                                }
                                 EditingMode::None => {
                                 // Режим просмотра списка таблиц
                                 view! {
@@ -512,6 +627,24 @@ pub fn Sidebar(
                                                 <Icon name=icons::PLUS class="icon-btn"/>
                                                 "New Table"
                                             </button>
+                                            <div class="mt-2 grid grid-cols-2 gap-2">
+                                                <button
+                                                    class="px-3 py-2 btn-secondary rounded-lg text-sm flex items-center justify-center transition-all"
+                                                    title="Suggest foreign keys from column naming conventions"
+                                                    on:click=move |_| set_editing_mode.set(EditingMode::InferringRelationships)
+                                                >
+                                                    <Icon name=icons::SPARKLES class="icon-btn"/>
+                                                    "Infer Relationships"
+                                                </button>
+                                                <button
+                                                    class="px-3 py-2 btn-secondary rounded-lg text-sm flex items-center justify-center transition-all"
+                                                    title="Find how tables connect and generate a JOIN query"
+                                                    on:click=move |_| set_editing_mode.set(EditingMode::FindingJoinPath)
+                                                >
+                                                    <Icon name=icons::CODE class="icon-btn"/>
+                                                    "Join Path"
+                                                </button>
+                                            </div>
                                         </div>
 
                                         // Список таблиц
@@ -729,7 +862,16 @@ pub fn Sidebar(
             }
         }}
 
-
+        <ImpactConfirmDialog
+            report=Signal::derive(move || pending_delete.with(|p| p.as_ref().map(|(_, r)| r.clone())))
+            on_confirm=move || {
+                if let Some((target, _)) = pending_delete.get_untracked() {
+                    close_impact();
+                    perform_delete(target);
+                }
+            }
+            on_cancel=move || close_impact()
+        />
         </div>
     }
 }
@@ -4,16 +4,31 @@
 //! This is an alternative view to the visual canvas editor.
 //! Includes syntax validation with error underlines and semantic validation.
 //! Save button validates SQL and applies changes to graph with LiveShare sync.
+//! Schema lint issues (per-diagram rule settings) are shown next to validation diagnostics.
+//! The DDL is rendered in the diagram's target dialect; switching dialects re-renders it
+//! and reports column types that do not map cleanly.
+//! "Format document" pretty-prints the text with the diagram's SQL formatting options.
+//! Editing is schema-aware: completions (Ctrl+Space), hover summaries and
+//! go-to-definition (F12 / Ctrl+Click) work on the unsaved text.
 
 use crate::core::{
-    CanvasNotification, ErrorSeverity, ExportOptions, SchemaExporter, SchemaGraph, SqlDialect,
-    SqlValidationResult, UnderlineRange, apply_sql_to_graph, validate_sql,
+    CanvasNotification, CodeFix, CommaStyle, CompletionKind, CompletionList, DiagramSettings,
+    ErrorSeverity, ExportOptions, FormatOptions, HoverInfo, KeywordCase, LintContext, LintEngine,
+    SchemaExporter, SchemaGraph, SqlDialect, SqlValidationResult, UnderlineRange,
+    apply_sql_to_graph, check_type_conversions, collapse_junction_tables, complete_at,
+    definition_at, format_sql, hover_at, lint_sql, many_to_many_tables, validate_and_lint_sql,
+    validate_sql, validate_sql_with_lint,
 };
+use crate::ui::history::use_history_context;
 use crate::ui::icon::{Icon, icons};
-use crate::ui::liveshare_client::{ConnectionState, use_liveshare_context};
 use leptos::prelude::*;
 use leptos::web_sys;
 
+/// Editor metrics used to map text positions to pixels (1.5rem lines, p-3 padding)
+const LINE_HEIGHT: f64 = 24.0;
+const CHAR_WIDTH: f64 = 8.4;
+const PADDING: f64 = 12.0;
+
 /// View mode for the schema editor
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub enum EditorMode {
@@ -29,6 +44,9 @@ pub enum EditorMode {
 pub fn SourceEditor(
     /// The schema graph to display/edit
     graph: RwSignal<SchemaGraph>,
+    /// Diagram settings (target SQL dialect)
+    #[prop(optional)]
+    settings: Option<RwSignal<DiagramSettings>>,
     /// Whether the editor is in read-only mode
     #[prop(default = false)]
     readonly: bool,
@@ -39,17 +57,24 @@ pub fn SourceEditor(
     #[prop(optional)]
     on_validation: Option<Callback<SqlValidationResult>>,
 ) -> impl IntoView {
-    // Get LiveShare context for sync
-    let liveshare_ctx = use_liveshare_context();
+    // Applied SQL is sent to LiveShare and recorded for undo
+    let history = use_history_context();
+
+    let settings = settings.unwrap_or_else(|| RwSignal::new(DiagramSettings::default()));
+    let dialect = Memo::new(move |_| settings.with(|s| s.dialect.clone()));
+    let format_options = Memo::new(move |_| settings.with(|s| s.sql_format.clone()));
+    let lint_settings = Memo::new(move |_| settings.with(|s| s.lint.clone()));
 
     // SQL content derived from graph
     let sql_content = Memo::new(move |_| {
+        let sql_dialect = dialect.get();
         graph.with(|g| {
             let options = ExportOptions {
-                sql_dialect: SqlDialect::MySQL,
+                sql_dialect,
                 include_positions: true,
                 include_drop_statements: false,
                 pretty_print: true,
+                sql_format: Some(format_options.get()),
                 ..Default::default()
             };
             SchemaExporter::export_sql(g, &options).unwrap_or_else(|e| format!("-- Error: {}", e))
@@ -68,13 +93,29 @@ pub fn SourceEditor(
     let (scroll_top, set_scroll_top) = signal(0.0f64);
     let (scroll_left, set_scroll_left) = signal(0.0f64);
 
-    // Sync local content when graph changes (only if not modified)
+    // Sync local content when graph or dialect changes (only if not modified)
     Effect::new(move |_| {
         if !is_modified.get() {
-            set_local_content.set(sql_content.get());
-            // Clear validation when content syncs from graph
-            set_validation_result.set(None);
-            set_underline_ranges.set(Vec::new());
+            let content = sql_content.get();
+            // Replace previous validation with dialect conversion warnings and lint issues
+            let sql_dialect = dialect.get();
+            let conversions = graph.with(|g| {
+                let context = LintContext::new(sql_dialect.clone());
+                let report =
+                    LintEngine::default().run_with_context(g, &lint_settings.get(), &context);
+                let mut conversions = check_type_conversions(g, &sql_dialect, &content);
+                report.merge_into(&mut conversions, &content, &sql_dialect);
+                conversions
+            });
+            if conversions.diagnostics.is_empty() {
+                set_validation_result.set(None);
+                set_underline_ranges.set(Vec::new());
+            } else {
+                set_underline_ranges
+                    .set(conversions.get_underline_ranges_with_source(Some(&content)));
+                set_validation_result.set(Some(conversions));
+            }
+            set_local_content.set(content);
         }
     });
 
@@ -87,6 +128,159 @@ pub fn SourceEditor(
         }
     });
 
+    // Schema-aware editing: completion popup, hover tooltip, go-to-definition
+    let textarea_ref = NodeRef::<leptos::html::Textarea>::new();
+    let completion = RwSignal::new(None::<CompletionList>);
+    let completion_index = RwSignal::new(0usize);
+    let hover = RwSignal::new(None::<(HoverInfo, f64, f64)>);
+    let last_hover_offset = StoredValue::new(None::<usize>);
+
+    // Caret position as a byte offset in the editor text
+    let caret_offset = move || -> Option<usize> {
+        let textarea = textarea_ref.get_untracked()?;
+        let caret = textarea.selection_start().ok().flatten()? as usize;
+        Some(display_content.with_untracked(|text| utf16_to_byte_offset(text, caret)))
+    };
+
+    // Recompute completions at the caret; `force` opens the popup without a typed prefix
+    let update_completion = move |force: bool| {
+        let Some(offset) = caret_offset() else {
+            return;
+        };
+        let sql_dialect = dialect.get_untracked();
+        let list = display_content.with_untracked(|text| complete_at(text, offset, &sql_dialect));
+        if list.items.is_empty() || (!force && list.replace_start == list.replace_end) {
+            completion.set(None);
+        } else {
+            completion_index.set(0);
+            completion.set(Some(list));
+        }
+    };
+
+    let accept_completion = move |index: usize| {
+        let Some(list) = completion.get_untracked() else {
+            return;
+        };
+        let Some(item) = list.items.get(index) else {
+            return;
+        };
+        let mut text = display_content.get_untracked();
+        text.replace_range(list.replace_start..list.replace_end, &item.insert_text);
+        let caret = byte_to_utf16_offset(&text, list.replace_start + item.insert_text.len()) as u32;
+
+        set_local_content.set(text.clone());
+        set_is_modified.set(true);
+        set_validation_result.set(None);
+        set_underline_ranges.set(Vec::new());
+        completion.set(None);
+
+        if let Some(textarea) = textarea_ref.get_untracked() {
+            textarea.set_value(&text);
+            let _ = textarea.set_selection_range(caret, caret);
+        }
+    };
+
+    // Select the definition of the table/column at `offset` and scroll to it
+    let go_to_definition = move |offset: usize| {
+        let text = display_content.get_untracked();
+        let Some(span) = definition_at(&text, offset, &dialect.get_untracked()) else {
+            return;
+        };
+        if let Some(textarea) = textarea_ref.get_untracked() {
+            let start = byte_to_utf16_offset(&text, span.start.offset) as u32;
+            let end = byte_to_utf16_offset(&text, span.end.offset) as u32;
+            let _ = textarea.focus();
+            let _ = textarea.set_selection_range(start, end);
+            let top = (span.start.line as f64 - 1.0) * LINE_HEIGHT
+                - textarea.client_height() as f64 / 3.0;
+            textarea.set_scroll_top(top.max(0.0) as i32);
+        }
+    };
+
+    let on_keydown = move |ev: leptos::ev::KeyboardEvent| {
+        let key = ev.key();
+        if (ev.ctrl_key() || ev.meta_key()) && key == " " {
+            ev.prevent_default();
+            update_completion(true);
+            return;
+        }
+        if key == "F12" {
+            ev.prevent_default();
+            if let Some(offset) = caret_offset() {
+                go_to_definition(offset);
+            }
+            return;
+        }
+
+        let Some(count) = completion.with_untracked(|c| c.as_ref().map(|list| list.items.len()))
+        else {
+            return;
+        };
+        match key.as_str() {
+            "ArrowDown" => {
+                ev.prevent_default();
+                completion_index.update(|i| *i = (*i + 1) % count);
+            }
+            "ArrowUp" => {
+                ev.prevent_default();
+                completion_index.update(|i| *i = (*i + count - 1) % count);
+            }
+            "Enter" | "Tab" => {
+                ev.prevent_default();
+                accept_completion(completion_index.get_untracked());
+            }
+            "Escape" => {
+                ev.prevent_default();
+                completion.set(None);
+            }
+            "ArrowLeft" | "ArrowRight" | "Home" | "End" => completion.set(None),
+            _ => {}
+        }
+    };
+
+    let on_click = move |ev: leptos::ev::MouseEvent| {
+        completion.set(None);
+        if (ev.ctrl_key() || ev.meta_key())
+            && let Some(offset) = caret_offset()
+        {
+            go_to_definition(offset);
+        }
+    };
+
+    // Hover summary for the table/column under the mouse
+    let on_mouse_move = move |ev: leptos::ev::MouseEvent| {
+        let st = scroll_top.get_untracked();
+        let sl = scroll_left.get_untracked();
+        let y = ev.offset_y() as f64 + st - PADDING;
+        let x = ev.offset_x() as f64 + sl - PADDING;
+        let offset = if x < 0.0 || y < 0.0 {
+            None
+        } else {
+            display_content.with_untracked(|text| {
+                offset_at_line_column(text, (y / LINE_HEIGHT) as usize, (x / CHAR_WIDTH) as usize)
+            })
+        };
+        if last_hover_offset.get_value() == offset {
+            return;
+        }
+        last_hover_offset.set_value(offset);
+
+        let sql_dialect = dialect.get_untracked();
+        let info = offset.and_then(|offset| {
+            display_content.with_untracked(|text| hover_at(text, offset, &sql_dialect))
+        });
+        hover.set(info.map(|info| {
+            let top = PADDING + info.span.start.line as f64 * LINE_HEIGHT - st;
+            let left = PADDING + (info.span.start.column - 1) as f64 * CHAR_WIDTH - sl;
+            (info, top, left)
+        }));
+    };
+
+    let on_mouse_leave = move |_: leptos::ev::MouseEvent| {
+        last_hover_offset.set_value(None);
+        hover.set(None);
+    };
+
     // Handle text input changes
     let on_input = move |ev: leptos::ev::Event| {
         use leptos::wasm_bindgen::JsCast;
@@ -98,6 +292,7 @@ pub fn SourceEditor(
         // Clear previous validation on edit
         set_validation_result.set(None);
         set_underline_ranges.set(Vec::new());
+        update_completion(false);
     };
 
     // Handle scroll synchronization for underline overlay
@@ -107,6 +302,71 @@ pub fn SourceEditor(
         let textarea = target.dyn_ref::<web_sys::HtmlTextAreaElement>().unwrap();
         set_scroll_top.set(textarea.scroll_top() as f64);
         set_scroll_left.set(textarea.scroll_left() as f64);
+        completion.set(None);
+        hover.set(None);
+    };
+
+    // Validate the current text with the diagram's lint settings
+    let validate_content = move |content: &str| {
+        validate_and_lint_sql(
+            content,
+            &dialect.get_untracked(),
+            &lint_settings.get_untracked(),
+        )
+    };
+
+    // Apply a code fix (or all of them) and re-validate the fixed text
+    let apply_fixed_content = move |fixed: Result<String, String>| match fixed {
+        Ok(fixed) => {
+            let validation = validate_content(&fixed);
+            set_underline_ranges.set(validation.get_underline_ranges_with_source(Some(&fixed)));
+            set_validation_result.set(Some(validation));
+            set_local_content.set(fixed);
+            set_is_modified.set(true);
+        }
+        Err(e) => {
+            if let Some(cb) = on_notification {
+                cb.run(CanvasNotification::error("Failed to Apply Fix", e));
+            }
+        }
+    };
+
+    // Fixes carry byte offsets of the text they were computed for: re-validate the
+    // current text and apply only fixes that are still offered for it
+    let apply_fix = move |fix: CodeFix| {
+        let text = display_content.get_untracked();
+        let current = validate_content(&text);
+        let still_offered = current.diagnostics.iter().any(|d| d.fixes.contains(&fix));
+        if still_offered {
+            apply_fixed_content(fix.apply(&text));
+        } else {
+            set_underline_ranges.set(current.get_underline_ranges_with_source(Some(&text)));
+            set_validation_result.set(Some(current));
+            if let Some(cb) = on_notification {
+                cb.run(CanvasNotification::warning(
+                    "Fix No Longer Applies",
+                    "The text has changed since it was validated. Diagnostics were refreshed.",
+                ));
+            }
+        }
+    };
+
+    let fix_all = move |_: leptos::ev::MouseEvent| {
+        let text = display_content.get_untracked();
+        apply_fixed_content(validate_content(&text).fix_all(&text));
+    };
+
+    // Format document with the diagram's formatting options
+    let show_format_options = RwSignal::new(false);
+    let format_document = move |_: leptos::ev::MouseEvent| {
+        let text = display_content.get_untracked();
+        let formatted = format_sql(&text, &format_options.get_untracked());
+        if formatted != text {
+            set_local_content.set(formatted);
+            set_is_modified.set(true);
+            set_validation_result.set(None);
+            set_underline_ranges.set(Vec::new());
+        }
     };
 
     // Reset to graph state
@@ -124,7 +384,11 @@ pub fn SourceEditor(
         let content = local_content.get();
 
         // First validate
-        let validation = validate_sql(&content, SqlDialect::MySQL);
+        let sql_dialect = dialect.get_untracked();
+        let settings = lint_settings.get_untracked();
+        let mut validation = validate_sql_with_lint(&content, sql_dialect.clone(), &settings);
+        let lint_report = lint_sql(&content, &sql_dialect, &settings);
+        lint_report.merge_into(&mut validation, &content, &sql_dialect);
 
         // Update underline ranges with source for better context
         let ranges = validation.get_underline_ranges_with_source(Some(&content));
@@ -153,18 +417,16 @@ pub fn SourceEditor(
         }
 
         // Validation passed - apply changes to graph
+        let before = graph.get_untracked();
+        let mut applied_ops = None;
         graph.update(|g| {
-            let result = apply_sql_to_graph(&content, SqlDialect::MySQL, g);
+            let result = apply_sql_to_graph(&content, sql_dialect, g);
 
             if result.success {
-                // Send graph operations through LiveShare
-                if liveshare_ctx.connection_state.with_untracked(|v| *v)
-                    == ConnectionState::Connected
-                {
-                    for op in &result.graph_ops {
-                        liveshare_ctx.send_graph_op(op.clone());
-                    }
-                }
+                // Экспортированные промежуточные таблицы снова показываются связями N:M
+                let mut ops = result.graph_ops.clone();
+                ops.extend(collapse_junction_tables(g, &many_to_many_tables(&content)));
+                applied_ops = Some(ops);
 
                 // Clear modified flag since changes are now applied
                 set_is_modified.set(false);
@@ -174,6 +436,9 @@ pub fn SourceEditor(
                 if let Some(cb) = on_notification {
                     let notification = CanvasNotification::from_apply_result(&result);
                     cb.run(notification);
+                    if !lint_report.is_empty() {
+                        cb.run(CanvasNotification::from_lint_report(&lint_report));
+                    }
                 }
             } else {
                 // Application failed
@@ -190,6 +455,11 @@ pub fn SourceEditor(
             }
         });
 
+        // Весь применённый SQL - один шаг истории
+        if let Some(ops) = applied_ops {
+            history.commit(before, ops);
+        }
+
         set_is_saving.set(false);
     };
 
@@ -199,6 +469,38 @@ pub fn SourceEditor(
             <div class="flex items-center justify-between px-4 py-2 bg-theme-secondary border-b border-theme-primary theme-transition">
                 <div class="flex items-center gap-2">
                     <span class="text-theme-secondary text-sm font-medium">"SQL Source"</span>
+                    // Target dialect selector (locked while there are unsaved edits)
+                    <select
+                        class="px-2 py-0.5 text-xs rounded-md bg-theme-tertiary text-theme-secondary border border-theme-primary outline-none disabled:opacity-50"
+                        title=move || {
+                            if is_modified.get() {
+                                "Save or reset your changes before switching dialect"
+                            } else {
+                                "Target SQL dialect for this diagram"
+                            }
+                        }
+                        disabled=move || readonly || is_modified.get()
+                        on:change=move |ev| {
+                            if let Some(new_dialect) = SqlDialect::from_name(&event_target_value(&ev)) {
+                                settings.update(|s| s.dialect = new_dialect);
+                            }
+                        }
+                    >
+                        {SqlDialect::ALL
+                            .iter()
+                            .map(|d| {
+                                let option_dialect = d.clone();
+                                view! {
+                                    <option
+                                        value=d.label()
+                                        selected=move || dialect.get() == option_dialect
+                                    >
+                                        {d.label()}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
                     <span class="text-theme-muted text-xs">
                         {move || format!("{} lines", line_count.get())}
                     </span>
@@ -236,7 +538,27 @@ pub fn SourceEditor(
                 </div>
 
                 // Action buttons
-                <div class="flex items-center gap-2">
+                <div class="flex items-center gap-2 relative">
+                    {(!readonly).then(|| view! {
+                        <button
+                            class="px-3 py-1.5 text-xs font-medium rounded-lg bg-theme-tertiary text-theme-secondary hover:bg-theme-primary transition-colors"
+                            title="Format document"
+                            on:click=format_document
+                        >
+                            "Format"
+                        </button>
+                        <button
+                            class="px-2 py-1.5 text-xs rounded-lg bg-theme-tertiary text-theme-secondary hover:bg-theme-primary transition-colors"
+                            title="Formatting options"
+                            on:click=move |_| show_format_options.update(|v| *v = !*v)
+                        >
+                            <Icon name=icons::SETTINGS class="w-3.5 h-3.5" />
+                        </button>
+                    })}
+                    {move || show_format_options.get().then(|| view! {
+                        <FormatOptionsPanel settings=settings />
+                    })}
+
                     // Save button (only show if modified and not readonly)
                     {move || {
                         if !readonly && is_modified.get() {
@@ -289,8 +611,22 @@ pub fn SourceEditor(
                     if result.diagnostics.is_empty() {
                         None
                     } else {
+                        let fix_count = result.diagnostics.iter().filter(|d| !d.fixes.is_empty()).count();
                         Some(view! {
                             <div class="max-h-32 overflow-auto border-b border-theme-primary bg-theme-tertiary">
+                                {(!readonly && fix_count > 0).then(|| view! {
+                                    <div class="px-3 py-1 text-xs flex items-center justify-between border-b border-theme-primary">
+                                        <span class="text-theme-muted">
+                                            {format!("{} issue(s) can be fixed automatically", fix_count)}
+                                        </span>
+                                        <button
+                                            class="px-2 py-0.5 rounded-md bg-blue-500/20 text-blue-400 hover:bg-blue-500/30 transition-colors"
+                                            on:click=fix_all
+                                        >
+                                            "Fix all"
+                                        </button>
+                                    </div>
+                                })}
                                 {result.diagnostics.iter().map(|diag| {
                                     let icon = match diag.severity {
                                         ErrorSeverity::Error => "❌",
@@ -305,6 +641,7 @@ pub fn SourceEditor(
                                     let position = diag.span.as_ref().map(|s| format!("[L{}:{}] ", s.start.line, s.start.column)).unwrap_or_default();
                                     let message = diag.message.clone();
                                     let suggestion = diag.suggestion.clone();
+                                    let fixes = if readonly { Vec::new() } else { diag.fixes.clone() };
 
                                     view! {
                                         <div class={format!("px-3 py-1.5 text-xs border-l-2 {} flex flex-col gap-0.5", bg_class)}>
@@ -318,6 +655,21 @@ pub fn SourceEditor(
                                                     "→ " {s}
                                                 </div>
                                             })}
+                                            {(!fixes.is_empty()).then(|| view! {
+                                                <div class="flex items-center gap-1.5 pl-5">
+                                                    {fixes.into_iter().map(|fix| {
+                                                        let title = fix.title.clone();
+                                                        view! {
+                                                            <button
+                                                                class="px-2 py-0.5 rounded-md bg-blue-500/20 text-blue-400 hover:bg-blue-500/30 transition-colors"
+                                                                on:click=move |_| apply_fix(fix.clone())
+                                                            >
+                                                                {title}
+                                                            </button>
+                                                        }
+                                                    }).collect_view()}
+                                                </div>
+                                            })}
                                         </div>
                                     }
                                 }).collect_view()}
@@ -393,9 +745,24 @@ pub fn SourceEditor(
                                         style="line-height: 1.5rem; tab-size: 4;"
                                         spellcheck="false"
                                         prop:value=move || display_content.get()
+                                        node_ref=textarea_ref
                                         on:input=on_input
                                         on:scroll=on_scroll
+                                        on:keydown=on_keydown
+                                        on:click=on_click
+                                        on:mousemove=on_mouse_move
+                                        on:mouseleave=on_mouse_leave
+                                        on:blur=move |_| completion.set(None)
                                     />
+                                    <CompletionPopup
+                                        content=display_content
+                                        completion=completion
+                                        selected=completion_index
+                                        scroll_top=scroll_top
+                                        scroll_left=scroll_left
+                                        on_accept=Callback::new(accept_completion)
+                                    />
+                                    <HoverTooltip hover=hover />
                                     // Error underline overlay with tooltips (above textarea for hover)
                                     <ErrorUnderlinesWithScroll
                                         content=display_content
@@ -419,7 +786,10 @@ pub fn SourceEditor(
                         } else if is_modified.get() {
                             "Modified. Click 'Save' to validate and apply changes.".to_string()
                         } else {
-                            "SQL DDL representation of your schema.".to_string()
+                            format!(
+                                "{} DDL representation of your schema. Ctrl+Space to complete, F12 or Ctrl+Click to go to definition.",
+                                dialect.get().label(),
+                            )
                         }
                     }}
                 </span>
@@ -442,6 +812,207 @@ pub fn SourceEditor(
     }
 }
 
+/// Formatting options dropdown (keyword case, indentation, alignment, commas)
+#[component]
+fn FormatOptionsPanel(settings: RwSignal<DiagramSettings>) -> impl IntoView {
+    let options = move || settings.with(|s| s.sql_format.clone());
+    let update = move |f: &dyn Fn(&mut FormatOptions)| settings.update(|s| f(&mut s.sql_format));
+    let select_class = "px-2 py-0.5 text-xs rounded-md bg-theme-tertiary text-theme-secondary border border-theme-primary outline-none";
+
+    view! {
+        <div class="absolute right-0 top-full mt-2 z-40 w-56 p-3 rounded-lg shadow-lg border border-theme-primary bg-theme-secondary text-xs flex flex-col gap-2">
+            <label class="flex items-center justify-between gap-2 text-theme-secondary">
+                "Keywords"
+                <select
+                    class=select_class
+                    on:change=move |ev| {
+                        let value = event_target_value(&ev);
+                        if let Some(case) = KeywordCase::ALL.into_iter().find(|c| c.label() == value) {
+                            update(&|o| o.keyword_case = case);
+                        }
+                    }
+                >
+                    {KeywordCase::ALL
+                        .into_iter()
+                        .map(|case| view! {
+                            <option value=case.label() selected=move || options().keyword_case == case>
+                                {case.label()}
+                            </option>
+                        })
+                        .collect_view()}
+                </select>
+            </label>
+            <label class="flex items-center justify-between gap-2 text-theme-secondary">
+                "Indent"
+                <select
+                    class=select_class
+                    on:change=move |ev| {
+                        if let Ok(indent) = event_target_value(&ev).parse::<usize>() {
+                            update(&|o| o.indent = indent);
+                        }
+                    }
+                >
+                    {[2usize, 4]
+                        .into_iter()
+                        .map(|indent| view! {
+                            <option value=indent.to_string() selected=move || options().indent == indent>
+                                {format!("{} spaces", indent)}
+                            </option>
+                        })
+                        .collect_view()}
+                </select>
+            </label>
+            <label class="flex items-center justify-between gap-2 text-theme-secondary">
+                "Commas"
+                <select
+                    class=select_class
+                    on:change=move |ev| {
+                        let value = event_target_value(&ev);
+                        if let Some(style) = CommaStyle::ALL.into_iter().find(|c| c.label() == value) {
+                            update(&|o| o.comma_style = style);
+                        }
+                    }
+                >
+                    {CommaStyle::ALL
+                        .into_iter()
+                        .map(|style| view! {
+                            <option value=style.label() selected=move || options().comma_style == style>
+                                {style.label()}
+                            </option>
+                        })
+                        .collect_view()}
+                </select>
+            </label>
+            <label class="flex items-center justify-between gap-2 text-theme-secondary">
+                "Align columns"
+                <input
+                    type="checkbox"
+                    prop:checked=move || options().align_columns
+                    on:change=move |ev| {
+                        let checked = event_target_checked(&ev);
+                        update(&|o| o.align_columns = checked);
+                    }
+                />
+            </label>
+        </div>
+    }
+}
+
+/// Completion popup shown under the completed word
+#[component]
+fn CompletionPopup(
+    content: Memo<String>,
+    completion: RwSignal<Option<CompletionList>>,
+    selected: RwSignal<usize>,
+    scroll_top: ReadSignal<f64>,
+    scroll_left: ReadSignal<f64>,
+    on_accept: Callback<usize>,
+) -> impl IntoView {
+    move || {
+        completion.get().map(|list| {
+            let (line, column) =
+                content.with_untracked(|text| line_column_at(text, list.replace_start));
+            let top = PADDING + (line + 1) as f64 * LINE_HEIGHT - scroll_top.get_untracked();
+            let left = PADDING + column as f64 * CHAR_WIDTH - scroll_left.get_untracked();
+
+            view! {
+                <div
+                    class="absolute z-30 min-w-56 max-h-56 overflow-auto rounded-lg shadow-lg border border-theme-primary bg-theme-secondary font-mono text-xs"
+                    style=format!("top: {}px; left: {}px;", top, left)
+                >
+                    {list
+                        .items
+                        .into_iter()
+                        .enumerate()
+                        .map(|(idx, item)| {
+                            let (badge, badge_class) = match item.kind {
+                                CompletionKind::Table => ("T", "text-blue-400"),
+                                CompletionKind::Column => ("C", "text-green-400"),
+                                CompletionKind::DataType => ("D", "text-purple-400"),
+                                CompletionKind::Keyword => ("K", "text-theme-muted"),
+                            };
+                            view! {
+                                <div
+                                    class="flex items-center gap-2 px-2 py-1 cursor-pointer hover:bg-theme-tertiary"
+                                    class=("bg-theme-tertiary", move || selected.get() == idx)
+                                    on:mousedown=move |ev: leptos::ev::MouseEvent| {
+                                        // Keep textarea focus
+                                        ev.prevent_default();
+                                        on_accept.run(idx);
+                                    }
+                                >
+                                    <span class=format!("w-3 font-bold {}", badge_class)>{badge}</span>
+                                    <span class="text-theme-primary">{item.label}</span>
+                                    <span class="ml-auto pl-4 text-theme-muted">
+                                        {item.detail.unwrap_or_default()}
+                                    </span>
+                                </div>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+            }
+        })
+    }
+}
+
+/// Hover summary tooltip for tables and columns
+#[component]
+fn HoverTooltip(hover: RwSignal<Option<(HoverInfo, f64, f64)>>) -> impl IntoView {
+    move || {
+        hover.get().map(|(info, top, left)| {
+            view! {
+                <div
+                    class="absolute z-30 px-3 py-2 text-xs rounded-lg shadow-lg bg-gray-900/95 text-gray-100 whitespace-nowrap"
+                    style=format!("top: {}px; left: {}px; pointer-events: none;", top, left)
+                >
+                    <div class="font-medium mb-1">{info.title}</div>
+                    {info
+                        .lines
+                        .into_iter()
+                        .map(|line| view! { <div class="font-mono text-gray-300">{line}</div> })
+                        .collect_view()}
+                </div>
+            }
+        })
+    }
+}
+
+/// Convert a textarea (UTF-16) offset into a byte offset
+fn utf16_to_byte_offset(text: &str, utf16_offset: usize) -> usize {
+    let mut units = 0;
+    for (idx, ch) in text.char_indices() {
+        if units >= utf16_offset {
+            return idx;
+        }
+        units += ch.len_utf16();
+    }
+    text.len()
+}
+
+/// Convert a byte offset into a textarea (UTF-16) offset
+fn byte_to_utf16_offset(text: &str, byte_offset: usize) -> usize {
+    text[..byte_offset.min(text.len())].encode_utf16().count()
+}
+
+/// 0-based line and column (in chars) of a byte offset
+fn line_column_at(text: &str, byte_offset: usize) -> (usize, usize) {
+    let before = &text[..byte_offset.min(text.len())];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    (line, before[line_start..].chars().count())
+}
+
+/// Byte offset of a 0-based line/column (in chars), if it is inside the line
+fn offset_at_line_column(text: &str, line: usize, column: usize) -> Option<usize> {
+    let line_start: usize = text.split_inclusive('\n').take(line).map(str::len).sum();
+    let line_text = text[line_start..].lines().next()?;
+    line_text
+        .char_indices()
+        .nth(column)
+        .map(|(idx, _)| line_start + idx)
+}
+
 /// Component to render error underlines with tooltips (for readonly mode)
 #[component]
 fn ErrorUnderlinesStatic(
@@ -715,6 +1286,6 @@ pub fn validate_for_llm(sql: &str, dialect: SqlDialect) -> serde_json::Value {
 
 /// Check schema and return validation result
 /// Use this when saving or before applying changes
-pub fn check_before_save(graph: &SchemaGraph) -> SqlValidationResult {
-    crate::core::check_schema_sql(graph, SqlDialect::MySQL)
+pub fn check_before_save(graph: &SchemaGraph, dialect: SqlDialect) -> SqlValidationResult {
+    crate::core::check_schema_sql(graph, dialect)
 }
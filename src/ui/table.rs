@@ -1,4 +1,4 @@
-use crate::core::{Column, TableNode};
+use crate::core::{Column, TableDisplay, TableNode, TableRows};
 use crate::ui::{Icon, icons};
 use leptos::html::Div;
 use leptos::prelude::*;
@@ -17,8 +17,35 @@ pub fn TableNodeView(
     /// Whether this table is selected (highlights all its relationships)
     #[prop(default = false)]
     is_selected: bool,
+    /// Pending delete: this table depends on the deleted object, with its affected columns
+    #[prop(default = None)]
+    affected_columns: Option<Vec<String>>,
+    /// Enter focus mode on this table (show only its neighborhood)
+    #[prop(optional, into)]
+    on_focus: Option<Callback<()>>,
+    /// Zoomed-out rendering: a name-only box of the table's size
+    #[prop(default = false)]
+    compact: bool,
+    /// Start dragging a relationship from a column (drag-to-connect)
+    #[prop(optional, into)]
+    on_connect_start: Option<Callback<(String, web_sys::MouseEvent)>>,
+    /// While a relationship is being dragged: columns of this table it can connect to
+    #[prop(default = None)]
+    connect_targets: Option<Vec<String>>,
+    /// Toggle pinning (pinned tables are kept in place by auto layout)
+    #[prop(optional, into)]
+    on_toggle_pin: Option<Callback<()>>,
+    /// Rows shown at the table's display level
+    rows: TableRows,
+    /// Change the display level (header button, `+N columns` row)
+    #[prop(optional, into)]
+    on_display_change: Option<Callback<TableDisplay>>,
+    /// Color of a LiveShare participant who has this table selected
+    #[prop(default = None)]
+    remote_selection_color: Option<String>,
 ) -> impl IntoView {
     let (x, y) = node.position;
+    let is_pinned = node.pinned;
     let node_ref = NodeRef::<Div>::new();
 
     // Сохраняем имя таблицы для избежания клонирования в замыканиях
@@ -27,13 +54,42 @@ pub fn TableNodeView(
 
     // No CSS transition for position - we use requestAnimationFrame interpolation for smooth remote updates
     // is_being_dragged disables any remaining transitions for both local and remote drags
-    let table_class = if is_selected {
+    let table_class = if affected_columns.is_some() {
+        "absolute bg-theme-surface border-2 border-red-500 shadow-theme-xl select-none theme-transition ring-2 ring-red-400 ring-opacity-50"
+    } else if is_selected {
         "absolute bg-theme-surface border-2 border-blue-500 shadow-theme-xl select-none theme-transition ring-2 ring-blue-400 ring-opacity-50"
     } else {
         "absolute bg-theme-surface border-2 border-theme-primary shadow-theme-lg select-none hover:shadow-theme-xl theme-transition"
     };
     let _ = is_being_dragged; // Used in canvas.rs to track dragging state
 
+    // На мелком масштабе колонки не читаются - рисуем только имя
+    if compact {
+        return view! {
+            <div
+                class=table_class
+                style:left=format!("{}px", x)
+                style:top=format!("{}px", y)
+                style:width="280px"
+                style:height=format!("{}px", rows.height())
+                style:user-select="none"
+                style:z-index="10"
+                style:border-radius="8px"
+                style:outline=remote_selection_color.map(|color| format!("2px dashed {}", color))
+                style:outline-offset="4px"
+            >
+                <div
+                    class="w-full h-full flex items-center justify-center px-4 cursor-move"
+                    on:mousedown=move |ev| on_mouse_down.run(ev)
+                    on:click=move |ev| on_click.run(ev)
+                >
+                    <span class="text-3xl font-bold text-theme-primary truncate">{table_name}</span>
+                </div>
+            </div>
+        }
+        .into_any();
+    }
+
     view! {
         <div
             node_ref=node_ref
@@ -44,6 +100,8 @@ pub fn TableNodeView(
             style:user-select="none"
             style:z-index="10"
             style:border-radius="8px"
+            style:outline=remote_selection_color.map(|color| format!("2px dashed {}", color))
+            style:outline-offset="4px"
         >
             // Заголовок таблицы
             <div
@@ -53,38 +111,147 @@ pub fn TableNodeView(
                 on:click=move |ev| on_click.run(ev)
             >
                 <span class="text-lg">{table_name}</span>
-                <Icon name=icons::GRIP_HORIZONTAL class="w-5 h-5 text-gray-400"/>
+                <div class="flex items-center gap-1">
+                    {on_focus.map(|focus| view! {
+                        <button
+                            class="p-1 rounded cursor-pointer opacity-40 hover:opacity-100"
+                            title="Focus on this table and its neighbors"
+                            on:mousedown=|ev| ev.stop_propagation()
+                            on:click=move |ev| {
+                                ev.stop_propagation();
+                                focus.run(());
+                            }
+                        >
+                            <Icon name=icons::EYE class="w-4 h-4"/>
+                        </button>
+                    })}
+                    {on_display_change.map(|change| {
+                        let display = rows.display;
+                        let next = display.next();
+                        view! {
+                            <button
+                                class="p-1 rounded cursor-pointer"
+                                style:opacity=if display == TableDisplay::Full { "0.4" } else { "1" }
+                                title=format!("{} (click for {})", display.label(), next.label().to_lowercase())
+                                on:mousedown=|ev| ev.stop_propagation()
+                                on:click=move |ev| {
+                                    ev.stop_propagation();
+                                    change.run(next);
+                                }
+                            >
+                                <Icon
+                                    name=if display == TableDisplay::Full { icons::COLLAPSE } else { icons::EXPAND }
+                                    class="w-4 h-4"
+                                />
+                            </button>
+                        }
+                    })}
+                    {on_toggle_pin.map(|toggle| view! {
+                        <button
+                            class="p-1 rounded cursor-pointer"
+                            style:opacity=if is_pinned { "1" } else { "0.4" }
+                            title=if is_pinned {
+                                "Unpin table (auto layout may move it)"
+                            } else {
+                                "Pin table (auto layout keeps it in place)"
+                            }
+                            on:mousedown=|ev| ev.stop_propagation()
+                            on:click=move |ev| {
+                                ev.stop_propagation();
+                                toggle.run(());
+                            }
+                        >
+                            <Icon name=icons::PIN class="w-4 h-4"/>
+                        </button>
+                    })}
+                    <Icon name=icons::GRIP_HORIZONTAL class="w-5 h-5 text-gray-400"/>
+                </div>
             </div>
 
-            // Список колонок
-            <div class="p-2 max-h-96 overflow-y-auto">
-                {if !has_columns {
-                    view! {
-                        <div class="text-center py-4 text-theme-muted text-sm">
-                            "No columns"
-                            <div class="text-xs mt-1 text-theme-tertiary">"Use sidebar to add"</div>
-                        </div>
-                    }
-                        .into_any()
-                } else {
-                    node
-                        .columns
-                        .into_iter()
-                        .map(|column| {
-                            view! { <ColumnRow column=column/> }
-                        })
-                        .collect_view()
-                        .into_any()
-                }}
-            </div>
+            // Список колонок (у таблицы только с именем его нет)
+            {(rows.display != TableDisplay::NameOnly).then(|| {
+                let hidden = rows.hidden;
+                let has_summary_row = rows.has_summary_row();
+                let columns: Vec<Column> = rows
+                    .columns
+                    .iter()
+                    .map(|&i| node.columns[i].clone())
+                    .collect();
+                view! {
+                    <div class="p-2 max-h-96 overflow-y-auto">
+                        {if !has_columns {
+                            view! {
+                                <div class="text-center py-4 text-theme-muted text-sm">
+                                    "No columns"
+                                    <div class="text-xs mt-1 text-theme-tertiary">"Use sidebar to add"</div>
+                                </div>
+                            }
+                                .into_any()
+                        } else {
+                            columns
+                                .into_iter()
+                                .map(|column| {
+                                    let highlight = connect_targets
+                                        .as_ref()
+                                        .map(|targets| targets.contains(&column.name));
+                                    let affected = affected_columns
+                                        .as_ref()
+                                        .is_some_and(|columns| columns.contains(&column.name));
+                                    let on_connect_start = on_connect_start.map(|start| {
+                                        let name = column.name.clone();
+                                        Callback::new(move |ev| start.run((name.clone(), ev)))
+                                    });
+                                    view! {
+                                        <ColumnRow
+                                            column=column
+                                            highlight=highlight
+                                            affected=affected
+                                            on_connect_start=on_connect_start
+                                        />
+                                    }
+                                })
+                                .collect_view()
+                                .into_any()
+                        }}
+                        // Скрытые колонки: щелчок разворачивает таблицу
+                        {has_summary_row.then(|| view! {
+                            <div
+                                class="py-2 px-2 rounded text-sm italic text-theme-tertiary hover:bg-theme-secondary cursor-pointer theme-transition"
+                                title="Show all columns"
+                                on:mousedown=|ev| ev.stop_propagation()
+                                on:click=move |ev| {
+                                    ev.stop_propagation();
+                                    if let Some(change) = on_display_change {
+                                        change.run(TableDisplay::Full);
+                                    }
+                                }
+                            >
+                                {format!("+{} columns", hidden)}
+                            </div>
+                        })}
+                    </div>
+                }
+            })}
         </div>
     }
+    .into_any()
 }
 
 /// Optimized ColumnRow component using CSS-based conditional styling
 /// instead of multiple into_any() calls for conditional rendering
 #[component]
-fn ColumnRow(column: Column) -> impl IntoView {
+fn ColumnRow(
+    column: Column,
+    /// During a connection drag: whether the row is a compatible target
+    #[prop(default = None)]
+    highlight: Option<bool>,
+    /// Pending delete: the column depends on the deleted object
+    #[prop(default = false)]
+    affected: bool,
+    /// Mouse down on the connection handle
+    #[prop(default = None)]
+    on_connect_start: Option<Callback<web_sys::MouseEvent>>,
+) -> impl IntoView {
     // Pre-compute CSS classes and text content to avoid runtime branching in view
     let pk_class = if column.is_primary_key {
         "text-yellow-500 font-bold mr-2 text-xs flex-shrink-0"
@@ -110,8 +277,40 @@ fn ColumnRow(column: Column) -> impl IntoView {
     // Clone data_type once for display
     let data_type_display = column.data_type.clone();
 
+    let row_class = match highlight {
+        Some(true) => {
+            "group relative flex items-center justify-between py-2 px-2 rounded text-sm border-b border-theme-primary last:border-b-0 theme-transition bg-green-500/15 ring-1 ring-green-500"
+        }
+        Some(false) => {
+            "group relative flex items-center justify-between py-2 px-2 rounded text-sm border-b border-theme-primary last:border-b-0 theme-transition opacity-40"
+        }
+        None if affected => {
+            "group relative flex items-center justify-between py-2 px-2 rounded text-sm border-b border-theme-primary last:border-b-0 theme-transition bg-red-500/15 ring-1 ring-red-500"
+        }
+        None => {
+            "group relative flex items-center justify-between py-2 px-2 hover:bg-theme-secondary rounded text-sm border-b border-theme-primary last:border-b-0 theme-transition"
+        }
+    };
+
     view! {
-        <div class="flex items-center justify-between py-2 px-2 hover:bg-theme-secondary rounded text-sm border-b border-theme-primary last:border-b-0 theme-transition">
+        <div class=row_class>
+            // Ручка для протягивания связи к колонке другой таблицы
+            {on_connect_start.map(|start| view! {
+                <span
+                    class="absolute -right-2 top-1/2 -translate-y-1/2 w-3 h-3 rounded-full bg-blue-500 border-2 border-white cursor-crosshair opacity-0 group-hover:opacity-100 transition-opacity"
+                    style="z-index: 20;"
+                    title="Drag to another table's column to create a relationship"
+                    on:mousedown=move |ev| {
+                        if ev.button() != 0 {
+                            return;
+                        }
+                        ev.prevent_default();
+                        ev.stop_propagation();
+                        start.run(ev);
+                    }
+                    on:click=|ev| ev.stop_propagation()
+                ></span>
+            })}
             <div class="flex items-center flex-1 min-w-0">
                 <span class=pk_class title="Primary Key">{pk_text}</span>
                 <span class="font-medium text-theme-primary truncate">{column.name}</span>
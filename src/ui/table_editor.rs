@@ -1,6 +1,9 @@
-use crate::core::{SchemaGraph, TableOps};
-use crate::ui::liveshare_client::{ConnectionState, GraphOperation, use_liveshare_context};
-use crate::ui::{ErrorMessage, Icon, SaveCancelHints, icons};
+use crate::core::{
+    DiagramSettings, SchemaGraph, TableOps, ValidationLevel, validate_identifier_for,
+};
+use crate::ui::history::use_history_context;
+use crate::ui::liveshare_client::GraphOperation;
+use crate::ui::{DialectNameNotes, ErrorMessage, Icon, SaveCancelHints, icons};
 use leptos::prelude::*;
 use leptos::web_sys;
 use petgraph::graph::NodeIndex;
@@ -33,8 +36,8 @@ pub fn TableEditor(
     #[prop(into)] on_cancel: Callback<()>,
     #[prop(into)] on_delete: Callback<()>,
 ) -> impl IntoView {
-    // Get LiveShare context for sync
-    let liveshare_ctx = use_liveshare_context();
+    // Переименование отправляется участникам и записывается в историю отмены
+    let history = use_history_context();
 
     // Получаем текущее имя таблицы (untracked - это начальное значение)
     let initial_name = graph.with_untracked(|g| {
@@ -57,6 +60,21 @@ pub fn TableEditor(
         }
     });
 
+    // Проверка имени с учётом целевого диалекта диаграммы
+    let diagram_settings = use_context::<RwSignal<DiagramSettings>>();
+    let dialect_check = move || {
+        let dialect = diagram_settings
+            .map(|s| s.with(|v| v.dialect.clone()))
+            .unwrap_or_default();
+        validate_identifier_for(&table_name.get(), ValidationLevel::Standard, &dialect)
+    };
+    let quoted_name = move || {
+        diagram_settings
+            .map(|s| s.with(|v| v.dialect.clone()))
+            .unwrap_or_default()
+            .quote_identifier(table_name.get().trim())
+    };
+
     let handle_save = move || {
         let name = table_name.get().trim().to_string();
 
@@ -65,28 +83,30 @@ pub fn TableEditor(
             set_error.set(Some("Table name cannot be empty".to_string()));
             return;
         }
+        if let Err(e) = dialect_check().to_result() {
+            set_error.set(Some(e.to_string()));
+            return;
+        }
 
         set_is_saving.set(true);
         set_error.set(None);
 
         // Попытка переименовать таблицу
+        let before = graph.get_untracked();
         match graph.write().rename_table(node_idx, name.clone()) {
             Ok(()) => {
-                // Send sync op
-                if liveshare_ctx.connection_state.with_untracked(|v| *v)
-                    == ConnectionState::Connected
-                {
-                    let table_uuid = graph.with(|g| {
-                        g.node_weight(node_idx)
-                            .map(|n| n.uuid)
-                            .unwrap_or_else(uuid::Uuid::new_v4)
-                    });
-                    liveshare_ctx.send_graph_op(GraphOperation::RenameTable {
+                let table_uuid = before
+                    .node_weight(node_idx)
+                    .map(|n| n.uuid)
+                    .unwrap_or_else(uuid::Uuid::new_v4);
+                history.commit(
+                    before,
+                    vec![GraphOperation::RenameTable {
                         node_id: node_idx.index() as u32,
                         table_uuid,
                         new_name: name,
-                    });
-                }
+                    }],
+                );
                 set_is_saving.set(false);
 
                 // Trigger save after table rename
@@ -153,6 +173,11 @@ pub fn TableEditor(
                         on:keydown=handle_keydown
                         disabled=move || is_saving.get()
                     />
+                    <DialectNameNotes
+                        result=Signal::derive(dialect_check)
+                        quoted=Signal::derive(quoted_name)
+                        on_quote=Callback::new(move |_| handle_save())
+                    />
 
                     <ErrorMessage error=error/>
                 </div>